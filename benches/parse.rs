@@ -0,0 +1,189 @@
+use cql_nom::model::{CqlTypeRef, TypeArena};
+use cql_nom::{parse_cql, resolve_references};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use nom::branch::alt;
+use nom::bytes::complete::{tag, take_till, take_until};
+use nom::character::complete::{multispace1, not_line_ending};
+use nom::combinator::recognize;
+use nom::multi::many0;
+use nom::sequence::{delimited, pair};
+use nom::IResult;
+
+#[allow(dead_code, unused_imports, unused_macros)]
+#[path = "../src/utils.rs"]
+mod utils;
+
+const COMMENTS: &str = include_str!("../tests/fixtures/comments.cql");
+const QUOTED: &str = include_str!("../tests/fixtures/quoted.cql");
+
+/// Repeats the fixture to get a file of realistic size.
+fn repeat(fixture: &str, times: usize) -> String {
+    let mut input = String::with_capacity(fixture.len() * times);
+    for _ in 0..times {
+        input.push_str(fixture);
+    }
+    input
+}
+
+/// Removes all comments from the fixture, leaving plain whitespace.
+fn strip_comments(fixture: &str) -> String {
+    fixture
+        .lines()
+        .map(|line| {
+            let line = line.split("--").next().unwrap();
+            line.split("//").next().unwrap()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+        .split("/*")
+        .enumerate()
+        .map(|(i, part)| {
+            if i == 0 {
+                part
+            } else {
+                part.split_once("*/").map(|(_, rest)| rest).unwrap_or("")
+            }
+        })
+        .collect()
+}
+
+fn bench_whitespace(c: &mut Criterion) {
+    let commented = repeat(COMMENTS, 100);
+    let plain = repeat(&strip_comments(COMMENTS), 100);
+
+    let mut group = c.benchmark_group("whitespace");
+    group.bench_function("comment_heavy", |b| {
+        b.iter(|| parse_cql(black_box(&commented)).unwrap())
+    });
+    group.bench_function("plain", |b| {
+        b.iter(|| parse_cql(black_box(&plain)).unwrap())
+    });
+    group.bench_function("skip/ws0", |b| {
+        b.iter(|| skip_all(black_box(&commented), utils::ws0::<_, ()>))
+    });
+    group.bench_function("skip/multispace", |b| {
+        b.iter(|| skip_all(black_box(&commented), multispace_comments0))
+    });
+    group.finish();
+}
+
+/// The skipper `ws0` replaced: `multispace1` extended with a branch per comment form.
+fn multispace_comments0(input: &str) -> IResult<&str, &str, ()> {
+    recognize(many0(alt((
+        multispace1,
+        recognize(pair(alt((tag("--"), tag("//"))), not_line_ending)),
+        recognize(delimited(tag("/*"), take_until("*/"), tag("*/"))),
+    ))))(input)
+}
+
+/// Walks the whole input, alternating between the skipper and the next token.
+fn skip_all<'a>(
+    mut input: &'a str,
+    mut skipper: impl FnMut(&'a str) -> IResult<&'a str, &'a str, ()>,
+) -> usize {
+    let mut skipped = 0;
+    while !input.is_empty() {
+        let (rest, whitespace) = skipper(input).unwrap();
+        let (rest, _) = take_till::<_, _, ()>(char::is_whitespace)(rest).unwrap();
+        skipped += whitespace.len();
+        input = if rest.len() == input.len() {
+            &rest[rest.chars().next().unwrap().len_utf8()..]
+        } else {
+            rest
+        };
+    }
+    skipped
+}
+
+fn bench_quoted(c: &mut Criterion) {
+    let input = repeat(QUOTED, 10);
+
+    c.bench_function("quoted_identifiers", |b| {
+        b.iter(|| parse_cql(black_box(&input)).unwrap())
+    });
+}
+
+/// A schema where every column has a deeply nested type.
+fn nested_types_schema(tables: usize, columns: usize) -> String {
+    let mut input = String::from("CREATE TYPE my_ks.my_udt (my_field int);\n");
+    for table in 0..tables {
+        input.push_str(&format!("CREATE TABLE my_ks.table_{} (\n", table));
+        for column in 0..columns {
+            input.push_str(&format!(
+                "    column_{} frozen<map<text, frozen<list<frozen<tuple<int, my_udt, set<text>>>>>>>,\n",
+                column
+            ));
+        }
+        input.push_str("    PRIMARY KEY (column_0)\n);\n");
+    }
+    input
+}
+
+/// Resolves the same parsed schema with boxed types and with a type arena, then compares the
+/// types of neighboring columns.
+fn bench_type_arena(c: &mut Criterion) {
+    let input = nested_types_schema(50, 20);
+    let (_, statements) = parse_cql(&input).unwrap();
+    let keyspace = cql_nom::model::CqlIdentifier::new("my_ks");
+
+    let mut group = c.benchmark_group("type_representation");
+    group.bench_function("boxed", |b| {
+        b.iter(|| {
+            let statements = black_box(&statements);
+            let resolved = resolve_references(statements.clone(), None).unwrap();
+            let resolved_types = resolved[1..]
+                .iter()
+                .flat_map(|statement| statement.create_table().unwrap().columns())
+                .map(|column| column.cql_type())
+                .collect::<Vec<_>>();
+            resolved_types
+                .windows(2)
+                .filter(|types| types[0] == types[1])
+                .count()
+        })
+    });
+    group.bench_function("arena", |b| {
+        b.iter(|| {
+            let statements = black_box(&statements);
+            let context = resolve_references(statements[..1].to_vec(), None).unwrap();
+            let mut arena = TypeArena::new();
+            let references = statements[1..]
+                .iter()
+                .flat_map(|statement| statement.create_table().unwrap().columns())
+                .map(|column| arena.insert(column.cql_type().clone()))
+                .collect::<Vec<CqlTypeRef>>();
+            let resolved = arena.reference_types(Some(&keyspace), &context).unwrap();
+            references
+                .windows(2)
+                .filter(|references| resolved.type_eq(references[0], references[1]))
+                .count()
+        })
+    });
+    group.finish();
+}
+
+#[cfg(feature = "rayon")]
+fn bench_parallel(c: &mut Criterion) {
+    let input = repeat(COMMENTS, 1000);
+
+    let mut group = c.benchmark_group("parallel");
+    group.bench_function("sequential", |b| {
+        b.iter(|| parse_cql(black_box(&input)).unwrap())
+    });
+    group.bench_function("parallel", |b| {
+        b.iter(|| cql_nom::parse_cql_parallel(black_box(&input)).unwrap())
+    });
+    group.finish();
+}
+
+#[cfg(not(feature = "rayon"))]
+criterion_group!(benches, bench_whitespace, bench_quoted, bench_type_arena);
+#[cfg(feature = "rayon")]
+criterion_group!(
+    benches,
+    bench_whitespace,
+    bench_quoted,
+    bench_type_arena,
+    bench_parallel
+);
+criterion_main!(benches);
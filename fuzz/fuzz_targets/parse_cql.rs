@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Any input must be parsed or rejected, the parser must never panic or overflow the stack.
+fuzz_target!(|data: &[u8]| {
+    let input = String::from_utf8_lossy(data);
+    if let Ok((_, statements)) = cql_nom::parse_cql(&input) {
+        let _ = cql_nom::resolve_references(statements, None);
+    }
+    let _ = cql_nom::parse_cql_complete(&input);
+    let _ = cql_nom::parse_cql_lenient(&input);
+    let _ = cql_nom::parse_cql_type(&input);
+    let _ = cql_nom::parse_cql_with_comments(&input);
+    let _ = cql_nom::parse_cql_recovering(&input);
+    if let Ok((_, statements)) = cql_nom::parse_cql_spanned(&input) {
+        for offset in 0..=input.len() {
+            let _ = cql_nom::lookup::find_node_at(&input, &statements, offset);
+        }
+    }
+});
@@ -0,0 +1,463 @@
+use crate::model::*;
+use crate::print::Printer;
+
+/// Renames every keyspace, table, type, column, field, function, trigger, role and index of the
+/// schema and renders it as cql.
+///
+/// Names are generated from the seed, the same name is always replaced by the same generated
+/// name, while different names never share one. Comments are dropped and strings, like the
+/// `comment` option, trigger and index classes, passwords, mbean names and masking arguments,
+/// are emptied. The structure, types, primary keys and remaining options are kept.
+///
+/// Generated names are lowercase and unquoted. Only names which differ from another name just
+/// in their case, like `"Id"` next to `id`, are replaced by a quoted name differing from the
+/// other replacement just in its case.
+///
+/// Example:
+/// ```rust
+/// use cql_nom::anonymize::anonymize;
+/// use cql_nom::parse_cql;
+///
+/// let (_, statements) = parse_cql("CREATE TABLE shop_1.orders_v1 (order_id uuid PRIMARY KEY)").unwrap();
+/// let anonymized = anonymize(&statements, 42);
+/// assert!(!anonymized.contains("orders_v1"));
+/// assert_eq!(parse_cql(&anonymized).unwrap().1.len(), 1);
+/// ```
+pub fn anonymize(statements: &[ParsedCqlStatement<&str>], seed: u64) -> String {
+    let mut printer = Printer::anonymizing(seed);
+    // The first pass collects the spellings of the names, the variant of a spelling depends on
+    // the spellings which come after it.
+    printer.print(statements);
+    printer.print(statements)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_cql, resolve_references};
+    use std::rc::Rc;
+
+    const SCHEMA: &str = r#"
+        -- The address of a keeper.
+        CREATE TYPE zoo_ks.address_type (street_name text, "House Number" int);
+        /* Keepers with their addresses. */
+        CREATE TYPE zoo_ks."Keeper Card" (card_id uuid, home_1 frozen<address_type>);
+        CREATE TABLE zoo_ks.keepers_v1 (
+            keeper_id timeuuid,
+            "Keeper_Id" text STATIC,
+            shift_date date,
+            card_1 frozen<"Keeper Card">,
+            visit_log list<frozen<tuple<timestamp, address_type>>>,
+            PRIMARY KEY ((keeper_id, "Keeper_Id"), shift_date)
+        ) WITH COMPACT STORAGE AND CLUSTERING ORDER BY (shift_date DESC);
+        ALTER MATERIALIZED VIEW zoo_ks.keepers_by_shift
+            WITH comment = 'Secret notes' AND gc_grace_seconds = 3600;
+        ALTER TYPE zoo_ks.address_type ADD zip_code frozen<address_type>;
+        ALTER TYPE zoo_ks.address_type RENAME zip_code TO "Zip Code" AND street_name TO road_1;
+        USE zoo_ks;
+        CREATE TABLE IF NOT EXISTS animal_1 (animal_id uuid PRIMARY KEY, tag_set set<text>,
+            notes_1 text MASKED WITH mask_replace('Hidden notes'),
+            weight_1 int MASKED WITH DEFAULT);
+        CREATE TABLE animal_copy LIKE animal_1 WITH comment = 'Copied animals';
+        CREATE INDEX animal_tags ON animal_1 (VALUES(tag_set));
+        CREATE CUSTOM INDEX ON zoo_ks.keepers_v1 ((keeper_id, shift_date), animal_id)
+            USING 'org.zoo.Index' WITH OPTIONS = {'mode_1': 'CONTAINS'};
+        DROP AGGREGATE IF EXISTS average_1(frozen<address_type>, int);
+        CREATE TRIGGER audit_1 ON zoo_ks.keepers_v1 USING 'org.zoo.AuditTrigger';
+        DROP TRIGGER IF EXISTS audit_1 ON keepers_v1;
+        CREATE ROLE IF NOT EXISTS keeper_admin WITH PASSWORD = 'hunter_2' AND LOGIN = true
+            AND OPTIONS = {'team_1': 'zoo'} AND ACCESS TO DATACENTERS {'dc_zoo'};
+        ALTER ROLE keeper_admin WITH SUPERUSER = false AND PASSWORD = 'hunter_3';
+        GRANT SELECT ON zoo_ks.keepers_v1 TO keeper_admin;
+        GRANT ALL PERMISSIONS ON KEYSPACE zoo_ks TO keeper_admin;
+        GRANT EXECUTE ON FUNCTION zoo_ks.average_1(frozen<address_type>, int) TO keeper_admin;
+        REVOKE MODIFY ON ALL KEYSPACES FROM keeper_admin;
+        DROP ROLE IF EXISTS keeper_admin;
+        SELECT keeper_id, shift_date FROM zoo_ks.keepers_v1 WHERE keeper_id = 'Keeper 7'
+            AND shift_date >= 20240101 ORDER BY shift_date DESC LIMIT ? ALLOW FILTERING;
+        SELECT "writetime"(card_1) AS written_1, CAST(keeper_id AS text) FROM zoo_ks.keepers_v1;
+        SELECT * FROM zoo_ks.keepers_v1 ORDER BY card_1 ANN OF [0.5, -1.5] LIMIT 3;
+        SELECT DISTINCT keeper_id FROM zoo_ks.keepers_v1 WHERE TOKEN(keeper_id) > "token"(42)
+            AND shift_date < zoo_ks.to_day(12, 'Day 1');
+        SELECT token(keeper_id) FROM zoo_ks.keepers_v1 WHERE TOKEN(keeper_id) > token(?);
+        SELECT JSON * FROM zoo_ks.keepers_v1 WHERE keeper_id LIKE 'Keeper%'
+            AND shift_date IN (20240101, ?) AND visit_log CONTAINS null
+            PER PARTITION LIMIT 2 LIMIT 10;
+        SELECT keeper_id, "count"(*), zoo_ks.average_1(card_1, zoo_ks.to_day(shift_date))
+            FROM zoo_ks.keepers_v1 GROUP BY keeper_id;
+        INSERT INTO zoo_ks.keepers_v1 (keeper_id, "Keeper_Id", card_1, visit_log)
+            VALUES ('Keeper 8', 8, {card_id: 5a1c395e-b41f-11e5-9f22-ba0be0483c18, home_1: null},
+            [(20240101, {street_name: 'Main'})]) IF NOT EXISTS;
+        INSERT INTO keepers_v1 JSON '{"keeper_id": "Keeper 9"}' DEFAULT UNSET
+            USING TTL 86400 AND TIMEOUT 5s;
+        UPDATE zoo_ks.keepers_v1 USING TTL 60 AND TIMESTAMP 7 SET shift_date = 20240102,
+            visit_log = visit_log - [], card_1.home_1 = null, visit_log = [] + visit_log,
+            visit_log[0] = null WHERE keeper_id = 'Keeper 10' IF shift_date = 20240101
+            AND card_1.home_1 IN (null, 1) AND "Keeper_Id" IN :keepers_1;
+        CREATE TABLE zoo_ks.animal_visits (animal_id uuid PRIMARY KEY, visits_1 counter);
+        UPDATE zoo_ks.animal_visits SET visits_1 = visits_1 + 1 WHERE animal_id = 1;
+        DELETE FROM zoo_ks.keepers_v1 USING TIMESTAMP 8 WHERE keeper_id = 'Keeper 11'
+            AND shift_date >= 20240101 AND shift_date < 20250101
+            AND (shift_date, keeper_id) IN ((20240101, 'Keeper 13'), ?);
+        DELETE visit_log[0], card_1.home_1 FROM keepers_v1 WHERE keeper_id = 'Keeper 12' IF EXISTS;
+    "#;
+
+    /// The statements of a schema with their kinds, column types and referenced types.
+    fn shape(schema: &str) -> Vec<String> {
+        let (remaining, statements) = parse_cql(schema).unwrap();
+        assert_eq!(remaining, "");
+        let statements = resolve_references(statements, None).unwrap();
+        let index = |udt: &Rc<CqlUserDefinedType<&str>>| {
+            statements
+                .iter()
+                .position(|statement| {
+                    statement
+                        .create_user_defined_type()
+                        .is_some_and(|other| Rc::ptr_eq(udt, other))
+                })
+                .unwrap()
+        };
+        fn describe(
+            cql_type: &CqlType<Rc<CqlUserDefinedType<&str>>>,
+            index: &dyn Fn(&Rc<CqlUserDefinedType<&str>>) -> usize,
+        ) -> String {
+            match cql_type {
+                CqlType::FROZEN(inner) | CqlType::SET(inner) | CqlType::LIST(inner) => {
+                    format!("{}<{}>", cql_type.kind(), describe(inner, index))
+                }
+                CqlType::MAP(entry) => format!(
+                    "map<{}, {}>",
+                    describe(&entry.0, index),
+                    describe(&entry.1, index)
+                ),
+                CqlType::TUPLE(types) => format!(
+                    "tuple<{}>",
+                    types
+                        .iter()
+                        .map(|cql_type| describe(cql_type, index))
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ),
+                CqlType::UserDefined(udt) => format!("#{}", index(udt)),
+                cql_type => cql_type.kind().to_string(),
+            }
+        }
+
+        statements
+            .iter()
+            .map(|statement| match statement {
+                CqlStatement::CreateTable(table) => {
+                    let position = |column: &Rc<CqlColumn<&str, _>>| {
+                        table
+                            .columns()
+                            .iter()
+                            .position(|other| Rc::ptr_eq(column, other))
+                            .unwrap()
+                    };
+                    let primary_key = table.primary_key().as_ref().map(|primary_key| {
+                        (
+                            primary_key
+                                .partition_key()
+                                .iter()
+                                .map(position)
+                                .collect::<Vec<_>>(),
+                            *primary_key.composite_partition(),
+                            primary_key
+                                .clustering_columns()
+                                .iter()
+                                .map(position)
+                                .collect::<Vec<_>>(),
+                        )
+                    });
+                    let options = table.options().as_ref().map(|options| {
+                        (
+                            options.compact_storage(),
+                            options
+                                .clustering_order()
+                                .iter()
+                                .map(|(column, order)| (position(column), *order))
+                                .collect::<Vec<_>>(),
+                        )
+                    });
+                    format!(
+                        "table {} {:?} {:?} {:?} {:?}",
+                        table.if_not_exists(),
+                        table
+                            .columns()
+                            .iter()
+                            .map(|column| (
+                                describe(column.cql_type(), &index),
+                                column.is_static(),
+                                column.is_primary_key(),
+                                column.mask().as_ref().map(CqlColumnMask::is_default)
+                            ))
+                            .collect::<Vec<_>>(),
+                        primary_key,
+                        options,
+                        table.name().keyspace().is_some(),
+                    )
+                }
+                CqlStatement::Use(_) => "use".to_string(),
+                CqlStatement::CreateKeyspace(create_keyspace) => format!(
+                    "create keyspace {:?} {:?}",
+                    create_keyspace.replication().total_replicas(),
+                    create_keyspace.durable_writes()
+                ),
+                CqlStatement::DropType(_) => "drop type".to_string(),
+                CqlStatement::CreateTableLike(like) => {
+                    let source = like.source_table().as_ref().unwrap();
+                    let source = statements.iter().position(|statement| {
+                        statement
+                            .create_table()
+                            .is_some_and(|table| Rc::ptr_eq(source, table))
+                    });
+                    format!("table like {:?}", source)
+                }
+                CqlStatement::CreateTrigger(_) => "create trigger".to_string(),
+                CqlStatement::DropTrigger(_) => "drop trigger".to_string(),
+                CqlStatement::CreateRole(create_role) => {
+                    format!("create role {:?}", create_role.options().login())
+                }
+                CqlStatement::AlterRole(alter_role) => {
+                    format!("alter role {:?}", alter_role.options().superuser())
+                }
+                CqlStatement::DropRole(_) => "drop role".to_string(),
+                CqlStatement::Grant(grant) => format!("grant {:?}", grant.permission()),
+                CqlStatement::Revoke(revoke) => format!("revoke {:?}", revoke.permission()),
+                CqlStatement::CreateIndex(create_index) => {
+                    format!("index {:?}", create_index.is_local())
+                }
+                CqlStatement::Select(select) => format!(
+                    "select {} {} {:?} {:?} {:?} {:?} {:?} {:?} {:?}",
+                    select.json(),
+                    select.distinct(),
+                    match select.selection() {
+                        CqlSelection::All => None,
+                        CqlSelection::Selectors(selectors) => Some(
+                            selectors
+                                .iter()
+                                .map(|selector| match selector {
+                                    CqlSelector::Column(_) => "column",
+                                    CqlSelector::Function(_, _) => "function",
+                                    CqlSelector::Cast(_, _) => "cast",
+                                    CqlSelector::Alias(_, _) => "alias",
+                                })
+                                .collect::<Vec<_>>()
+                        ),
+                    },
+                    select.group_by().len(),
+                    select
+                        .where_clause()
+                        .as_ref()
+                        .map(|clause| clause.relations().len()),
+                    select
+                        .order_by()
+                        .iter()
+                        .map(|ordering| match ordering {
+                            CqlOrdering::Column(_, order) => Some(*order),
+                            CqlOrdering::Ann(_, _) => None,
+                        })
+                        .collect::<Vec<_>>(),
+                    select.per_partition_limit(),
+                    select.limit(),
+                    select.allow_filtering()
+                ),
+                CqlStatement::Insert(insert) => match insert.values() {
+                    CqlInsertValues::Values(values) => format!(
+                        "insert {:?} {:?}",
+                        values
+                            .iter()
+                            .map(|(_, value)| value.kind())
+                            .collect::<Vec<_>>(),
+                        insert.condition()
+                    ),
+                    CqlInsertValues::Json(_, default) => {
+                        format!(
+                            "insert json {:?} {:?} {:?}",
+                            default,
+                            insert.condition(),
+                            insert.using()
+                        )
+                    }
+                },
+                CqlStatement::Update(update) => format!(
+                    "update {:?} {:?} {} {:?}",
+                    update.using(),
+                    update
+                        .assignments()
+                        .iter()
+                        .map(|assignment| match assignment {
+                            CqlAssignment::Column(_, _) => "column",
+                            CqlAssignment::Element(_, _, _) => "element",
+                            CqlAssignment::Field(_, _, _) => "field",
+                            CqlAssignment::Append(_, _) => "append",
+                            CqlAssignment::Prepend(_, _) => "prepend",
+                            CqlAssignment::Remove(_, _) => "remove",
+                            CqlAssignment::CounterDelta(_, _, _) => "counter",
+                            CqlAssignment::BindMarkerDelta(_, _, _) => "delta",
+                        })
+                        .collect::<Vec<_>>(),
+                    update.where_clause().relations().len(),
+                    update
+                        .condition()
+                        .as_ref()
+                        .map(|condition| match condition {
+                            CqlCondition::Columns(conditions) => conditions
+                                .iter()
+                                .map(|condition| (
+                                    condition.field().is_some(),
+                                    condition.operator()
+                                ))
+                                .collect(),
+                            _ => vec![],
+                        })
+                ),
+                CqlStatement::Delete(delete) => format!(
+                    "delete {:?} {:?} {} {:?}",
+                    delete
+                        .deletions()
+                        .iter()
+                        .map(|deletion| match deletion {
+                            CqlDeletion::Column(_) => "column",
+                            CqlDeletion::Element(_, _) => "element",
+                            CqlDeletion::Field(_, _) => "field",
+                        })
+                        .collect::<Vec<_>>(),
+                    delete.using(),
+                    delete.where_clause().relations().len(),
+                    delete.condition().as_ref().map(CqlCondition::is_exists)
+                ),
+                CqlStatement::DropAggregate(drop_aggregate) => {
+                    format!("drop aggregate {:?}", drop_aggregate.signature().is_some())
+                }
+                CqlStatement::AlterType(alter_type) => {
+                    format!("alter type {}", alter_type.operation().is_add())
+                }
+                CqlStatement::AlterMaterializedView(view) => {
+                    format!("view {}", view.options().options().len())
+                }
+                CqlStatement::CreateUserDefinedType(udt) => format!(
+                    "type {:?}",
+                    udt.fields()
+                        .iter()
+                        .map(|(_, cql_type)| describe(cql_type, &index))
+                        .collect::<Vec<_>>()
+                ),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_anonymize() {
+        let (_, statements) = parse_cql(SCHEMA).unwrap();
+        let anonymized = anonymize(&statements, 7);
+
+        assert_eq!(shape(&anonymized), shape(SCHEMA));
+        for name in [
+            "zoo_ks",
+            "address_type",
+            "street_name",
+            "House",
+            "Keeper",
+            "keeper_id",
+            "card_1",
+            "visit_log",
+            "animal",
+            "Secret",
+            "zip_code",
+            "road_1",
+            "keepers_by_shift",
+            "average_1",
+            "animal_copy",
+            "animal_tags",
+            "mode_1",
+            "Copied",
+            "written_1",
+            "notes_1",
+            "Hidden",
+            "audit_1",
+            "AuditTrigger",
+            "keeper_admin",
+            "hunter_2",
+            "hunter_3",
+            "team_1",
+            "dc_zoo",
+            "--",
+            "/*",
+        ] {
+            assert!(!anonymized.contains(name), "{} in {}", name, anonymized);
+        }
+        // Only `"Keeper_Id"` needs quoting, as `keeper_id` also matches it. The built-in
+        // `"count"`, `"token"` and `"writetime"` are kept as written.
+        assert_eq!(anonymized.matches('"').count(), 14);
+        assert!(anonymized.contains(" > token(?)"), "{}", anonymized);
+    }
+
+    #[test]
+    fn test_conflicting_primary_key() {
+        for schema in [
+            "CREATE TABLE t (a int PRIMARY KEY, b text, PRIMARY KEY (b))",
+            "CREATE TABLE t (a int PRIMARY KEY, b text PRIMARY KEY)",
+        ] {
+            let (_, statements) = parse_cql(schema).unwrap();
+            let anonymized = anonymize(&statements, 7);
+            let (_, statements) = parse_cql(&anonymized).unwrap();
+            assert!(
+                resolve_references(statements, None)
+                    .unwrap_err()
+                    .is_conflicting_primary_key(),
+                "{}",
+                anonymized
+            );
+        }
+        // A clause repeating the single marked column is no conflict.
+        let (_, statements) =
+            parse_cql("CREATE TABLE t (a int PRIMARY KEY, b text, PRIMARY KEY (a))").unwrap();
+        let anonymized = anonymize(&statements, 7);
+        let (_, statements) = parse_cql(&anonymized).unwrap();
+        assert!(resolve_references(statements, None).is_ok());
+    }
+
+    #[test]
+    fn test_names_are_stable() {
+        let (_, statements) = parse_cql(SCHEMA).unwrap();
+
+        assert_eq!(anonymize(&statements, 7), anonymize(&statements, 7));
+        assert_ne!(anonymize(&statements, 7), anonymize(&statements, 8));
+
+        let anonymized = anonymize(&statements, 7);
+        let (_, statements) = parse_cql(&anonymized).unwrap();
+        let table = statements[2].create_table().unwrap();
+        let (keeper_id, quoted) = (table.columns()[0].name(), table.columns()[1].name());
+        assert!(quoted.is_quoted());
+        assert_ne!(**keeper_id, **quoted);
+        assert!(keeper_id.eq_ignore_ascii_case(quoted));
+        // Every mention of a name is replaced by the same name.
+        let keyspace = table.name().keyspace().as_ref().unwrap();
+        assert_eq!(
+            statements[0]
+                .create_user_defined_type()
+                .unwrap()
+                .name()
+                .keyspace()
+                .as_ref(),
+            Some(keyspace)
+        );
+    }
+
+    #[test]
+    fn test_fixtures() {
+        for schema in [
+            include_str!("../tests/fixtures/quoted.cql"),
+            include_str!("../tests/fixtures/comments.cql"),
+            include_str!("../tests/fixtures/composite_key.cql"),
+            include_str!("../tests/fixtures/scylla.cql"),
+            include_str!("../tests/fixtures/cassandra_4_1.cql"),
+            include_str!("../tests/fixtures/cassandra_5_0.cql"),
+        ] {
+            let (_, statements) = parse_cql(schema).unwrap();
+            let anonymized = anonymize(&statements, 1);
+            assert_eq!(shape(&anonymized), shape(schema));
+        }
+    }
+}
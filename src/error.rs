@@ -0,0 +1,582 @@
+use crate::model::{CqlIdentifier, CqlPrimaryKey, CqlQualifiedIdentifier};
+use crate::utils::{debug_event, line_column, snippet, ErrorInput};
+use derive_more::IsVariant;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use nom::error::{ContextError, ErrorKind, ParseError};
+use nom::{InputLength, Offset};
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::fmt::{Debug, Display, Formatter};
+use std::ops::Deref;
+
+/// The maximum number of characters of the input kept in a [`CqlParseError`].
+const SNIPPET_LENGTH: usize = 32;
+
+/// An error of the parser, owning everything needed to report it.
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+pub struct CqlParseError {
+    /// The byte offset into the input at which parsing failed.
+    #[getset(get_copy = "pub")]
+    offset: usize,
+    /// The input at the failure, up to the end of the line.
+    #[getset(get = "pub")]
+    snippet: String,
+    /// A description of the failure.
+    #[getset(get = "pub")]
+    message: String,
+    /// What could have followed at the failure, keywords like `"CREATE"`, or placeholders like
+    /// `"identifier"`, sorted.
+    #[getset(get = "pub")]
+    expected: Vec<String>,
+}
+
+impl CqlParseError {
+    /// Creates an error at a position of the input.
+    pub(crate) fn new(input: &str, position: &str, message: impl AsRef<str>) -> Self {
+        let offset = input.offset(position);
+        debug_event!(offset, message = message.as_ref(), "parse error");
+
+        Self {
+            offset,
+            snippet: snippet(position, SNIPPET_LENGTH).to_string(),
+            message: message.as_ref().to_string(),
+            expected: Vec::new(),
+        }
+    }
+
+    /// Converts a nom error, `input` is the complete input handed to the parser.
+    pub(crate) fn from_nom(input: &str, error: nom::Err<RichError<&str>>) -> Self {
+        let error = match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => error,
+            nom::Err::Incomplete(_) => {
+                return Self::new(input, &input[input.len()..], "unexpected end of input")
+            }
+        };
+        let expected = error
+            .expected
+            .iter()
+            .map(|expected| expected.to_string())
+            .collect::<Vec<_>>();
+        let message = match (error.context, expected.as_slice()) {
+            (Some(context), _) => context.to_string(),
+            (None, []) if error.input.is_empty() => "unexpected end of input".to_string(),
+            (None, []) => "unexpected input".to_string(),
+            (None, [expected]) => format!("expected {}", expected),
+            (None, expected) => format!("expected one of {}", expected.join(", ")),
+        };
+
+        Self {
+            expected,
+            ..Self::new(input, error.input, message)
+        }
+    }
+}
+
+impl CqlParseError {
+    /// Converts an error of [`parse_cql`](crate::parse_cql), `input` is the complete input
+    /// handed to it.
+    ///
+    /// A nom error only knows the failing position and its [`ErrorKind`], so the input is parsed
+    /// again to report what was expected there. Calling
+    /// [`parse_cql_complete`](crate::parse_cql_complete) directly saves the second pass.
+    ///
+    /// ```rust
+    /// use cql_nom::error::CqlParseError;
+    /// use cql_nom::parse_cql;
+    ///
+    /// let input = "CREATE TABLE my_table (my_field int STATIC STATIC)";
+    /// let error = parse_cql(input).map(|_| ()).unwrap_err();
+    /// let error: Box<dyn std::error::Error> =
+    ///     Box::new(CqlParseError::from_nom_error(input, error));
+    /// assert_eq!(error.to_string(), "duplicate STATIC modifier at offset 43: `STATIC)`");
+    /// ```
+    pub fn from_nom_error(input: &str, error: nom::Err<nom::error::Error<&str>>) -> Self {
+        let error = match error {
+            nom::Err::Error(error) | nom::Err::Failure(error) => error,
+            nom::Err::Incomplete(_) => {
+                return Self::new(input, &input[input.len()..], "unexpected end of input")
+            }
+        };
+        let offset = input.len() - error.input.len();
+        match crate::parse_cql_complete(input) {
+            Err(rich) if rich.offset == offset => rich,
+            _ if error.input.is_empty() => Self::new(input, error.input, "unexpected end of input"),
+            _ => Self::new(input, error.input, "unexpected input"),
+        }
+    }
+}
+
+/// The error used by the parser internally.
+///
+/// Alternatives failing at the same position are merged, so the error knows everything that
+/// was expected at the furthest position the parser reached. Productions are labeled with
+/// [`context`](nom::error::context), a label is expected if its production failed without
+/// consuming input.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct RichError<I> {
+    input: I,
+    kind: ErrorKind,
+    expected: BTreeSet<&'static str>,
+    /// The message of a failed check, these use [`ErrorKind::Verify`].
+    context: Option<&'static str>,
+}
+
+impl<I: InputLength> ParseError<I> for RichError<I> {
+    fn from_error_kind(input: I, kind: ErrorKind) -> Self {
+        Self {
+            input,
+            kind,
+            expected: BTreeSet::new(),
+            context: None,
+        }
+    }
+
+    fn append(_: I, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn or(mut self, mut other: Self) -> Self {
+        match self.input.input_len().cmp(&other.input.input_len()) {
+            Ordering::Less => self,
+            Ordering::Greater => other,
+            Ordering::Equal => {
+                other.expected.append(&mut self.expected);
+                other.context = other.context.or(self.context);
+                other
+            }
+        }
+    }
+}
+
+impl<I> ErrorInput<I> for RichError<I> {
+    fn error_input(&self) -> Option<&I> {
+        Some(&self.input)
+    }
+}
+
+impl<I: InputLength> ContextError<I> for RichError<I> {
+    fn add_context(input: I, context: &'static str, mut other: Self) -> Self {
+        if other.kind == ErrorKind::Verify {
+            other.context = other.context.or(Some(context));
+        } else if other.input.input_len() == input.input_len() {
+            other.expected.insert(context);
+        }
+
+        other
+    }
+}
+
+impl Display for CqlParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.snippet.is_empty() {
+            write!(f, "{} at offset {}", self.message, self.offset)
+        } else {
+            write!(
+                f,
+                "{} at offset {}: `{}`",
+                self.message, self.offset, self.snippet
+            )
+        }
+    }
+}
+
+impl std::error::Error for CqlParseError {}
+
+/// An error of a statement, as reported by
+/// [`parse_cql_recovering`](crate::parse_cql_recovering).
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+pub struct Diagnostic {
+    /// The byte offset into the input at which parsing failed.
+    #[getset(get_copy = "pub")]
+    offset: usize,
+    /// The one based line of the offset.
+    #[getset(get_copy = "pub")]
+    line: usize,
+    /// The one based column of the offset, in characters.
+    #[getset(get_copy = "pub")]
+    column: usize,
+    /// The error of the statement.
+    #[getset(get = "pub")]
+    error: CqlParseError,
+}
+
+impl Diagnostic {
+    /// Locates the error of a statement in the input.
+    pub(crate) fn new(input: &str, error: CqlParseError) -> Self {
+        let offset = error.offset;
+        let (line, column) = line_column(input, offset);
+
+        Self {
+            offset,
+            line,
+            column,
+            error,
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} at line {}, column {}",
+            self.error.message, self.line, self.column
+        )
+    }
+}
+
+/// A statement skipped by [`parse_cql_lenient`](crate::parse_cql_lenient), because it could not
+/// be parsed.
+#[derive(Debug, Clone, PartialEq, Eq, Getters, CopyGetters)]
+pub struct SkippedStatement<I> {
+    /// The source text of the statement, without the terminating semicolon.
+    #[getset(get = "pub")]
+    text: I,
+    /// The byte offset of the statement in the input.
+    #[getset(get_copy = "pub")]
+    offset: usize,
+}
+
+impl<I> SkippedStatement<I> {
+    /// Creates a skipped statement.
+    pub(crate) fn new(text: I, offset: usize) -> Self {
+        Self { text, offset }
+    }
+}
+
+/// An error converting a [`CqlTerm`](crate::model::CqlTerm) into a
+/// [`CqlValue`](crate::model::CqlValue), or a value into a Rust type.
+#[derive(Debug, Clone, PartialEq, Eq, IsVariant)]
+pub enum CqlValueError {
+    /// The literal or value is of a kind the type does not accept.
+    Mismatch {
+        /// The expected type.
+        expected: &'static str,
+        /// The kind of the literal or value.
+        found: &'static str,
+    },
+    /// The literal is of an accepted kind, but not a valid value of the type.
+    Invalid {
+        /// The expected type.
+        expected: &'static str,
+        /// The literal.
+        literal: String,
+        /// Why the literal is invalid.
+        reason: &'static str,
+    },
+    /// A tuple literal with a different number of components than its type.
+    TupleLength {
+        /// The number of components of the type.
+        expected: usize,
+        /// The number of components of the literal.
+        found: usize,
+    },
+    /// A vector literal with a different number of elements than its dimension.
+    VectorLength {
+        /// The dimension of the type.
+        expected: usize,
+        /// The number of elements of the literal.
+        found: usize,
+    },
+    /// A user defined type literal with a field the type does not have.
+    UnknownField {
+        /// The name of the field.
+        field: String,
+    },
+}
+
+impl Display for CqlValueError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CqlValueError::Mismatch { expected, found } => {
+                write!(f, "expected {}, found {}", expected, found)
+            }
+            CqlValueError::Invalid {
+                expected,
+                literal,
+                reason,
+            } => write!(f, "invalid {} `{}`: {}", expected, literal, reason),
+            CqlValueError::TupleLength { expected, found } => write!(
+                f,
+                "expected a tuple of {} components, found {}",
+                expected, found
+            ),
+            CqlValueError::VectorLength { expected, found } => write!(
+                f,
+                "expected a vector of {} elements, found {}",
+                expected, found
+            ),
+            CqlValueError::UnknownField { field } => write!(f, "unknown field `{}`", field),
+        }
+    }
+}
+
+impl std::error::Error for CqlValueError {}
+
+/// The error of [`CqlType::validate_literal`](crate::model::CqlType::validate_literal).
+pub type LiteralError = CqlValueError;
+
+/// An error of [`resolve_references`](crate::resolve_references).
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str>)]
+pub enum CqlResolveError<I> {
+    /// A user defined type or column which does not exist, with the keyspace it was looked up in.
+    Unresolved(CqlQualifiedIdentifier<I>),
+    /// The primary key of a table is declared twice.
+    ConflictingPrimaryKey {
+        /// The table.
+        table: CqlQualifiedIdentifier<I>,
+        /// The first declaration.
+        first: CqlPrimaryKeyDeclaration<I>,
+        /// The declaration conflicting with the first one.
+        second: CqlPrimaryKeyDeclaration<I>,
+    },
+}
+
+/// A declaration of the primary key of a table.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str>)]
+pub enum CqlPrimaryKeyDeclaration<I> {
+    /// A column marked `PRIMARY KEY`, with the name of the column.
+    Column(CqlIdentifier<I>),
+    /// A `PRIMARY KEY (...)` clause.
+    Clause(Box<CqlPrimaryKey<CqlIdentifier<I>>>),
+}
+
+impl<I> From<CqlQualifiedIdentifier<I>> for CqlResolveError<I> {
+    fn from(identifier: CqlQualifiedIdentifier<I>) -> Self {
+        CqlResolveError::Unresolved(identifier)
+    }
+}
+
+impl<I: Deref<Target = str>> Display for CqlPrimaryKeyDeclaration<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CqlPrimaryKeyDeclaration::Column(column) => write!(f, "{} PRIMARY KEY", column),
+            CqlPrimaryKeyDeclaration::Clause(primary_key) => {
+                write!(f, "PRIMARY KEY {}", primary_key)
+            }
+        }
+    }
+}
+
+impl<I: Deref<Target = str>> Display for CqlResolveError<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CqlResolveError::Unresolved(identifier) => {
+                write!(f, "unresolved reference `{}`", identifier)
+            }
+            CqlResolveError::ConflictingPrimaryKey {
+                table,
+                first,
+                second,
+            } => write!(
+                f,
+                "conflicting primary key declarations `{}` and `{}` in table `{}`",
+                first, second, table
+            ),
+        }
+    }
+}
+
+impl<I: Debug + Deref<Target = str>> std::error::Error for CqlResolveError<I> {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_cql, parse_cql_statement, resolve_references};
+
+    const TYPES: [&str; 28] = [
+        "ASCII",
+        "BIGINT",
+        "BLOB",
+        "BOOLEAN",
+        "COUNTER",
+        "DATE",
+        "DECIMAL",
+        "DOUBLE",
+        "DURATION",
+        "FLOAT",
+        "FROZEN",
+        "INET",
+        "INT",
+        "LIST",
+        "MAP",
+        "SET",
+        "SMALLINT",
+        "TEXT",
+        "TIME",
+        "TIMESTAMP",
+        "TIMEUUID",
+        "TINYINT",
+        "TUPLE",
+        "UUID",
+        "VARCHAR",
+        "VARINT",
+        "VECTOR",
+        "identifier",
+    ];
+
+    fn expected(input: &str) -> Vec<String> {
+        let error = parse_cql_statement(input).unwrap_err();
+        assert_eq!(error.offset(), input.len(), "{}", error);
+
+        error.expected().clone()
+    }
+
+    #[test]
+    fn test_expected_statement_kind() {
+        assert_eq!(
+            expected("CREATE "),
+            vec!["INDEX", "KEYSPACE", "ROLE", "TABLE", "TRIGGER", "TYPE"]
+        );
+    }
+
+    #[test]
+    fn test_expected_name() {
+        assert_eq!(expected("CREATE TABLE "), vec!["identifier"]);
+        assert_eq!(expected("CREATE TABLE my_table "), vec!["(", "LIKE"]);
+    }
+
+    #[test]
+    fn test_expected_column_type() {
+        assert_eq!(expected("CREATE TABLE my_table (my_column "), TYPES);
+        assert_eq!(expected("CREATE TYPE my_type (my_field frozen<"), TYPES);
+        assert_eq!(expected("CREATE TYPE my_type (my_field map<int, "), TYPES);
+    }
+
+    #[test]
+    fn test_expected_column_or_end() {
+        assert_eq!(expected("CREATE TYPE my_type ("), vec![")", "identifier"]);
+        assert_eq!(
+            expected("CREATE TABLE my_table (my_column int, "),
+            vec!["PRIMARY KEY", "identifier"]
+        );
+    }
+
+    #[test]
+    fn test_expected_options() {
+        let input = "CREATE TABLE my_table (my_column int PRIMARY KEY) WITH ";
+        let expected = expected(input);
+        assert_eq!(
+            expected[..3],
+            ["CLUSTERING", "COMPACT", "bloom_filter_fp_chance"]
+        );
+        assert!(expected.contains(&"gc_grace_seconds".to_string()));
+        assert!(expected.contains(&"option".to_string()));
+        let error = parse_cql_statement(input).unwrap_err();
+        assert!(error
+            .message()
+            .starts_with("expected one of CLUSTERING, COMPACT, bloom_filter_fp_chance, "));
+    }
+
+    #[test]
+    fn test_invalid_option() {
+        let input = "CREATE TABLE my_table (my_column int PRIMARY KEY) WITH bogus";
+        let error = parse_cql_statement(input).unwrap_err();
+        assert_eq!(error.offset(), input.len() - "bogus".len());
+        assert_eq!(error.message(), "invalid option");
+    }
+
+    #[test]
+    fn test_from_nom_error() {
+        let input = "CREATE TABLE my_table (my_field int) WITH comment = 'x' AND bogus";
+        let error = crate::parse_cql(input).map(|_| ()).unwrap_err();
+        let error = CqlParseError::from_nom_error(input, error);
+        assert_eq!(error.offset(), input.len() - "bogus".len());
+        assert_eq!(error.message(), "invalid option");
+
+        // An error not reproduced by the parser keeps its position.
+        let error = nom::Err::Error(nom::error::Error::new(&input[37..], ErrorKind::Tag));
+        let error = CqlParseError::from_nom_error(input, error);
+        assert_eq!(error.offset(), 37);
+        assert_eq!(error.snippet(), "WITH comment = 'x' AND bogus");
+        assert_eq!(error.message(), "unexpected input");
+        let error =
+            CqlParseError::from_nom_error(input, nom::Err::Incomplete(nom::Needed::Unknown));
+        assert_eq!(error.offset(), input.len());
+    }
+
+    #[test]
+    fn test_boxed() {
+        fn parse(input: &str) -> Result<usize, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(crate::parse_cql_complete(input)?.len())
+        }
+
+        assert_eq!(parse("DROP TYPE a; DROP TYPE b").unwrap(), 2);
+        let error = parse("DROP TYPE a DROP TYPE b").unwrap_err();
+        assert_eq!(error.downcast_ref::<CqlParseError>().unwrap().offset(), 12);
+    }
+
+    fn resolve(input: &str) -> Result<(), CqlResolveError<&str>> {
+        let (remaining, statements) = parse_cql(input).unwrap();
+        assert_eq!(remaining, "");
+
+        resolve_references(statements, None).map(|_| ())
+    }
+
+    #[test]
+    fn test_primary_key_marker_and_same_clause() {
+        assert_eq!(
+            resolve("CREATE TABLE my_table (a1 int PRIMARY KEY, b1 text, PRIMARY KEY (a1))"),
+            Ok(())
+        );
+        assert_eq!(
+            resolve("CREATE TABLE my_table (a1 int PRIMARY KEY, b1 text, PRIMARY KEY ((a1)))"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_primary_key_marker_and_other_clause() {
+        let error =
+            resolve("CREATE TABLE my_table (a1 int PRIMARY KEY, b1 text, PRIMARY KEY (a1, b1))")
+                .unwrap_err();
+        assert_eq!(
+            error,
+            CqlResolveError::ConflictingPrimaryKey {
+                table: CqlQualifiedIdentifier::new(None, CqlIdentifier::new("my_table")),
+                first: CqlPrimaryKeyDeclaration::Column(CqlIdentifier::new("a1")),
+                second: CqlPrimaryKeyDeclaration::Clause(Box::new(CqlPrimaryKey::new(
+                    vec![CqlIdentifier::new("a1")],
+                    false,
+                    vec![CqlIdentifier::new("b1")],
+                ))),
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "conflicting primary key declarations `a1 PRIMARY KEY` and `PRIMARY KEY (a1, b1)` \
+             in table `my_table`"
+        );
+        assert!(
+            resolve("CREATE TABLE my_table (a1 int PRIMARY KEY, b1 text, PRIMARY KEY (b1))")
+                .unwrap_err()
+                .is_conflicting_primary_key()
+        );
+    }
+
+    #[test]
+    fn test_primary_key_marked_twice() {
+        let error =
+            resolve("CREATE TABLE my_table (a1 int PRIMARY KEY, b1 text PRIMARY KEY)").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "conflicting primary key declarations `a1 PRIMARY KEY` and `b1 PRIMARY KEY` \
+             in table `my_table`"
+        );
+    }
+
+    #[test]
+    fn test_primary_key_declared_once() {
+        assert_eq!(
+            resolve("CREATE TABLE my_table (a1 int PRIMARY KEY, b1 text)"),
+            Ok(())
+        );
+        assert_eq!(
+            resolve("CREATE TABLE my_table (a1 int, b1 text, PRIMARY KEY (a1, b1))"),
+            Ok(())
+        );
+    }
+}
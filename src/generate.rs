@@ -0,0 +1,392 @@
+use crate::model::*;
+use derive_new::new;
+use getset::CopyGetters;
+use rand::Rng;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::rc::Rc;
+
+/// A resolved table.
+pub type ResolvedTable<'a> = ResolvedCqlTable<&'a str>;
+
+/// The first timestamp generated, `2000-01-01`, in milliseconds since the unix epoch.
+const MIN_TIMESTAMP: i64 = 946_684_800_000;
+/// The last timestamp generated, `2040-01-01`, in milliseconds since the unix epoch.
+const MAX_TIMESTAMP: i64 = 2_208_988_800_000;
+const NANOSECONDS_PER_DAY: i64 = 86_400 * 1_000_000_000;
+
+/// Bounds of the generated values.
+#[derive(Debug, Clone, Copy, PartialEq, CopyGetters, new)]
+pub struct GeneratorOptions {
+    /// The maximum number of elements of a collection.
+    #[getset(get_copy = "pub")]
+    max_collection_size: usize,
+    /// The maximum length of texts and blobs.
+    #[getset(get_copy = "pub")]
+    max_text_length: usize,
+    /// The probability of a column or field outside of the primary key being null.
+    #[getset(get_copy = "pub")]
+    null_probability: f64,
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self::new(3, 16, 0.1)
+    }
+}
+
+/// Generates values of the types of a schema.
+///
+/// The values only depend on the state of the random number generator, a generator seeded with
+/// the same seed generates the same values.
+#[derive(Debug, Clone, new)]
+pub struct Generator<R> {
+    rng: R,
+    options: GeneratorOptions,
+}
+
+impl<R: Rng> Generator<R> {
+    /// Generates a row of the table, a value for each column in declaration order.
+    ///
+    /// Columns of the primary key are never null.
+    pub fn row(&mut self, table: &ResolvedTable<'_>) -> Vec<(String, CqlValue)> {
+        let (partition_key, clustering_columns) = table.key_columns();
+        let key = partition_key
+            .into_iter()
+            .chain(clustering_columns)
+            .collect::<Vec<_>>();
+
+        table
+            .columns()
+            .iter()
+            .map(|column| {
+                let is_key = key.iter().any(|key| std::ptr::eq(*key, &**column));
+                let value = if is_key {
+                    self.key(column.cql_type())
+                } else {
+                    self.nullable(column.cql_type())
+                };
+
+                (column.name().to_string(), value)
+            })
+            .collect()
+    }
+
+    /// Generates a row of the table, rendered as an `INSERT` statement.
+    pub fn insert(&mut self, table: &ResolvedTable<'_>) -> String {
+        let row = self.row(table);
+        let columns = table
+            .columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect::<Vec<_>>();
+        let values = row
+            .iter()
+            .map(|(_, value)| value.to_string())
+            .collect::<Vec<_>>();
+
+        format!(
+            "INSERT INTO {} ({}) VALUES ({});",
+            table.name(),
+            columns.join(", "),
+            values.join(", ")
+        )
+    }
+
+    /// Generates a value of the type, which is never null.
+    pub fn value(&mut self, cql_type: &CqlType<Rc<CqlUserDefinedType<&str>>>) -> CqlValue {
+        self.generate(cql_type, false)
+    }
+
+    /// Generates a value of a key column, texts and blobs are not empty.
+    fn key(&mut self, cql_type: &CqlType<Rc<CqlUserDefinedType<&str>>>) -> CqlValue {
+        self.generate(cql_type, true)
+    }
+
+    fn nullable(&mut self, cql_type: &CqlType<Rc<CqlUserDefinedType<&str>>>) -> CqlValue {
+        if self.rng.gen_bool(self.options.null_probability) {
+            CqlValue::Null
+        } else {
+            self.generate(cql_type, false)
+        }
+    }
+
+    fn generate(
+        &mut self,
+        cql_type: &CqlType<Rc<CqlUserDefinedType<&str>>>,
+        non_empty: bool,
+    ) -> CqlValue {
+        let min_length = usize::from(non_empty);
+        match cql_type {
+            CqlType::ASCII | CqlType::TEXT | CqlType::VARCHAR => {
+                CqlValue::Text(self.text(min_length))
+            }
+            CqlType::TINYINT => CqlValue::TinyInt(self.rng.gen()),
+            CqlType::SMALLINT => CqlValue::SmallInt(self.rng.gen()),
+            CqlType::INT => CqlValue::Int(self.rng.gen()),
+            CqlType::BIGINT | CqlType::COUNTER => CqlValue::BigInt(self.rng.gen()),
+            CqlType::VARINT => CqlValue::Varint(self.rng.gen::<i64>().to_string()),
+            CqlType::FLOAT => CqlValue::Float(self.rng.gen_range(-1e6..1e6)),
+            CqlType::DOUBLE => CqlValue::Double(self.rng.gen_range(-1e12..1e12)),
+            CqlType::DECIMAL => CqlValue::Decimal(format!(
+                "{}.{:02}",
+                self.rng.gen_range(-1_000_000..1_000_000),
+                self.rng.gen_range(0..100)
+            )),
+            CqlType::BOOLEAN => CqlValue::Boolean(self.rng.gen()),
+            CqlType::UUID => CqlValue::Uuid(self.uuid(4)),
+            CqlType::TIMEUUID => CqlValue::Uuid(self.uuid(1)),
+            CqlType::TIMESTAMP => {
+                CqlValue::Timestamp(self.rng.gen_range(MIN_TIMESTAMP..MAX_TIMESTAMP))
+            }
+            CqlType::DATE => CqlValue::Date(
+                self.rng
+                    .gen_range(MIN_TIMESTAMP / 86_400_000..MAX_TIMESTAMP / 86_400_000)
+                    as i32,
+            ),
+            CqlType::TIME => CqlValue::Time(self.rng.gen_range(0..NANOSECONDS_PER_DAY)),
+            CqlType::INET => CqlValue::Inet(if self.rng.gen_bool(0.8) {
+                IpAddr::V4(Ipv4Addr::from(self.rng.gen::<u32>()))
+            } else {
+                IpAddr::V6(Ipv6Addr::from(self.rng.gen::<u128>()))
+            }),
+            CqlType::BLOB => {
+                let length = self.rng.gen_range(min_length..=self.max_length(min_length));
+                CqlValue::Blob((0..length).map(|_| self.rng.gen()).collect())
+            }
+            CqlType::DURATION => CqlValue::Duration(CqlDuration::new(
+                self.rng.gen_range(0..24),
+                self.rng.gen_range(0..31),
+                self.rng.gen_range(0..NANOSECONDS_PER_DAY),
+            )),
+            CqlType::FROZEN(cql_type) => self.generate(cql_type, non_empty),
+            CqlType::LIST(element) => {
+                let length = self.rng.gen_range(0..=self.options.max_collection_size);
+                CqlValue::List((0..length).map(|_| self.value(element)).collect())
+            }
+            CqlType::VECTOR(element, dimension) => {
+                CqlValue::List((0..*dimension).map(|_| self.value(element)).collect())
+            }
+            CqlType::SET(element) => {
+                let mut elements = Vec::new();
+                for _ in 0..self.rng.gen_range(0..=self.options.max_collection_size) {
+                    let element = self.value(element);
+                    if !elements.contains(&element) {
+                        elements.push(element);
+                    }
+                }
+                CqlValue::Set(elements)
+            }
+            CqlType::MAP(entry) => {
+                let (key, value) = &**entry;
+                let mut entries: Vec<(CqlValue, CqlValue)> = Vec::new();
+                for _ in 0..self.rng.gen_range(0..=self.options.max_collection_size) {
+                    let key = self.value(key);
+                    let value = self.value(value);
+                    if !entries.iter().any(|(k, _)| *k == key) {
+                        entries.push((key, value));
+                    }
+                }
+                CqlValue::Map(entries)
+            }
+            CqlType::TUPLE(types) => CqlValue::Tuple(
+                types
+                    .iter()
+                    .map(|cql_type| self.nullable(cql_type))
+                    .collect(),
+            ),
+            CqlType::UserDefined(udt) => CqlValue::Udt(
+                udt.fields()
+                    .iter()
+                    .map(|(name, cql_type)| (name.folded(), self.nullable(cql_type)))
+                    .collect(),
+            ),
+        }
+    }
+
+    fn max_length(&self, min_length: usize) -> usize {
+        self.options.max_text_length.max(min_length)
+    }
+
+    fn text(&mut self, min_length: usize) -> String {
+        const ALPHABET: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789 '";
+
+        let length = self.rng.gen_range(min_length..=self.max_length(min_length));
+        (0..length)
+            .map(|_| char::from(ALPHABET[self.rng.gen_range(0..ALPHABET.len())]))
+            .collect()
+    }
+
+    /// Generates a uuid of the version, with the RFC 4122 variant.
+    fn uuid(&mut self, version: u128) -> u128 {
+        let uuid = self.rng.gen::<u128>();
+        let uuid = (uuid & !(0xf << 76)) | (version << 76);
+
+        (uuid & !(0b11 << 62)) | (0b10 << 62)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::insert::CqlInsertValues;
+    use crate::{parse_cql, parse_cql_statement, resolve_references};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    const SCHEMA: &str = r#"
+        CREATE TYPE zoo_ks.address_type (street_name text, "House Number" int);
+        CREATE TABLE zoo_ks.keepers_v1 (
+            keeper_id timeuuid,
+            shift_date date,
+            full_name text,
+            home_address frozen<address_type>,
+            skill_set set<text>,
+            rating_map map<text, double>,
+            visit_log list<frozen<tuple<timestamp, inet>>>,
+            contract_1 blob,
+            over_time duration,
+            salary_2 decimal,
+            PRIMARY KEY ((keeper_id), shift_date)
+        );
+    "#;
+
+    fn generate(
+        seed: u64,
+        options: GeneratorOptions,
+        f: impl FnOnce(&mut Generator<StdRng>, &ResolvedTable<'_>),
+    ) {
+        let (_, statements) = parse_cql(SCHEMA).unwrap();
+        let statements = resolve_references(statements, None).unwrap();
+        let table = statements[1].create_table().unwrap();
+        let mut generator = Generator::new(StdRng::seed_from_u64(seed), options);
+        f(&mut generator, table);
+    }
+
+    fn inserts(seed: u64) -> Vec<String> {
+        let mut inserts = Vec::new();
+        generate(seed, GeneratorOptions::default(), |generator, table| {
+            inserts = (0..10).map(|_| generator.insert(table)).collect();
+        });
+
+        inserts
+    }
+
+    #[test]
+    fn test_deterministic() {
+        assert_eq!(inserts(7), inserts(7));
+        assert_ne!(inserts(7), inserts(8));
+    }
+
+    #[test]
+    fn test_primary_key_populated() {
+        let options = GeneratorOptions::new(3, 0, 1.0);
+        generate(1, options, |generator, table| {
+            for _ in 0..10 {
+                let row = generator.row(table);
+                assert!(row[0].1.is_uuid());
+                assert!(row[1].1.is_date());
+                assert!(row[2..].iter().all(|(_, value)| value.is_null()));
+            }
+        });
+    }
+
+    #[test]
+    fn test_values_conform_to_the_schema() {
+        generate(3, GeneratorOptions::new(4, 8, 0.2), |generator, table| {
+            for _ in 0..50 {
+                let row = generator.row(table);
+                for ((name, value), column) in row.iter().zip(table.columns()) {
+                    assert_eq!(name, &column.name().to_string());
+                    assert_conforms(value, column.cql_type());
+                }
+                let keeper_id = row[0].1.as_uuid().unwrap();
+                assert_eq!((keeper_id >> 76) & 0xf, 1);
+                for element in row[4].1.as_set().unwrap_or_default() {
+                    assert!(element.as_text().unwrap().len() <= 8);
+                }
+            }
+        });
+    }
+
+    #[test]
+    fn test_insert() {
+        let insert = &inserts(5)[0];
+        assert!(insert.starts_with(
+            "INSERT INTO zoo_ks.keepers_v1 (keeper_id, shift_date, full_name, home_address, \
+             skill_set, rating_map, visit_log, contract_1, over_time, salary_2) VALUES ("
+        ));
+        assert!(insert.ends_with(");"));
+    }
+
+    #[test]
+    fn test_insert_parses_back() {
+        generate(5, GeneratorOptions::new(3, 4, 0.2), |generator, table| {
+            for _ in 0..20 {
+                let insert = generator.insert(table);
+                let statement = parse_cql_statement(&insert).unwrap();
+                let CqlInsertValues::Values(values) = statement.insert().unwrap().values() else {
+                    panic!("expected values, got {}", insert);
+                };
+                assert_eq!(values.len(), table.columns().len());
+                for ((name, term), column) in values.iter().zip(table.columns()) {
+                    assert_eq!(name, column.name());
+                    if let Err(error) = column.cql_type().validate_literal(term) {
+                        panic!("{} in {}: {}", name, insert, error);
+                    }
+                }
+            }
+        });
+    }
+
+    fn assert_conforms(value: &CqlValue, cql_type: &CqlType<Rc<CqlUserDefinedType<&str>>>) {
+        let conforms = match (cql_type, value) {
+            (_, CqlValue::Null) => true,
+            (CqlType::FROZEN(cql_type), value) => {
+                assert_conforms(value, cql_type);
+                true
+            }
+            (CqlType::TEXT, CqlValue::Text(_))
+            | (CqlType::TIMEUUID, CqlValue::Uuid(_))
+            | (CqlType::DATE, CqlValue::Date(_))
+            | (CqlType::DOUBLE, CqlValue::Double(_))
+            | (CqlType::TIMESTAMP, CqlValue::Timestamp(_))
+            | (CqlType::INET, CqlValue::Inet(_))
+            | (CqlType::BLOB, CqlValue::Blob(_))
+            | (CqlType::DURATION, CqlValue::Duration(_))
+            | (CqlType::DECIMAL, CqlValue::Decimal(_))
+            | (CqlType::INT, CqlValue::Int(_)) => true,
+            (CqlType::SET(element), CqlValue::Set(elements))
+            | (CqlType::LIST(element), CqlValue::List(elements)) => {
+                elements.iter().for_each(|e| assert_conforms(e, element));
+                true
+            }
+            (CqlType::VECTOR(element, dimension), CqlValue::List(elements)) => {
+                elements.iter().for_each(|e| assert_conforms(e, element));
+                elements.len() == *dimension as usize
+            }
+            (CqlType::MAP(entry), CqlValue::Map(entries)) => {
+                for (key, value) in entries {
+                    assert_conforms(key, &entry.0);
+                    assert_conforms(value, &entry.1);
+                }
+                true
+            }
+            (CqlType::TUPLE(types), CqlValue::Tuple(elements)) => {
+                types
+                    .iter()
+                    .zip(elements)
+                    .for_each(|(cql_type, e)| assert_conforms(e, cql_type));
+                types.len() == elements.len()
+            }
+            (CqlType::UserDefined(udt), CqlValue::Udt(fields)) => {
+                for ((name, cql_type), (field, value)) in udt.fields().iter().zip(fields) {
+                    assert_eq!(&name.folded(), field);
+                    assert_conforms(value, cql_type);
+                }
+                udt.fields().len() == fields.len()
+            }
+            _ => false,
+        };
+        assert!(conforms, "{:?} is not a {:?}", value, cql_type);
+    }
+}
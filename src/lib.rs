@@ -16,75 +16,504 @@
 //! let result = parse_cql(input).unwrap();
 //! ```
 //!
+//! ## Untrusted input
+//!
+//! The parser never panics, any input is either parsed or an error. Deeply nested types, terms
+//! and selectors are an error instead of overflowing the stack. The `fuzz` directory contains a
+//! [cargo-fuzz](https://github.com/rust-fuzz/cargo-fuzz) target checking this.
+//!
 //! The code is available on [GitHub](https://github.com/28Smiles/cql-nom).
 
+use crate::error::{CqlParseError, CqlResolveError, Diagnostic, SkippedStatement};
+use crate::lookup::SpannedStatement;
+use crate::model::cql_type::CqlType;
+use crate::model::documented::DocumentedStatement;
 use crate::model::identifier::CqlIdentifier;
-use crate::model::qualified_identifier::CqlQualifiedIdentifier;
-use crate::model::statement::CqlStatement;
-use crate::model::table::column::CqlColumn;
-use crate::model::table::CqlTable;
-use crate::model::user_defined_type::{CqlUserDefinedType, ParsedCqlUserDefinedType};
+use crate::model::span::Spanned;
+use crate::model::statement::{CqlStatement, ParsedCqlStatement, ResolvedCqlStatement};
 use crate::parse::Parse;
-use crate::utils::space0_around;
+use crate::utils::{
+    debug_event, doc_comment, find_statement_end, line_column, space0_around, traced_statements,
+    ws0, Whitespace,
+};
 use nom::bytes::complete::tag;
-use nom::character::complete::multispace0;
 use nom::combinator::opt;
 use nom::multi::separated_list0;
 use nom::IResult;
-use std::rc::Rc;
 
+/// Renaming of schemas, to share them without their names.
+pub mod anonymize;
+/// Errors of the parser.
+pub mod error;
+/// Pretty printing of parsed statements.
+pub mod format;
+/// Generation of rows consistent with a schema, for tests.
+#[cfg(feature = "rand")]
+pub mod generate;
+/// Lookup of tree nodes by their position in the input.
+pub mod lookup;
 /// The tree elements of the Cassandra Query Language.
 pub mod model;
 mod parse;
+mod print;
+/// Lazily resolved schemas.
+pub mod schema;
+/// Size estimation of rows and partitions.
+pub mod size;
+/// Incremental parsing of CQL text arriving in chunks.
+pub mod stream;
 mod utils;
+/// Bindings exposing the parser to JavaScript.
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 /// Parses a CQL statement into a tree.
-pub fn parse_cql(
+pub fn parse_cql(input: &str) -> IResult<&str, Vec<ParsedCqlStatement<&str>>> {
+    let (input, statements) = separated_list0(
+        tag(";"),
+        space0_around(traced_statements(CqlStatement::parse)),
+    )(input)?;
+    let (input, _) = opt(tag(";"))(input)?;
+    let (input, _) = ws0(input)?;
+
+    Ok((input, statements))
+}
+
+/// Parses CQL statements into a tree, failing unless the whole input is parsed.
+///
+/// Unlike [`parse_cql`], which returns the input it could not parse, anything but whitespace and
+/// comments after the last statement is an error. A statement failing to parse reports the
+/// position it failed at.
+///
+/// ```rust
+/// use cql_nom::parse_cql_complete;
+///
+/// let statements = parse_cql_complete("CREATE TYPE a (b int); CREATE TYPE c (d int); -- done");
+/// assert_eq!(statements.unwrap().len(), 2);
+///
+/// let error = parse_cql_complete("CREATE TYPE a (b int); CREATE TABLE t (a in t)").unwrap_err();
+/// assert_eq!(error.offset(), 41);
+/// assert_eq!(error.snippet(), "in t)");
+/// ```
+pub fn parse_cql_complete(input: &str) -> Result<Vec<ParsedCqlStatement<&str>>, CqlParseError> {
+    use crate::error::RichError;
+
+    let (remaining, statements) = separated_list0(
+        tag(";"),
+        space0_around(traced_statements(CqlStatement::parse)),
+    )(input)
+    .map_err(|e: nom::Err<RichError<&str>>| CqlParseError::from_nom(input, e))?;
+    let (remaining, semicolon) = opt(tag::<_, _, RichError<&str>>(";"))(remaining)
+        .map_err(|e| CqlParseError::from_nom(input, e))?;
+    let (remaining, _) =
+        ws0::<_, RichError<&str>>(remaining).map_err(|e| CqlParseError::from_nom(input, e))?;
+    if remaining.is_empty() {
+        return Ok(statements);
+    }
+    let unterminated = !statements.is_empty() && semicolon.is_none();
+    if !unterminated {
+        // The list stops at a statement failing to parse, parsing it again reports its error.
+        parse_single_statement(input, remaining)?;
+    }
+
+    Err(trailing_input_error(
+        input,
+        remaining,
+        unterminated,
+        "expected a statement or the end of the input",
+    ))
+}
+
+/// Parses CQL statements into a tree, skipping the statements which can not be parsed.
+///
+/// A statement failing to parse is skipped up to the next top-level `;`, semicolons in string
+/// literals, quoted identifiers and comments are ignored. The parsed statements are returned in
+/// input order, together with the skipped ones.
+///
+/// ```rust
+/// use cql_nom::parse_cql_lenient;
+///
+/// let input = "CREATE TYPE a (b int); DROP TABLE 'x;y'; CREATE TYPE c (d int)";
+/// let (statements, skipped) = parse_cql_lenient(input);
+/// assert_eq!(statements.len(), 2);
+/// assert_eq!(skipped[0].text(), &"DROP TABLE 'x;y'");
+/// assert_eq!(skipped[0].offset(), 23);
+/// ```
+pub fn parse_cql_lenient(
     input: &str,
-) -> IResult<
-    &str,
-    Vec<
-        CqlStatement<
-            CqlTable<&str, CqlColumn<&str, CqlIdentifier<&str>>, CqlIdentifier<&str>>,
-            ParsedCqlUserDefinedType<&str, CqlIdentifier<&str>>,
-        >,
-    >,
-> {
-    let (input, statements) = separated_list0(tag(";"), space0_around(CqlStatement::parse))(input)?;
+) -> (Vec<ParsedCqlStatement<&str>>, Vec<SkippedStatement<&str>>) {
+    use nom::Offset;
+
+    let mut statements = Vec::new();
+    let mut skipped = Vec::new();
+    let mut rest = input;
+    loop {
+        let (start, _) = Whitespace::split_whitespace(rest);
+        if start.is_empty() {
+            return (statements, skipped);
+        }
+        let parsed: IResult<_, _, nom::error::Error<&str>> =
+            traced_statements(space0_around(CqlStatement::parse))(start);
+        let parsed =
+            parsed
+                .ok()
+                .and_then(|(remaining, statement)| match remaining.strip_prefix(';') {
+                    Some(next) => Some((next, statement)),
+                    None if remaining.is_empty() => Some((remaining, statement)),
+                    // The statement is followed by something it does not support.
+                    None => None,
+                });
+        match parsed {
+            Some((next, statement)) => {
+                statements.push(statement);
+                rest = next;
+            }
+            None => {
+                let (text, next) = match find_statement_end(start) {
+                    Some(end) => (&start[..end], &start[end + 1..]),
+                    None => (start, ""),
+                };
+                debug_event!(offset = input.offset(start), "skipping statement");
+                skipped.push(SkippedStatement::new(text.trim_end(), input.offset(start)));
+                rest = next;
+            }
+        }
+    }
+}
+
+/// Parses CQL statements into a tree, reporting every statement which can not be parsed.
+///
+/// The input is split into statements at top-level `;`, each statement is parsed on its own so
+/// a broken statement does not hide the errors of later ones. The parsed statements are returned
+/// in input order, together with a diagnostic for each failed statement.
+///
+/// ```rust
+/// use cql_nom::parse_cql_recovering;
+///
+/// let input = "CREATE TYPE a (b int);\nCREATE TYPE c (d in t);\nCREATE TYPE e (f int)";
+/// let (statements, diagnostics) = parse_cql_recovering(input);
+/// assert_eq!(statements.len(), 2);
+/// assert_eq!(diagnostics[0].offset(), 40);
+/// assert_eq!((diagnostics[0].line(), diagnostics[0].column()), (2, 18));
+/// ```
+pub fn parse_cql_recovering(input: &str) -> (Vec<ParsedCqlStatement<&str>>, Vec<Diagnostic>) {
+    let mut statements = Vec::new();
+    let mut diagnostics = Vec::new();
+    for chunk in crate::utils::split_statements(input) {
+        match parse_single_statement(input, chunk) {
+            Ok(statement) => statements.push(statement),
+            Err(error) => diagnostics.push(Diagnostic::new(input, error)),
+        }
+    }
+
+    (statements, diagnostics)
+}
+
+/// Parses CQL statements into a tree which keeps the source text of its nodes.
+///
+/// Statements, tables, user defined types, their names, columns, column names and references,
+/// primary keys and table options are [`Spanned`], [`Spanned::locate`] gives their byte range,
+/// line and column. See [`find_node_at`](lookup::find_node_at) to look up nodes by offset.
+///
+/// ```rust
+/// use cql_nom::parse_cql_spanned;
+///
+/// let input = "USE my_ks;\nCREATE TABLE my_table (\n  id int PRIMARY KEY\n)";
+/// let (_, statements) = parse_cql_spanned(input).unwrap();
+/// let table = statements[1].create_table().unwrap();
+/// let column = table.columns()[0].locate(input).unwrap();
+/// assert_eq!((column.line(), column.column()), (3, 3));
+/// assert_eq!(&input[column.range()], "id int PRIMARY KEY");
+/// assert_eq!(*table.name().span(), "my_table");
+/// ```
+pub fn parse_cql_spanned(input: &str) -> IResult<&str, Vec<SpannedStatement<'_>>> {
+    let (input, statements) =
+        separated_list0(tag(";"), space0_around(traced_statements(Spanned::parse)))(input)?;
     let (input, _) = opt(tag(";"))(input)?;
-    let (input, _) = multispace0(input)?;
+    let (input, _) = ws0(input)?;
 
     Ok((input, statements))
 }
 
+/// Parses CQL statements into a tree which keeps the doc comments of its nodes.
+///
+/// Tables, user defined types and columns are [`Documented`](model::Documented) by the comments
+/// directly preceding them, the other statements drop their comments like [`parse_cql`]. A blank
+/// line breaks the attachment: comments before a blank line, or followed by one, are not part of
+/// the doc comment. A comment on the line of the preceding token, like `id int, -- the key`,
+/// belongs to that token and is not part of the doc comment of the next node.
+///
+/// ```rust
+/// use cql_nom::model::CqlStatement;
+/// use cql_nom::parse_cql_with_comments;
+///
+/// let input = "-- The users.
+/// CREATE TABLE users (
+///     -- The key.
+///     id int PRIMARY KEY,
+///     name text
+/// );";
+/// let (_, statements) = parse_cql_with_comments(input).unwrap();
+/// let CqlStatement::CreateTable(table) = &statements[0] else { unreachable!() };
+/// assert_eq!(table.doc().as_deref(), Some("The users."));
+/// assert_eq!(table.columns()[0].doc().as_deref(), Some("The key."));
+/// assert_eq!(table.columns()[1].doc(), &None);
+/// ```
+pub fn parse_cql_with_comments(input: &str) -> IResult<&str, Vec<DocumentedStatement<'_>>> {
+    use nom::Offset;
+
+    let start = input;
+    let (input, statements) = separated_list0(
+        tag(";"),
+        traced_statements(|input| {
+            // Statements are documented after parsing, only the first one starts a line.
+            let (remaining, leading) = ws0(input)?;
+            let (remaining, mut statement) = DocumentedStatement::parse(remaining)?;
+            let doc = doc_comment(leading, start.offset(leading) == 0);
+            match &mut statement {
+                CqlStatement::CreateTable(table) => table.set_doc(doc),
+                CqlStatement::CreateUserDefinedType(udt) => udt.set_doc(doc),
+                _ => {}
+            }
+            let (remaining, _) = ws0(remaining)?;
+
+            Ok((remaining, statement))
+        }),
+    )(input)?;
+    let (input, _) = opt(tag(";"))(input)?;
+    let (input, _) = ws0(input)?;
+
+    Ok((input, statements))
+}
+
+/// Parses exactly one CQL statement.
+///
+/// The statement may be followed by a semicolon, whitespace and comments, anything else is an
+/// error.
+///
+/// ```rust
+/// use cql_nom::parse_cql_statement;
+///
+/// let statement = parse_cql_statement("CREATE TYPE my_type (my_field int); -- done").unwrap();
+/// assert!(statement.is_create_user_defined_type());
+///
+/// let error = parse_cql_statement("CREATE TYPE my_type (my_field int); CREATE").unwrap_err();
+/// assert_eq!(error.offset(), 36);
+/// ```
+pub fn parse_cql_statement(input: &str) -> Result<ParsedCqlStatement<&str>, CqlParseError> {
+    parse_single_statement(input, input)
+}
+
+/// Parses one statement, a slice of `input`, the errors are positioned in `input`.
+fn parse_single_statement<'a>(
+    input: &'a str,
+    statement: &'a str,
+) -> Result<ParsedCqlStatement<&'a str>, CqlParseError> {
+    use crate::error::RichError;
+
+    let (remaining, statement) = space0_around(CqlStatement::parse)(statement)
+        .map_err(|e: nom::Err<RichError<&str>>| CqlParseError::from_nom(input, e))?;
+    let (remaining, semicolon) = opt(tag::<_, _, RichError<&str>>(";"))(remaining)
+        .map_err(|e| CqlParseError::from_nom(input, e))?;
+    let (remaining, _) =
+        ws0::<_, RichError<&str>>(remaining).map_err(|e| CqlParseError::from_nom(input, e))?;
+    if !remaining.is_empty() {
+        return Err(trailing_input_error(
+            input,
+            remaining,
+            semicolon.is_none(),
+            "expected the end of the statement",
+        ));
+    }
+
+    Ok(statement)
+}
+
+/// The keywords starting a statement.
+const STATEMENT_KEYWORDS: [&str; 10] = [
+    "ALTER", "CREATE", "DELETE", "DROP", "GRANT", "INSERT", "REVOKE", "SELECT", "UPDATE", "USE",
+];
+
+/// The error for the input remaining after a statement.
+///
+/// Unless the statement is terminated by a semicolon, a statement keyword hints at a missing
+/// semicolon between two statements.
+fn trailing_input_error(
+    input: &str,
+    remaining: &str,
+    unterminated: bool,
+    message: &str,
+) -> CqlParseError {
+    use nom::Offset;
+
+    let starts_statement = STATEMENT_KEYWORDS.iter().any(|keyword| {
+        matches!(remaining.get(..keyword.len()), Some(word) if word.eq_ignore_ascii_case(keyword))
+            && !remaining[keyword.len()..]
+                .starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+    });
+    if unterminated && starts_statement {
+        let (line, _) = line_column(input, input.offset(remaining));
+        return CqlParseError::new(
+            input,
+            remaining,
+            format!("expected ';' before the next statement at line {}", line),
+        );
+    }
+
+    CqlParseError::new(input, remaining, message)
+}
+
+/// The deepest nesting of types accepted by the parser, `frozen<list<int>>` has a depth of 2.
+///
+/// Deeper types are an error instead of overflowing the stack.
+pub const DEFAULT_MAX_TYPE_DEPTH: usize = 128;
+
+/// Parses a standalone CQL type, like the `type` column of `system_schema.columns`.
+///
+/// The whole input must be the type, only surrounding whitespace and comments are skipped.
+///
+/// ```rust
+/// use cql_nom::model::CqlType;
+/// use cql_nom::parse_cql_type;
+///
+/// let cql_type = parse_cql_type("frozen<list<int>>").unwrap();
+/// assert_eq!(cql_type.element_type(), Some(&CqlType::INT));
+///
+/// let error = parse_cql_type("map<int, text> extra").unwrap_err();
+/// assert_eq!(error.offset(), 15);
+/// ```
+pub fn parse_cql_type(input: &str) -> Result<CqlType<CqlIdentifier<&str>>, CqlParseError> {
+    parse_cql_type_with_max_depth(input, DEFAULT_MAX_TYPE_DEPTH)
+}
+
+/// Parses a standalone CQL type like [`parse_cql_type`], with a custom limit for the nesting of
+/// types instead of [`DEFAULT_MAX_TYPE_DEPTH`].
+pub fn parse_cql_type_with_max_depth(
+    input: &str,
+    max_depth: usize,
+) -> Result<CqlType<CqlIdentifier<&str>>, CqlParseError> {
+    use crate::error::RichError;
+
+    let (remaining, cql_type) =
+        space0_around(|input| crate::parse::cql_type::parse_type(input, 0, max_depth))(input)
+            .map_err(|e: nom::Err<RichError<&str>>| CqlParseError::from_nom(input, e))?;
+    if !remaining.is_empty() {
+        return Err(CqlParseError::new(
+            input,
+            remaining,
+            "expected the end of the type",
+        ));
+    }
+
+    Ok(cql_type)
+}
+
+/// An error of [`parse_cql_parallel`], paired with the byte offset into the input.
+#[cfg(feature = "rayon")]
+pub type ParallelError<'a> = (usize, nom::Err<nom::error::Error<&'a str>>);
+
+/// Parses CQL statements in parallel.
+///
+/// The input is split into statements at top-level semicolons first, the statements are then
+/// parsed on the rayon thread pool. The output is in input order. On failure all errors are
+/// returned, each paired with the byte offset into the input at which parsing failed.
+#[cfg(feature = "rayon")]
+pub fn parse_cql_parallel(
+    input: &str,
+) -> Result<Vec<ParsedCqlStatement<&str>>, Vec<ParallelError<'_>>> {
+    use nom::combinator::all_consuming;
+    use nom::Offset;
+    use rayon::prelude::*;
+
+    let results = crate::utils::split_statements(input)
+        .into_par_iter()
+        .map(|chunk| {
+            all_consuming(space0_around(CqlStatement::parse))(chunk)
+                .map(|(_, statement)| statement)
+                .map_err(|e: nom::Err<nom::error::Error<&str>>| {
+                    let offset = match &e {
+                        nom::Err::Error(error) | nom::Err::Failure(error) => {
+                            input.offset(error.input)
+                        }
+                        nom::Err::Incomplete(_) => input.offset(chunk),
+                    };
+                    (offset, e)
+                })
+        })
+        .collect::<Vec<_>>();
+
+    let mut statements = Vec::with_capacity(results.len());
+    let mut errors = Vec::new();
+    for result in results {
+        match result {
+            Ok(statement) => statements.push(statement),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(statements)
+    } else {
+        Err(errors)
+    }
+}
+
 /// Resolves the identifiers of the CQL statements.
+///
+/// Unqualified names are in the given keyspace, until a `USE` statement switches to its keyspace.
+/// The names of resolved tables and user defined types are qualified with their keyspace.
 pub fn resolve_references<'a>(
-    input: Vec<
-        CqlStatement<
-            CqlTable<&'a str, CqlColumn<&'a str, CqlIdentifier<&'a str>>, CqlIdentifier<&'a str>>,
-            ParsedCqlUserDefinedType<&'a str, CqlIdentifier<&'a str>>,
-        >,
-    >,
+    input: Vec<ParsedCqlStatement<&'a str>>,
     keyspace: Option<&'a CqlIdentifier<&'a str>>,
-) -> Result<
-    Vec<
-        CqlStatement<
-            Rc<
-                CqlTable<
-                    &'a str,
-                    Rc<CqlColumn<&'a str, Rc<CqlUserDefinedType<&'a str>>>>,
-                    Rc<CqlColumn<&'a str, Rc<CqlUserDefinedType<&'a str>>>>,
-                >,
-            >,
-            Rc<CqlUserDefinedType<&'a str>>,
-        >,
-    >,
-    CqlQualifiedIdentifier<&'a str>,
-> {
+) -> Result<Vec<ResolvedCqlStatement<&'a str>>, CqlResolveError<&'a str>> {
+    #[cfg(feature = "tracing")]
+    let _span = tracing::debug_span!("resolve", statements = input.len()).entered();
+    let mut keyspace = keyspace.cloned();
     let mut result = Vec::new();
     for i in input {
-        let i = i.reference_types(keyspace.clone(), &result)?;
-        result.push(i);
+        #[cfg(feature = "tracing")]
+        let name = match &i {
+            CqlStatement::CreateTable(table) => Some(table.name()),
+            CqlStatement::CreateTableLike(like) => Some(like.name()),
+            CqlStatement::CreateUserDefinedType(udt) => Some(udt.name()),
+            CqlStatement::AlterMaterializedView(view) => Some(view.name()),
+            CqlStatement::AlterType(alter_type) => Some(alter_type.name()),
+            CqlStatement::DropType(drop_type) => Some(drop_type.name()),
+            CqlStatement::DropAggregate(drop_aggregate) => Some(drop_aggregate.name()),
+            CqlStatement::CreateTrigger(create_trigger) => Some(create_trigger.table()),
+            CqlStatement::DropTrigger(drop_trigger) => Some(drop_trigger.table()),
+            CqlStatement::CreateIndex(create_index) => Some(create_index.table()),
+            CqlStatement::Select(select) => Some(select.table()),
+            CqlStatement::Insert(insert) => Some(insert.table()),
+            CqlStatement::Update(update) => Some(update.table()),
+            CqlStatement::Delete(delete) => Some(delete.table()),
+            CqlStatement::Use(_)
+            | CqlStatement::CreateKeyspace(_)
+            | CqlStatement::CreateRole(_)
+            | CqlStatement::AlterRole(_)
+            | CqlStatement::DropRole(_)
+            | CqlStatement::Grant(_)
+            | CqlStatement::Revoke(_) => None,
+        };
+        #[cfg(feature = "tracing")]
+        if let Some(name) = name {
+            tracing::debug!(
+                index = result.len(),
+                keyspace = name.keyspace().as_ref().map(|keyspace| &**keyspace),
+                name = &**name.name(),
+                "resolving"
+            );
+        }
+        if let Some(active) = i.use_keyspace() {
+            debug_event!(index = result.len(), keyspace = &**active, "using");
+            keyspace = Some(active.clone());
+        }
+        let i = i.reference_types(keyspace.as_ref(), &result);
+        #[cfg(feature = "tracing")]
+        if let Err(error) = &i {
+            tracing::debug!(index = result.len(), %error, "failed to resolve");
+        }
+        result.push(i?);
     }
 
     Ok(result)
@@ -94,6 +523,348 @@ pub fn resolve_references<'a>(
 mod test {
     use super::*;
     use crate::model::*;
+    use proptest::prelude::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_parse_cql_statement() {
+        let statement = parse_cql_statement(
+            "\n  CREATE TABLE my_table (my_field int PRIMARY KEY) ; /* trailing */\n",
+        )
+        .unwrap();
+        assert_eq!(
+            statement.create_table().unwrap().name(),
+            &CqlQualifiedIdentifier::new(None, CqlIdentifier::new("my_table"))
+        );
+        assert!(parse_cql_statement("CREATE TYPE my_type (my_field int)").is_ok());
+    }
+
+    #[test]
+    fn test_parse_cql_statement_trailing_statement() {
+        let input = "CREATE TYPE my_type (my_field int);\nCREATE TYPE my_type2 (my_field int);";
+        let error = parse_cql_statement(input).unwrap_err();
+        assert_eq!(error.offset(), 36);
+        assert_eq!(error.snippet(), "CREATE TYPE my_type2 (my_field i");
+        assert_eq!(error.message(), "expected the end of the statement");
+    }
+
+    #[test]
+    fn test_parse_cql_statement_empty() {
+        for input in ["", "  -- nothing\n"] {
+            let error = parse_cql_statement(input).unwrap_err();
+            assert_eq!(error.offset(), input.len());
+            assert_eq!(
+                error.message(),
+                "expected one of ALTER, CREATE, DELETE, DROP, GRANT, INSERT, REVOKE, SELECT, UPDATE, USE"
+            );
+            assert_eq!(
+                error.expected(),
+                &vec![
+                    "ALTER", "CREATE", "DELETE", "DROP", "GRANT", "INSERT", "REVOKE", "SELECT",
+                    "UPDATE", "USE"
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_cql_statement_error_position() {
+        let input = "CREATE TABLE my_table (my_field int STATIC STATIC)";
+        let error = parse_cql_statement(input).unwrap_err();
+        assert_eq!(error.offset(), 43);
+        assert_eq!(error.message(), "duplicate STATIC modifier");
+        assert_eq!(
+            error.to_string(),
+            "duplicate STATIC modifier at offset 43: `STATIC)`"
+        );
+    }
+
+    #[test]
+    fn test_parse_cql_complete() {
+        assert_eq!(super::parse_cql_complete(" -- nothing\n"), Ok(vec![]));
+        let error = super::parse_cql_complete("CREATE TYPE a (b int);;").unwrap_err();
+        assert_eq!(error.offset(), 22);
+        assert_eq!(error.snippet(), ";");
+
+        let input =
+            "CREATE TYPE a (b int);\nCREATE TABLE c (d int PRIMARY KEY)\nCREATE TYPE e (f int)";
+        let error = super::parse_cql_complete(input).unwrap_err();
+        assert_eq!(error.offset(), 58);
+        assert_eq!(error.snippet(), "CREATE TYPE e (f int)");
+        let (remaining, _) = super::parse_cql(input).unwrap();
+        assert_eq!(remaining, "CREATE TYPE e (f int)");
+
+        // A failing statement reports where it failed, not where it starts.
+        let input = "CREATE TYPE a (b int);\nCREATE TABLE t (a int, b map<int, text);\nDROP TYPE a";
+        let error = super::parse_cql_complete(input).unwrap_err();
+        let (_, diagnostics) = super::parse_cql_recovering(input);
+        assert_eq!(error.offset(), 61);
+        assert_eq!(error.snippet(), ");");
+        assert_eq!(diagnostics[0].offset(), error.offset());
+        let error = super::parse_cql_complete("CREATE TYPE a (b int);;").unwrap_err();
+        assert!(error.expected().contains(&"CREATE".to_string()));
+    }
+
+    #[test]
+    fn test_missing_semicolon() {
+        // Missing in the middle of the file.
+        let input = "CREATE TYPE a (b int)\n  create table c (d int PRIMARY KEY);\nDROP TYPE a;";
+        let error = super::parse_cql_complete(input).unwrap_err();
+        assert_eq!(error.offset(), 24);
+        assert_eq!(
+            error.message(),
+            "expected ';' before the next statement at line 2"
+        );
+        let error = parse_cql_statement(input).unwrap_err();
+        assert_eq!(
+            error.message(),
+            "expected ';' before the next statement at line 2"
+        );
+        let (statements, diagnostics) = super::parse_cql_recovering(input);
+        assert_eq!(statements.len(), 1);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!((diagnostics[0].line(), diagnostics[0].column()), (2, 3));
+        assert_eq!(
+            diagnostics[0].error().message(),
+            "expected ';' before the next statement at line 2"
+        );
+
+        // Missing between the last two statements, the last one needs none.
+        let input = "CREATE TYPE a (b int);\nALTER TYPE a ADD c int\nDROP TYPE a";
+        let error = super::parse_cql_complete(input).unwrap_err();
+        assert_eq!(error.offset(), 46);
+        assert_eq!(
+            error.message(),
+            "expected ';' before the next statement at line 3"
+        );
+        assert_eq!(
+            super::parse_cql_complete("CREATE TYPE a (b int);\nDROP TYPE a")
+                .unwrap()
+                .len(),
+            2
+        );
+
+        // Anything else keeps the generic message, as does a keyword after a semicolon.
+        let error = super::parse_cql_complete("DROP TYPE a created").unwrap_err();
+        assert_eq!(
+            error.message(),
+            "expected a statement or the end of the input"
+        );
+        let error = parse_cql_statement("DROP TYPE a; DROP TYPE b").unwrap_err();
+        assert_eq!(error.message(), "expected the end of the statement");
+    }
+
+    #[test]
+    fn test_parse_cql_lenient() {
+        let input = r#"CREATE TYPE a (b int);
+DROP TABLE "x;y" /* ; */ -- ;
+;
+CREATE TYPE c (d int) extra;
+CREATE TABLE e (f int PRIMARY KEY);
+ALTER TABLE e ADD g $$;$$ "#;
+        let (statements, skipped) = super::parse_cql_lenient(input);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].is_create_user_defined_type());
+        assert!(statements[1].is_create_table());
+        assert_eq!(
+            skipped,
+            vec![
+                SkippedStatement::new("DROP TABLE \"x;y\" /* ; */ -- ;", 23),
+                SkippedStatement::new("CREATE TYPE c (d int) extra", 55),
+                SkippedStatement::new("ALTER TABLE e ADD g $$;$$", 120),
+            ]
+        );
+
+        assert_eq!(super::parse_cql_lenient(" -- ;\n"), (vec![], vec![]));
+    }
+
+    /// Inputs made of CQL tokens, which get further into the grammar than arbitrary text.
+    fn token_soup() -> impl Strategy<Value = String> {
+        let token = prop::sample::select(vec![
+            "CREATE", "TABLE", "TYPE", "SELECT", "INSERT", "INTO", "VALUES", "UPDATE", "SET",
+            "WHERE", "CAST", "AS", "PRIMARY", "KEY", "WITH", "AND", "frozen", "list", "map", "a",
+            "ks", "1", "-1.5", "?", "(", ")", "<", ">", "{", "}", "[", "]", ",", ";", ":", ".",
+            "=", "*", "\"", "'", "$$", "--", "/*", "*/", " ", "\n", "é",
+        ]);
+        prop::collection::vec(token, 0..64).prop_map(|tokens| tokens.concat())
+    }
+
+    proptest! {
+        #[test]
+        #[cfg_attr(miri, ignore)]
+        fn test_never_panics(input in prop_oneof!["\\PC*", token_soup()]) {
+            if let Ok((_, statements)) = super::parse_cql(&input) {
+                let _ = super::resolve_references(statements, None);
+            }
+            let _ = super::parse_cql_complete(&input);
+            let _ = super::parse_cql_lenient(&input);
+            let _ = super::parse_cql_type(&input);
+            let _ = super::parse_cql_with_comments(&input);
+            let _ = super::parse_cql_recovering(&input);
+            if let Ok((_, statements)) = super::parse_cql_spanned(&input) {
+                for offset in 0..=input.len() {
+                    let _ = super::lookup::find_node_at(&input, &statements, offset);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_deep_nesting() {
+        let insert = "INSERT INTO t (a) VALUES (";
+        for (prefix, open, leaf, close, suffix) in [
+            (insert, "[", "1", "]", ")"),
+            (insert, "{", "1", "}", ")"),
+            (insert, "{a: ", "1", "}", ")"),
+            (insert, "f(", "1", ")", ")"),
+            ("SELECT ", "f(", "a", ")", " FROM t"),
+            ("SELECT ", "CAST(", "a", " AS int)", " FROM t"),
+            (
+                "CREATE TABLE t (a int PRIMARY KEY) WITH b = ",
+                "{'c': ",
+                "1",
+                "}",
+                "",
+            ),
+            ("CREATE TABLE t (a ", "frozen<", "int", ">", " PRIMARY KEY)"),
+        ] {
+            let nest = |depth, close: &str, suffix| {
+                format!(
+                    "{}{}{}{}{}",
+                    prefix,
+                    open.repeat(depth),
+                    leaf,
+                    close.repeat(depth),
+                    suffix
+                )
+            };
+            // Nesting is an error long before the stack overflows.
+            assert!(super::parse_cql_complete(&nest(100_000, close, suffix)).is_err());
+            // Reasonable nesting parses, unterminated nesting fails, both in linear time.
+            assert!(super::parse_cql_complete(&nest(32, close, suffix)).is_ok());
+            assert!(super::parse_cql_complete(&nest(32, "", "")).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parse_cql_recovering() {
+        let input = r#"CREATE TYPE a (b int);
+CREATE TABLE c (d int PRIMARY KEY, e lst<int>);
+CREATE TYPE "f;g" (h int);
+CREATE TABLE i (j int PRIMRY KEY);
+-- A comment; with a semicolon.
+CREATE TYPE k (l map<int>);
+CREATE TYPE m (n int)"#;
+        let (statements, diagnostics) = super::parse_cql_recovering(input);
+        assert_eq!(statements.len(), 3);
+        assert_eq!(
+            statements
+                .iter()
+                .map(|statement| statement
+                    .create_user_defined_type()
+                    .unwrap()
+                    .name()
+                    .to_string())
+                .collect::<Vec<_>>(),
+            vec!["a", "\"f;g\"", "m"]
+        );
+        assert_eq!(
+            diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.line(), diagnostic.column()))
+                .collect::<Vec<_>>(),
+            vec![(2, 41), (4, 23), (6, 25)]
+        );
+        for diagnostic in &diagnostics {
+            assert_eq!(
+                &input[diagnostic.offset()..][..diagnostic.error().snippet().len()],
+                diagnostic.error().snippet()
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_cql_type() {
+        assert_eq!(
+            parse_cql_type(" map<text, frozen<my_udt>> -- a type\n"),
+            Ok(CqlType::MAP(Box::new((
+                CqlType::TEXT,
+                CqlType::FROZEN(Box::new(CqlType::UserDefined(CqlIdentifier::new("my_udt")))),
+            ))))
+        );
+        let error = parse_cql_type("list<int>>").unwrap_err();
+        assert_eq!(error.offset(), 9);
+        assert_eq!(error.message(), "expected the end of the type");
+        assert!(parse_cql_type("frozen<").is_err());
+        assert!(parse_cql_type("").is_err());
+    }
+
+    #[test]
+    fn test_parse_cql_type_with_max_depth() {
+        assert!(parse_cql_type_with_max_depth("frozen<list<int>>", 2).is_ok());
+        let error = parse_cql_type_with_max_depth("map<int, frozen<list<int>>>", 2).unwrap_err();
+        assert_eq!(error.offset(), 21);
+        assert_eq!(error.message(), "type nested too deeply");
+        let error = parse_cql_statement(&format!(
+            "CREATE TYPE my_type (my_field {}int{})",
+            "frozen<".repeat(10_000),
+            ">".repeat(10_000)
+        ))
+        .unwrap_err();
+        assert_eq!(error.message(), "type nested too deeply");
+    }
+
+    #[test]
+    fn test_type_keyword_prefix() {
+        let statement = parse_cql_statement(
+            "CREATE TABLE ks_1.t_1 (intake_1 int PRIMARY KEY, data_1 textual_data)",
+        )
+        .unwrap();
+        let columns = statement.create_table().unwrap().columns();
+        assert_eq!(columns[0].cql_type(), &CqlType::INT);
+        assert_eq!(
+            columns[1].cql_type(),
+            &CqlType::UserDefined(CqlIdentifier::new("textual_data"))
+        );
+    }
+
+    #[test]
+    fn test_letters_only_identifiers() {
+        // The example of the Cassandra documentation.
+        let statement = parse_cql_statement(
+            "CREATE TABLE monkey_species (
+                species text PRIMARY KEY,
+                common_name text,
+                population varint,
+                average_size int
+            ) WITH comment='Important biological records';",
+        )
+        .unwrap();
+        let table = statement.create_table().unwrap();
+        let names = table
+            .columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            vec!["species", "common_name", "population", "average_size"]
+        );
+        assert!(table.columns()[0].is_primary_key());
+        assert_eq!(
+            table.primary_key().as_ref().unwrap().partition_key(),
+            &vec![CqlIdentifier::new("species")]
+        );
+
+        let statement =
+            parse_cql_statement("CREATE TABLE t (a int, b intake, PRIMARY KEY (a))").unwrap();
+        let columns = statement.create_table().unwrap().columns();
+        assert_eq!(
+            columns[1].cql_type(),
+            &CqlType::UserDefined(CqlIdentifier::new("intake"))
+        );
+    }
 
     #[test]
     fn test() {
@@ -226,6 +997,7 @@ mod test {
             ],
             Some(CqlPrimaryKey::new(
                 vec![column_my_field1.clone()],
+                false,
                 vec![column_my_field2.clone()],
             )),
             Some(CqlTableOptions::new(
@@ -236,4 +1008,473 @@ mod test {
         ));
         assert_eq!(my_table, &my_table_ref);
     }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parse_cql_parallel() {
+        let fixtures = [
+            include_str!("../tests/fixtures/cassandra_4_1.cql"),
+            include_str!("../tests/fixtures/cassandra_5_0.cql"),
+            include_str!("../tests/fixtures/comments.cql"),
+            include_str!("../tests/fixtures/composite_key.cql"),
+            include_str!("../tests/fixtures/quoted.cql"),
+            include_str!("../tests/fixtures/scylla.cql"),
+        ];
+        for fixture in fixtures {
+            let (remaining, sequential) = super::parse_cql(fixture).unwrap();
+            assert_eq!(remaining, "");
+            let parallel = super::parse_cql_parallel(fixture).unwrap();
+            assert_eq!(parallel, sequential);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_parse_cql_parallel_errors() {
+        let input = "CREATE TYPE ok_type (my_field int);\n\
+            CREATE TYPE broken_type (my_field int;\n\
+            CREATE TYPE other_type (my_field text);\n\
+            CREATE TABLE broken_table (my_field int) garbage;";
+        let errors = super::parse_cql_parallel(input).unwrap_err();
+        assert_eq!(errors.len(), 2);
+        let broken_type = input.find("CREATE TYPE broken_type").unwrap();
+        let other_type = input.find("CREATE TYPE other_type").unwrap();
+        assert!((broken_type..other_type).contains(&errors[0].0));
+        assert_eq!(errors[1].0, input.find("garbage").unwrap());
+    }
+
+    #[test]
+    fn test_quoted() {
+        let input = include_str!("../tests/fixtures/quoted.cql");
+        let (remaining, parse_tree) = super::parse_cql(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(parse_tree.len(), 40);
+        let ast = super::resolve_references(parse_tree, None).unwrap();
+        let table = ast[1].create_table().unwrap();
+        assert_eq!(
+            table.name(),
+            &CqlQualifiedIdentifier::new(
+                Some(CqlIdentifier::Quoted("Analytics".to_string())),
+                CqlIdentifier::Quoted("Events By Customer 0".to_string()),
+            )
+        );
+        assert_eq!(table.columns().len(), 15);
+    }
+
+    #[test]
+    fn test_comments() {
+        let input = include_str!("../tests/fixtures/comments.cql");
+        let (remaining, parse_tree) = super::parse_cql(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(parse_tree.len(), 3);
+        let ast = super::resolve_references(parse_tree, None).unwrap();
+        let keepers = ast[1].create_table().unwrap();
+        assert_eq!(keepers.columns().len(), 3);
+        assert_eq!(
+            keepers.columns()[2].cql_type(),
+            &CqlType::FROZEN(Box::new(CqlType::UserDefined(
+                ast[0].create_user_defined_type().unwrap().clone()
+            )))
+        );
+        let monkeys = ast[2].create_table().unwrap();
+        assert_eq!(monkeys.columns().len(), 4);
+        assert_eq!(
+            monkeys.primary_key().as_ref().unwrap().clustering_columns(),
+            &vec![monkeys.columns()[1].clone()]
+        );
+    }
+
+    #[test]
+    fn test_parse_cql_with_comments() {
+        let input = r#"-- Not attached, followed by a blank line.
+
+/* The users
+   of the shop. */
+CREATE TABLE users (
+    id int, -- Trailing, belongs to id.
+    -- The name,
+    -- split over lines.
+    name text,
+    -- Not attached.
+
+    email text,
+    PRIMARY KEY (id)
+); -- Trailing, belongs to users.
+-- The address.
+CREATE TYPE address (street text);
+-- Not attached, the statement is not documented.
+USE shop;
+
+CREATE TABLE orders (id int PRIMARY KEY)"#;
+        let (remaining, statements) = super::parse_cql_with_comments(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(statements.len(), 4);
+
+        let users = statements[0].create_table().unwrap();
+        assert_eq!(users.doc().as_deref(), Some("The users\nof the shop."));
+        let docs = users
+            .columns()
+            .iter()
+            .map(|column| column.doc().as_deref())
+            .collect::<Vec<_>>();
+        assert_eq!(docs, vec![None, Some("The name,\nsplit over lines."), None]);
+        let address = statements[1].create_user_defined_type().unwrap();
+        assert_eq!(address.doc().as_deref(), Some("The address."));
+        assert_eq!(statements[3].create_table().unwrap().doc(), &None);
+
+        // The tree is the one of parse_cql, apart from the comments.
+        let (_, plain) = super::parse_cql(input).unwrap();
+        assert_eq!(
+            plain[0].create_table().unwrap().columns()[1].name(),
+            users.columns()[1].name()
+        );
+    }
+
+    #[test]
+    fn test_describe_schema() {
+        for input in [
+            include_str!("../tests/fixtures/cassandra_4_1.cql"),
+            include_str!("../tests/fixtures/cassandra_5_0.cql"),
+        ] {
+            let (remaining, parse_tree) = super::parse_cql(input).unwrap();
+            assert_eq!(remaining, "");
+            let ast = super::resolve_references(parse_tree, None).unwrap();
+            let keyspace = ast[0].create_keyspace().unwrap();
+            assert_eq!(keyspace.durable_writes(), Some(true));
+            let table = ast[2].create_table().unwrap();
+            let options = table.options().as_ref().unwrap();
+            assert_eq!(options.read_repair(), Some("BLOCKING"));
+            assert_eq!(options.speculative_retry(), Some("99p"));
+            assert!(options.duplicates().is_empty());
+            assert!(options.compaction().unwrap().diagnostics().is_empty());
+        }
+    }
+
+    #[test]
+    fn test_scylla() {
+        let input = include_str!("../tests/fixtures/scylla.cql");
+        let (remaining, parse_tree) = super::parse_cql(input).unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(parse_tree.len(), 4);
+        let ast = super::resolve_references(parse_tree, None).unwrap();
+        let customers = ast[1].create_table().unwrap();
+        let options = customers.options().as_ref().unwrap();
+        assert_eq!(options.options().len(), 16);
+        assert_eq!(options.paxos_grace_seconds(), Some(864000));
+        assert_eq!(
+            options.tombstone_gc().unwrap()[0],
+            (
+                "mode".to_string(),
+                CqlOptionValue::String("repair".to_string())
+            )
+        );
+        assert_eq!(
+            options.compression().unwrap().class(),
+            &Some(CqlCompressor::Lz4)
+        );
+        // Unknown options are kept.
+        assert!(options
+            .options()
+            .iter()
+            .any(|option| option.name() == "dclocal_read_repair_chance"));
+
+        let orders = ast[2].create_table().unwrap();
+        let options = orders.options().as_ref().unwrap();
+        assert_eq!(options.cdc(), Some(true));
+        assert_eq!(options.per_partition_rate_limit().unwrap().len(), 2);
+        assert_eq!(
+            options.compaction().unwrap().class(),
+            &Some(CqlCompactionStrategy::TimeWindow)
+        );
+
+        let view = ast[3].alter_materialized_view().unwrap();
+        assert_eq!(view.options().synchronous_updates(), Some(true));
+    }
+
+    #[test]
+    fn test_use() {
+        let input = r#"
+            USE ks_a;
+            CREATE TYPE address_type (street_name text);
+            CREATE TABLE people_a (person_id int PRIMARY KEY, home_1 frozen<address_type>);
+            USE ks_b;
+            CREATE TABLE people_b (person_id int PRIMARY KEY, home_1 frozen<address_type>);
+        "#;
+        let (_, parse_tree) = super::parse_cql(input).unwrap();
+        let keyspace = |name| Some(CqlIdentifier::new(name));
+
+        let error = super::resolve_references(parse_tree.clone(), None).unwrap_err();
+        assert_eq!(
+            error,
+            CqlResolveError::Unresolved(CqlQualifiedIdentifier::new(
+                keyspace("ks_b"),
+                CqlIdentifier::new("address_type")
+            ))
+        );
+
+        let ast = super::resolve_references(parse_tree[..3].to_vec(), None).unwrap();
+        let udt = ast[1].create_user_defined_type().unwrap();
+        assert_eq!(udt.name().keyspace(), &keyspace("ks_a"));
+        let people = ast[2].create_table().unwrap();
+        assert_eq!(people.name().keyspace(), &keyspace("ks_a"));
+        assert_eq!(
+            people.columns()[1].cql_type(),
+            &CqlType::FROZEN(Box::new(CqlType::UserDefined(udt.clone())))
+        );
+    }
+
+    #[test]
+    fn test_vector() {
+        let input = r#"
+            CREATE TYPE ks_a.point_2d (x_1 float, y_1 float);
+            CREATE TABLE ks_a.items_1 (
+                item_id int PRIMARY KEY,
+                embedding_1 vector<float, 1536>,
+                corners_1 vector<frozen<point_2d>, 8>
+            );
+        "#;
+        let (_, parse_tree) = super::parse_cql(input).unwrap();
+        let ast = super::resolve_references(parse_tree, None).unwrap();
+        let udt = ast[0].create_user_defined_type().unwrap();
+        let items = ast[1].create_table().unwrap();
+        assert_eq!(
+            items.columns()[1].cql_type(),
+            &CqlType::VECTOR(Box::new(CqlType::FLOAT), 1536)
+        );
+        assert_eq!(
+            items.columns()[2].cql_type(),
+            &CqlType::VECTOR(
+                Box::new(CqlType::FROZEN(Box::new(CqlType::UserDefined(udt.clone())))),
+                8
+            )
+        );
+    }
+
+    #[test]
+    fn test_collect_udts() {
+        let input = r#"
+            CREATE TYPE ks_a.point_2d (x_1 float, y_1 float);
+            CREATE TYPE ks_a.line_2d (from_1 frozen<point_2d>, to_1 frozen<point_2d>);
+            CREATE TABLE ks_a.shapes_1 (
+                shape_id int PRIMARY KEY,
+                lines_1 list<frozen<line_2d>>,
+                center_1 frozen<point_2d>,
+                path_1 frozen<list<frozen<line_2d>>>
+            );
+        "#;
+        let (_, parse_tree) = super::parse_cql(input).unwrap();
+        assert_eq!(
+            parse_tree[2].create_table().unwrap().collect_udts(),
+            vec![
+                &CqlIdentifier::new("line_2d"),
+                &CqlIdentifier::new("point_2d")
+            ]
+        );
+
+        let ast = super::resolve_references(parse_tree, None).unwrap();
+        let point = ast[0].create_user_defined_type().unwrap();
+        let line = ast[1].create_user_defined_type().unwrap();
+        let shapes = ast[2].create_table().unwrap();
+        assert!(point.collect_udts().is_empty());
+        assert_eq!(line.collect_udts(), vec![point]);
+        assert_eq!(shapes.collect_udts(), vec![line, point]);
+        assert_eq!(shapes.columns()[1].collect_udts(), vec![line]);
+        // The types of a type are found transitively.
+        assert!(Rc::ptr_eq(
+            shapes.collect_udts()[0].collect_udts()[0],
+            point
+        ));
+    }
+
+    #[test]
+    fn test_frozen_columns() {
+        let input = r#"
+            CREATE TYPE ks_a.address_type (street_name text);
+            CREATE TABLE ks_a.people_a (
+                person_id int PRIMARY KEY,
+                homes_1 frozen<list<frozen<address_type>>>,
+                homes_2 list<frozen<address_type>>,
+                home_1 address_type
+            );
+        "#;
+        let (_, parse_tree) = super::parse_cql(input).unwrap();
+        let ast = super::resolve_references(parse_tree, None).unwrap();
+        let udt = ast[0].create_user_defined_type().unwrap();
+        let columns = ast[1].create_table().unwrap().columns();
+        let frozen_udt = CqlType::FROZEN(Box::new(CqlType::UserDefined(udt.clone())));
+        assert_eq!(
+            columns[1].cql_type(),
+            &CqlType::FROZEN(Box::new(CqlType::LIST(Box::new(frozen_udt.clone()))))
+        );
+        assert!(columns[1].is_frozen_at_top_level());
+        assert!(!columns[2].is_frozen_at_top_level());
+        assert!(columns[2]
+            .cql_type()
+            .element_type()
+            .unwrap()
+            .is_frozen_at_top_level());
+        assert!(!columns[3].is_frozen_at_top_level());
+    }
+
+    #[test]
+    fn test_identifier_case() {
+        let input = r#"
+            CREATE TYPE ks_a."MyType" (street_name text);
+            CREATE TYPE ks_a."mytype" (zip_code text);
+            CREATE TABLE ks_a.people_a (person_id int PRIMARY KEY, home_1 frozen<MyType>);
+            CREATE TABLE ks_a.people_b (person_id int PRIMARY KEY, home_1 frozen<"MyType">);
+        "#;
+        let (_, parse_tree) = super::parse_cql(input).unwrap();
+        let ast = super::resolve_references(parse_tree.clone(), None).unwrap();
+        let folded = ast[1].create_user_defined_type().unwrap();
+        let quoted = ast[0].create_user_defined_type().unwrap();
+        assert_eq!(
+            ast[2].create_table().unwrap().columns()[1].cql_type(),
+            &CqlType::FROZEN(Box::new(CqlType::UserDefined(folded.clone())))
+        );
+        assert_eq!(
+            ast[3].create_table().unwrap().columns()[1].cql_type(),
+            &CqlType::FROZEN(Box::new(CqlType::UserDefined(quoted.clone())))
+        );
+
+        // Without the lowercase type, the unquoted name does not match the quoted one.
+        let parse_tree = vec![parse_tree[0].clone(), parse_tree[2].clone()];
+        let error = super::resolve_references(parse_tree, None).unwrap_err();
+        assert_eq!(
+            error,
+            CqlResolveError::Unresolved(CqlQualifiedIdentifier::new(
+                Some(CqlIdentifier::new("ks_a")),
+                CqlIdentifier::new("MyType")
+            ))
+        );
+    }
+
+    #[test]
+    fn test_table_like() {
+        let input = r#"
+            CREATE TABLE ks_a.people_a (person_id int PRIMARY KEY, name_1 text);
+            USE ks_a;
+            CREATE TABLE people_b LIKE people_a WITH comment = 'copy';
+            CREATE TABLE people_c LIKE people_b;
+            CREATE TABLE people_d LIKE ks_b.people_a;
+        "#;
+        let (_, parse_tree) = super::parse_cql(input).unwrap();
+
+        let error = super::resolve_references(parse_tree.clone(), None).unwrap_err();
+        assert_eq!(
+            error,
+            CqlResolveError::Unresolved(CqlQualifiedIdentifier::new(
+                Some(CqlIdentifier::new("ks_b")),
+                CqlIdentifier::new("people_a")
+            ))
+        );
+
+        let ast = super::resolve_references(parse_tree[..4].to_vec(), None).unwrap();
+        let people = ast[0].create_table().unwrap();
+        for statement in &ast[2..] {
+            let like = statement.create_table_like().unwrap();
+            assert_eq!(like.name().keyspace(), &Some(CqlIdentifier::new("ks_a")));
+            assert!(Rc::ptr_eq(like.source_table().as_ref().unwrap(), people));
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    #[test]
+    fn test_tracing() {
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::Attributes;
+        use tracing::{Event, Id, Subscriber};
+        use tracing_subscriber::layer::{Context, SubscriberExt};
+        use tracing_subscriber::Layer;
+
+        /// Records the spans as `name` or `name:production`, and the names of events.
+        #[derive(Default)]
+        struct Recorder(Arc<Mutex<Vec<String>>>);
+
+        #[derive(Default)]
+        struct Fields(Vec<String>);
+
+        impl Visit for Fields {
+            fn record_str(&mut self, field: &Field, value: &str) {
+                if matches!(field.name(), "production" | "name") {
+                    self.0.push(value.to_string());
+                }
+            }
+
+            fn record_u64(&mut self, field: &Field, value: u64) {
+                if field.name() == "offset" {
+                    self.0.push(format!("offset={}", value));
+                }
+            }
+
+            fn record_debug(&mut self, _: &Field, _: &dyn std::fmt::Debug) {}
+        }
+
+        impl<S: Subscriber> Layer<S> for Recorder {
+            fn on_new_span(&self, attributes: &Attributes<'_>, _: &Id, _: Context<'_, S>) {
+                let mut fields = Fields::default();
+                attributes.record(&mut fields);
+                let mut record = attributes.metadata().name().to_string();
+                for field in fields.0 {
+                    record = format!("{}:{}", record, field);
+                }
+                self.0.lock().unwrap().push(record);
+            }
+
+            fn on_event(&self, event: &Event<'_>, _: Context<'_, S>) {
+                let mut fields = Fields::default();
+                event.record(&mut fields);
+                for field in fields.0 {
+                    self.0.lock().unwrap().push(format!("event:{}", field));
+                }
+            }
+        }
+
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(Recorder(records.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            let input = include_str!("../tests/fixtures/comments.cql");
+            let (_, parse_tree) = super::parse_cql(input).unwrap();
+            super::resolve_references(parse_tree, None).unwrap();
+        });
+
+        let records = records.lock().unwrap();
+        for expected in [
+            "statement",
+            "parse:user_defined_type",
+            "parse:table",
+            "parse:type",
+            "event:keeper_address",
+            "event:keepers_v1",
+            "event:monkeys_v1",
+            "resolve",
+        ] {
+            assert!(
+                records.iter().any(|record| record == expected),
+                "{}",
+                expected
+            );
+        }
+        let statements = records.iter().filter(|record| *record == "statement");
+        // The list parser also tries a statement after the last semicolon.
+        assert_eq!(statements.count(), 4);
+
+        // A failure records where it failed, relative to the start of its span.
+        let records = Arc::new(Mutex::new(Vec::new()));
+        let subscriber = tracing_subscriber::registry().with(Recorder(records.clone()));
+        tracing::subscriber::with_default(subscriber, || {
+            let input = "CREATE TYPE a (b int); CREATE TABLE t (a in t)";
+            assert!(super::parse_cql_complete(input).is_err());
+        });
+        let records = records.lock().unwrap();
+        // The statement failed at `in t)`, the error reports the offset into the whole input.
+        for expected in ["event:offset=18", "event:offset=41"] {
+            assert!(
+                records.iter().any(|record| record == expected),
+                "{}",
+                expected
+            );
+        }
+    }
 }
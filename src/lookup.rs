@@ -0,0 +1,489 @@
+use crate::model::*;
+use crate::parse::Parse;
+use crate::utils::{ws0, ws1};
+use derive_more::IsVariant;
+use nom::bytes::complete::tag;
+use nom::combinator::opt;
+use nom::{IResult, Offset};
+
+/// A column name or reference together with its source text.
+pub type SpannedIdentifier<'de> = Spanned<&'de str, CqlIdentifier<&'de str>>;
+/// A table or user defined type name together with its source text.
+pub type SpannedQualifiedIdentifier<'de> = Spanned<&'de str, CqlQualifiedIdentifier<&'de str>>;
+/// A primary key definition together with its source text.
+pub type SpannedPrimaryKey<'de> = Spanned<&'de str, CqlPrimaryKey<SpannedIdentifier<'de>>>;
+/// A table option together with its source text, like `comment = 'x'`.
+pub type SpannedTableOption<'de> = Spanned<&'de str, CqlTableOption<&'de str>>;
+/// A parsed column together with its source text.
+pub type SpannedColumn<'de> =
+    Spanned<&'de str, CqlColumn<&'de str, CqlIdentifier<&'de str>, SpannedIdentifier<'de>>>;
+/// A parsed table together with its source text.
+pub type SpannedTable<'de> = Spanned<
+    &'de str,
+    CqlTable<
+        &'de str,
+        SpannedColumn<'de>,
+        SpannedIdentifier<'de>,
+        SpannedQualifiedIdentifier<'de>,
+        SpannedPrimaryKey<'de>,
+        SpannedTableOption<'de>,
+    >,
+>;
+/// A parsed user defined type together with its source text.
+pub type SpannedUserDefinedType<'de> = Spanned<
+    &'de str,
+    ParsedCqlUserDefinedType<&'de str, CqlIdentifier<&'de str>, SpannedQualifiedIdentifier<'de>>,
+>;
+/// A statement together with its source text, as returned by
+/// [`parse_cql_spanned`](crate::parse_cql_spanned).
+pub type SpannedStatement<'de> =
+    Spanned<&'de str, CqlStatement<&'de str, SpannedTable<'de>, SpannedUserDefinedType<'de>>>;
+
+/// A node of the path returned by [`find_node_at`].
+#[derive(Debug, Clone, Copy, PartialEq, IsVariant)]
+pub enum CqlNode<'a, 'de> {
+    /// A statement of any kind.
+    Statement(&'a SpannedStatement<'de>),
+    /// A `CREATE TABLE` statement.
+    Table(&'a SpannedTable<'de>),
+    /// A `CREATE TYPE` statement.
+    UserDefinedType(&'a SpannedUserDefinedType<'de>),
+    /// The name of a table or user defined type.
+    Name(&'a CqlQualifiedIdentifier<&'de str>),
+    /// A keyspace, table, column or field name.
+    Identifier(&'a CqlIdentifier<&'de str>),
+    /// A column definition of a table.
+    Column(&'a SpannedColumn<'de>),
+    /// A field of a user defined type.
+    Field(&'a (CqlIdentifier<&'de str>, CqlType<CqlIdentifier<&'de str>>)),
+    /// A type, possibly nested in another type.
+    Type(&'a CqlType<CqlIdentifier<&'de str>>),
+    /// The primary key definition of a table.
+    PrimaryKey(&'a CqlPrimaryKey<SpannedIdentifier<'de>>),
+    /// The options of a table.
+    Options(&'a CqlTableOptions<&'de str, SpannedIdentifier<'de>, SpannedTableOption<'de>>),
+    /// A `name = value` entry of the table options.
+    TableOption(&'a SpannedTableOption<'de>),
+    /// A reference to a column, in the primary key or the clustering order.
+    ColumnRef(&'a SpannedIdentifier<'de>),
+}
+
+/// Finds the most specific node covering a byte offset of the input.
+///
+/// Returns the path from the statement down to that node, e.g. statement → table → column → type.
+/// An offset in whitespace between nodes yields the enclosing node, an offset outside of any
+/// statement yields an empty path. The statements must be parsed from `input` by
+/// [`parse_cql_spanned`](crate::parse_cql_spanned).
+///
+/// ```rust
+/// use cql_nom::lookup::{find_node_at, CqlNode};
+/// use cql_nom::parse_cql_spanned;
+///
+/// let input = "CREATE TABLE my_table (my_field int PRIMARY KEY)";
+/// let (_, statements) = parse_cql_spanned(input).unwrap();
+/// let path = find_node_at(input, &statements, 33);
+/// assert!(path[0].is_statement());
+/// assert!(path[1].is_table());
+/// assert!(path[2].is_column());
+/// assert!(path[3].is_type());
+/// ```
+pub fn find_node_at<'a, 'de>(
+    input: &'de str,
+    statements: &'a [SpannedStatement<'de>],
+    offset: usize,
+) -> Vec<CqlNode<'a, 'de>> {
+    let mut path = Vec::new();
+    let locator = Locator { input, offset };
+    for statement in statements.iter().filter(|s| locator.covers(s.span())) {
+        path.push(CqlNode::Statement(statement));
+        match statement.node() {
+            CqlStatement::CreateTable(table) if locator.covers(table.span()) => {
+                path.push(CqlNode::Table(table));
+                locator.table(table, &mut path);
+            }
+            CqlStatement::CreateUserDefinedType(udt) if locator.covers(udt.span()) => {
+                path.push(CqlNode::UserDefinedType(udt));
+                locator.user_defined_type(udt, &mut path);
+            }
+            _ => {}
+        }
+    }
+
+    path
+}
+
+struct Locator<'de> {
+    input: &'de str,
+    offset: usize,
+}
+
+impl<'de> Locator<'de> {
+    fn covers(&self, span: &str) -> bool {
+        let start = self.input.offset(span);
+        start <= self.offset && self.offset < start + span.len()
+    }
+
+    fn table<'a>(&self, table: &'a SpannedTable<'de>, path: &mut Vec<CqlNode<'a, 'de>>) {
+        if self.name(table.name(), path) {
+            return;
+        }
+        if let Some(column) = table
+            .columns()
+            .iter()
+            .find(|column| self.covers(column.span()))
+        {
+            path.push(CqlNode::Column(column));
+            self.column(column, path);
+            return;
+        }
+        if let Some(primary_key) = table.primary_key() {
+            let columns = primary_key.partition_key().iter();
+            let columns = columns.chain(primary_key.clustering_columns().iter());
+            if let Some(column) = columns.into_iter().find(|c| self.covers(c.span())) {
+                path.push(CqlNode::PrimaryKey(primary_key.node()));
+                path.push(CqlNode::ColumnRef(column));
+                return;
+            }
+        }
+        if let Some(options) = table.options() {
+            if let Some((column, _)) = options
+                .clustering_order()
+                .iter()
+                .find(|(c, _)| self.covers(c.span()))
+            {
+                path.push(CqlNode::Options(options));
+                path.push(CqlNode::ColumnRef(column));
+            } else if let Some(option) = options
+                .options()
+                .iter()
+                .find(|option| self.covers(option.span()))
+            {
+                path.push(CqlNode::Options(options));
+                path.push(CqlNode::TableOption(option));
+            }
+        }
+    }
+
+    fn user_defined_type<'a>(
+        &self,
+        udt: &'a SpannedUserDefinedType<'de>,
+        path: &mut Vec<CqlNode<'a, 'de>>,
+    ) -> Option<()> {
+        if self.name(udt.name(), path) {
+            return Some(());
+        }
+        let mut input = skip(after(udt.span(), udt.name().span()), "(");
+        for field in udt.fields() {
+            input = skip(input, ",");
+            let (name, remaining) = consumed(input, CqlIdentifier::parse)?;
+            let (remaining, _) = ws1::<_, Error>(remaining).ok()?;
+            let (cql_type, remaining) = consumed(remaining, CqlType::parse)?;
+            let span = &input[..input.offset(remaining)];
+            if self.covers(span) {
+                path.push(CqlNode::Field(field));
+                if self.covers(name) {
+                    path.push(CqlNode::Identifier(&field.0));
+                } else if self.covers(cql_type) {
+                    self.cql_type(cql_type, &field.1, path);
+                }
+                return Some(());
+            }
+            input = remaining;
+        }
+
+        Some(())
+    }
+
+    /// Locates the name of a statement, returns whether the offset is within the name.
+    fn name<'a>(
+        &self,
+        name: &'a SpannedQualifiedIdentifier<'de>,
+        path: &mut Vec<CqlNode<'a, 'de>>,
+    ) -> bool {
+        if !self.covers(name.span()) {
+            return false;
+        }
+        path.push(CqlNode::Name(name.node()));
+        self.qualified_identifier(name.span(), name.node(), path);
+
+        true
+    }
+
+    fn qualified_identifier<'a>(
+        &self,
+        span: &'de str,
+        name: &'a CqlQualifiedIdentifier<&'de str>,
+        path: &mut Vec<CqlNode<'a, 'de>>,
+    ) -> Option<()> {
+        let (first, remaining) = consumed(span, CqlIdentifier::parse)?;
+        match name.keyspace() {
+            Some(keyspace) if self.covers(first) => path.push(CqlNode::Identifier(keyspace)),
+            Some(_) => {
+                let remaining = skip(remaining, ".");
+                let (identifier, _) = consumed(remaining, CqlIdentifier::parse)?;
+                if self.covers(identifier) {
+                    path.push(CqlNode::Identifier(name.identifier()));
+                }
+            }
+            None if self.covers(first) => path.push(CqlNode::Identifier(name.identifier())),
+            None => {}
+        }
+
+        Some(())
+    }
+
+    fn column<'a>(&self, column: &'a SpannedColumn<'de>, path: &mut Vec<CqlNode<'a, 'de>>) {
+        if self.covers(column.name().span()) {
+            path.push(CqlNode::Identifier(column.name().node()));
+            return;
+        }
+        let remaining = skip(after(column.span(), column.name().span()), "");
+        if let Some((cql_type, _)) = consumed(remaining, CqlType::parse) {
+            if self.covers(cql_type) {
+                self.cql_type(cql_type, column.cql_type(), path);
+            }
+        }
+    }
+
+    fn cql_type<'a>(
+        &self,
+        span: &'de str,
+        cql_type: &'a CqlType<CqlIdentifier<&'de str>>,
+        path: &mut Vec<CqlNode<'a, 'de>>,
+    ) {
+        path.push(CqlNode::Type(cql_type));
+        let nested = match cql_type {
+            CqlType::FROZEN(inner)
+            | CqlType::SET(inner)
+            | CqlType::LIST(inner)
+            | CqlType::VECTOR(inner, _) => vec![inner.as_ref()],
+            CqlType::MAP(map) => vec![&map.0, &map.1],
+            CqlType::TUPLE(types) => types.iter().collect(),
+            _ => return,
+        };
+        let Some(start) = span.find('<') else {
+            return;
+        };
+        let mut input = &span[start + 1..];
+        for cql_type in nested {
+            input = skip(input, ",");
+            let Some((nested_span, remaining)) = consumed(input, CqlType::parse) else {
+                return;
+            };
+            if self.covers(nested_span) {
+                self.cql_type(nested_span, cql_type, path);
+                return;
+            }
+            input = remaining;
+        }
+    }
+}
+
+type Error<'de> = nom::error::Error<&'de str>;
+
+/// Splits the input into the text consumed by the parser and the remaining input.
+///
+/// The types and fields keep no spans, they are located by parsing their source text again,
+/// which is known to succeed.
+fn consumed<'de, O>(
+    input: &'de str,
+    mut parser: impl FnMut(&'de str) -> IResult<&'de str, O, Error<'de>>,
+) -> Option<(&'de str, &'de str)> {
+    let (remaining, _) = parser(input).ok()?;
+
+    Some((&input[..input.offset(remaining)], remaining))
+}
+
+/// The text of `span` after `node`, a slice of it.
+fn after<'de>(span: &'de str, node: &'de str) -> &'de str {
+    &span[span.offset(node) + node.len()..]
+}
+
+/// Skips whitespace, comments and an optional separator.
+fn skip<'de>(input: &'de str, separator: &'static str) -> &'de str {
+    let (input, _) = ws0::<_, Error>(input).unwrap_or((input, input));
+    let (input, _) = opt(tag::<_, _, Error>(separator))(input).unwrap_or((input, None));
+    let (input, _) = ws0::<_, Error>(input).unwrap_or((input, input));
+
+    input
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_cql_spanned;
+
+    const INPUT: &str = r#"CREATE TYPE my_ks."My ""Type""" (
+    my_field1 int,
+    my_field2 frozen<map<text, frozen<list<my_udt>>>>
+);
+
+CREATE TABLE my_ks.my_table (
+    my_field1 int,
+    my_field2 frozen<map<text, frozen<list<my_udt>>>>,
+    PRIMARY KEY ((my_field1), my_field2)
+) WITH CLUSTERING ORDER BY (my_field2 DESC) AND comment = 'hello';
+
+SELECT my_field1 FROM my_ks.my_table;
+"#;
+
+    fn kind(cql_type: &CqlType<CqlIdentifier<&str>>) -> &'static str {
+        match cql_type {
+            CqlType::FROZEN(_) => "frozen",
+            CqlType::MAP(_) => "map",
+            CqlType::LIST(_) => "list",
+            CqlType::TEXT => "text",
+            CqlType::INT => "int",
+            CqlType::UserDefined(_) => "udt",
+            _ => unreachable!(),
+        }
+    }
+
+    fn path_at(needle: &str, delta: usize) -> Vec<String> {
+        let (_, statements) = parse_cql_spanned(INPUT).unwrap();
+        let offset = INPUT.find(needle).unwrap() + delta;
+        find_node_at(INPUT, &statements, offset)
+            .into_iter()
+            .map(|node| match node {
+                CqlNode::Statement(_) => "statement".to_string(),
+                CqlNode::Table(_) => "table".to_string(),
+                CqlNode::UserDefinedType(_) => "udt".to_string(),
+                CqlNode::Name(_) => "name".to_string(),
+                CqlNode::Identifier(identifier) => format!("identifier {}", &**identifier),
+                CqlNode::Column(column) => format!("column {}", &**column.name()),
+                CqlNode::Field((name, _)) => format!("field {}", &**name),
+                CqlNode::Type(cql_type) => format!("type {}", kind(cql_type)),
+                CqlNode::PrimaryKey(_) => "primary key".to_string(),
+                CqlNode::Options(_) => "options".to_string(),
+                CqlNode::TableOption(option) => format!("option {}", option.name()),
+                CqlNode::ColumnRef(column) => format!("ref {}", &***column),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_column_type() {
+        let offset = "    my_field2 frozen<map<text, frozen<list<my".len();
+        let needle = "    my_field2 frozen<map<text, frozen<list<my_udt>>>>,";
+        assert_eq!(
+            path_at(needle, offset),
+            vec![
+                "statement",
+                "table",
+                "column my_field2",
+                "type frozen",
+                "type map",
+                "type frozen",
+                "type list",
+                "type udt",
+            ]
+        );
+        assert_eq!(
+            path_at(needle, "    my_field2 frozen<map<te".len()),
+            vec![
+                "statement",
+                "table",
+                "column my_field2",
+                "type frozen",
+                "type map",
+                "type text"
+            ]
+        );
+        assert_eq!(
+            path_at(needle, "    my_fi".len()),
+            vec![
+                "statement",
+                "table",
+                "column my_field2",
+                "identifier my_field2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_quoted_identifier() {
+        assert_eq!(
+            path_at("\"My \"\"Type\"\"\"", 5),
+            vec!["statement", "udt", "name", "identifier My \"Type\""]
+        );
+        assert_eq!(
+            path_at("my_ks.\"My", 2),
+            vec!["statement", "udt", "name", "identifier my_ks"]
+        );
+        // The dot is part of the name, but of neither identifier.
+        assert_eq!(path_at(".\"My", 0), vec!["statement", "udt", "name"]);
+    }
+
+    #[test]
+    fn test_user_defined_type_field() {
+        assert_eq!(
+            path_at("my_field2 frozen<map<text, frozen<list<my_udt>>>>\n)", 20),
+            vec![
+                "statement",
+                "udt",
+                "field my_field2",
+                "type frozen",
+                "type map"
+            ]
+        );
+        assert_eq!(
+            path_at("my_field1 int,", 1),
+            vec![
+                "statement",
+                "udt",
+                "field my_field1",
+                "identifier my_field1"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_column_references() {
+        assert_eq!(
+            path_at("((my_field1), my_field2)", 3),
+            vec!["statement", "table", "primary key", "ref my_field1"]
+        );
+        assert_eq!(
+            path_at("(my_field2 DESC)", 1),
+            vec!["statement", "table", "options", "ref my_field2"]
+        );
+    }
+
+    #[test]
+    fn test_statement() {
+        // Statements without nested nodes are found as well.
+        assert_eq!(path_at("SELECT my_field1", 8), vec!["statement"]);
+        let (_, statements) = parse_cql_spanned(INPUT).unwrap();
+        let offset = INPUT.find("SELECT").unwrap();
+        let path = find_node_at(INPUT, &statements, offset);
+        assert_eq!(path, vec![CqlNode::Statement(&statements[2])]);
+        assert!(statements[2].node().is_select());
+    }
+
+    #[test]
+    fn test_table_option() {
+        assert_eq!(
+            path_at("comment = 'hello'", 12),
+            vec!["statement", "table", "options", "option comment"]
+        );
+        assert_eq!(
+            path_at("comment = 'hello'", 0),
+            vec!["statement", "table", "options", "option comment"]
+        );
+    }
+
+    #[test]
+    fn test_between_nodes() {
+        // Whitespace between columns belongs to the table.
+        assert_eq!(
+            path_at(
+                "int,\n    my_field2 frozen<map<text, frozen<list<my_udt>>>>,",
+                5
+            ),
+            vec!["statement", "table"]
+        );
+        // Whitespace between statements belongs to no statement.
+        assert_eq!(path_at(");\n\nCREATE TABLE", 3), Vec::<String>::new());
+        assert_eq!(path_at(");\n\nCREATE TABLE", 1), Vec::<String>::new());
+    }
+}
@@ -1,25 +1,87 @@
+/// Definition of the conditions of lightweight transactions.
+pub mod condition;
 /// Definition of the possible types of the CQL data model.
 pub mod cql_type;
+/// Definition of delete statements.
+pub mod delete;
+/// Doc comments of tree nodes.
+pub mod documented;
+/// Definition of function and aggregate statements.
+pub mod function;
 /// Definition of an identifier.
 pub mod identifier;
+/// Definition of index statements.
+pub mod index;
+/// Definition of insert statements.
+pub mod insert;
+/// Definition of keyspace statements.
+pub mod keyspace;
+/// Definition of materialized view statements.
+pub mod materialized_view;
 /// Definition of order.
 pub mod order;
+/// Definition of permission statements.
+pub mod permission;
 /// Definition of an identifier with a possible keyspace.
 pub mod qualified_identifier;
+/// Definition of relations of a where clause.
+pub mod relation;
+/// Definition of the replication of keyspaces.
+pub mod replication;
+/// Definition of role statements.
+pub mod role;
+/// Definition of select statements.
+pub mod select;
+/// Source locations of tree nodes.
+pub mod span;
 /// Definition of a statement.
 pub mod statement;
 /// Definition of a table.
 pub mod table;
+/// Definition of literals.
+pub mod term;
+/// Definition of trigger statements.
+pub mod trigger;
+/// Arena-backed representation of cql types.
+pub mod type_arena;
+/// Definition of update statements.
+pub mod update;
 /// Definition of a user defined type.
 pub mod user_defined_type;
+/// Definition of the `USING` clause of statements modifying data.
+pub mod using;
+/// Definition of typed values.
+pub mod value;
 
+pub use condition::*;
 pub use cql_type::*;
+pub use delete::*;
+pub use documented::*;
+pub use function::*;
 pub use identifier::*;
+pub use index::*;
+pub use insert::*;
+pub use keyspace::*;
+pub use materialized_view::*;
 pub use order::*;
+pub use permission::*;
 pub use qualified_identifier::*;
+pub use relation::*;
+pub use replication::*;
+pub use role::*;
+pub use select::*;
+pub use span::*;
 pub use statement::*;
 pub use table::*;
+pub use term::*;
+pub use trigger::*;
+pub use type_arena::*;
+pub use update::*;
 pub use user_defined_type::*;
+pub use using::*;
+pub use value::*;
+
+use std::rc::Rc;
 
 /// A tree node with an identifier.
 pub trait Identifiable<I: Clone> {
@@ -51,3 +113,15 @@ pub trait Identifiable<I: Clone> {
         )
     }
 }
+
+impl<I: Clone, T: Identifiable<I>> Identifiable<I> for Rc<T> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        (**self).keyspace()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        (**self).identifier()
+    }
+}
@@ -0,0 +1,65 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::relation::CqlOperator;
+use crate::model::term::{CqlBindMarker, CqlTerm};
+use derive_more::IsVariant;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The `IF` clause of a lightweight transaction, the statement is only applied if it holds.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/dml.html#update-statement>
+///
+/// Grammar:
+/// ```bnf
+/// condition_clause::= IF ( NOT EXISTS | EXISTS | condition ( AND condition )* )
+/// condition::= column_name [ '.' field_name ] operator term
+///         | column_name [ '.' field_name ] IN ( '(' term ( ',' term )* ')' | bind_marker )
+/// ```
+///
+/// Example:
+/// ```cql
+/// IF NOT EXISTS
+/// IF version = 3 AND owner IN ('Alice', 'Bob') AND address.city = 'Paris'
+/// ```
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlCondition<I> {
+    /// The row does not exist yet, `IF NOT EXISTS`, only for an `INSERT`.
+    NotExists,
+    /// The row exists, `IF EXISTS`.
+    Exists,
+    /// The columns of the row satisfy the conditions, joined by `AND`.
+    Columns(Vec<CqlColumnCondition<I>>),
+}
+
+impl<I> CqlCondition<I> {
+    /// The bind markers of the conditions, in the order they are written.
+    pub fn bind_markers(&self) -> impl Iterator<Item = &CqlBindMarker<I>> {
+        let conditions = match self {
+            CqlCondition::Columns(conditions) => &conditions[..],
+            CqlCondition::NotExists | CqlCondition::Exists => &[],
+        };
+        conditions
+            .iter()
+            .flat_map(|condition| condition.term.bind_markers())
+    }
+}
+
+/// A condition on the current value of a column or of a field of it, `version = 3`.
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlColumnCondition<I> {
+    /// The column compared.
+    #[getset(get = "pub")]
+    column: CqlIdentifier<I>,
+    /// The compared field of a user defined type column.
+    #[getset(get = "pub")]
+    field: Option<CqlIdentifier<I>>,
+    /// The comparison.
+    #[getset(get_copy = "pub")]
+    operator: CqlOperator,
+    /// The term the value is compared to, a tuple of the values or a bind marker for `IN`.
+    #[getset(get = "pub")]
+    term: CqlTerm<I>,
+}
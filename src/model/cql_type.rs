@@ -1,5 +1,7 @@
+use crate::error::{CqlValueError, LiteralError};
 use crate::model::*;
 use derive_more::{IsVariant, Unwrap};
+use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::rc::Rc;
 
@@ -34,6 +36,7 @@ pub enum CqlType<UdtType> {
     /// 16-bit signed integer.
     SMALLINT,
     /// UTF-8 character string.
+    #[is_variant(ignore)]
     TEXT,
     /// Time without a time zone. More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/types.html#times>
     TIME,
@@ -59,85 +62,741 @@ pub enum CqlType<UdtType> {
     LIST(Box<CqlType<UdtType>>),
     /// The tuple type is used to indicate that the type is a tuple type.
     TUPLE(Vec<CqlType<UdtType>>),
+    /// A vector of a fixed number of values, with its element type and dimension. More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/types.html#vectors>
+    VECTOR(Box<CqlType<UdtType>>, u32),
     /// The user defined type is used to indicate that the type is a user defined type.
     UserDefined(UdtType),
 }
 
 impl<UdtTypeRef> CqlType<UdtTypeRef> {
-    pub(crate) fn reference_types<I, Table, UdtType>(
-        self,
-        keyspace: Option<&CqlIdentifier<I>>,
-        context: &Vec<CqlStatement<Table, Rc<UdtType>>>,
-    ) -> Result<CqlType<Rc<UdtType>>, CqlQualifiedIdentifier<I>>
+    /// The name of the type in cql, without its parameters.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CqlType::ASCII => "ascii",
+            CqlType::BIGINT => "bigint",
+            CqlType::BLOB => "blob",
+            CqlType::BOOLEAN => "boolean",
+            CqlType::COUNTER => "counter",
+            CqlType::DATE => "date",
+            CqlType::DECIMAL => "decimal",
+            CqlType::DOUBLE => "double",
+            CqlType::DURATION => "duration",
+            CqlType::FLOAT => "float",
+            CqlType::INET => "inet",
+            CqlType::INT => "int",
+            CqlType::SMALLINT => "smallint",
+            CqlType::TEXT => "text",
+            CqlType::TIME => "time",
+            CqlType::TIMESTAMP => "timestamp",
+            CqlType::TIMEUUID => "timeuuid",
+            CqlType::TINYINT => "tinyint",
+            CqlType::UUID => "uuid",
+            CqlType::VARCHAR => "varchar",
+            CqlType::VARINT => "varint",
+            CqlType::FROZEN(_) => "frozen",
+            CqlType::MAP(_) => "map",
+            CqlType::SET(_) => "set",
+            CqlType::LIST(_) => "list",
+            CqlType::TUPLE(_) => "tuple",
+            CqlType::VECTOR(_, _) => "vector",
+            CqlType::UserDefined(_) => "user defined type",
+        }
+    }
+
+    /// The type without its `frozen` wrappers, `frozen<frozen<list<int>>>` is `list<int>`.
+    pub fn unfrozen(&self) -> &Self {
+        match self {
+            CqlType::FROZEN(inner) => inner.unfrozen(),
+            cql_type => cql_type,
+        }
+    }
+
+    /// Whether values of the type are only written as a whole, the type is wrapped in `frozen`
+    /// or is a tuple or vector, which are always frozen.
+    ///
+    /// Types nested in a frozen type are frozen as well, regardless of their own wrapper.
+    pub fn is_frozen_at_top_level(&self) -> bool {
+        matches!(
+            self,
+            CqlType::FROZEN(_) | CqlType::TUPLE(_) | CqlType::VECTOR(_, _)
+        )
+    }
+
+    /// Collapses repeated `frozen` wrappers at every level into a single one, Cassandra treats
+    /// `frozen<frozen<list<int>>>` as `frozen<list<int>>`.
+    pub fn normalize_frozen(self) -> Self {
+        match self {
+            CqlType::FROZEN(inner) => match inner.normalize_frozen() {
+                frozen @ CqlType::FROZEN(_) => frozen,
+                inner => CqlType::FROZEN(Box::new(inner)),
+            },
+            CqlType::MAP(map) => {
+                let (key, value) = *map;
+                CqlType::MAP(Box::new((key.normalize_frozen(), value.normalize_frozen())))
+            }
+            CqlType::SET(inner) => CqlType::SET(Box::new(inner.normalize_frozen())),
+            CqlType::LIST(inner) => CqlType::LIST(Box::new(inner.normalize_frozen())),
+            CqlType::TUPLE(types) => {
+                CqlType::TUPLE(types.into_iter().map(Self::normalize_frozen).collect())
+            }
+            CqlType::VECTOR(inner, dimension) => {
+                CqlType::VECTOR(Box::new(inner.normalize_frozen()), dimension)
+            }
+            cql_type => cql_type,
+        }
+    }
+
+    /// Whether the type is a list, set or map, also when frozen.
+    pub fn is_collection(&self) -> bool {
+        matches!(
+            self.unfrozen(),
+            CqlType::LIST(_) | CqlType::SET(_) | CqlType::MAP(_)
+        )
+    }
+
+    /// Whether the type is one of the integer, floating point, decimal or counter types.
+    pub fn is_numeric(&self) -> bool {
+        matches!(
+            self.unfrozen(),
+            CqlType::TINYINT
+                | CqlType::SMALLINT
+                | CqlType::INT
+                | CqlType::BIGINT
+                | CqlType::VARINT
+                | CqlType::FLOAT
+                | CqlType::DOUBLE
+                | CqlType::DECIMAL
+                | CqlType::COUNTER
+        )
+    }
+
+    /// Whether the type is one of the character string types, `ascii`, `text` or `varchar`.
+    pub fn is_text(&self) -> bool {
+        matches!(
+            self.unfrozen(),
+            CqlType::ASCII | CqlType::TEXT | CqlType::VARCHAR
+        )
+    }
+
+    /// The element type of a list, set or vector, also when frozen.
+    pub fn element_type(&self) -> Option<&Self> {
+        match self.unfrozen() {
+            CqlType::LIST(element) | CqlType::SET(element) | CqlType::VECTOR(element, _) => {
+                Some(element)
+            }
+            _ => None,
+        }
+    }
+
+    /// The key and value types of a map, also when frozen.
+    pub fn key_value_types(&self) -> Option<(&Self, &Self)> {
+        match self.unfrozen() {
+            CqlType::MAP(entry) => Some((&entry.0, &entry.1)),
+            _ => None,
+        }
+    }
+
+    /// The component types of a tuple, also when frozen.
+    pub fn tuple_types(&self) -> Option<&[Self]> {
+        match self.unfrozen() {
+            CqlType::TUPLE(types) => Some(types),
+            _ => None,
+        }
+    }
+
+    /// The user defined types referenced by the type, also nested ones, in order of occurrence
+    /// and without duplicates.
+    pub fn collect_udts<I>(&self) -> Vec<&UdtTypeRef>
     where
-        I: Deref<Target = str> + Clone,
+        I: Clone + Deref<Target = str>,
+        UdtTypeRef: Identifiable<I>,
+    {
+        let mut udts = Vec::new();
+        self.extend_udts(&mut udts);
+        udts
+    }
+
+    /// Adds the user defined types referenced by the type, which are not already known.
+    pub(crate) fn extend_udts<'a, I>(&'a self, udts: &mut Vec<&'a UdtTypeRef>)
+    where
+        I: Clone + Deref<Target = str>,
         UdtTypeRef: Identifiable<I>,
-        UdtType: Identifiable<I>,
     {
         match self {
-            CqlType::ASCII => Ok(CqlType::ASCII),
-            CqlType::BIGINT => Ok(CqlType::BIGINT),
-            CqlType::BLOB => Ok(CqlType::BLOB),
-            CqlType::BOOLEAN => Ok(CqlType::BOOLEAN),
-            CqlType::COUNTER => Ok(CqlType::COUNTER),
-            CqlType::DATE => Ok(CqlType::DATE),
-            CqlType::DECIMAL => Ok(CqlType::DECIMAL),
-            CqlType::DOUBLE => Ok(CqlType::DOUBLE),
-            CqlType::DURATION => Ok(CqlType::DURATION),
-            CqlType::FLOAT => Ok(CqlType::FLOAT),
-            CqlType::INET => Ok(CqlType::INET),
-            CqlType::INT => Ok(CqlType::INT),
-            CqlType::SMALLINT => Ok(CqlType::SMALLINT),
-            CqlType::TEXT => Ok(CqlType::TEXT),
-            CqlType::TIME => Ok(CqlType::TIME),
-            CqlType::TIMESTAMP => Ok(CqlType::TIMESTAMP),
-            CqlType::TIMEUUID => Ok(CqlType::TIMEUUID),
-            CqlType::TINYINT => Ok(CqlType::TINYINT),
-            CqlType::UUID => Ok(CqlType::UUID),
-            CqlType::VARCHAR => Ok(CqlType::VARCHAR),
-            CqlType::VARINT => Ok(CqlType::VARINT),
-            CqlType::FROZEN(udt) => Ok(CqlType::FROZEN(Box::new(
-                udt.reference_types(keyspace, context)?,
-            ))),
+            CqlType::FROZEN(inner)
+            | CqlType::SET(inner)
+            | CqlType::LIST(inner)
+            | CqlType::VECTOR(inner, _) => inner.extend_udts(udts),
             CqlType::MAP(map) => {
-                let (key, value) = *map;
-                Ok(CqlType::MAP(Box::new((
-                    key.reference_types(keyspace, context)?,
-                    value.reference_types(keyspace, context)?,
-                ))))
+                map.0.extend_udts(udts);
+                map.1.extend_udts(udts);
             }
-            CqlType::SET(udt) => {
-                let udt = *udt;
-                Ok(CqlType::SET(Box::new(
-                    udt.reference_types(keyspace, context)?,
-                )))
+            CqlType::TUPLE(types) => {
+                for cql_type in types {
+                    cql_type.extend_udts(udts);
+                }
             }
-            CqlType::LIST(udt) => {
-                let udt = *udt;
-                Ok(CqlType::LIST(Box::new(
-                    udt.reference_types(keyspace, context)?,
-                )))
+            CqlType::UserDefined(udt) => {
+                let known = udts.iter().any(|known| {
+                    known.keyspace() == udt.keyspace() && known.identifier() == udt.identifier()
+                });
+                if !known {
+                    udts.push(udt);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Rebuilds the type with the user defined types converted by the function.
+    ///
+    /// The function is called for every user defined type in order of occurrence, the first
+    /// error is returned.
+    pub fn map_udt<U, E>(
+        self,
+        f: &mut impl FnMut(UdtTypeRef) -> Result<U, E>,
+    ) -> Result<CqlType<U>, E> {
+        Ok(match self {
+            CqlType::ASCII => CqlType::ASCII,
+            CqlType::BIGINT => CqlType::BIGINT,
+            CqlType::BLOB => CqlType::BLOB,
+            CqlType::BOOLEAN => CqlType::BOOLEAN,
+            CqlType::COUNTER => CqlType::COUNTER,
+            CqlType::DATE => CqlType::DATE,
+            CqlType::DECIMAL => CqlType::DECIMAL,
+            CqlType::DOUBLE => CqlType::DOUBLE,
+            CqlType::DURATION => CqlType::DURATION,
+            CqlType::FLOAT => CqlType::FLOAT,
+            CqlType::INET => CqlType::INET,
+            CqlType::INT => CqlType::INT,
+            CqlType::SMALLINT => CqlType::SMALLINT,
+            CqlType::TEXT => CqlType::TEXT,
+            CqlType::TIME => CqlType::TIME,
+            CqlType::TIMESTAMP => CqlType::TIMESTAMP,
+            CqlType::TIMEUUID => CqlType::TIMEUUID,
+            CqlType::TINYINT => CqlType::TINYINT,
+            CqlType::UUID => CqlType::UUID,
+            CqlType::VARCHAR => CqlType::VARCHAR,
+            CqlType::VARINT => CqlType::VARINT,
+            CqlType::FROZEN(inner) => CqlType::FROZEN(Box::new(inner.map_udt(f)?)),
+            CqlType::MAP(map) => {
+                let (key, value) = *map;
+                CqlType::MAP(Box::new((key.map_udt(f)?, value.map_udt(f)?)))
             }
-            CqlType::TUPLE(udts) => Ok(CqlType::TUPLE(
-                udts.into_iter()
-                    .map(|udt| udt.reference_types(keyspace, context))
+            CqlType::SET(inner) => CqlType::SET(Box::new(inner.map_udt(f)?)),
+            CqlType::LIST(inner) => CqlType::LIST(Box::new(inner.map_udt(f)?)),
+            CqlType::TUPLE(types) => CqlType::TUPLE(
+                types
+                    .into_iter()
+                    .map(|cql_type| cql_type.map_udt(f))
                     .collect::<Result<Vec<_>, _>>()?,
-            )),
-            CqlType::UserDefined(udt) => context
+            ),
+            CqlType::VECTOR(inner, dimension) => {
+                CqlType::VECTOR(Box::new(inner.map_udt(f)?), dimension)
+            }
+            CqlType::UserDefined(udt) => CqlType::UserDefined(f(udt)?),
+        })
+    }
+
+    pub(crate) fn reference_types<I, Table, UdtType>(
+        self,
+        keyspace: Option<&CqlIdentifier<I>>,
+        context: &[CqlStatement<I, Table, Rc<UdtType>>],
+    ) -> Result<CqlType<Rc<UdtType>>, CqlQualifiedIdentifier<I>>
+    where
+        I: Deref<Target = str> + Clone,
+        UdtTypeRef: Identifiable<I>,
+        UdtType: Identifiable<I>,
+    {
+        self.map_udt(&mut |udt| {
+            context
                 .iter()
-                .find(|statement| {
+                .find_map(|statement| {
                     statement
                         .create_user_defined_type()
-                        .map(|udt_definition| {
-                            udt_definition.contextualized_identifier(keyspace.clone())
-                                == udt.contextualized_identifier(keyspace.clone())
+                        .filter(|udt_definition| {
+                            udt_definition.contextualized_identifier(keyspace)
+                                == udt.contextualized_identifier(keyspace)
                         })
-                        .unwrap_or(false)
                 })
-                .map(|udt_definition| {
-                    CqlType::UserDefined(udt_definition.create_user_defined_type().unwrap().clone())
+                .cloned()
+                .ok_or_else(|| udt.contextualized_identifier(keyspace))
+        })
+    }
+}
+
+/// Writes the type in its canonical lowercase form, like `frozen<map<text, list<int>>>`.
+impl<UdtType: Display> Display for CqlType<UdtType> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CqlType::FROZEN(inner) => write!(f, "frozen<{}>", inner),
+            CqlType::MAP(entry) => write!(f, "map<{}, {}>", entry.0, entry.1),
+            CqlType::SET(element) => write!(f, "set<{}>", element),
+            CqlType::LIST(element) => write!(f, "list<{}>", element),
+            CqlType::TUPLE(types) => {
+                f.write_str("tuple<")?;
+                for (i, cql_type) in types.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", cql_type)?;
+                }
+                f.write_str(">")
+            }
+            CqlType::VECTOR(element, dimension) => {
+                write!(f, "vector<{}, {}>", element, dimension)
+            }
+            CqlType::UserDefined(udt) => write!(f, "{}", udt),
+            cql_type => f.write_str(cql_type.kind()),
+        }
+    }
+}
+
+impl<I: Deref<Target = str>> CqlType<Rc<CqlUserDefinedType<I>>> {
+    /// Checks that the literal is a valid value of the type.
+    ///
+    /// Bind markers and function calls are accepted everywhere, also inside of collections, their
+    /// values are only known when the statement is executed. Fields missing in a user defined type literal are
+    /// null, unknown fields are an error. The empty `{}` is also an empty set.
+    pub fn validate_literal(&self, term: &CqlTerm<I>) -> Result<(), LiteralError> {
+        match (self, term) {
+            (_, CqlTerm::BindMarker(_) | CqlTerm::FunctionCall(_, _)) => Ok(()),
+            (CqlType::FROZEN(cql_type), term) => cql_type.validate_literal(term),
+            (CqlType::SET(_), CqlTerm::Map(entries)) if entries.is_empty() => Ok(()),
+            (CqlType::LIST(element), CqlTerm::List(elements))
+            | (CqlType::SET(element), CqlTerm::Set(elements)) => elements
+                .iter()
+                .try_for_each(|term| element.validate_literal(term)),
+            (CqlType::VECTOR(element, dimension), CqlTerm::List(elements)) => {
+                if elements.len() != *dimension as usize {
+                    return Err(CqlValueError::VectorLength {
+                        expected: *dimension as usize,
+                        found: elements.len(),
+                    });
+                }
+                elements
+                    .iter()
+                    .try_for_each(|term| element.validate_literal(term))
+            }
+            (CqlType::MAP(entry), CqlTerm::Map(entries)) => {
+                let (key, value) = entry.deref();
+                entries.iter().try_for_each(|(k, v)| {
+                    key.validate_literal(k)?;
+                    value.validate_literal(v)
+                })
+            }
+            (CqlType::TUPLE(types), CqlTerm::TupleLiteral(elements)) => {
+                if types.len() != elements.len() {
+                    return Err(CqlValueError::TupleLength {
+                        expected: types.len(),
+                        found: elements.len(),
+                    });
+                }
+                types
+                    .iter()
+                    .zip(elements)
+                    .try_for_each(|(cql_type, term)| cql_type.validate_literal(term))
+            }
+            (CqlType::UserDefined(udt), CqlTerm::UdtLiteral(fields)) => {
+                fields.iter().try_for_each(|(name, term)| {
+                    let (_, cql_type) = udt
+                        .fields()
+                        .iter()
+                        .find(|(field, _)| field == name)
+                        .ok_or_else(|| CqlValueError::UnknownField {
+                            field: name.to_string(),
+                        })?;
+                    cql_type.validate_literal(term)
                 })
-                .ok_or(udt.contextualized_identifier(keyspace)),
+            }
+            (cql_type, term) => CqlValue::from_term(term, cql_type).map(|_| ()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse::Parse;
+    use nom::IResult;
+    use proptest::prelude::*;
+
+    type Type = CqlType<Rc<CqlUserDefinedType<&'static str>>>;
+
+    fn text(text: &str) -> CqlTerm<&'static str> {
+        CqlTerm::Constant(CqlConstant::String(text.to_string()))
+    }
+
+    #[test]
+    fn test_validate_native_literals() {
+        let cases: Vec<(Type, CqlTerm<&str>, CqlTerm<&str>)> = vec![
+            (CqlType::ASCII, text("abc"), text("äbc")),
+            (
+                CqlType::BIGINT,
+                CqlTerm::Constant(CqlConstant::Integer("-9000000000")),
+                CqlTerm::Constant(CqlConstant::Float("1.5")),
+            ),
+            (
+                CqlType::BLOB,
+                CqlTerm::Constant(CqlConstant::Blob("0xcafe")),
+                CqlTerm::Constant(CqlConstant::Blob("0xcaf")),
+            ),
+            (
+                CqlType::BOOLEAN,
+                CqlTerm::Constant(CqlConstant::Boolean(false)),
+                text("false"),
+            ),
+            (
+                CqlType::COUNTER,
+                CqlTerm::Constant(CqlConstant::Integer("1")),
+                text("1"),
+            ),
+            (CqlType::DATE, text("2024-02-29"), text("2023-02-29")),
+            (
+                CqlType::DECIMAL,
+                CqlTerm::Constant(CqlConstant::Float("1.25")),
+                CqlTerm::Constant(CqlConstant::Float("Infinity")),
+            ),
+            (
+                CqlType::DOUBLE,
+                CqlTerm::Constant(CqlConstant::Float("1e300")),
+                text("1"),
+            ),
+            (
+                CqlType::DURATION,
+                CqlTerm::Constant(CqlConstant::Duration("1h30m")),
+                CqlTerm::Constant(CqlConstant::Duration("1x")),
+            ),
+            (
+                CqlType::FLOAT,
+                CqlTerm::Constant(CqlConstant::Integer("3")),
+                CqlTerm::Constant(CqlConstant::Boolean(true)),
+            ),
+            (CqlType::INET, text("10.0.0.1"), text("10.0.0.256")),
+            (
+                CqlType::INT,
+                CqlTerm::Constant(CqlConstant::Integer("2147483647")),
+                CqlTerm::Constant(CqlConstant::Integer("2147483648")),
+            ),
+            (
+                CqlType::SMALLINT,
+                CqlTerm::Constant(CqlConstant::Integer("-32768")),
+                CqlTerm::Constant(CqlConstant::Integer("32768")),
+            ),
+            (
+                CqlType::TEXT,
+                text("ok"),
+                CqlTerm::Constant(CqlConstant::Integer("1")),
+            ),
+            (CqlType::TIME, text("23:59:59.999"), text("23:59")),
+            (
+                CqlType::TIMESTAMP,
+                text("2024-01-01 10:00:00+0200"),
+                text("2024-01-01 10h"),
+            ),
+            (
+                CqlType::TIMEUUID,
+                CqlTerm::Constant(CqlConstant::Uuid("5a1c395e-b41f-11e5-9f22-ba0be0483c18")),
+                CqlTerm::Constant(CqlConstant::Uuid("5a1c395e-b41f-41e5-9f22-ba0be0483c18")),
+            ),
+            (
+                CqlType::TINYINT,
+                CqlTerm::Constant(CqlConstant::Integer("127")),
+                CqlTerm::Constant(CqlConstant::Integer("128")),
+            ),
+            (
+                CqlType::UUID,
+                CqlTerm::Constant(CqlConstant::Uuid("5a1c395e-b41f-41e5-9f22-ba0be0483c18")),
+                CqlTerm::Constant(CqlConstant::Uuid("5a1c395e-b41f-41e5-9f22")),
+            ),
+            (
+                CqlType::VARCHAR,
+                text(""),
+                CqlTerm::Constant(CqlConstant::Boolean(true)),
+            ),
+            (
+                CqlType::VARINT,
+                CqlTerm::Constant(CqlConstant::Integer("-99999999999999999999")),
+                text("1"),
+            ),
+        ];
+        for (cql_type, valid, invalid) in cases {
+            assert_eq!(cql_type.validate_literal(&valid), Ok(()), "{:?}", cql_type);
+            assert!(
+                cql_type.validate_literal(&invalid).is_err(),
+                "{:?} accepted {:?}",
+                cql_type,
+                invalid
+            );
         }
     }
+
+    #[test]
+    fn test_validate_bind_markers() {
+        let marker = CqlTerm::BindMarker(CqlBindMarker::Positional);
+        assert_eq!(Type::INT.validate_literal(&marker), Ok(()));
+        let list: Type = CqlType::LIST(Box::new(CqlType::INT));
+        assert_eq!(
+            list.validate_literal(&CqlTerm::List(vec![
+                CqlTerm::Constant(CqlConstant::Integer("1")),
+                CqlTerm::BindMarker(CqlBindMarker::Named(CqlIdentifier::new("my_value"))),
+            ])),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_collections() {
+        let map: Type = CqlType::MAP(Box::new((CqlType::TEXT, CqlType::SMALLINT)));
+        assert_eq!(
+            map.validate_literal(&CqlTerm::Map(vec![(
+                text("a"),
+                CqlTerm::Constant(CqlConstant::Integer("1"))
+            )])),
+            Ok(())
+        );
+        assert_eq!(
+            map.validate_literal(&CqlTerm::Map(vec![(
+                text("a"),
+                CqlTerm::Constant(CqlConstant::Integer("99999"))
+            )])),
+            Err(CqlValueError::Invalid {
+                expected: "smallint",
+                literal: "99999".to_string(),
+                reason: "out of range",
+            })
+        );
+        let tuple: Type = CqlType::TUPLE(vec![CqlType::INT, CqlType::TEXT]);
+        assert!(tuple
+            .validate_literal(&CqlTerm::TupleLiteral(vec![CqlTerm::Constant(
+                CqlConstant::Integer("1")
+            )]))
+            .is_err());
+        let set: Type = CqlType::SET(Box::new(CqlType::INT));
+        assert_eq!(set.validate_literal(&CqlTerm::Map(vec![])), Ok(()));
+        assert!(set
+            .validate_literal(&CqlTerm::Map(vec![(
+                CqlTerm::Constant(CqlConstant::Integer("1")),
+                CqlTerm::Constant(CqlConstant::Integer("2"))
+            )]))
+            .is_err());
+    }
+
+    #[test]
+    fn test_validate_udt() {
+        let udt: Type = CqlType::UserDefined(Rc::new(CqlUserDefinedType::new(
+            false,
+            CqlQualifiedIdentifier::new(None, CqlIdentifier::new("address_type")),
+            vec![
+                (CqlIdentifier::new("street"), CqlType::TEXT),
+                (CqlIdentifier::new("number"), CqlType::INT),
+            ],
+        )));
+        let literal = |field: &'static str, term| {
+            CqlTerm::UdtLiteral(vec![(CqlIdentifier::new(field), term)])
+        };
+        assert_eq!(
+            udt.validate_literal(&literal("street", text("Main"))),
+            Ok(())
+        );
+        assert!(udt
+            .validate_literal(&literal("number", text("42")))
+            .unwrap_err()
+            .is_mismatch());
+        assert_eq!(
+            udt.validate_literal(&literal("zip", text("1234"))),
+            Err(CqlValueError::UnknownField {
+                field: "zip".to_string()
+            })
+        );
+    }
+
+    fn parse(input: &str) -> CqlType<CqlIdentifier<&str>> {
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(input);
+        result.unwrap().1
+    }
+
+    #[test]
+    fn test_predicates() {
+        let counter = parse("counter");
+        assert!(counter.is_counter() && counter.is_numeric() && !counter.is_collection());
+        assert!(parse("varint").is_numeric());
+        assert!(!parse("text").is_numeric());
+        assert!(parse("varchar").is_text());
+        assert!(!parse("blob").is_text());
+        assert!(parse("frozen<set<int>>").is_collection());
+        assert!(!parse("tuple<int>").is_collection());
+        assert!(!parse("vector<float, 3>").is_collection());
+        assert!(parse("frozen<my_udt>").is_frozen());
+        assert!(!parse("list<frozen<my_udt>>").is_frozen());
+    }
+
+    #[test]
+    fn test_nested_accessors() {
+        let cql_type = parse("frozen<frozen<map<text, frozen<list<frozen<tuple<int, my_udt>>>>>>>");
+        assert!(cql_type.unfrozen().is_map());
+        assert!(cql_type.element_type().is_none());
+        let (key, value) = cql_type.key_value_types().unwrap();
+        assert_eq!(key, &CqlType::TEXT);
+        assert!(value.is_frozen() && value.is_collection());
+        let element = value.element_type().unwrap();
+        assert!(element.key_value_types().is_none());
+        assert_eq!(
+            element.tuple_types().unwrap(),
+            &[
+                CqlType::INT,
+                CqlType::UserDefined(CqlIdentifier::new("my_udt"))
+            ]
+        );
+        assert_eq!(
+            parse("set<frozen<my_udt>>")
+                .element_type()
+                .unwrap()
+                .unfrozen(),
+            &CqlType::UserDefined(CqlIdentifier::new("my_udt"))
+        );
+        assert_eq!(
+            parse("vector<float, 3>").element_type(),
+            Some(&CqlType::FLOAT)
+        );
+        assert_eq!(parse("int").unfrozen(), &CqlType::INT);
+    }
+
+    #[test]
+    fn test_collect_udts() {
+        let cql_type =
+            parse("map<frozen<udt_b>, frozen<tuple<udt_a, \"udt_b\", list<frozen<udt_b>>>>>");
+        assert_eq!(
+            cql_type.collect_udts(),
+            vec![&CqlIdentifier::new("udt_b"), &CqlIdentifier::new("udt_a")]
+        );
+        assert_eq!(
+            parse("vector<frozen<udt_a>, 2>").collect_udts(),
+            vec![&CqlIdentifier::new("udt_a")]
+        );
+        assert!(parse("frozen<list<int>>").collect_udts().is_empty());
+    }
+
+    #[test]
+    fn test_map_udt() {
+        let cql_type = parse("frozen<map<udt_a, tuple<int, list<udt_b>, vector<udt_c, 2>>>>");
+        let mut seen = Vec::new();
+        let mapped: Result<CqlType<usize>, ()> = cql_type.clone().map_udt(&mut |udt| {
+            seen.push(udt.to_string());
+            Ok(seen.len())
+        });
+        assert_eq!(seen, vec!["udt_a", "udt_b", "udt_c"]);
+        assert_eq!(
+            mapped,
+            Ok(CqlType::FROZEN(Box::new(CqlType::MAP(Box::new((
+                CqlType::UserDefined(1),
+                CqlType::TUPLE(vec![
+                    CqlType::INT,
+                    CqlType::LIST(Box::new(CqlType::UserDefined(2))),
+                    CqlType::VECTOR(Box::new(CqlType::UserDefined(3)), 2),
+                ]),
+            ))))))
+        );
+
+        let mut calls = 0;
+        let failed: Result<CqlType<()>, String> = cql_type.map_udt(&mut |udt| {
+            calls += 1;
+            Err(udt.to_string())
+        });
+        assert_eq!(failed, Err("udt_a".to_string()));
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_display() {
+        let cql_type = parse("FROZEN < MAP<TEXT,list< Int >> >");
+        assert_eq!(cql_type.to_string(), "frozen<map<text, list<int>>>");
+        let cql_type = parse("tuple<\"My \"\"Type\"\"\", vector<FLOAT,1536>, timeuuid>");
+        assert_eq!(
+            cql_type.to_string(),
+            "tuple<\"My \"\"Type\"\"\", vector<float, 1536>, timeuuid>"
+        );
+        let udt = Rc::new(CqlUserDefinedType::new(
+            false,
+            CqlQualifiedIdentifier::new(
+                Some(CqlIdentifier::new("ks_1")),
+                CqlIdentifier::new("address_type"),
+            ),
+            vec![],
+        ));
+        let resolved = CqlType::SET(Box::new(CqlType::FROZEN(Box::new(CqlType::UserDefined(
+            udt,
+        )))));
+        assert_eq!(resolved.to_string(), "set<frozen<ks_1.address_type>>");
+    }
+
+    fn cql_type() -> impl Strategy<Value = CqlType<CqlIdentifier<&'static str>>> {
+        let leaf = prop_oneof![
+            Just(CqlType::ASCII),
+            Just(CqlType::BIGINT),
+            Just(CqlType::BLOB),
+            Just(CqlType::BOOLEAN),
+            Just(CqlType::COUNTER),
+            Just(CqlType::DATE),
+            Just(CqlType::DECIMAL),
+            Just(CqlType::DOUBLE),
+            Just(CqlType::DURATION),
+            Just(CqlType::FLOAT),
+            Just(CqlType::INET),
+            Just(CqlType::INT),
+            Just(CqlType::SMALLINT),
+            Just(CqlType::TEXT),
+            Just(CqlType::TIME),
+            Just(CqlType::TIMESTAMP),
+            Just(CqlType::TIMEUUID),
+            Just(CqlType::TINYINT),
+            Just(CqlType::UUID),
+            Just(CqlType::VARCHAR),
+            Just(CqlType::VARINT),
+            prop::sample::select(vec!["udt_a", "udt_b2", "Udt_C"])
+                .prop_map(|name| CqlType::UserDefined(CqlIdentifier::new(name))),
+            "[a-zA-Z \"]{1,6}"
+                .prop_map(|name| CqlType::UserDefined(CqlIdentifier::new_quoted(name))),
+        ];
+        leaf.prop_recursive(4, 32, 3, |inner| {
+            prop_oneof![
+                inner.clone().prop_map(|ty| CqlType::FROZEN(Box::new(ty))),
+                (inner.clone(), inner.clone())
+                    .prop_map(|(key, value)| CqlType::MAP(Box::new((key, value)))),
+                inner.clone().prop_map(|ty| CqlType::SET(Box::new(ty))),
+                inner.clone().prop_map(|ty| CqlType::LIST(Box::new(ty))),
+                prop::collection::vec(inner.clone(), 1..4).prop_map(CqlType::TUPLE),
+                (inner, 1..4096u32)
+                    .prop_map(|(ty, dimension)| CqlType::VECTOR(Box::new(ty), dimension)),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn test_display_parses_back(ty in cql_type()) {
+            let displayed = ty.to_string();
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(&displayed);
+            prop_assert_eq!(result, Ok(("", ty)));
+        }
+    }
+
+    #[test]
+    fn test_frozen() {
+        let cql_type = parse("frozen<list<frozen<my_udt>>>");
+        assert!(cql_type.is_frozen_at_top_level());
+        let element = cql_type.element_type().unwrap();
+        assert!(element.is_frozen_at_top_level());
+        assert_eq!(cql_type.clone().normalize_frozen(), cql_type);
+
+        let cql_type = parse("list<frozen<frozen<map<int, frozen<frozen<frozen<my_udt>>>>>>>");
+        assert!(!cql_type.is_frozen_at_top_level());
+        assert_eq!(
+            cql_type.normalize_frozen(),
+            parse("list<frozen<map<int, frozen<my_udt>>>>")
+        );
+        assert!(parse("tuple<int, text>").is_frozen_at_top_level());
+        assert!(!parse("my_udt").is_frozen_at_top_level());
+        assert!(!parse("set<int>").is_frozen_at_top_level());
+    }
 }
@@ -0,0 +1,105 @@
+use crate::model::condition::CqlCondition;
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::relation::CqlWhereClause;
+use crate::model::term::{CqlBindMarker, CqlTerm};
+use crate::model::using::CqlUsing;
+use crate::model::Identifiable;
+use derive_more::IsVariant;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::Getters;
+use std::ops::Deref;
+
+/// The cql `DELETE` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/dml.html#delete_statement>
+///
+/// Grammar:
+/// ```bnf
+/// delete_statement::= DELETE [ simple_selection ( ',' simple_selection )* ]
+///         FROM table_name
+///         [ USING TIMESTAMP ( integer | bind_marker ) ]
+///         WHERE where_clause
+///         [ IF ( EXISTS | condition ( AND condition )* ) ]
+/// simple_selection::= column_name
+///         | column_name '[' term ']'
+///         | column_name '.' field_name
+/// ```
+///
+/// Example:
+/// ```cql
+/// DELETE FROM users WHERE userid = 42 AND created >= '2024-01-01';
+/// DELETE phones['home'], address.street FROM users USING TIMESTAMP 1234 WHERE userid = 42 IF EXISTS;
+/// ```
+#[derive(Debug, Clone, Getters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlDelete<I> {
+    /// The deleted values, the whole rows if empty.
+    #[getset(get = "pub")]
+    deletions: Vec<CqlDeletion<I>>,
+    /// The table deleted from.
+    #[getset(get = "pub")]
+    table: CqlQualifiedIdentifier<I>,
+    /// The timestamp of the deletion.
+    #[getset(get = "pub")]
+    using: Option<CqlUsing<I>>,
+    /// The relations selecting the deleted rows, relations on clustering columns can select a
+    /// range.
+    #[getset(get = "pub")]
+    where_clause: CqlWhereClause<I>,
+    /// The condition of a lightweight transaction.
+    #[getset(get = "pub")]
+    condition: Option<CqlCondition<I>>,
+}
+
+impl<I> CqlDelete<I> {
+    /// The bind markers of the statement, in the order they are written.
+    pub fn bind_markers(&self) -> impl Iterator<Item = &CqlBindMarker<I>> {
+        self.deletions
+            .iter()
+            .filter_map(|deletion| match deletion {
+                CqlDeletion::Element(_, element) => Some(element),
+                _ => None,
+            })
+            .flat_map(CqlTerm::bind_markers)
+            .chain(self.using.iter().flat_map(CqlUsing::bind_markers))
+            .chain(self.where_clause.bind_markers())
+            .chain(self.condition.iter().flat_map(CqlCondition::bind_markers))
+    }
+}
+
+/// A value deleted by a `DELETE` statement.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlDeletion<I> {
+    /// The value of the column, `name`.
+    Column(CqlIdentifier<I>),
+    /// The element of a list or map column, `phones['home']`.
+    Element(CqlIdentifier<I>, CqlTerm<I>),
+    /// The field of a user defined type column, `address.street`.
+    Field(CqlIdentifier<I>, CqlIdentifier<I>),
+}
+
+impl<I> CqlDeletion<I> {
+    /// The column the deleted value belongs to.
+    pub fn column(&self) -> &CqlIdentifier<I> {
+        match self {
+            CqlDeletion::Column(column)
+            | CqlDeletion::Element(column, _)
+            | CqlDeletion::Field(column, _) => column,
+        }
+    }
+}
+
+/// A delete is made in the keyspace of its table.
+impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlDelete<I> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.table.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.table.name()
+    }
+}
@@ -0,0 +1,69 @@
+use crate::model::*;
+use derive_new::new;
+use getset::Getters;
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+
+/// A parsed column together with its doc comment.
+pub type DocumentedColumn<'de> = Documented<CqlColumn<&'de str, CqlIdentifier<&'de str>>>;
+/// A parsed table together with its doc comment.
+pub type DocumentedTable<'de> =
+    Documented<CqlTable<&'de str, DocumentedColumn<'de>, CqlIdentifier<&'de str>>>;
+/// A parsed user defined type together with its doc comment.
+pub type DocumentedUserDefinedType<'de> =
+    Documented<ParsedCqlUserDefinedType<&'de str, CqlIdentifier<&'de str>>>;
+/// A statement as returned by [`parse_cql_with_comments`](crate::parse_cql_with_comments).
+pub type DocumentedStatement<'de> =
+    CqlStatement<&'de str, DocumentedTable<'de>, DocumentedUserDefinedType<'de>>;
+
+/// A tree node together with the comments directly preceding it in the input.
+///
+/// Used in place of a node type parameter, e.g. as the column of a [`CqlTable`], to keep the
+/// comments which document the node.
+#[derive(Debug, Clone, PartialEq, Getters, new)]
+pub struct Documented<T> {
+    /// The doc comment, without comment markers and with trimmed lines.
+    #[getset(get = "pub")]
+    doc: Option<String>,
+    /// The node.
+    #[getset(get = "pub")]
+    node: T,
+}
+
+impl<T> Documented<T> {
+    /// Drops the doc comment and returns the node.
+    pub fn into_node(self) -> T {
+        self.node
+    }
+
+    /// Replaces the doc comment.
+    pub(crate) fn set_doc(&mut self, doc: Option<String>) {
+        self.doc = doc;
+    }
+}
+
+impl<T> Deref for Documented<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+impl<T: Display> Display for Documented<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.node.fmt(f)
+    }
+}
+
+impl<I: Clone, T: Identifiable<I>> Identifiable<I> for Documented<T> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.node.keyspace()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.node.identifier()
+    }
+}
@@ -0,0 +1,46 @@
+use crate::model::cql_type::CqlType;
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::Identifiable;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The argument types of a function or aggregate, selecting one of its overloads.
+pub type CqlFunctionSignature<I> = Vec<CqlType<CqlIdentifier<I>>>;
+
+/// The cql `DROP AGGREGATE` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/functions.html#drop-aggregate-statement>
+///
+/// Grammar:
+/// ```bnf
+/// drop_aggregate_statement::= DROP AGGREGATE [ IF EXISTS ] function_name
+///         [ '(' arguments_signature ')' ]
+/// arguments_signature::= cql_type ( ',' cql_type )*
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlDropAggregate<I> {
+    /// If the aggregate should only be dropped if it exists.
+    #[getset(get_copy = "pub")]
+    if_exists: bool,
+    /// The name of the aggregate.
+    #[getset(get = "pub")]
+    name: CqlQualifiedIdentifier<I>,
+    /// The argument types, required if the aggregate is overloaded.
+    #[getset(get = "pub")]
+    signature: Option<CqlFunctionSignature<I>>,
+}
+
+impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlDropAggregate<I> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.name.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.name.identifier()
+    }
+}
@@ -1,7 +1,174 @@
 use crate::model::*;
 use derive_more::{IsVariant, Unwrap};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
 
+/// The reserved keywords of CQL, in uppercase and sorted. They are only identifiers when quoted.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/appendices.html#appendix-A>
+pub const RESERVED_KEYWORDS: [&str; 59] = [
+    "ADD",
+    "ALLOW",
+    "ALTER",
+    "AND",
+    "APPLY",
+    "ASC",
+    "AUTHORIZE",
+    "BATCH",
+    "BEGIN",
+    "BY",
+    "COLUMNFAMILY",
+    "CREATE",
+    "DELETE",
+    "DESC",
+    "DESCRIBE",
+    "DROP",
+    "ENTRIES",
+    "EXECUTE",
+    "FROM",
+    "FULL",
+    "GRANT",
+    "IF",
+    "IN",
+    "INDEX",
+    "INFINITY",
+    "INSERT",
+    "INTO",
+    "KEYSPACE",
+    "LIMIT",
+    "MATERIALIZED",
+    "MBEAN",
+    "MBEANS",
+    "MODIFY",
+    "NAN",
+    "NORECURSIVE",
+    "NOT",
+    "NULL",
+    "OF",
+    "ON",
+    "OR",
+    "ORDER",
+    "PRIMARY",
+    "RENAME",
+    "REPLACE",
+    "REVOKE",
+    "SCHEMA",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "TO",
+    "TOKEN",
+    "TRUNCATE",
+    "UNLOGGED",
+    "UPDATE",
+    "USE",
+    "USING",
+    "VIEW",
+    "WHERE",
+    "WITH",
+];
+
+/// The keywords of CQL which are not reserved, in uppercase and sorted. They are also unquoted
+/// identifiers, but may be confused with the keyword.
+pub const UNRESERVED_KEYWORDS: [&str; 74] = [
+    "AGGREGATE",
+    "ALL",
+    "AS",
+    "ASCII",
+    "BIGINT",
+    "BLOB",
+    "BOOLEAN",
+    "CALLED",
+    "CLUSTERING",
+    "COMPACT",
+    "CONTAINS",
+    "COUNT",
+    "COUNTER",
+    "CUSTOM",
+    "DATE",
+    "DECIMAL",
+    "DISTINCT",
+    "DOUBLE",
+    "DURATION",
+    "EXISTS",
+    "FILTERING",
+    "FINALFUNC",
+    "FLOAT",
+    "FROZEN",
+    "FUNCTION",
+    "FUNCTIONS",
+    "INET",
+    "INITCOND",
+    "INPUT",
+    "INT",
+    "JSON",
+    "KEY",
+    "KEYS",
+    "KEYSPACES",
+    "LANGUAGE",
+    "LIKE",
+    "LIST",
+    "LOGIN",
+    "MAP",
+    "NOLOGIN",
+    "NOSUPERUSER",
+    "OPTIONS",
+    "PARTITION",
+    "PASSWORD",
+    "PER",
+    "PERMISSION",
+    "PERMISSIONS",
+    "RETURNS",
+    "ROLE",
+    "ROLES",
+    "SFUNC",
+    "SMALLINT",
+    "STATIC",
+    "STORAGE",
+    "STYPE",
+    "SUPERUSER",
+    "TEXT",
+    "TIME",
+    "TIMESTAMP",
+    "TIMEUUID",
+    "TINYINT",
+    "TRIGGER",
+    "TTL",
+    "TUPLE",
+    "TYPE",
+    "UNSET",
+    "USER",
+    "USERS",
+    "UUID",
+    "VALUES",
+    "VARCHAR",
+    "VARINT",
+    "VECTOR",
+    "WRITETIME",
+];
+
+/// Looks up a word case-insensitively in a sorted list of uppercase keywords.
+fn contains_keyword(keywords: &[&str], word: &str) -> bool {
+    keywords
+        .binary_search_by(|keyword| {
+            keyword
+                .bytes()
+                .cmp(word.bytes().map(|b| b.to_ascii_uppercase()))
+        })
+        .is_ok()
+}
+
+/// Whether the word is a reserved keyword, which can not be an unquoted identifier.
+pub fn is_reserved_keyword(word: &str) -> bool {
+    contains_keyword(&RESERVED_KEYWORDS, word)
+}
+
+/// Whether the word is a keyword of CQL, reserved or not.
+pub fn is_keyword(word: &str) -> bool {
+    is_reserved_keyword(word) || contains_keyword(&UNRESERVED_KEYWORDS, word)
+}
+
 /// Cql Identifier.
 /// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/types.html#identifiers>
 /// ```bnf
@@ -31,17 +198,67 @@ impl<I> CqlIdentifier<I> {
     }
 }
 
+/// Whether the unquoted identifier, which Cassandra folds to lowercase, is the quoted one.
+fn folds_to(unquoted: &str, quoted: &str) -> bool {
+    unquoted.len() == quoted.len()
+        && unquoted
+            .bytes()
+            .zip(quoted.bytes())
+            .all(|(u, q)| u.to_ascii_lowercase() == q)
+}
+
+/// Unquoted identifiers are case-insensitive, they are folded to lowercase. Quoted identifiers
+/// are case-sensitive, `MyTable` is `"mytable"` but not `"MyTable"`.
 impl<I: Deref<Target = str>> PartialEq for CqlIdentifier<I> {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (CqlIdentifier::Unquoted(s), CqlIdentifier::Unquoted(o)) => s.eq_ignore_ascii_case(o),
-            (CqlIdentifier::Unquoted(s), CqlIdentifier::Quoted(o)) => s.eq_ignore_ascii_case(o),
-            (CqlIdentifier::Quoted(s), CqlIdentifier::Unquoted(o)) => s.eq_ignore_ascii_case(o),
+            (CqlIdentifier::Unquoted(s), CqlIdentifier::Quoted(o)) => folds_to(s, o),
+            (CqlIdentifier::Quoted(s), CqlIdentifier::Unquoted(o)) => folds_to(o, s),
             (CqlIdentifier::Quoted(s), CqlIdentifier::Quoted(o)) => s == o,
         }
     }
 }
 
+impl<I: Deref<Target = str>> Eq for CqlIdentifier<I> {}
+
+/// Hashes the folded name, equal identifiers have equal hashes.
+impl<I: Deref<Target = str>> Hash for CqlIdentifier<I> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        match self {
+            CqlIdentifier::Unquoted(s) if s.bytes().any(|b| b.is_ascii_uppercase()) => {
+                s.to_ascii_lowercase().hash(state)
+            }
+            CqlIdentifier::Unquoted(s) => s.deref().hash(state),
+            CqlIdentifier::Quoted(s) => s.hash(state),
+        }
+    }
+}
+
+/// The bytes of the name Cassandra uses, unquoted identifiers are folded to lowercase.
+fn folded_bytes<I: Deref<Target = str>>(
+    identifier: &CqlIdentifier<I>,
+) -> impl Iterator<Item = u8> + '_ {
+    let unquoted = identifier.is_unquoted();
+    identifier
+        .bytes()
+        .map(move |b| if unquoted { b.to_ascii_lowercase() } else { b })
+}
+
+/// Orders by the folded name, consistent with equality: `MyTable` and `"mytable"` are equal,
+/// `"MyTable"` orders before both.
+impl<I: Deref<Target = str>> Ord for CqlIdentifier<I> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        folded_bytes(self).cmp(folded_bytes(other))
+    }
+}
+
+impl<I: Deref<Target = str>> PartialOrd for CqlIdentifier<I> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<I: Deref<Target = str>> Deref for CqlIdentifier<I> {
     type Target = str;
 
@@ -53,6 +270,54 @@ impl<I: Deref<Target = str>> Deref for CqlIdentifier<I> {
     }
 }
 
+impl<I: Deref<Target = str>> CqlIdentifier<I> {
+    /// Renders the identifier as cql, which parses back to an equal identifier.
+    ///
+    /// Quoted identifiers are quoted with their quotes doubled. Unquoted identifiers are quoted
+    /// in their folded lowercase form if they are reserved keywords or no valid unquoted
+    /// identifiers.
+    pub fn to_cql(&self) -> String {
+        self.to_string()
+    }
+
+    /// The name Cassandra uses, unquoted identifiers are folded to lowercase.
+    pub(crate) fn folded(&self) -> String {
+        match self {
+            CqlIdentifier::Unquoted(s) => s.to_ascii_lowercase(),
+            CqlIdentifier::Quoted(s) => s.clone(),
+        }
+    }
+}
+
+/// Writes the name in double quotes, with its quotes doubled.
+fn write_quoted(f: &mut Formatter<'_>, name: &str) -> std::fmt::Result {
+    f.write_str("\"")?;
+    for (i, part) in name.split('"').enumerate() {
+        if i > 0 {
+            f.write_str("\"\"")?;
+        }
+        f.write_str(part)?;
+    }
+    f.write_str("\"")
+}
+
+impl<I: Deref<Target = str>> Display for CqlIdentifier<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CqlIdentifier::Unquoted(s) => {
+                let valid = s.starts_with(|c: char| c.is_ascii_alphabetic())
+                    && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+                if valid && !is_reserved_keyword(s) {
+                    f.write_str(s)
+                } else {
+                    write_quoted(f, &s.to_ascii_lowercase())
+                }
+            }
+            CqlIdentifier::Quoted(s) => write_quoted(f, s),
+        }
+    }
+}
+
 impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlIdentifier<I> {
     #[inline(always)]
     fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
@@ -61,6 +326,123 @@ impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlIdentifier<I> {
 
     #[inline(always)]
     fn identifier(&self) -> &CqlIdentifier<I> {
-        &self
+        self
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_eq() {
+        let unquoted = CqlIdentifier::new("MyTable");
+        assert_eq!(unquoted, CqlIdentifier::new("mytable"));
+        assert_eq!(unquoted, CqlIdentifier::new_quoted("mytable".to_string()));
+        assert_eq!(CqlIdentifier::new_quoted("mytable".to_string()), unquoted);
+        assert_ne!(unquoted, CqlIdentifier::new_quoted("MyTable".to_string()));
+        assert_ne!(CqlIdentifier::new_quoted("MyTable".to_string()), unquoted);
+        assert_ne!(
+            CqlIdentifier::<&str>::new_quoted("MyTable".to_string()),
+            CqlIdentifier::new_quoted("mytable".to_string())
+        );
+        assert_ne!(unquoted, CqlIdentifier::new_quoted("my_table".to_string()));
+    }
+
+    fn hash(identifier: &CqlIdentifier<&str>) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        identifier.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_hash() {
+        let identifiers = [
+            CqlIdentifier::new("MyTable"),
+            CqlIdentifier::new("mytable"),
+            CqlIdentifier::new("MYTABLE"),
+            CqlIdentifier::new_quoted("mytable".to_string()),
+            CqlIdentifier::new_quoted("MyTable".to_string()),
+            CqlIdentifier::new_quoted("MYTABLE".to_string()),
+        ];
+        for left in &identifiers {
+            for right in &identifiers {
+                if left == right {
+                    assert_eq!(hash(left), hash(right), "{:?} {:?}", left, right);
+                }
+            }
+        }
+        let set = identifiers
+            .into_iter()
+            .collect::<std::collections::HashSet<_>>();
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_keywords() {
+        for keywords in [&RESERVED_KEYWORDS[..], &UNRESERVED_KEYWORDS[..]] {
+            assert!(keywords.windows(2).all(|pair| pair[0] < pair[1]));
+            assert!(keywords.iter().all(|keyword| is_keyword(keyword)));
+        }
+        assert!(is_reserved_keyword("select"));
+        assert!(is_reserved_keyword("Primary"));
+        assert!(!is_reserved_keyword("selects"));
+        assert!(!is_reserved_keyword("ttl"));
+        assert!(is_keyword("ttl"));
+        assert!(!is_keyword("my_table"));
+        assert!(!is_keyword(""));
+    }
+
+    #[test]
+    fn test_to_cql() {
+        for (identifier, cql) in [
+            (CqlIdentifier::new("my_table"), "my_table"),
+            (CqlIdentifier::new("MyTable"), "MyTable"),
+            (CqlIdentifier::new("Select"), "\"select\""),
+            (CqlIdentifier::new("my table"), "\"my table\""),
+            (
+                CqlIdentifier::new_quoted("MyTable".to_string()),
+                "\"MyTable\"",
+            ),
+            (
+                CqlIdentifier::new_quoted("we\"ird".to_string()),
+                "\"we\"\"ird\"",
+            ),
+            (CqlIdentifier::new_quoted("\"".to_string()), "\"\"\"\""),
+        ] {
+            assert_eq!(identifier.to_cql(), cql);
+            assert_eq!(identifier.to_string(), cql);
+        }
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut identifiers = [
+            CqlIdentifier::new("orders"),
+            CqlIdentifier::new_quoted("Orders".to_string()),
+            CqlIdentifier::new("Customers"),
+            CqlIdentifier::new_quoted("addresses".to_string()),
+            CqlIdentifier::new("Items"),
+        ];
+        identifiers.sort();
+        assert_eq!(
+            identifiers.iter().map(|i| i.to_cql()).collect::<Vec<_>>(),
+            vec![
+                "\"Orders\"",
+                "\"addresses\"",
+                "Customers",
+                "Items",
+                "orders"
+            ]
+        );
+
+        assert_eq!(
+            CqlIdentifier::new("MyTable").cmp(&CqlIdentifier::new_quoted("mytable".to_string())),
+            Ordering::Equal
+        );
+        assert_eq!(
+            CqlIdentifier::new("b").cmp(&CqlIdentifier::new("A")),
+            Ordering::Greater
+        );
     }
 }
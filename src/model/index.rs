@@ -0,0 +1,104 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use derive_more::IsVariant;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The cql `CREATE INDEX` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/indexes.html#create-index-statement>
+///
+/// ScyllaDB local indexes repeat the partition key of the table before the indexed column, the
+/// index is then kept within each partition.
+/// More Information: <https://opensource.docs.scylladb.com/stable/features/local-secondary-indexes.html>
+///
+/// Grammar:
+/// ```bnf
+/// create_index_statement::= CREATE [ CUSTOM ] INDEX [ IF NOT EXISTS ] [ index_name ]
+///         ON table_name '(' [ '(' column_name ( ',' column_name )* ')' ',' ] index_target ')'
+///         [ USING string [ WITH OPTIONS = map_literal ] ]
+/// index_target::= column_name
+///         | KEYS '(' column_name ')'
+///         | VALUES '(' column_name ')'
+///         | ENTRIES '(' column_name ')'
+///         | FULL '(' column_name ')'
+/// ```
+///
+/// Example:
+/// ```cql
+/// CREATE INDEX IF NOT EXISTS orders_by_user ON shop.orders (user_id);
+/// CREATE INDEX ON shop.orders ((shop_id, region), user_id);
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlCreateIndex<I> {
+    /// If the index is implemented by a custom class.
+    #[getset(get_copy = "pub")]
+    custom: bool,
+    /// If the index should only be created if it does not exist.
+    #[getset(get_copy = "pub")]
+    if_not_exists: bool,
+    /// The name of the index, generated by the database if missing.
+    #[getset(get = "pub")]
+    name: Option<CqlIdentifier<I>>,
+    /// The indexed table.
+    #[getset(get = "pub")]
+    table: CqlQualifiedIdentifier<I>,
+    /// The partition key columns of a local index.
+    #[getset(get = "pub")]
+    partition_key: Option<Vec<CqlIdentifier<I>>>,
+    /// The indexed column.
+    #[getset(get = "pub")]
+    target: CqlIndexTarget<I>,
+    /// The `USING` clause, selecting the implementation of the index.
+    #[getset(get = "pub")]
+    using: Option<CqlIndexClass>,
+}
+
+/// The implementation of an index.
+#[derive(Debug, Clone, PartialEq, Getters, new)]
+pub struct CqlIndexClass {
+    /// The class or name of the implementation, with its escapes resolved.
+    #[getset(get = "pub")]
+    class: String,
+    /// The options of the implementation.
+    #[getset(get = "pub")]
+    options: Option<Vec<(String, String)>>,
+}
+
+impl<I> CqlCreateIndex<I> {
+    /// If the index is a ScyllaDB local index, restricted to a partition.
+    pub fn is_local(&self) -> bool {
+        self.partition_key.is_some()
+    }
+}
+
+/// The indexed part of a column.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str>)]
+pub enum CqlIndexTarget<I> {
+    /// The values of the column, or of a collection.
+    Column(CqlIdentifier<I>),
+    /// `KEYS(column)`, the keys of a map.
+    Keys(CqlIdentifier<I>),
+    /// `VALUES(column)`, the values of a collection.
+    Values(CqlIdentifier<I>),
+    /// `ENTRIES(column)`, the entries of a map.
+    Entries(CqlIdentifier<I>),
+    /// `FULL(column)`, a frozen collection as a whole.
+    Full(CqlIdentifier<I>),
+}
+
+impl<I> CqlIndexTarget<I> {
+    /// The indexed column.
+    pub fn column(&self) -> &CqlIdentifier<I> {
+        match self {
+            CqlIndexTarget::Column(column)
+            | CqlIndexTarget::Keys(column)
+            | CqlIndexTarget::Values(column)
+            | CqlIndexTarget::Entries(column)
+            | CqlIndexTarget::Full(column) => column,
+        }
+    }
+}
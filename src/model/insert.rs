@@ -0,0 +1,91 @@
+use crate::model::condition::CqlCondition;
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::term::{CqlBindMarker, CqlTerm};
+use crate::model::using::CqlUsing;
+use crate::model::Identifiable;
+use derive_more::IsVariant;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::Getters;
+use std::ops::Deref;
+
+/// The cql `INSERT` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/dml.html#insert-statement>
+///
+/// Grammar:
+/// ```bnf
+/// insert_statement::= INSERT INTO table_name ( names_values | json_clause )
+///         [ IF NOT EXISTS ]
+///         [ USING update_parameter ( AND update_parameter )* ]
+/// names_values::= '(' column_name ( ',' column_name )* ')' VALUES '(' term ( ',' term )* ')'
+/// json_clause::= JSON ( string | bind_marker ) [ DEFAULT ( NULL | UNSET ) ]
+/// ```
+///
+/// Example:
+/// ```cql
+/// INSERT INTO users (userid, name) VALUES (42, 'Alice') IF NOT EXISTS;
+/// INSERT INTO users JSON '{"userid": 42, "name": "Alice"}' DEFAULT UNSET USING TTL 86400;
+/// ```
+#[derive(Debug, Clone, Getters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlInsert<I> {
+    /// The table inserted into.
+    #[getset(get = "pub")]
+    table: CqlQualifiedIdentifier<I>,
+    /// The inserted values.
+    #[getset(get = "pub")]
+    values: CqlInsertValues<I>,
+    /// The condition of a lightweight transaction, only `IF NOT EXISTS`.
+    #[getset(get = "pub")]
+    condition: Option<CqlCondition<I>>,
+    /// The time to live, the timestamp and the timeout of the write.
+    #[getset(get = "pub")]
+    using: Option<CqlUsing<I>>,
+}
+
+impl<I> CqlInsert<I> {
+    /// The bind markers of the statement, in the order they are written.
+    pub fn bind_markers(&self) -> impl Iterator<Item = &CqlBindMarker<I>> {
+        let terms: Vec<_> = match &self.values {
+            CqlInsertValues::Values(values) => values.iter().map(|(_, term)| term).collect(),
+            CqlInsertValues::Json(row, _) => vec![row],
+        };
+        terms
+            .into_iter()
+            .flat_map(CqlTerm::bind_markers)
+            .chain(self.using.iter().flat_map(CqlUsing::bind_markers))
+    }
+}
+
+/// The values of an `INSERT` statement.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlInsertValues<I> {
+    /// The columns with their values, `(userid, name) VALUES (42, 'Alice')`.
+    Values(Vec<(CqlIdentifier<I>, CqlTerm<I>)>),
+    /// A json encoded row, a string or a bind marker, and what omitted columns are set to.
+    Json(CqlTerm<I>, Option<CqlJsonDefault>),
+}
+
+/// What the columns omitted from a json encoded row are set to.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IsVariant)]
+pub enum CqlJsonDefault {
+    /// `DEFAULT NULL`, omitted columns are deleted, the default.
+    Null,
+    /// `DEFAULT UNSET`, omitted columns keep their value.
+    Unset,
+}
+
+/// An insert is made in the keyspace of its table.
+impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlInsert<I> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.table.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.table.name()
+    }
+}
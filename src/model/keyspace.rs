@@ -0,0 +1,41 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::replication::CqlReplication;
+use crate::model::table::options::CqlOptionValue;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The cql `CREATE KEYSPACE` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/ddl.html#create-keyspace-statement>
+///
+/// Grammar:
+/// ```bnf
+/// create_keyspace_statement::= CREATE KEYSPACE [ IF NOT EXISTS ] keyspace_name
+///         WITH options
+/// ```
+///
+/// Example:
+/// ```cql
+/// CREATE KEYSPACE shop WITH replication = {'class': 'NetworkTopologyStrategy', 'dc1': 3}
+///     AND durable_writes = true;
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlCreateKeyspace<I> {
+    /// If the keyspace should only be created if it does not exist.
+    #[getset(get_copy = "pub")]
+    if_not_exists: bool,
+    /// The name of the keyspace.
+    #[getset(get = "pub")]
+    name: CqlIdentifier<I>,
+    /// The replication of the keyspace, `replication`.
+    #[getset(get = "pub")]
+    replication: CqlReplication<I>,
+    /// Whether the commit log is used for the keyspace, `durable_writes`.
+    #[getset(get_copy = "pub")]
+    durable_writes: Option<bool>,
+    /// The other options, with their names.
+    #[getset(get = "pub")]
+    options: Vec<(I, CqlOptionValue<I>)>,
+}
@@ -0,0 +1,46 @@
+use crate::model::*;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The cql `ALTER MATERIALIZED VIEW` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/mvs.html#alter-materialized-view>
+///
+/// Grammar:
+/// ```bnf
+/// alter_materialized_view_statement::= ALTER MATERIALIZED VIEW [ IF EXISTS ] view_name
+///     WITH table_options
+/// ```
+///
+/// Example:
+/// ```cql
+/// ALTER MATERIALIZED VIEW cycling.cyclist_by_age
+///     WITH comment = 'A most excellent and useful view'
+///     AND bloom_filter_fp_chance = 0.02;
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlAlterMaterializedView<I> {
+    /// If the view should only be altered if it exists.
+    #[getset(get_copy = "pub")]
+    if_exists: bool,
+    /// The name of the view.
+    #[getset(get = "pub")]
+    name: CqlQualifiedIdentifier<I>,
+    /// The new options of the view.
+    #[getset(get = "pub")]
+    options: CqlTableOptions<I, CqlIdentifier<I>>,
+}
+
+impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlAlterMaterializedView<I> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.name.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.name.identifier()
+    }
+}
@@ -0,0 +1,139 @@
+use crate::model::function::CqlFunctionSignature;
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use derive_more::IsVariant;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The cql `GRANT` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/security.html#grant-permission-statement>
+///
+/// Grammar:
+/// ```bnf
+/// grant_permission_statement::= GRANT permissions ON resource TO role_name
+/// permissions::= ALL [ PERMISSIONS ] | permission [ PERMISSION ]
+/// permission::= CREATE | ALTER | DROP | SELECT | MODIFY | AUTHORIZE | DESCRIBE | EXECUTE
+///         | UNMASK | SELECT_MASKED
+/// resource::= ALL KEYSPACES
+///         | KEYSPACE keyspace_name
+///         | [ TABLE ] table_name
+///         | ALL ROLES
+///         | ROLE role_name
+///         | ALL FUNCTIONS [ IN KEYSPACE keyspace_name ]
+///         | FUNCTION function_name '(' [ cql_type ( ',' cql_type )* ] ')'
+///         | ALL MBEANS
+///         | ( MBEAN | MBEANS ) string
+/// ```
+///
+/// Example:
+/// ```cql
+/// GRANT SELECT ON KEYSPACE shop TO analyst;
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlGrant<I> {
+    /// The granted permission.
+    #[getset(get_copy = "pub")]
+    permission: CqlPermission,
+    /// The resource the permission is granted on.
+    #[getset(get = "pub")]
+    resource: CqlResource<I>,
+    /// The role the permission is granted to.
+    #[getset(get = "pub")]
+    role: CqlIdentifier<I>,
+}
+
+/// The cql `REVOKE` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/security.html#revoke-permission-statement>
+///
+/// Grammar:
+/// ```bnf
+/// revoke_permission_statement::= REVOKE permissions ON resource FROM role_name
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlRevoke<I> {
+    /// The revoked permission.
+    #[getset(get_copy = "pub")]
+    permission: CqlPermission,
+    /// The resource the permission is revoked on.
+    #[getset(get = "pub")]
+    resource: CqlResource<I>,
+    /// The role the permission is revoked from.
+    #[getset(get = "pub")]
+    role: CqlIdentifier<I>,
+}
+
+/// A permission on a resource.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IsVariant)]
+pub enum CqlPermission {
+    /// Creating keyspaces, tables, functions or roles.
+    Create,
+    /// Altering keyspaces, tables, functions or roles.
+    Alter,
+    /// Dropping keyspaces, tables, functions or roles.
+    Drop,
+    /// Selecting data, or reading mbeans.
+    Select,
+    /// Inserting, updating, deleting and truncating data, or writing mbeans.
+    Modify,
+    /// Granting and revoking permissions.
+    Authorize,
+    /// Listing roles, or mbeans.
+    Describe,
+    /// Executing functions.
+    Execute,
+    /// Reading the unmasked values of masked columns.
+    Unmask,
+    /// Selecting with restrictions on masked columns.
+    SelectMasked,
+    /// Every permission applicable to the resource.
+    All,
+}
+
+impl CqlPermission {
+    /// The keyword of the permission, like `SELECT`.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            CqlPermission::Create => "CREATE",
+            CqlPermission::Alter => "ALTER",
+            CqlPermission::Drop => "DROP",
+            CqlPermission::Select => "SELECT",
+            CqlPermission::Modify => "MODIFY",
+            CqlPermission::Authorize => "AUTHORIZE",
+            CqlPermission::Describe => "DESCRIBE",
+            CqlPermission::Execute => "EXECUTE",
+            CqlPermission::Unmask => "UNMASK",
+            CqlPermission::SelectMasked => "SELECT_MASKED",
+            CqlPermission::All => "ALL",
+        }
+    }
+}
+
+/// A resource permissions are granted on.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlResource<I> {
+    /// `ALL KEYSPACES`, every keyspace and table.
+    AllKeyspaces,
+    /// `KEYSPACE name`, the keyspace and its tables.
+    Keyspace(CqlIdentifier<I>),
+    /// `[TABLE] name`.
+    Table(CqlQualifiedIdentifier<I>),
+    /// `ALL ROLES`.
+    AllRoles,
+    /// `ROLE name`.
+    Role(CqlIdentifier<I>),
+    /// `ALL FUNCTIONS`, optionally only those `IN KEYSPACE name`.
+    AllFunctions(Option<CqlIdentifier<I>>),
+    /// `FUNCTION name(types)`, a single overload of a function.
+    Function(CqlQualifiedIdentifier<I>, CqlFunctionSignature<I>),
+    /// `ALL MBEANS`.
+    AllMBeans,
+    /// `MBEAN 'name'`.
+    MBean(String),
+    /// `MBEANS 'pattern'`, the mbeans matching the pattern.
+    MBeans(String),
+}
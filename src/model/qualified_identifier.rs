@@ -1,7 +1,14 @@
+use crate::error::{CqlParseError, RichError};
 use crate::model::*;
+use crate::parse::Parse;
+use crate::utils::space0_around;
 use derive_new::new;
 use getset::Getters;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
 use std::ops::Deref;
+use std::str::FromStr;
 
 /// A identifier with a possible keyspace prefix.
 #[derive(Debug, Clone, new, Getters)]
@@ -21,6 +28,30 @@ impl<I: Deref<Target = str>> PartialEq for CqlQualifiedIdentifier<I> {
     }
 }
 
+impl<I: Deref<Target = str>> Eq for CqlQualifiedIdentifier<I> {}
+
+impl<I: Deref<Target = str>> Hash for CqlQualifiedIdentifier<I> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.keyspace.hash(state);
+        self.name.hash(state);
+    }
+}
+
+/// Orders by keyspace, then by name, an identifier without keyspace orders first.
+impl<I: Deref<Target = str>> Ord for CqlQualifiedIdentifier<I> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.keyspace
+            .cmp(&other.keyspace)
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
+impl<I: Deref<Target = str>> PartialOrd for CqlQualifiedIdentifier<I> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
 impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlQualifiedIdentifier<I> {
     #[inline(always)]
     fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
@@ -32,3 +63,159 @@ impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlQualifiedIdentifier<
         &self.name
     }
 }
+
+impl<I: Deref<Target = str>> Display for CqlQualifiedIdentifier<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if let Some(keyspace) = &self.keyspace {
+            write!(f, "{}.", keyspace)?;
+        }
+
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Copies the name of a parsed identifier, keeping whether it is quoted.
+fn owned_identifier(identifier: CqlIdentifier<&str>) -> CqlIdentifier<String> {
+    match identifier {
+        CqlIdentifier::Unquoted(name) => CqlIdentifier::Unquoted(name.to_string()),
+        CqlIdentifier::Quoted(name) => CqlIdentifier::Quoted(name),
+    }
+}
+
+/// Parses a qualified identifier like `keyspace.name`, surrounding whitespace is skipped.
+///
+/// ```rust
+/// use cql_nom::model::{CqlIdentifier, CqlQualifiedIdentifier};
+///
+/// let table: CqlQualifiedIdentifier<String> = "shop.\"Orders\"".parse().unwrap();
+/// assert_eq!(table.keyspace(), &Some(CqlIdentifier::new("shop".to_string())));
+/// assert_eq!(table.to_string(), "shop.\"Orders\"");
+///
+/// let error = "shop.orders extra".parse::<CqlQualifiedIdentifier<String>>().unwrap_err();
+/// assert_eq!(error.offset(), 12);
+/// ```
+impl FromStr for CqlQualifiedIdentifier<String> {
+    type Err = CqlParseError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (remaining, identifier) = space0_around(CqlQualifiedIdentifier::parse)(input)
+            .map_err(|e: nom::Err<RichError<&str>>| CqlParseError::from_nom(input, e))?;
+        if !remaining.is_empty() {
+            return Err(CqlParseError::new(
+                input,
+                remaining,
+                "expected the end of the identifier",
+            ));
+        }
+
+        Ok(CqlQualifiedIdentifier::new(
+            identifier.keyspace.map(owned_identifier),
+            owned_identifier(identifier.name),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_hash_map() {
+        let qualified = |keyspace: Option<CqlIdentifier<&'static str>>, name| {
+            CqlQualifiedIdentifier::new(keyspace, name)
+        };
+        let mut tables = HashMap::new();
+        tables.insert(
+            qualified(
+                Some(CqlIdentifier::new("Shop")),
+                CqlIdentifier::new("Orders"),
+            ),
+            1,
+        );
+        tables.insert(qualified(None, CqlIdentifier::new("orders")), 2);
+        let lookup = |keyspace: Option<CqlIdentifier<&'static str>>, name| {
+            tables.get(&qualified(keyspace, name)).copied()
+        };
+        assert_eq!(
+            lookup(
+                Some(CqlIdentifier::new_quoted("shop".to_string())),
+                CqlIdentifier::new("ORDERS")
+            ),
+            Some(1)
+        );
+        assert_eq!(
+            lookup(None, CqlIdentifier::new_quoted("orders".to_string())),
+            Some(2)
+        );
+        assert_eq!(
+            lookup(
+                Some(CqlIdentifier::new_quoted("Shop".to_string())),
+                CqlIdentifier::new("orders")
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_from_str() {
+        let identifier = |name: &str| CqlIdentifier::new(name.to_string());
+        let quoted = |name: &str| CqlIdentifier::new_quoted(name.to_string());
+        for (input, keyspace, name) in [
+            ("orders", None, identifier("orders")),
+            (
+                " Shop . \"My \"\"Orders\"\"\" ",
+                Some(identifier("shop")),
+                quoted("My \"Orders\""),
+            ),
+        ] {
+            assert_eq!(
+                input.parse(),
+                Ok(CqlQualifiedIdentifier::new(keyspace, name))
+            );
+        }
+
+        for input in ["", "shop.", "shop.orders.items", "select"] {
+            assert!(input.parse::<CqlQualifiedIdentifier<String>>().is_err());
+        }
+    }
+
+    #[test]
+    fn test_display_parses_back() {
+        for input in [
+            "orders",
+            "Shop.Orders",
+            "\"Shop\".\"select\"",
+            "shop.\"we\"\"ird\"",
+        ] {
+            let identifier: CqlQualifiedIdentifier<String> = input.parse().unwrap();
+            assert_eq!(identifier.to_string(), input);
+            assert_eq!(identifier.to_string().parse(), Ok(identifier));
+        }
+    }
+
+    #[test]
+    fn test_ord() {
+        let mut tables = [
+            "shop.orders",
+            "Customers",
+            "\"Shop\".items",
+            "SHOP.Items",
+            "orders",
+        ]
+        .into_iter()
+        .map(|input| input.parse::<CqlQualifiedIdentifier<String>>().unwrap())
+        .collect::<Vec<_>>();
+        tables.sort();
+        assert_eq!(
+            tables.iter().map(|t| t.to_string()).collect::<Vec<_>>(),
+            vec![
+                "Customers",
+                "orders",
+                "\"Shop\".items",
+                "SHOP.Items",
+                "shop.orders"
+            ]
+        );
+    }
+}
@@ -0,0 +1,140 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::term::{CqlBindMarker, CqlTerm};
+use derive_more::IsVariant;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::Getters;
+use std::ops::Deref;
+
+/// The `WHERE` clause of a statement, relations which all have to hold.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/dml.html#where-clause>
+///
+/// Grammar:
+/// ```bnf
+/// where_clause::= relation ( AND relation )*
+/// relation::= column_name operator term
+///         | column_name IN ( '(' ( term ( ',' term )* )? ')' | bind_marker )
+///         | column_name ( CONTAINS | CONTAINS KEY ) term
+///         | column_name LIKE ( string | bind_marker )
+///         | TOKEN '(' column_name ( ',' column_name )* ')' operator term
+///         | '(' column_name ( ',' column_name )* ')' operator ( tuple_literal | bind_marker )
+///         | '(' column_name ( ',' column_name )* ')' IN '(' ( tuple_literal | bind_marker )* ')'
+///         | '(' column_name ( ',' column_name )* ')' IN bind_marker
+/// operator::= '=' | '<' | '>' | '<=' | '>=' | '!='
+/// ```
+///
+/// Example:
+/// ```cql
+/// WHERE user_id = 42 AND "Created" >= '2024-01-01'
+/// WHERE token(user_id) > token(42) AND created < now()
+/// WHERE user_id = 42 AND (day, hour) > (?, ?)
+/// WHERE name LIKE 'Ali%'
+/// WHERE user_id IN (1, 2, 3) AND tags CONTAINS 'rust' AND settings CONTAINS KEY 'theme'
+/// WHERE user_id = 42 AND (day, hour) IN ((1, 2), (3, 4))
+/// ```
+#[derive(Debug, Clone, Getters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlWhereClause<I> {
+    /// The relations, joined by `AND`.
+    #[getset(get = "pub")]
+    relations: Vec<CqlRelation<I>>,
+}
+
+impl<I> CqlWhereClause<I> {
+    /// The bind markers of the relations, in the order they are written.
+    pub fn bind_markers(&self) -> impl Iterator<Item = &CqlBindMarker<I>> {
+        self.relations
+            .iter()
+            .flat_map(|relation| relation.term().bind_markers())
+    }
+}
+
+/// A relation of a `WHERE` clause.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlRelation<I> {
+    /// A relation of a single column to a term, `user_id = 42`.
+    Column(CqlIdentifier<I>, CqlOperator, CqlTerm<I>),
+    /// A relation of the token of the partition key columns to a term, `token(user_id) > 42`.
+    Token(Vec<CqlIdentifier<I>>, CqlOperator, CqlTerm<I>),
+    /// A relation of multiple clustering columns to a tuple, `(day, hour) > (1, 2)`.
+    ///
+    /// The term is a tuple or a bind marker, for `IN` a tuple of tuples or a bind marker.
+    Tuple(Vec<CqlIdentifier<I>>, CqlOperator, CqlTerm<I>),
+}
+
+impl<I> CqlRelation<I> {
+    /// The columns the relation restricts, all partition key columns of a token relation.
+    pub fn columns(&self) -> &[CqlIdentifier<I>] {
+        match self {
+            CqlRelation::Column(column, _, _) => std::slice::from_ref(column),
+            CqlRelation::Token(columns, _, _) | CqlRelation::Tuple(columns, _, _) => columns,
+        }
+    }
+
+    /// The operator of the relation.
+    pub fn operator(&self) -> CqlOperator {
+        match self {
+            CqlRelation::Column(_, operator, _)
+            | CqlRelation::Token(_, operator, _)
+            | CqlRelation::Tuple(_, operator, _) => *operator,
+        }
+    }
+
+    /// The term the columns are related to.
+    pub fn term(&self) -> &CqlTerm<I> {
+        match self {
+            CqlRelation::Column(_, _, term)
+            | CqlRelation::Token(_, _, term)
+            | CqlRelation::Tuple(_, _, term) => term,
+        }
+    }
+}
+
+/// The operator of a relation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IsVariant)]
+pub enum CqlOperator {
+    /// `=`
+    Eq,
+    /// `<`
+    Lt,
+    /// `>`
+    Gt,
+    /// `<=`
+    Le,
+    /// `>=`
+    Ge,
+    /// `!=`
+    Ne,
+    /// `IN`, the value is one of a tuple of values.
+    In,
+    /// `LIKE`, the value matches a pattern, like `'Ali%'`.
+    Like,
+    /// `CONTAINS`, the value of a collection column contains an element.
+    Contains,
+    /// `CONTAINS KEY`, the value of a map column contains a key.
+    ContainsKey,
+}
+
+impl CqlOperator {
+    /// Whether the operator restricts the elements of a collection column.
+    pub fn is_collection_operator(&self) -> bool {
+        matches!(self, CqlOperator::Contains | CqlOperator::ContainsKey)
+    }
+
+    /// The symbol of the operator, as written in a statement.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            CqlOperator::Eq => "=",
+            CqlOperator::Lt => "<",
+            CqlOperator::Gt => ">",
+            CqlOperator::Le => "<=",
+            CqlOperator::Ge => ">=",
+            CqlOperator::Ne => "!=",
+            CqlOperator::In => "IN",
+            CqlOperator::Like => "LIKE",
+            CqlOperator::Contains => "CONTAINS",
+            CqlOperator::ContainsKey => "CONTAINS KEY",
+        }
+    }
+}
@@ -0,0 +1,170 @@
+use crate::model::*;
+use derive_more::IsVariant;
+use std::ops::Deref;
+
+/// The replication of a keyspace, typed from its `replication` map like
+/// `{'class': 'NetworkTopologyStrategy', 'dc1': 3, 'dc2': '2'}`.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/ddl.html#replication>
+#[derive(Debug, Clone, PartialEq, IsVariant)]
+pub enum CqlReplication<I> {
+    /// `SimpleStrategy`, with its `replication_factor`.
+    SimpleStrategy(u32),
+    /// `NetworkTopologyStrategy`, with the replication factor of each datacenter.
+    NetworkTopologyStrategy(Vec<(String, u32)>),
+    /// Another strategy, or a strategy with other entries, with its class and the entries
+    /// besides the class.
+    Other(String, Vec<(String, CqlOptionValue<I>)>),
+}
+
+impl<I: Deref<Target = str>> CqlReplication<I> {
+    /// Types the entries of a `replication` map, the replication factors may be quoted or not.
+    ///
+    /// The class may be written with the `org.apache.cassandra.locator.` package.
+    pub fn from_entries(entries: Vec<(String, CqlOptionValue<I>)>) -> Self {
+        let mut class = String::new();
+        let mut others = Vec::new();
+        for (key, value) in entries {
+            match (key.as_str(), value.as_str()) {
+                ("class", Some(name)) => class = name.to_string(),
+                _ => others.push((key, value)),
+            }
+        }
+        let factors = others
+            .iter()
+            .map(|(key, value)| {
+                let factor = value
+                    .as_i64()
+                    .and_then(|factor| u32::try_from(factor).ok())?;
+                Some((key.clone(), factor))
+            })
+            .collect::<Option<Vec<_>>>();
+
+        let name = class
+            .strip_prefix("org.apache.cassandra.locator.")
+            .unwrap_or(&class);
+        match (name, factors) {
+            ("SimpleStrategy", Some(factors))
+                if factors.len() == 1 && factors[0].0 == "replication_factor" =>
+            {
+                CqlReplication::SimpleStrategy(factors[0].1)
+            }
+            ("NetworkTopologyStrategy", Some(factors))
+                if factors.iter().all(|(key, _)| key != "replication_factor") =>
+            {
+                CqlReplication::NetworkTopologyStrategy(factors)
+            }
+            _ => CqlReplication::Other(class, others),
+        }
+    }
+
+    /// The entries of the `replication` map, the replication factors as strings.
+    pub fn to_entries(&self) -> Vec<(String, CqlOptionValue<I>)>
+    where
+        I: Clone,
+    {
+        let (class, entries) = match self {
+            CqlReplication::SimpleStrategy(factor) => (
+                "SimpleStrategy",
+                vec![(
+                    "replication_factor".to_string(),
+                    CqlOptionValue::String(factor.to_string()),
+                )],
+            ),
+            CqlReplication::NetworkTopologyStrategy(datacenters) => (
+                "NetworkTopologyStrategy",
+                datacenters
+                    .iter()
+                    .map(|(datacenter, factor)| {
+                        (
+                            datacenter.clone(),
+                            CqlOptionValue::String(factor.to_string()),
+                        )
+                    })
+                    .collect(),
+            ),
+            CqlReplication::Other(class, entries) => (&**class, entries.clone()),
+        };
+
+        let mut map = vec![(
+            "class".to_string(),
+            CqlOptionValue::String(class.to_string()),
+        )];
+        map.extend(entries);
+        map
+    }
+
+    /// The total number of replicas, the sum over the datacenters of a network topology.
+    pub fn total_replicas(&self) -> Option<u32> {
+        match self {
+            CqlReplication::SimpleStrategy(factor) => Some(*factor),
+            CqlReplication::NetworkTopologyStrategy(datacenters) => {
+                Some(datacenters.iter().map(|(_, factor)| factor).sum())
+            }
+            CqlReplication::Other(_, _) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn string(value: &str) -> CqlOptionValue<&str> {
+        CqlOptionValue::String(value.to_string())
+    }
+
+    #[test]
+    fn test_simple_strategy() {
+        let replication = CqlReplication::from_entries(vec![
+            ("class".to_string(), string("SimpleStrategy")),
+            (
+                "replication_factor".to_string(),
+                CqlOptionValue::Integer("3"),
+            ),
+        ]);
+        assert_eq!(replication, CqlReplication::SimpleStrategy(3));
+        assert_eq!(replication.total_replicas(), Some(3));
+    }
+
+    #[test]
+    fn test_network_topology_strategy() {
+        let replication = CqlReplication::from_entries(vec![
+            (
+                "class".to_string(),
+                string("org.apache.cassandra.locator.NetworkTopologyStrategy"),
+            ),
+            ("dc1".to_string(), string("3")),
+            ("dc2".to_string(), CqlOptionValue::Integer("2")),
+        ]);
+        assert_eq!(
+            replication,
+            CqlReplication::NetworkTopologyStrategy(vec![
+                ("dc1".to_string(), 3),
+                ("dc2".to_string(), 2)
+            ])
+        );
+        assert_eq!(replication.total_replicas(), Some(5));
+    }
+
+    #[test]
+    fn test_other_strategy() {
+        let replication = CqlReplication::from_entries(vec![
+            ("class".to_string(), string("EverywhereStrategy")),
+            ("dc1".to_string(), string("all")),
+        ]);
+        assert_eq!(
+            replication,
+            CqlReplication::Other(
+                "EverywhereStrategy".to_string(),
+                vec![("dc1".to_string(), string("all"))]
+            )
+        );
+        // A factor which is no count is no network topology.
+        let replication = CqlReplication::from_entries(vec![
+            ("class".to_string(), string("NetworkTopologyStrategy")),
+            ("dc1".to_string(), string("-1")),
+        ]);
+        assert!(replication.is_other());
+        assert_eq!(replication.total_replicas(), None);
+    }
+}
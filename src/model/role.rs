@@ -0,0 +1,112 @@
+use crate::model::identifier::CqlIdentifier;
+use derive_more::IsVariant;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The cql `CREATE ROLE` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/security.html#create-role-statement>
+///
+/// Grammar:
+/// ```bnf
+/// create_role_statement::= CREATE ROLE [ IF NOT EXISTS ] role_name
+///         [ WITH role_options ]
+/// role_options::= role_option ( AND role_option )*
+/// role_option::= PASSWORD '=' string
+///         | HASHED PASSWORD '=' string
+///         | LOGIN '=' boolean
+///         | SUPERUSER '=' boolean
+///         | OPTIONS '=' map_literal
+///         | ACCESS TO DATACENTERS set_literal
+///         | ACCESS TO ALL DATACENTERS
+/// ```
+///
+/// Example:
+/// ```cql
+/// CREATE ROLE IF NOT EXISTS admin WITH PASSWORD = 'secret' AND LOGIN = true;
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlCreateRole<I> {
+    /// If the role should only be created if it does not exist.
+    #[getset(get_copy = "pub")]
+    if_not_exists: bool,
+    /// The name of the role.
+    #[getset(get = "pub")]
+    name: CqlIdentifier<I>,
+    /// The options of the role, options which are not given are `None`.
+    #[getset(get = "pub")]
+    options: CqlRoleOptions,
+}
+
+/// The cql `ALTER ROLE` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/security.html#alter-role-statement>
+///
+/// Grammar:
+/// ```bnf
+/// alter_role_statement::= ALTER ROLE [ IF EXISTS ] role_name WITH role_options
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlAlterRole<I> {
+    /// If the role should only be altered if it exists.
+    #[getset(get_copy = "pub")]
+    if_exists: bool,
+    /// The name of the role.
+    #[getset(get = "pub")]
+    name: CqlIdentifier<I>,
+    /// The changed options of the role, options which are not given are `None`.
+    #[getset(get = "pub")]
+    options: CqlRoleOptions,
+}
+
+/// The cql `DROP ROLE` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/security.html#drop-role-statement>
+///
+/// Grammar:
+/// ```bnf
+/// drop_role_statement::= DROP ROLE [ IF EXISTS ] role_name
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlDropRole<I> {
+    /// If the role should only be dropped if it exists.
+    #[getset(get_copy = "pub")]
+    if_exists: bool,
+    /// The name of the role.
+    #[getset(get = "pub")]
+    name: CqlIdentifier<I>,
+}
+
+/// The options of a role.
+#[derive(Debug, Clone, Default, PartialEq, Getters, CopyGetters, new)]
+pub struct CqlRoleOptions {
+    /// The password of the role.
+    #[getset(get = "pub")]
+    password: Option<String>,
+    /// The hash of the password of the role.
+    #[getset(get = "pub")]
+    hashed_password: Option<String>,
+    /// If the role may log in.
+    #[getset(get_copy = "pub")]
+    login: Option<bool>,
+    /// If the role is a superuser.
+    #[getset(get_copy = "pub")]
+    superuser: Option<bool>,
+    /// The custom options of the authenticator.
+    #[getset(get = "pub")]
+    options: Option<Vec<(String, String)>>,
+    /// The datacenters the role may access.
+    #[getset(get = "pub")]
+    datacenters: Option<CqlDatacenters>,
+}
+
+/// The datacenters a role may access.
+#[derive(Debug, Clone, PartialEq, IsVariant)]
+pub enum CqlDatacenters {
+    /// `ACCESS TO ALL DATACENTERS`.
+    All,
+    /// `ACCESS TO DATACENTERS {'dc1', 'dc2'}`.
+    Only(Vec<String>),
+}
@@ -0,0 +1,182 @@
+use crate::model::cql_type::CqlType;
+use crate::model::identifier::CqlIdentifier;
+use crate::model::order::CqlOrder;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::relation::CqlWhereClause;
+use crate::model::term::{CqlBindMarker, CqlTerm};
+use crate::model::Identifiable;
+use derive_more::IsVariant;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The cql `SELECT` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/dml.html#select-statement>
+///
+/// Grammar:
+/// ```bnf
+/// select_statement::= SELECT [ JSON | DISTINCT ] select_clause
+///         FROM table_name
+///         [ WHERE where_clause ]
+///         [ GROUP BY column_name ( ',' column_name )* ]
+///         [ ORDER BY ordering_clause ]
+///         [ PER PARTITION LIMIT ( integer | bind_marker ) ]
+///         [ LIMIT ( integer | bind_marker ) ]
+///         [ ALLOW FILTERING ]
+/// select_clause::= '*' | selector [ AS identifier ] ( ',' selector [ AS identifier ] )*
+/// selector::= column_name
+///         | CAST '(' selector AS cql_type ')'
+///         | function_name '(' [ '*' | selector ( ',' selector )* ] ')'
+/// ordering_clause::= column_name [ ASC | DESC ] ( ',' column_name [ ASC | DESC ] )*
+///         | column_name ANN OF ( list_literal | bind_marker )
+/// ```
+///
+/// Example:
+/// ```cql
+/// SELECT name, occupation FROM users WHERE userid = 42 ORDER BY created DESC LIMIT 10;
+/// SELECT country, count(*) FROM users GROUP BY country;
+/// SELECT JSON * FROM users;
+/// SELECT * FROM events PER PARTITION LIMIT 3 LIMIT 100;
+/// SELECT writetime(name) AS written, CAST(userid AS text) FROM users;
+/// SELECT * FROM products ORDER BY embedding ANN OF [0.1, 0.2, 0.3] LIMIT 10;
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlSelect<I> {
+    /// The selected columns.
+    #[getset(get = "pub")]
+    selection: CqlSelection<I>,
+    /// The table selected from.
+    #[getset(get = "pub")]
+    table: CqlQualifiedIdentifier<I>,
+    /// The relations the selected rows satisfy.
+    #[getset(get = "pub")]
+    where_clause: Option<CqlWhereClause<I>>,
+    /// The columns the selected rows are grouped by, a prefix of the primary key.
+    #[getset(get = "pub")]
+    group_by: Vec<CqlIdentifier<I>>,
+    /// The order of the selected rows, columns without an order are ascending.
+    #[getset(get = "pub")]
+    order_by: Vec<CqlOrdering<I>>,
+    /// The maximum number of selected rows, an integer or a bind marker.
+    #[getset(get = "pub")]
+    limit: Option<CqlTerm<I>>,
+    /// Whether the query may filter rows on the server, `ALLOW FILTERING`.
+    #[getset(get_copy = "pub")]
+    allow_filtering: bool,
+    /// Whether each row is returned as a single json encoded text column, `SELECT JSON`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    json: bool,
+    /// Whether only distinct partition keys are returned, `SELECT DISTINCT`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    distinct: bool,
+    /// The maximum number of selected rows of each partition, an integer or a bind marker.
+    #[new(default)]
+    #[getset(get = "pub")]
+    per_partition_limit: Option<CqlTerm<I>>,
+}
+
+impl<I> CqlSelect<I> {
+    /// The bind markers of the statement, in the order they are written.
+    pub fn bind_markers(&self) -> impl Iterator<Item = &CqlBindMarker<I>> {
+        self.where_clause
+            .iter()
+            .flat_map(CqlWhereClause::bind_markers)
+            .chain(self.order_by.iter().flat_map(CqlOrdering::bind_markers))
+            .chain(
+                self.per_partition_limit
+                    .iter()
+                    .flat_map(CqlTerm::bind_markers),
+            )
+            .chain(self.limit.iter().flat_map(CqlTerm::bind_markers))
+    }
+
+    /// Returns each row as a single json encoded text column.
+    pub fn with_json(mut self, json: bool) -> Self {
+        self.json = json;
+        self
+    }
+
+    /// Returns only distinct partition keys.
+    pub fn with_distinct(mut self, distinct: bool) -> Self {
+        self.distinct = distinct;
+        self
+    }
+
+    /// Limits the number of selected rows of each partition.
+    pub fn with_per_partition_limit(mut self, per_partition_limit: Option<CqlTerm<I>>) -> Self {
+        self.per_partition_limit = per_partition_limit;
+        self
+    }
+}
+
+/// What a `SELECT` statement selects.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlSelection<I> {
+    /// Every column, `*`.
+    All,
+    /// The listed selectors.
+    Selectors(Vec<CqlSelector<I>>),
+}
+
+/// A single item of the selection.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlSelector<I> {
+    /// The value of a column.
+    Column(CqlIdentifier<I>),
+    /// A function call, like `max(price)`, or `count(*)` with all columns as argument.
+    Function(CqlQualifiedIdentifier<I>, CqlSelection<I>),
+    /// A conversion to another type, `CAST(price AS text)`.
+    Cast(Box<CqlSelector<I>>, CqlType<CqlIdentifier<I>>),
+    /// A selected item with another name in the result, `writetime(name) AS written`.
+    Alias(Box<CqlSelector<I>>, CqlIdentifier<I>),
+}
+
+/// An item of the `ORDER BY` clause of a `SELECT` statement.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlOrdering<I> {
+    /// The rows ordered by a clustering column, `created DESC`.
+    Column(CqlIdentifier<I>, CqlOrder),
+    /// The rows nearest to a vector first, `embedding ANN OF [0.1, 0.2]`.
+    ///
+    /// The vector is a list literal or a bind marker.
+    Ann(CqlIdentifier<I>, CqlTerm<I>),
+}
+
+impl<I> CqlOrdering<I> {
+    /// The column the rows are ordered by, a vector column for an approximate nearest neighbor
+    /// search.
+    pub fn column(&self) -> &CqlIdentifier<I> {
+        match self {
+            CqlOrdering::Column(column, _) | CqlOrdering::Ann(column, _) => column,
+        }
+    }
+
+    /// The bind markers of the vector of an approximate nearest neighbor search.
+    pub fn bind_markers(&self) -> impl Iterator<Item = &CqlBindMarker<I>> {
+        let vector = match self {
+            CqlOrdering::Column(_, _) => None,
+            CqlOrdering::Ann(_, vector) => Some(vector),
+        };
+        vector.into_iter().flat_map(CqlTerm::bind_markers)
+    }
+}
+
+/// A select is made in the keyspace of its table.
+impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlSelect<I> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.table.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.table.name()
+    }
+}
@@ -0,0 +1,112 @@
+use crate::model::*;
+use crate::utils::line_column;
+use derive_new::new;
+use getset::{CopyGetters, Getters};
+use nom::Offset;
+use std::borrow::Borrow;
+use std::fmt::{Display, Formatter};
+use std::ops::{Deref, Range};
+
+/// A tree node together with the source text it was parsed from.
+///
+/// Used in place of a node type parameter, e.g. as the column of a [`CqlTable`], to keep the
+/// location of the node in the input.
+#[derive(Debug, Clone, PartialEq, Getters, new)]
+pub struct Spanned<I, T> {
+    /// The source text of the node.
+    #[getset(get = "pub")]
+    span: I,
+    /// The node.
+    #[getset(get = "pub")]
+    node: T,
+}
+
+impl<I, T> Spanned<I, T> {
+    /// Drops the span and returns the node.
+    pub fn into_node(self) -> T {
+        self.node
+    }
+}
+
+impl<T> Spanned<&str, T> {
+    /// Locates the node in `input`, the text it was parsed from.
+    ///
+    /// Returns `None` if the node was parsed from another text.
+    pub fn locate(&self, input: &str) -> Option<CqlSpan> {
+        CqlSpan::locate(input, self.span)
+    }
+}
+
+/// The location of a source text in the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CopyGetters)]
+pub struct CqlSpan {
+    /// The byte offset of the start.
+    #[getset(get_copy = "pub")]
+    start: usize,
+    /// The byte offset after the end.
+    #[getset(get_copy = "pub")]
+    end: usize,
+    /// The one based line of the start.
+    #[getset(get_copy = "pub")]
+    line: usize,
+    /// The one based column of the start, in characters.
+    #[getset(get_copy = "pub")]
+    column: usize,
+}
+
+impl CqlSpan {
+    /// Locates `text` in `input`, returns `None` if `text` is no slice of `input`.
+    pub fn locate(input: &str, text: &str) -> Option<Self> {
+        let input_range = input.as_bytes().as_ptr_range();
+        let text_range = text.as_bytes().as_ptr_range();
+        if text_range.start < input_range.start || text_range.end > input_range.end {
+            return None;
+        }
+        let start = input.offset(text);
+        let (line, column) = line_column(input, start);
+
+        Some(Self {
+            start,
+            end: start + text.len(),
+            line,
+            column,
+        })
+    }
+
+    /// The byte range in the input.
+    pub fn range(&self) -> Range<usize> {
+        self.start..self.end
+    }
+}
+
+impl<I, T> Deref for Spanned<I, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.node
+    }
+}
+
+impl<I, T> Borrow<T> for Spanned<I, T> {
+    fn borrow(&self) -> &T {
+        &self.node
+    }
+}
+
+impl<I, T: Display> Display for Spanned<I, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.node.fmt(f)
+    }
+}
+
+impl<I: Clone, S, T: Identifiable<I>> Identifiable<I> for Spanned<S, T> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.node.keyspace()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.node.identifier()
+    }
+}
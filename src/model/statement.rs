@@ -1,18 +1,66 @@
+use crate::error::CqlResolveError;
 use crate::model::*;
 use derive_more::IsVariant;
+use derive_where::derive_where;
 use std::ops::Deref;
 use std::rc::Rc;
 
+/// A parsed statement.
+pub type ParsedCqlStatement<I> =
+    CqlStatement<I, ParsedCqlTable<I>, ParsedCqlUserDefinedType<I, CqlIdentifier<I>>>;
+/// A statement whose tables and user defined types are resolved.
+pub type ResolvedCqlStatement<I> =
+    CqlStatement<I, Rc<ResolvedCqlTable<I>>, Rc<CqlUserDefinedType<I>>>;
+
 /// The cql statement.
-#[derive(Debug, Clone, PartialEq, IsVariant)]
-pub enum CqlStatement<Table, UdtType> {
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq, Table: PartialEq, UdtType: PartialEq)]
+pub enum CqlStatement<I, Table, UdtType> {
     /// A `CREATE TABLE` statement.
     CreateTable(Table),
+    /// A `CREATE TABLE ... LIKE` statement.
+    CreateTableLike(CqlCreateTableLike<I, Table>),
     /// A `CREATE TYPE` statement.
     CreateUserDefinedType(UdtType),
+    /// A `USE` statement, unqualified names of the following statements are in its keyspace.
+    Use(CqlIdentifier<I>),
+    /// A `CREATE KEYSPACE` statement.
+    CreateKeyspace(CqlCreateKeyspace<I>),
+    /// An `ALTER MATERIALIZED VIEW` statement.
+    AlterMaterializedView(CqlAlterMaterializedView<I>),
+    /// An `ALTER TYPE` statement.
+    AlterType(CqlAlterType<I>),
+    /// A `DROP TYPE` statement.
+    DropType(CqlDropType<I>),
+    /// A `DROP AGGREGATE` statement.
+    DropAggregate(CqlDropAggregate<I>),
+    /// A `CREATE TRIGGER` statement.
+    CreateTrigger(CqlCreateTrigger<I>),
+    /// A `DROP TRIGGER` statement.
+    DropTrigger(CqlDropTrigger<I>),
+    /// A `CREATE ROLE` statement.
+    CreateRole(CqlCreateRole<I>),
+    /// An `ALTER ROLE` statement.
+    AlterRole(CqlAlterRole<I>),
+    /// A `DROP ROLE` statement.
+    DropRole(CqlDropRole<I>),
+    /// A `GRANT` statement.
+    Grant(CqlGrant<I>),
+    /// A `REVOKE` statement.
+    Revoke(CqlRevoke<I>),
+    /// A `CREATE INDEX` statement.
+    CreateIndex(CqlCreateIndex<I>),
+    /// A `SELECT` statement.
+    Select(CqlSelect<I>),
+    /// An `INSERT` statement.
+    Insert(CqlInsert<I>),
+    /// An `UPDATE` statement.
+    Update(CqlUpdate<I>),
+    /// A `DELETE` statement.
+    Delete(CqlDelete<I>),
 }
 
-impl<Table, UdtType> CqlStatement<Table, UdtType> {
+impl<I, Table, UdtType> CqlStatement<I, Table, UdtType> {
     /// Returns the `CREATE TABLE` statement.
     pub fn create_table(&self) -> Option<&Table> {
         match *self {
@@ -21,6 +69,14 @@ impl<Table, UdtType> CqlStatement<Table, UdtType> {
         }
     }
 
+    /// Returns the `CREATE TABLE ... LIKE` statement.
+    pub fn create_table_like(&self) -> Option<&CqlCreateTableLike<I, Table>> {
+        match *self {
+            CqlStatement::CreateTableLike(ref like) => Some(like),
+            _ => None,
+        }
+    }
+
     /// Returns the `CREATE TYPE` statement.
     pub fn create_user_defined_type(&self) -> Option<&UdtType> {
         match *self {
@@ -28,10 +84,155 @@ impl<Table, UdtType> CqlStatement<Table, UdtType> {
             _ => None,
         }
     }
+
+    /// Returns the `ALTER MATERIALIZED VIEW` statement.
+    pub fn alter_materialized_view(&self) -> Option<&CqlAlterMaterializedView<I>> {
+        match *self {
+            CqlStatement::AlterMaterializedView(ref view) => Some(view),
+            _ => None,
+        }
+    }
+
+    /// Returns the `ALTER TYPE` statement.
+    pub fn alter_type(&self) -> Option<&CqlAlterType<I>> {
+        match *self {
+            CqlStatement::AlterType(ref alter_type) => Some(alter_type),
+            _ => None,
+        }
+    }
+
+    /// Returns the `DROP TYPE` statement.
+    pub fn drop_type(&self) -> Option<&CqlDropType<I>> {
+        match *self {
+            CqlStatement::DropType(ref drop_type) => Some(drop_type),
+            _ => None,
+        }
+    }
+
+    /// Returns the `CREATE KEYSPACE` statement.
+    pub fn create_keyspace(&self) -> Option<&CqlCreateKeyspace<I>> {
+        match *self {
+            CqlStatement::CreateKeyspace(ref create_keyspace) => Some(create_keyspace),
+            _ => None,
+        }
+    }
+
+    /// Returns the `DROP AGGREGATE` statement.
+    pub fn drop_aggregate(&self) -> Option<&CqlDropAggregate<I>> {
+        match *self {
+            CqlStatement::DropAggregate(ref drop_aggregate) => Some(drop_aggregate),
+            _ => None,
+        }
+    }
+
+    /// Returns the `CREATE TRIGGER` statement.
+    pub fn create_trigger(&self) -> Option<&CqlCreateTrigger<I>> {
+        match *self {
+            CqlStatement::CreateTrigger(ref create_trigger) => Some(create_trigger),
+            _ => None,
+        }
+    }
+
+    /// Returns the `DROP TRIGGER` statement.
+    pub fn drop_trigger(&self) -> Option<&CqlDropTrigger<I>> {
+        match *self {
+            CqlStatement::DropTrigger(ref drop_trigger) => Some(drop_trigger),
+            _ => None,
+        }
+    }
+
+    /// Returns the `CREATE ROLE` statement.
+    pub fn create_role(&self) -> Option<&CqlCreateRole<I>> {
+        match *self {
+            CqlStatement::CreateRole(ref create_role) => Some(create_role),
+            _ => None,
+        }
+    }
+
+    /// Returns the `ALTER ROLE` statement.
+    pub fn alter_role(&self) -> Option<&CqlAlterRole<I>> {
+        match *self {
+            CqlStatement::AlterRole(ref alter_role) => Some(alter_role),
+            _ => None,
+        }
+    }
+
+    /// Returns the `DROP ROLE` statement.
+    pub fn drop_role(&self) -> Option<&CqlDropRole<I>> {
+        match *self {
+            CqlStatement::DropRole(ref drop_role) => Some(drop_role),
+            _ => None,
+        }
+    }
+
+    /// Returns the `GRANT` statement.
+    pub fn grant(&self) -> Option<&CqlGrant<I>> {
+        match *self {
+            CqlStatement::Grant(ref grant) => Some(grant),
+            _ => None,
+        }
+    }
+
+    /// Returns the `REVOKE` statement.
+    pub fn revoke(&self) -> Option<&CqlRevoke<I>> {
+        match *self {
+            CqlStatement::Revoke(ref revoke) => Some(revoke),
+            _ => None,
+        }
+    }
+
+    /// Returns the `CREATE INDEX` statement.
+    pub fn create_index(&self) -> Option<&CqlCreateIndex<I>> {
+        match *self {
+            CqlStatement::CreateIndex(ref create_index) => Some(create_index),
+            _ => None,
+        }
+    }
+
+    /// Returns the `SELECT` statement.
+    pub fn select(&self) -> Option<&CqlSelect<I>> {
+        match *self {
+            CqlStatement::Select(ref select) => Some(select),
+            _ => None,
+        }
+    }
+
+    /// Returns the `INSERT` statement.
+    pub fn insert(&self) -> Option<&CqlInsert<I>> {
+        match *self {
+            CqlStatement::Insert(ref insert) => Some(insert),
+            _ => None,
+        }
+    }
+
+    /// Returns the `UPDATE` statement.
+    pub fn update(&self) -> Option<&CqlUpdate<I>> {
+        match *self {
+            CqlStatement::Update(ref update) => Some(update),
+            _ => None,
+        }
+    }
+
+    /// Returns the `DELETE` statement.
+    pub fn delete(&self) -> Option<&CqlDelete<I>> {
+        match *self {
+            CqlStatement::Delete(ref delete) => Some(delete),
+            _ => None,
+        }
+    }
+
+    /// Returns the keyspace of the `USE` statement.
+    pub fn use_keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        match *self {
+            CqlStatement::Use(ref keyspace) => Some(keyspace),
+            _ => None,
+        }
+    }
 }
 
 impl<I, ColumnRef, UdtTypeRef>
     CqlStatement<
+        I,
         CqlTable<I, CqlColumn<I, UdtTypeRef>, ColumnRef>,
         ParsedCqlUserDefinedType<I, UdtTypeRef>,
     >
@@ -39,45 +240,54 @@ impl<I, ColumnRef, UdtTypeRef>
     pub(crate) fn reference_types(
         self,
         keyspace: Option<&CqlIdentifier<I>>,
-        context: &Vec<
-            CqlStatement<
-                Rc<
-                    CqlTable<
-                        I,
-                        Rc<CqlColumn<I, Rc<CqlUserDefinedType<I>>>>,
-                        Rc<CqlColumn<I, Rc<CqlUserDefinedType<I>>>>,
-                    >,
-                >,
-                Rc<CqlUserDefinedType<I>>,
-            >,
-        >,
-    ) -> Result<
-        CqlStatement<
-            Rc<
-                CqlTable<
-                    I,
-                    Rc<CqlColumn<I, Rc<CqlUserDefinedType<I>>>>,
-                    Rc<CqlColumn<I, Rc<CqlUserDefinedType<I>>>>,
-                >,
-            >,
-            Rc<CqlUserDefinedType<I>>,
-        >,
-        CqlQualifiedIdentifier<I>,
-    >
+        context: &[ResolvedCqlStatement<I>],
+    ) -> Result<ResolvedCqlStatement<I>, CqlResolveError<I>>
     where
         I: Deref<Target = str> + Clone,
         ColumnRef: Identifiable<I>,
         UdtTypeRef: Identifiable<I>,
     {
         match self {
-            CqlStatement::CreateTable(table) => Ok(CqlStatement::CreateTable(Rc::new(
-                table.reference_types(keyspace, context)?,
-            ))),
+            CqlStatement::CreateTable(table) => {
+                table.validate_primary_key()?;
+                Ok(CqlStatement::CreateTable(Rc::new(
+                    table.reference_types(keyspace, context)?,
+                )))
+            }
+            CqlStatement::CreateTableLike(like) => Ok(CqlStatement::CreateTableLike(
+                like.reference_types(keyspace, context)?,
+            )),
             CqlStatement::CreateUserDefinedType(udt_type) => {
                 Ok(CqlStatement::CreateUserDefinedType(Rc::new(
                     udt_type.reference_types(keyspace, context)?,
                 )))
             }
+            CqlStatement::Use(keyspace) => Ok(CqlStatement::Use(keyspace)),
+            CqlStatement::CreateKeyspace(create_keyspace) => {
+                Ok(CqlStatement::CreateKeyspace(create_keyspace))
+            }
+            CqlStatement::AlterMaterializedView(view) => {
+                Ok(CqlStatement::AlterMaterializedView(view))
+            }
+            CqlStatement::AlterType(alter_type) => Ok(CqlStatement::AlterType(alter_type)),
+            CqlStatement::DropType(drop_type) => Ok(CqlStatement::DropType(drop_type)),
+            CqlStatement::DropAggregate(drop_aggregate) => {
+                Ok(CqlStatement::DropAggregate(drop_aggregate))
+            }
+            CqlStatement::CreateTrigger(create_trigger) => {
+                Ok(CqlStatement::CreateTrigger(create_trigger))
+            }
+            CqlStatement::DropTrigger(drop_trigger) => Ok(CqlStatement::DropTrigger(drop_trigger)),
+            CqlStatement::CreateRole(create_role) => Ok(CqlStatement::CreateRole(create_role)),
+            CqlStatement::AlterRole(alter_role) => Ok(CqlStatement::AlterRole(alter_role)),
+            CqlStatement::DropRole(drop_role) => Ok(CqlStatement::DropRole(drop_role)),
+            CqlStatement::Grant(grant) => Ok(CqlStatement::Grant(grant)),
+            CqlStatement::Revoke(revoke) => Ok(CqlStatement::Revoke(revoke)),
+            CqlStatement::CreateIndex(create_index) => Ok(CqlStatement::CreateIndex(create_index)),
+            CqlStatement::Select(select) => Ok(CqlStatement::Select(select)),
+            CqlStatement::Insert(insert) => Ok(CqlStatement::Insert(insert)),
+            CqlStatement::Update(update) => Ok(CqlStatement::Update(update)),
+            CqlStatement::Delete(delete) => Ok(CqlStatement::Delete(delete)),
         }
     }
 }
@@ -1,40 +1,62 @@
+use crate::error::{CqlPrimaryKeyDeclaration, CqlResolveError};
 use crate::model::*;
 use derive_new::new;
-use derive_where::derive_where;
 use getset::{CopyGetters, Getters};
+use std::borrow::Borrow;
 use std::ops::Deref;
 use std::rc::Rc;
 
+/// The `caching` option of a table.
+pub mod caching;
 /// A column of a table.
 pub mod column;
+/// The `compaction` option of a table.
+pub mod compaction;
+/// The `compression` option of a table.
+pub mod compression;
+/// The `CREATE TABLE ... LIKE` statement.
+pub mod like;
 /// The table options.
 pub mod options;
 /// The table primary key definition.
 pub mod primary_key;
+/// Statement templates for tables.
+mod template;
 
+pub use caching::*;
 pub use column::*;
+pub use compaction::*;
+pub use compression::*;
+pub use like::*;
 pub use options::*;
 pub use primary_key::*;
 
+/// A parsed table.
+pub type ParsedCqlTable<I> = CqlTable<I, CqlColumn<I, CqlIdentifier<I>>, CqlIdentifier<I>>;
+/// A table whose columns and user defined types are resolved.
+pub type ResolvedCqlTable<I> = CqlTable<I, ResolvedCqlColumn<I>, ResolvedCqlColumn<I>>;
+
 /// The cql table.
 /// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/ddl.html#create-table-statement>
 ///
 /// Grammar:
 /// ```bnf
 /// create_table_statement::= CREATE TABLE [ IF NOT EXISTS ] table_name '('
-/// 	column_definition  ( ',' column_definition )*
-/// 	[ ',' PRIMARY KEY '(' primary_key ')' ]
-/// 	 ')' [ WITH table_options ]
+///     table_element ( ',' table_element )*
+///      ')' [ WITH table_options ]
+/// table_element::= column_definition | PRIMARY KEY '(' primary_key ')'
 /// column_definition::= column_name cql_type [ STATIC ] [ PRIMARY KEY]
 /// primary_key::= partition_key [ ',' clustering_columns ]
 /// partition_key::= column_name  | '(' column_name ( ',' column_name )* ')'
 /// clustering_columns::= column_name ( ',' column_name )*
 /// table_options:=: COMPACT STORAGE [ AND table_options ]
-/// 	| CLUSTERING ORDER BY '(' clustering_order ')'
-/// 	[ AND table_options ]  | options
+///     | CLUSTERING ORDER BY '(' clustering_order ')'
+///     [ AND table_options ]  | options
 /// clustering_order::= column_name (ASC | DESC) ( ',' column_name (ASC | DESC) )*
 /// ```
 ///
+/// The `PRIMARY KEY` clause may appear at most once, in any position of the element list.
+///
 /// Example:
 /// ```cql
 /// CREATE TABLE monkey_species (
@@ -61,32 +83,71 @@ pub use primary_key::*;
 ///     PRIMARY KEY ((machine, cpu), mtime)
 /// ) WITH CLUSTERING ORDER BY (mtime DESC);
 /// ```
+///
+/// The name, primary key and option entries are wrapped in [`Spanned`] by
+/// [`parse_cql_spanned`](crate::parse_cql_spanned) to keep their location.
 #[derive(Debug, Clone, Getters, CopyGetters, new)]
-#[derive_where(PartialEq; Column, ColumnRef, I: std::ops::Deref<Target = str> + std::cmp::PartialEq)]
-pub struct CqlTable<I, Column, ColumnRef> {
+pub struct CqlTable<
+    I,
+    Column,
+    ColumnRef,
+    Name = CqlQualifiedIdentifier<I>,
+    PrimaryKey = CqlPrimaryKey<ColumnRef>,
+    OptionEntry = CqlTableOption<I>,
+> {
     /// If the table should only be created if it does not exist.
     #[getset(get_copy = "pub")]
     if_not_exists: bool,
     /// The name of the table.
     #[getset(get = "pub")]
-    name: CqlQualifiedIdentifier<I>,
+    name: Name,
     /// The columns of the table.
     #[getset(get = "pub")]
     columns: Vec<Column>,
     /// The primary key of the table.
+    ///
+    /// Without a `PRIMARY KEY (...)` clause, the parser makes a single column marked
+    /// `PRIMARY KEY` the partition key.
     #[getset(get = "pub")]
-    primary_key: Option<CqlPrimaryKey<ColumnRef>>,
+    primary_key: Option<PrimaryKey>,
     /// The table options.
     #[getset(get = "pub")]
-    options: Option<CqlTableOptions<I, ColumnRef>>,
+    options: Option<CqlTableOptions<I, ColumnRef, OptionEntry>>,
+    /// The columns marked `PRIMARY KEY` which are not the primary key, as another declaration
+    /// conflicts with them. A table with conflicting declarations fails to resolve.
+    #[new(default)]
+    #[getset(get = "pub")]
+    conflicting_primary_key: Vec<ColumnRef>,
+}
+
+impl<I, Column, ColumnRef, Name, PrimaryKey, OptionEntry> PartialEq
+    for CqlTable<I, Column, ColumnRef, Name, PrimaryKey, OptionEntry>
+where
+    I: PartialEq,
+    Column: PartialEq,
+    ColumnRef: PartialEq,
+    Name: PartialEq,
+    PrimaryKey: PartialEq,
+    OptionEntry: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        // Resolved tables are shared through `Rc`, the same node needs no structural walk.
+        std::ptr::eq(self, other)
+            || (self.if_not_exists == other.if_not_exists
+                && self.name == other.name
+                && self.columns == other.columns
+                && self.primary_key == other.primary_key
+                && self.options == other.options
+                && self.conflicting_primary_key == other.conflicting_primary_key)
+    }
 }
 
-impl<I: Clone + Deref<Target = str>, Column, ColumnRef> Identifiable<I>
-    for CqlTable<I, Column, ColumnRef>
+impl<I: Clone, Column, ColumnRef, Name: Identifiable<I>, PrimaryKey, OptionEntry> Identifiable<I>
+    for CqlTable<I, Column, ColumnRef, Name, PrimaryKey, OptionEntry>
 {
     #[inline(always)]
     fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
-        self.name.keyspace().as_ref()
+        self.name.keyspace()
     }
     #[inline(always)]
     fn identifier(&self) -> &CqlIdentifier<I> {
@@ -94,19 +155,84 @@ impl<I: Clone + Deref<Target = str>, Column, ColumnRef> Identifiable<I>
     }
 }
 
+impl<I, Column, ColumnRef, Name, PrimaryKey, OptionEntry>
+    CqlTable<I, Column, ColumnRef, Name, PrimaryKey, OptionEntry>
+{
+    /// Sets the columns marked `PRIMARY KEY` which conflict with another declaration.
+    pub fn with_conflicting_primary_key(mut self, columns: Vec<ColumnRef>) -> Self {
+        self.conflicting_primary_key = columns;
+        self
+    }
+
+    /// The user defined types referenced by the columns, in order of occurrence and without
+    /// duplicates.
+    pub fn collect_udts<UdtTypeRef>(&self) -> Vec<&UdtTypeRef>
+    where
+        I: Clone + Deref<Target = str>,
+        Column: Borrow<CqlColumn<I, UdtTypeRef>>,
+        UdtTypeRef: Identifiable<I>,
+    {
+        let mut udts = Vec::new();
+        for column in &self.columns {
+            column.borrow().cql_type().extend_udts(&mut udts);
+        }
+        udts
+    }
+}
+
 impl<I, UdtTypeRef, ColumnRef> CqlTable<I, CqlColumn<I, UdtTypeRef>, ColumnRef> {
+    /// Checks that the primary key is declared once.
+    ///
+    /// The parser records the columns marked `PRIMARY KEY` which conflict with the clause or
+    /// with each other, see [`conflicting_primary_key`](Self::conflicting_primary_key).
+    pub(crate) fn validate_primary_key(&self) -> Result<(), CqlResolveError<I>>
+    where
+        I: Deref<Target = str> + Clone,
+        ColumnRef: Identifiable<I>,
+    {
+        let conflict = |first, second| CqlResolveError::ConflictingPrimaryKey {
+            table: self.name.clone(),
+            first,
+            second,
+        };
+        let mut conflicting = self
+            .conflicting_primary_key
+            .iter()
+            .map(|column| column.identifier().clone());
+        let Some(column) = conflicting.next() else {
+            return Ok(());
+        };
+        if let Some(other) = conflicting.next() {
+            return Err(conflict(
+                CqlPrimaryKeyDeclaration::Column(column),
+                CqlPrimaryKeyDeclaration::Column(other),
+            ));
+        }
+        if let Some(primary_key) = &self.primary_key {
+            let identifiers = |columns: &[ColumnRef]| {
+                columns
+                    .iter()
+                    .map(|column| column.identifier().clone())
+                    .collect()
+            };
+            return Err(conflict(
+                CqlPrimaryKeyDeclaration::Column(column),
+                CqlPrimaryKeyDeclaration::Clause(Box::new(CqlPrimaryKey::new(
+                    identifiers(primary_key.partition_key()),
+                    *primary_key.composite_partition(),
+                    identifiers(primary_key.clustering_columns()),
+                ))),
+            ));
+        }
+
+        Ok(())
+    }
+
     pub(crate) fn reference_types<Table>(
         self,
         keyspace: Option<&CqlIdentifier<I>>,
-        context: &Vec<CqlStatement<Table, Rc<CqlUserDefinedType<I>>>>,
-    ) -> Result<
-        CqlTable<
-            I,
-            Rc<CqlColumn<I, Rc<CqlUserDefinedType<I>>>>,
-            Rc<CqlColumn<I, Rc<CqlUserDefinedType<I>>>>,
-        >,
-        CqlQualifiedIdentifier<I>,
-    >
+        context: &[CqlStatement<I, Table, Rc<CqlUserDefinedType<I>>>],
+    ) -> Result<ResolvedCqlTable<I>, CqlQualifiedIdentifier<I>>
     where
         I: Deref<Target = str> + Clone,
         ColumnRef: Identifiable<I>,
@@ -130,13 +256,25 @@ impl<I, UdtTypeRef, ColumnRef> CqlTable<I, CqlColumn<I, UdtTypeRef>, ColumnRef>
             .options
             .map(|options| options.reference_types(keyspace.as_ref(), &columns))
             .transpose()?;
+        let conflicting_primary_key = self
+            .conflicting_primary_key
+            .into_iter()
+            .map(|column| {
+                columns
+                    .iter()
+                    .find(|c| c.name() == column.identifier())
+                    .map(Rc::clone)
+                    .ok_or_else(|| column.contextualized_identifier(keyspace.as_ref()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
 
         Ok(CqlTable::new(
             self.if_not_exists,
-            self.name,
+            CqlQualifiedIdentifier::new(keyspace, self.name.name().clone()),
             columns,
             primary_key,
             options,
-        ))
+        )
+        .with_conflicting_primary_key(conflicting_primary_key))
     }
 }
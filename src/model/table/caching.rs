@@ -0,0 +1,178 @@
+use crate::model::*;
+use derive_more::IsVariant;
+use derive_new::new;
+use getset::{CopyGetters, Getters};
+use std::fmt::{Display, Formatter};
+use std::ops::Deref;
+
+/// The `caching` option of a table, like `{'keys': 'ALL', 'rows_per_partition': '120'}`.
+///
+/// The single string form of Cassandra 2.x dumps, like `'KEYS_ONLY'`, is typed as well. A
+/// parameter with a value which can not be typed is kept as a diagnostic.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/ddl.html#caching-options>
+#[derive(Debug, Clone, PartialEq, Getters, CopyGetters, new)]
+pub struct CqlCaching<I> {
+    /// Whether the partition keys are cached, `keys`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    keys: Option<CqlCachingKeys>,
+    /// How many rows of a partition are cached, `rows_per_partition`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    rows_per_partition: Option<CqlRowsPerPartition>,
+    /// The parameters without a typed field.
+    #[new(default)]
+    #[getset(get = "pub")]
+    extras: Vec<(String, CqlOptionValue<I>)>,
+    /// The parameters with a value which can not be typed.
+    #[new(default)]
+    #[getset(get = "pub")]
+    diagnostics: Vec<CqlOptionDiagnostic<I>>,
+}
+
+impl<I: Deref<Target = str>> CqlCaching<I> {
+    /// Types the entries of a `caching` map.
+    pub fn from_entries(entries: Vec<(String, CqlOptionValue<I>)>) -> Self {
+        let mut caching = CqlCaching::new();
+        let diagnostics = &mut caching.diagnostics;
+        for (key, value) in entries {
+            match key.as_str() {
+                "keys" => {
+                    let keys = value.as_str().and_then(CqlCachingKeys::from_name);
+                    set(
+                        &mut caching.keys,
+                        keys,
+                        key,
+                        value,
+                        "ALL or NONE",
+                        diagnostics,
+                    )
+                }
+                "rows_per_partition" => {
+                    let rows = match &value {
+                        CqlOptionValue::Integer(count) => {
+                            count.parse().ok().map(CqlRowsPerPartition::Count)
+                        }
+                        value => value.as_str().and_then(CqlRowsPerPartition::from_name),
+                    };
+                    set(
+                        &mut caching.rows_per_partition,
+                        rows,
+                        key,
+                        value,
+                        "ALL, NONE or a count",
+                        diagnostics,
+                    )
+                }
+                _ => caching.extras.push((key, value)),
+            }
+        }
+
+        caching
+    }
+}
+
+impl<I> CqlCaching<I> {
+    /// Types the single string form of Cassandra 2.x, `ALL`, `KEYS_ONLY`, `ROWS_ONLY` or `NONE`.
+    pub fn from_legacy(caching: &str) -> Option<Self> {
+        let (keys, rows_per_partition) = match caching.to_ascii_uppercase().as_str() {
+            "ALL" => (CqlCachingKeys::All, CqlRowsPerPartition::All),
+            "KEYS_ONLY" => (CqlCachingKeys::All, CqlRowsPerPartition::None),
+            "ROWS_ONLY" => (CqlCachingKeys::None, CqlRowsPerPartition::All),
+            "NONE" => (CqlCachingKeys::None, CqlRowsPerPartition::None),
+            _ => return None,
+        };
+        let mut caching = CqlCaching::new();
+        caching.keys = Some(keys);
+        caching.rows_per_partition = Some(rows_per_partition);
+
+        Some(caching)
+    }
+}
+
+impl<I: Clone> CqlCaching<I> {
+    /// The entries of the `caching` map, the typed parameters as strings.
+    pub fn to_entries(&self) -> Vec<(String, CqlOptionValue<I>)> {
+        let mut entries = Vec::new();
+        if let Some(keys) = self.keys {
+            entries.push((
+                "keys".to_string(),
+                CqlOptionValue::String(keys.name().to_string()),
+            ));
+        }
+        if let Some(rows_per_partition) = self.rows_per_partition {
+            entries.push((
+                "rows_per_partition".to_string(),
+                CqlOptionValue::String(rows_per_partition.to_string()),
+            ));
+        }
+        entries.extend(
+            self.diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.key().clone(), diagnostic.value().clone())),
+        );
+        entries.extend(self.extras.iter().cloned());
+
+        entries
+    }
+}
+
+/// Whether the partition keys of a table are cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IsVariant)]
+pub enum CqlCachingKeys {
+    /// `ALL`, every key is cached.
+    All,
+    /// `NONE`, no key is cached.
+    None,
+}
+
+impl CqlCachingKeys {
+    /// Types a name, in any case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "ALL" => Some(CqlCachingKeys::All),
+            "NONE" => Some(CqlCachingKeys::None),
+            _ => None,
+        }
+    }
+
+    /// The name, `ALL` or `NONE`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CqlCachingKeys::All => "ALL",
+            CqlCachingKeys::None => "NONE",
+        }
+    }
+}
+
+/// How many rows of a partition are cached.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IsVariant)]
+pub enum CqlRowsPerPartition {
+    /// `ALL`, every row is cached.
+    All,
+    /// `NONE`, no row is cached.
+    None,
+    /// The number of the first rows which are cached, like `120`.
+    Count(u64),
+}
+
+impl CqlRowsPerPartition {
+    /// Types a name or a count, in any case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "ALL" => Some(CqlRowsPerPartition::All),
+            "NONE" => Some(CqlRowsPerPartition::None),
+            count => count.parse().ok().map(CqlRowsPerPartition::Count),
+        }
+    }
+}
+
+impl Display for CqlRowsPerPartition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CqlRowsPerPartition::All => f.write_str("ALL"),
+            CqlRowsPerPartition::None => f.write_str("NONE"),
+            CqlRowsPerPartition::Count(count) => write!(f, "{}", count),
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use crate::model::*;
+use derive_more::IsVariant;
 use derive_new::new;
 use derive_where::derive_where;
 use getset::{CopyGetters, Getters};
@@ -7,12 +8,13 @@ use std::rc::Rc;
 
 /// The cql column.
 /// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/ddl.html#create-table-statement>
+///
+/// The name is a [`CqlIdentifier`], or a [`Spanned`] one to keep its location.
 #[derive(Debug, Clone, Getters, CopyGetters, new)]
-#[derive_where(PartialEq; UdtType, I: std::ops::Deref<Target = str>)]
-pub struct CqlColumn<I, UdtType> {
+pub struct CqlColumn<I, UdtType, Name = CqlIdentifier<I>> {
     /// The name of the column.
     #[getset(get = "pub")]
-    name: CqlIdentifier<I>,
+    name: Name,
     /// The type of the column.
     #[getset(get = "pub")]
     cql_type: CqlType<UdtType>,
@@ -22,15 +24,77 @@ pub struct CqlColumn<I, UdtType> {
     /// Whether the column is part of the primary key.
     #[getset(get_copy = "pub")]
     is_primary_key: bool,
+    /// The mask applied to the values of the column, if any.
+    #[new(default)]
+    #[getset(get = "pub")]
+    mask: Option<CqlColumnMask<I>>,
+}
+
+/// A column whose user defined types are resolved.
+pub type ResolvedCqlColumn<I> = Rc<CqlColumn<I, Rc<CqlUserDefinedType<I>>>>;
+/// A column shared by the tables and keys referencing it.
+pub(crate) type SharedCqlColumn<I, UdtType> = Rc<CqlColumn<I, Rc<UdtType>>>;
+
+/// The dynamic data mask of a column.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/developing/cql/dynamic-data-masking.html>
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlColumnMask<I> {
+    /// `MASKED WITH DEFAULT`, the default mask of the type of the column.
+    Default,
+    /// `MASKED WITH mask_inner(2, null)`, a masking function and its literal arguments.
+    Function(CqlQualifiedIdentifier<I>, Vec<CqlTerm<I>>),
+}
+
+impl<I, UdtType, Name> CqlColumn<I, UdtType, Name> {
+    /// Masks the values of the column.
+    pub fn with_mask(mut self, mask: CqlColumnMask<I>) -> Self {
+        self.mask = Some(mask);
+        self
+    }
+
+    /// Whether the values of the column are frozen, see [`CqlType::is_frozen_at_top_level`].
+    ///
+    /// A non frozen collection or user defined type column can be updated in parts.
+    pub fn is_frozen_at_top_level(&self) -> bool {
+        self.cql_type.is_frozen_at_top_level()
+    }
+
+    /// The user defined types referenced by the type of the column, see
+    /// [`CqlType::collect_udts`].
+    pub fn collect_udts(&self) -> Vec<&UdtType>
+    where
+        I: Clone + Deref<Target = str>,
+        UdtType: Identifiable<I>,
+    {
+        self.cql_type.collect_udts()
+    }
+}
+
+impl<I, UdtType, Name> PartialEq for CqlColumn<I, UdtType, Name>
+where
+    I: Deref<Target = str> + PartialEq,
+    UdtType: PartialEq,
+    Name: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        // Primary key and clustering order point at the very same column.
+        std::ptr::eq(self, other)
+            || (self.name == other.name
+                && self.cql_type == other.cql_type
+                && self.is_static == other.is_static
+                && self.is_primary_key == other.is_primary_key
+                && self.mask == other.mask)
+    }
 }
 
-impl<I: Clone, UdtType> Identifiable<I> for CqlColumn<I, UdtType> {
+impl<I: Clone, UdtType, Name: Identifiable<I>> Identifiable<I> for CqlColumn<I, UdtType, Name> {
     fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
         None
     }
 
     fn identifier(&self) -> &CqlIdentifier<I> {
-        self.name()
+        self.name.identifier()
     }
 }
 
@@ -38,18 +102,19 @@ impl<I, UdtTypeRef> CqlColumn<I, UdtTypeRef> {
     pub(crate) fn reference_types<Table, UdtType>(
         self,
         keyspace: Option<&CqlIdentifier<I>>,
-        context: &Vec<CqlStatement<Table, Rc<UdtType>>>,
+        context: &[CqlStatement<I, Table, Rc<UdtType>>],
     ) -> Result<CqlColumn<I, Rc<UdtType>>, CqlQualifiedIdentifier<I>>
     where
         I: Deref<Target = str> + Clone,
         UdtTypeRef: Identifiable<I>,
         UdtType: Identifiable<I>,
     {
-        Ok(CqlColumn::new(
-            self.name,
-            self.cql_type.reference_types(keyspace, context)?,
-            self.is_static,
-            self.is_primary_key,
-        ))
+        Ok(CqlColumn {
+            name: self.name,
+            cql_type: self.cql_type.reference_types(keyspace, context)?,
+            is_static: self.is_static,
+            is_primary_key: self.is_primary_key,
+            mask: self.mask,
+        })
     }
 }
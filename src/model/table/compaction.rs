@@ -0,0 +1,356 @@
+use crate::model::*;
+use derive_more::IsVariant;
+use derive_new::new;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The `compaction` option of a table, like
+/// `{'class': 'TimeWindowCompactionStrategy', 'compaction_window_unit': 'DAYS'}`.
+///
+/// The values of a compaction map are usually strings, a typed parameter is read from a string as
+/// well. A parameter with a value which can not be typed is kept as a diagnostic.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/managing/operating/compaction/index.html>
+#[derive(Debug, Clone, PartialEq, Getters, CopyGetters, new)]
+pub struct CqlCompaction<I> {
+    /// The compaction strategy, `class`.
+    #[new(default)]
+    #[getset(get = "pub")]
+    class: Option<CqlCompactionStrategy>,
+    /// Whether the compaction is enabled, `enabled`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    enabled: Option<bool>,
+    /// The minimum number of sstables to compact, `min_threshold`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    min_threshold: Option<i64>,
+    /// The maximum number of sstables to compact, `max_threshold`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    max_threshold: Option<i64>,
+    /// The ratio of droppable tombstones to compact a single sstable, `tombstone_threshold`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    tombstone_threshold: Option<f64>,
+    /// The minimum age in seconds of a sstable to compact its tombstones,
+    /// `tombstone_compaction_interval`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    tombstone_compaction_interval: Option<i64>,
+    /// Whether single sstables are compacted for tombstones without checks,
+    /// `unchecked_tombstone_compaction`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    unchecked_tombstone_compaction: Option<bool>,
+    /// Whether only repaired tombstones are purged, `only_purge_repaired_tombstones`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    only_purge_repaired_tombstones: Option<bool>,
+    /// Whether the compactions are logged, `log_all`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    log_all: Option<bool>,
+    /// The bucket factor of the size tiered strategy, `bucket_low`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    bucket_low: Option<f64>,
+    /// The bucket factor of the size tiered strategy, `bucket_high`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    bucket_high: Option<f64>,
+    /// The target sstable size of the leveled strategy, `sstable_size_in_mb`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    sstable_size_in_mb: Option<i64>,
+    /// The size ratio of the levels of the leveled strategy, `fanout_size`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    fanout_size: Option<i64>,
+    /// The unit of the windows of the time window strategy, `compaction_window_unit`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    compaction_window_unit: Option<CqlCompactionWindowUnit>,
+    /// The number of units of a window of the time window strategy, `compaction_window_size`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    compaction_window_size: Option<i64>,
+    /// The resolution of the written timestamps, `timestamp_resolution`, like `MICROSECONDS`.
+    #[new(default)]
+    #[getset(get = "pub")]
+    timestamp_resolution: Option<String>,
+    /// The scaling parameters of the unified strategy, `scaling_parameters`, like `T4`.
+    #[new(default)]
+    #[getset(get = "pub")]
+    scaling_parameters: Option<String>,
+    /// The target sstable size of the unified strategy, `target_sstable_size`, like `1GiB`.
+    #[new(default)]
+    #[getset(get = "pub")]
+    target_sstable_size: Option<String>,
+    /// The base shard count of the unified strategy, `base_shard_count`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    base_shard_count: Option<i64>,
+    /// The parameters without a typed field.
+    #[new(default)]
+    #[getset(get = "pub")]
+    extras: Vec<(String, CqlOptionValue<I>)>,
+    /// The parameters with a value which can not be typed.
+    #[new(default)]
+    #[getset(get = "pub")]
+    diagnostics: Vec<CqlOptionDiagnostic<I>>,
+}
+
+impl<I: Deref<Target = str>> CqlCompaction<I> {
+    /// Types the entries of a `compaction` map.
+    pub fn from_entries(entries: Vec<(String, CqlOptionValue<I>)>) -> Self {
+        let mut compaction = CqlCompaction::new();
+        let diagnostics = &mut compaction.diagnostics;
+        for (key, value) in entries {
+            match key.as_str() {
+                "class" => {
+                    let class = value.as_str().map(CqlCompactionStrategy::from_class);
+                    set(
+                        &mut compaction.class,
+                        class,
+                        key,
+                        value,
+                        "a class",
+                        diagnostics,
+                    )
+                }
+                "enabled" => set(
+                    &mut compaction.enabled,
+                    value.as_bool(),
+                    key,
+                    value,
+                    "a boolean",
+                    diagnostics,
+                ),
+                "min_threshold" => set_i64(&mut compaction.min_threshold, key, value, diagnostics),
+                "max_threshold" => set_i64(&mut compaction.max_threshold, key, value, diagnostics),
+                "tombstone_threshold" => {
+                    set_f64(&mut compaction.tombstone_threshold, key, value, diagnostics)
+                }
+                "tombstone_compaction_interval" => set_i64(
+                    &mut compaction.tombstone_compaction_interval,
+                    key,
+                    value,
+                    diagnostics,
+                ),
+                "unchecked_tombstone_compaction" => set_bool(
+                    &mut compaction.unchecked_tombstone_compaction,
+                    key,
+                    value,
+                    diagnostics,
+                ),
+                "only_purge_repaired_tombstones" => set_bool(
+                    &mut compaction.only_purge_repaired_tombstones,
+                    key,
+                    value,
+                    diagnostics,
+                ),
+                "log_all" => set_bool(&mut compaction.log_all, key, value, diagnostics),
+                "bucket_low" => set_f64(&mut compaction.bucket_low, key, value, diagnostics),
+                "bucket_high" => set_f64(&mut compaction.bucket_high, key, value, diagnostics),
+                "sstable_size_in_mb" => {
+                    set_i64(&mut compaction.sstable_size_in_mb, key, value, diagnostics)
+                }
+                "fanout_size" => set_i64(&mut compaction.fanout_size, key, value, diagnostics),
+                "compaction_window_unit" => {
+                    let unit = value.as_str().and_then(CqlCompactionWindowUnit::from_name);
+                    set(
+                        &mut compaction.compaction_window_unit,
+                        unit,
+                        key,
+                        value,
+                        "MINUTES, HOURS or DAYS",
+                        diagnostics,
+                    )
+                }
+                "compaction_window_size" => set_i64(
+                    &mut compaction.compaction_window_size,
+                    key,
+                    value,
+                    diagnostics,
+                ),
+                "timestamp_resolution" => set_string(
+                    &mut compaction.timestamp_resolution,
+                    key,
+                    value,
+                    diagnostics,
+                ),
+                "scaling_parameters" => {
+                    set_string(&mut compaction.scaling_parameters, key, value, diagnostics)
+                }
+                "target_sstable_size" => {
+                    set_string(&mut compaction.target_sstable_size, key, value, diagnostics)
+                }
+                "base_shard_count" => {
+                    set_i64(&mut compaction.base_shard_count, key, value, diagnostics)
+                }
+                _ => compaction.extras.push((key, value)),
+            }
+        }
+
+        compaction
+    }
+}
+
+impl<I: Clone> CqlCompaction<I> {
+    /// The entries of the `compaction` map, the typed parameters as strings, like the values
+    /// written by `DESCRIBE`.
+    pub fn to_entries(&self) -> Vec<(String, CqlOptionValue<I>)> {
+        let mut entries = Vec::new();
+        let mut push = |key: &str, value: Option<String>| {
+            if let Some(value) = value {
+                entries.push((key.to_string(), CqlOptionValue::String(value)));
+            }
+        };
+        push(
+            "class",
+            self.class
+                .as_ref()
+                .map(|class| class.class_name().to_string()),
+        );
+        push("enabled", self.enabled.map(|value| value.to_string()));
+        push(
+            "min_threshold",
+            self.min_threshold.map(|value| value.to_string()),
+        );
+        push(
+            "max_threshold",
+            self.max_threshold.map(|value| value.to_string()),
+        );
+        push(
+            "tombstone_threshold",
+            self.tombstone_threshold.map(|value| value.to_string()),
+        );
+        push(
+            "tombstone_compaction_interval",
+            self.tombstone_compaction_interval
+                .map(|value| value.to_string()),
+        );
+        push(
+            "unchecked_tombstone_compaction",
+            self.unchecked_tombstone_compaction
+                .map(|value| value.to_string()),
+        );
+        push(
+            "only_purge_repaired_tombstones",
+            self.only_purge_repaired_tombstones
+                .map(|value| value.to_string()),
+        );
+        push("log_all", self.log_all.map(|value| value.to_string()));
+        push("bucket_low", self.bucket_low.map(|value| value.to_string()));
+        push(
+            "bucket_high",
+            self.bucket_high.map(|value| value.to_string()),
+        );
+        push(
+            "sstable_size_in_mb",
+            self.sstable_size_in_mb.map(|value| value.to_string()),
+        );
+        push(
+            "fanout_size",
+            self.fanout_size.map(|value| value.to_string()),
+        );
+        push(
+            "compaction_window_unit",
+            self.compaction_window_unit
+                .map(|unit| unit.name().to_string()),
+        );
+        push(
+            "compaction_window_size",
+            self.compaction_window_size.map(|value| value.to_string()),
+        );
+        push("timestamp_resolution", self.timestamp_resolution.clone());
+        push("scaling_parameters", self.scaling_parameters.clone());
+        push("target_sstable_size", self.target_sstable_size.clone());
+        push(
+            "base_shard_count",
+            self.base_shard_count.map(|value| value.to_string()),
+        );
+        entries.extend(
+            self.diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.key().clone(), diagnostic.value().clone())),
+        );
+        entries.extend(self.extras.iter().cloned());
+
+        entries
+    }
+}
+
+/// A compaction strategy, the `class` of a `compaction` map.
+#[derive(Debug, Clone, PartialEq, Eq, IsVariant)]
+pub enum CqlCompactionStrategy {
+    /// `SizeTieredCompactionStrategy`.
+    SizeTiered,
+    /// `LeveledCompactionStrategy`.
+    Leveled,
+    /// `TimeWindowCompactionStrategy`.
+    TimeWindow,
+    /// `UnifiedCompactionStrategy`.
+    Unified,
+    /// Another strategy, with its class as written.
+    Other(String),
+}
+
+impl CqlCompactionStrategy {
+    /// Types a class, with or without the `org.apache.cassandra.db.compaction.` package.
+    pub fn from_class(class: &str) -> Self {
+        let name = class
+            .strip_prefix("org.apache.cassandra.db.compaction.")
+            .unwrap_or(class);
+        match name {
+            "SizeTieredCompactionStrategy" => CqlCompactionStrategy::SizeTiered,
+            "LeveledCompactionStrategy" => CqlCompactionStrategy::Leveled,
+            "TimeWindowCompactionStrategy" => CqlCompactionStrategy::TimeWindow,
+            "UnifiedCompactionStrategy" => CqlCompactionStrategy::Unified,
+            _ => CqlCompactionStrategy::Other(class.to_string()),
+        }
+    }
+
+    /// The class of the strategy, without the package of a well-known strategy.
+    pub fn class_name(&self) -> &str {
+        match self {
+            CqlCompactionStrategy::SizeTiered => "SizeTieredCompactionStrategy",
+            CqlCompactionStrategy::Leveled => "LeveledCompactionStrategy",
+            CqlCompactionStrategy::TimeWindow => "TimeWindowCompactionStrategy",
+            CqlCompactionStrategy::Unified => "UnifiedCompactionStrategy",
+            CqlCompactionStrategy::Other(class) => class,
+        }
+    }
+}
+
+/// The unit of the windows of the time window compaction strategy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, IsVariant)]
+pub enum CqlCompactionWindowUnit {
+    /// `MINUTES`.
+    Minutes,
+    /// `HOURS`.
+    Hours,
+    /// `DAYS`.
+    Days,
+}
+
+impl CqlCompactionWindowUnit {
+    /// Types a unit, in any case.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_uppercase().as_str() {
+            "MINUTES" => Some(CqlCompactionWindowUnit::Minutes),
+            "HOURS" => Some(CqlCompactionWindowUnit::Hours),
+            "DAYS" => Some(CqlCompactionWindowUnit::Days),
+            _ => None,
+        }
+    }
+
+    /// The name of the unit.
+    pub fn name(&self) -> &'static str {
+        match self {
+            CqlCompactionWindowUnit::Minutes => "MINUTES",
+            CqlCompactionWindowUnit::Hours => "HOURS",
+            CqlCompactionWindowUnit::Days => "DAYS",
+        }
+    }
+}
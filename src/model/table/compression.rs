@@ -0,0 +1,166 @@
+use crate::model::*;
+use derive_more::IsVariant;
+use derive_new::new;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The `compression` option of a table, like
+/// `{'class': 'LZ4Compressor', 'chunk_length_in_kb': 64}`.
+///
+/// The deprecated keys `sstable_compression` and `chunk_length_kb` of old dumps are read as
+/// `class` and `chunk_length_in_kb`. A parameter with a value which can not be typed is kept as a
+/// diagnostic.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/managing/operating/compression.html>
+#[derive(Debug, Clone, PartialEq, Getters, CopyGetters, new)]
+pub struct CqlCompression<I> {
+    /// The compressor, `class`.
+    #[new(default)]
+    #[getset(get = "pub")]
+    class: Option<CqlCompressor>,
+    /// Whether the compression is enabled, `enabled`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    enabled: Option<bool>,
+    /// The size of the compressed chunks, `chunk_length_in_kb`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    chunk_length_in_kb: Option<i64>,
+    /// The chance of checking the checksums of compressed blocks, `crc_check_chance`.
+    #[new(default)]
+    #[getset(get_copy = "pub")]
+    crc_check_chance: Option<f64>,
+    /// The parameters without a typed field, like the `compression_level` of zstd.
+    #[new(default)]
+    #[getset(get = "pub")]
+    extras: Vec<(String, CqlOptionValue<I>)>,
+    /// The parameters with a value which can not be typed.
+    #[new(default)]
+    #[getset(get = "pub")]
+    diagnostics: Vec<CqlOptionDiagnostic<I>>,
+}
+
+impl<I: Deref<Target = str>> CqlCompression<I> {
+    /// Types the entries of a `compression` map.
+    pub fn from_entries(entries: Vec<(String, CqlOptionValue<I>)>) -> Self {
+        let mut compression = CqlCompression::new();
+        let diagnostics = &mut compression.diagnostics;
+        for (key, value) in entries {
+            match key.as_str() {
+                // An empty compressor of an old dump disables the compression.
+                "sstable_compression" if value.as_str() == Some("") => {
+                    compression.enabled = Some(false)
+                }
+                "class" | "sstable_compression" => {
+                    let class = value.as_str().map(CqlCompressor::from_class);
+                    set(
+                        &mut compression.class,
+                        class,
+                        key,
+                        value,
+                        "a class",
+                        diagnostics,
+                    )
+                }
+                "enabled" => set_bool(&mut compression.enabled, key, value, diagnostics),
+                "chunk_length_in_kb" | "chunk_length_kb" => {
+                    set_i64(&mut compression.chunk_length_in_kb, key, value, diagnostics)
+                }
+                "crc_check_chance" => {
+                    set_f64(&mut compression.crc_check_chance, key, value, diagnostics)
+                }
+                _ => compression.extras.push((key, value)),
+            }
+        }
+
+        compression
+    }
+}
+
+impl<I> CqlCompression<I> {
+    /// Whether the compression is enabled, it is unless `enabled` is `false`.
+    pub fn is_enabled(&self) -> bool {
+        self.enabled != Some(false)
+    }
+}
+
+impl<I: Clone> CqlCompression<I> {
+    /// The entries of the `compression` map with the current key names, the typed parameters as
+    /// strings.
+    pub fn to_entries(&self) -> Vec<(String, CqlOptionValue<I>)> {
+        let mut entries = Vec::new();
+        let mut push = |key: &str, value: Option<String>| {
+            if let Some(value) = value {
+                entries.push((key.to_string(), CqlOptionValue::String(value)));
+            }
+        };
+        push(
+            "class",
+            self.class
+                .as_ref()
+                .map(|class| class.class_name().to_string()),
+        );
+        push("enabled", self.enabled.map(|value| value.to_string()));
+        push(
+            "chunk_length_in_kb",
+            self.chunk_length_in_kb.map(|value| value.to_string()),
+        );
+        push(
+            "crc_check_chance",
+            self.crc_check_chance.map(|value| value.to_string()),
+        );
+        entries.extend(
+            self.diagnostics
+                .iter()
+                .map(|diagnostic| (diagnostic.key().clone(), diagnostic.value().clone())),
+        );
+        entries.extend(self.extras.iter().cloned());
+
+        entries
+    }
+}
+
+/// A compressor, the `class` of a `compression` map.
+#[derive(Debug, Clone, PartialEq, Eq, IsVariant)]
+pub enum CqlCompressor {
+    /// `LZ4Compressor`.
+    Lz4,
+    /// `SnappyCompressor`.
+    Snappy,
+    /// `DeflateCompressor`.
+    Deflate,
+    /// `ZstdCompressor`.
+    Zstd,
+    /// `NoopCompressor`.
+    Noop,
+    /// Another compressor, with its class as written.
+    Other(String),
+}
+
+impl CqlCompressor {
+    /// Types a class, with or without the `org.apache.cassandra.io.compress.` package.
+    pub fn from_class(class: &str) -> Self {
+        let name = class
+            .strip_prefix("org.apache.cassandra.io.compress.")
+            .unwrap_or(class);
+        match name {
+            "LZ4Compressor" => CqlCompressor::Lz4,
+            "SnappyCompressor" => CqlCompressor::Snappy,
+            "DeflateCompressor" => CqlCompressor::Deflate,
+            "ZstdCompressor" => CqlCompressor::Zstd,
+            "NoopCompressor" => CqlCompressor::Noop,
+            _ => CqlCompressor::Other(class.to_string()),
+        }
+    }
+
+    /// The class of the compressor, without the package of a well-known compressor.
+    pub fn class_name(&self) -> &str {
+        match self {
+            CqlCompressor::Lz4 => "LZ4Compressor",
+            CqlCompressor::Snappy => "SnappyCompressor",
+            CqlCompressor::Deflate => "DeflateCompressor",
+            CqlCompressor::Zstd => "ZstdCompressor",
+            CqlCompressor::Noop => "NoopCompressor",
+            CqlCompressor::Other(class) => class,
+        }
+    }
+}
@@ -0,0 +1,89 @@
+use crate::model::*;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// The cql `CREATE TABLE ... LIKE` statement, creating a table with the structure of another.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/developing/cql/ddl.html#copy-table>
+///
+/// Grammar:
+/// ```bnf
+/// create_table_like_statement::= CREATE TABLE [ IF NOT EXISTS ] table_name
+///     LIKE table_name
+///     [ WITH table_options ]
+/// ```
+///
+/// Example:
+/// ```cql
+/// CREATE TABLE shop.orders_copy LIKE shop.orders WITH comment = 'A copy';
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq, Table: PartialEq)]
+pub struct CqlCreateTableLike<I, Table> {
+    /// If the table should only be created if it does not exist.
+    #[getset(get_copy = "pub")]
+    if_not_exists: bool,
+    /// The name of the table.
+    #[getset(get = "pub")]
+    name: CqlQualifiedIdentifier<I>,
+    /// The name of the copied table.
+    #[getset(get = "pub")]
+    source: CqlQualifiedIdentifier<I>,
+    /// The copied table, `None` until the references are resolved.
+    #[new(default)]
+    #[getset(get = "pub")]
+    source_table: Option<Table>,
+    /// The options of the table, replacing those of the copied table.
+    #[getset(get = "pub")]
+    options: Option<CqlTableOptions<I, CqlIdentifier<I>>>,
+}
+
+impl<I, Table> CqlCreateTableLike<I, Table> {
+    /// Resolves the copied table among the previous statements, and qualifies both names with
+    /// the keyspace.
+    pub(crate) fn reference_types<UdtType>(
+        self,
+        keyspace: Option<&CqlIdentifier<I>>,
+        context: &[CqlStatement<I, Rc<ResolvedCqlTable<I>>, UdtType>],
+    ) -> Result<CqlCreateTableLike<I, Rc<ResolvedCqlTable<I>>>, CqlQualifiedIdentifier<I>>
+    where
+        I: Deref<Target = str> + Clone,
+    {
+        let name = self.name.contextualized_identifier(keyspace);
+        let source = self.source.contextualized_identifier(keyspace);
+        // The latest definition wins, a copy of a copy has the structure of the original.
+        let source_table = context
+            .iter()
+            .rev()
+            .find_map(|statement| match statement {
+                CqlStatement::CreateTable(table) if *table.name() == source => Some(table.clone()),
+                CqlStatement::CreateTableLike(like) if *like.name() == source => {
+                    like.source_table.clone()
+                }
+                _ => None,
+            })
+            .ok_or_else(|| source.clone())?;
+
+        Ok(CqlCreateTableLike {
+            if_not_exists: self.if_not_exists,
+            name,
+            source,
+            source_table: Some(source_table),
+            options: self.options,
+        })
+    }
+}
+
+impl<I: Clone + Deref<Target = str>, Table> Identifiable<I> for CqlCreateTableLike<I, Table> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.name.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.name.identifier()
+    }
+}
@@ -1,13 +1,20 @@
+use crate::model::value::uuid;
 use crate::model::*;
+use derive_more::IsVariant;
 use derive_new::new;
 use getset::{CopyGetters, Getters};
+use std::borrow::Borrow;
+use std::marker::PhantomData;
 use std::ops::Deref;
 use std::rc::Rc;
 
 /// The cql table options.
 /// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/ddl.html#create-table-statement>
+///
+/// The `name = value` entries are wrapped in [`Spanned`] by
+/// [`parse_cql_spanned`](crate::parse_cql_spanned) to keep their location.
 #[derive(Debug, Clone, PartialEq, Getters, CopyGetters, new)]
-pub struct CqlTableOptions<I, ColumnRef> {
+pub struct CqlTableOptions<I, ColumnRef, Entry = CqlTableOption<I>> {
     /// Has the compact storage keyword.
     #[getset(get_copy = "pub")]
     compact_storage: bool,
@@ -16,15 +23,420 @@ pub struct CqlTableOptions<I, ColumnRef> {
     clustering_order: Vec<(ColumnRef, CqlOrder)>,
     /// The other options.
     #[getset(get = "pub")]
-    options: Vec<(I, I)>,
+    options: Vec<Entry>,
+    /// The options given more than once, like `comment` in `comment = 'x' AND comment = 'y'`,
+    /// Cassandra rejects them. The names are lowercase, besides `CLUSTERING ORDER` and
+    /// `COMPACT STORAGE`.
+    #[new(default)]
+    #[getset(get = "pub")]
+    duplicates: Vec<String>,
+    /// The input type of the default entries.
+    #[new(default)]
+    input: PhantomData<I>,
+}
+
+impl<I, ColumnRef, Entry: Borrow<CqlTableOption<I>>> CqlTableOptions<I, ColumnRef, Entry> {
+    /// Records the options given more than once.
+    pub fn with_duplicates(mut self, duplicates: Vec<String>) -> Self {
+        self.duplicates = duplicates;
+        self
+    }
+
+    /// The options without their spans.
+    fn entries(&self) -> impl Iterator<Item = &CqlTableOption<I>> {
+        self.options.iter().map(Borrow::borrow)
+    }
+
+    /// The `comment` of the table.
+    pub fn comment(&self) -> Option<&str> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::Comment(comment) => Some(&**comment),
+            _ => None,
+        })
+    }
+
+    /// The `gc_grace_seconds`, how long tombstones are kept.
+    pub fn gc_grace_seconds(&self) -> Option<i64> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::GcGraceSeconds(seconds) => Some(*seconds),
+            _ => None,
+        })
+    }
+
+    /// The `default_time_to_live` of written values in seconds.
+    pub fn default_ttl(&self) -> Option<i64> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::DefaultTimeToLive(seconds) => Some(*seconds),
+            _ => None,
+        })
+    }
+
+    /// The `bloom_filter_fp_chance`.
+    pub fn bloom_filter_fp_chance(&self) -> Option<f64> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::BloomFilterFpChance(chance) => Some(*chance),
+            _ => None,
+        })
+    }
+
+    /// The `crc_check_chance`.
+    pub fn crc_check_chance(&self) -> Option<f64> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::CrcCheckChance(chance) => Some(*chance),
+            _ => None,
+        })
+    }
+
+    /// The `memtable_flush_period_in_ms`.
+    pub fn memtable_flush_period_in_ms(&self) -> Option<i64> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::MemtableFlushPeriodInMs(period) => Some(*period),
+            _ => None,
+        })
+    }
+
+    /// The `min_index_interval`.
+    pub fn min_index_interval(&self) -> Option<i64> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::MinIndexInterval(interval) => Some(*interval),
+            _ => None,
+        })
+    }
+
+    /// The `max_index_interval`.
+    pub fn max_index_interval(&self) -> Option<i64> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::MaxIndexInterval(interval) => Some(*interval),
+            _ => None,
+        })
+    }
+
+    /// The `speculative_retry`, like `99p` or `ALWAYS`.
+    pub fn speculative_retry(&self) -> Option<&str> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::SpeculativeRetry(retry) => Some(&**retry),
+            _ => None,
+        })
+    }
+
+    /// The `read_repair`, `BLOCKING` or `NONE`.
+    pub fn read_repair(&self) -> Option<&str> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::ReadRepair(read_repair) => Some(&**read_repair),
+            _ => None,
+        })
+    }
+
+    /// Whether change data capture is enabled, `cdc`, or the `enabled` entry of the Scylla map.
+    pub fn cdc(&self) -> Option<bool> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::Cdc(cdc) => Some(*cdc),
+            CqlTableOption::CdcOptions(entries) => entries
+                .iter()
+                .find(|(key, _)| key == "enabled")
+                .and_then(|(_, value)| value.as_bool()),
+            _ => None,
+        })
+    }
+
+    /// The `paxos_grace_seconds` of Scylla.
+    pub fn paxos_grace_seconds(&self) -> Option<i64> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::PaxosGraceSeconds(seconds) => Some(*seconds),
+            _ => None,
+        })
+    }
+
+    /// The entries of the `tombstone_gc` map of Scylla.
+    pub fn tombstone_gc(&self) -> Option<&[(String, CqlOptionValue<I>)]> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::TombstoneGc(entries) => Some(&**entries),
+            _ => None,
+        })
+    }
+
+    /// The entries of the `per_partition_rate_limit` map of Scylla.
+    pub fn per_partition_rate_limit(&self) -> Option<&[(String, CqlOptionValue<I>)]> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::PerPartitionRateLimit(entries) => Some(&**entries),
+            _ => None,
+        })
+    }
+
+    /// The `synchronous_updates` of a Scylla materialized view.
+    pub fn synchronous_updates(&self) -> Option<bool> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::SynchronousUpdates(synchronous) => Some(*synchronous),
+            _ => None,
+        })
+    }
+
+    /// The `id` of the table, set to recreate a table from a snapshot.
+    pub fn id(&self) -> Option<u128> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::Id(id) => Some(*id),
+            _ => None,
+        })
+    }
+
+    /// The `compaction` strategy and its parameters.
+    pub fn compaction(&self) -> Option<&CqlCompaction<I>> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::Compaction(compaction) => Some(&**compaction),
+            _ => None,
+        })
+    }
+
+    /// The `compression` of the sstables.
+    pub fn compression(&self) -> Option<&CqlCompression<I>> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::Compression(compression) => Some(&**compression),
+            _ => None,
+        })
+    }
+
+    /// The `caching` of keys and rows.
+    pub fn caching(&self) -> Option<&CqlCaching<I>> {
+        self.entries().find_map(|option| match option {
+            CqlTableOption::Caching(caching) => Some(caching),
+            _ => None,
+        })
+    }
+}
+
+/// An option of a table or a materialized view, the well-known options are typed.
+///
+/// An option with an unknown name, or with a value of another type, is kept as
+/// [`CqlTableOption::Other`].
+#[derive(Debug, Clone, PartialEq, IsVariant)]
+pub enum CqlTableOption<I> {
+    /// `comment`, a description of the table.
+    Comment(String),
+    /// `gc_grace_seconds`, how long tombstones are kept.
+    GcGraceSeconds(i64),
+    /// `default_time_to_live`, the time to live of written values in seconds.
+    DefaultTimeToLive(i64),
+    /// `bloom_filter_fp_chance`, the false positive chance of the sstable bloom filters.
+    BloomFilterFpChance(f64),
+    /// `crc_check_chance`, the chance of checking the checksums of compressed blocks.
+    CrcCheckChance(f64),
+    /// `memtable_flush_period_in_ms`, how often memtables are flushed.
+    MemtableFlushPeriodInMs(i64),
+    /// `min_index_interval`, the minimum gap between the entries of the partition summary.
+    MinIndexInterval(i64),
+    /// `max_index_interval`, the maximum gap between the entries of the partition summary.
+    MaxIndexInterval(i64),
+    /// `speculative_retry`, when a replica is queried again, like `99p`.
+    SpeculativeRetry(String),
+    /// `read_repair`, `BLOCKING` or `NONE`.
+    ReadRepair(String),
+    /// `cdc`, whether change data capture is enabled.
+    Cdc(bool),
+    /// `id`, the uuid of the table, quoted or not.
+    Id(u128),
+    /// `cdc` of Scylla, a map like `{'enabled': true, 'preimage': false}`.
+    CdcOptions(Vec<(String, CqlOptionValue<I>)>),
+    /// `paxos_grace_seconds` of Scylla, how long the paxos state is kept.
+    PaxosGraceSeconds(i64),
+    /// `tombstone_gc` of Scylla, a map like `{'mode': 'repair'}`.
+    TombstoneGc(Vec<(String, CqlOptionValue<I>)>),
+    /// `per_partition_rate_limit` of Scylla, a map like `{'max_writes_per_second': 100}`.
+    PerPartitionRateLimit(Vec<(String, CqlOptionValue<I>)>),
+    /// `synchronous_updates` of a Scylla materialized view.
+    SynchronousUpdates(bool),
+    /// `compaction`, the compaction strategy and its parameters.
+    Compaction(Box<CqlCompaction<I>>),
+    /// `compression`, the compressor and its parameters.
+    Compression(Box<CqlCompression<I>>),
+    /// `caching`, what is kept in the caches.
+    Caching(CqlCaching<I>),
+    /// Another option, with its name.
+    Other(I, CqlOptionValue<I>),
+}
+
+impl<I: Deref<Target = str>> CqlTableOption<I> {
+    /// Types an option, the name of a well-known option is matched case-insensitively.
+    pub fn from_value(name: I, value: CqlOptionValue<I>) -> Self {
+        let key = name.to_ascii_lowercase();
+        let option = match key.as_str() {
+            "comment" => value
+                .as_str()
+                .map(|comment| CqlTableOption::Comment(comment.to_string())),
+            "gc_grace_seconds" => value.as_i64().map(CqlTableOption::GcGraceSeconds),
+            "default_time_to_live" => value.as_i64().map(CqlTableOption::DefaultTimeToLive),
+            "bloom_filter_fp_chance" => value.as_f64().map(CqlTableOption::BloomFilterFpChance),
+            "crc_check_chance" => value.as_f64().map(CqlTableOption::CrcCheckChance),
+            "memtable_flush_period_in_ms" => {
+                value.as_i64().map(CqlTableOption::MemtableFlushPeriodInMs)
+            }
+            "min_index_interval" => value.as_i64().map(CqlTableOption::MinIndexInterval),
+            "max_index_interval" => value.as_i64().map(CqlTableOption::MaxIndexInterval),
+            "speculative_retry" => value
+                .as_str()
+                .map(|retry| CqlTableOption::SpeculativeRetry(retry.to_string())),
+            "read_repair" => value
+                .as_str()
+                .map(|read_repair| CqlTableOption::ReadRepair(read_repair.to_string())),
+            "cdc" => value.as_bool().map(CqlTableOption::Cdc),
+            "id" => value.as_uuid().and_then(uuid).map(CqlTableOption::Id),
+            "paxos_grace_seconds" => value.as_i64().map(CqlTableOption::PaxosGraceSeconds),
+            "synchronous_updates" => value.as_bool().map(CqlTableOption::SynchronousUpdates),
+            _ => None,
+        };
+        match (option, key.as_str(), value) {
+            (Some(option), _, _) => option,
+            (None, "compaction", CqlOptionValue::Map(entries)) => {
+                CqlTableOption::Compaction(Box::new(CqlCompaction::from_entries(entries)))
+            }
+            (None, "compression", CqlOptionValue::Map(entries)) => {
+                CqlTableOption::Compression(Box::new(CqlCompression::from_entries(entries)))
+            }
+            (None, "caching", CqlOptionValue::Map(entries)) => {
+                CqlTableOption::Caching(CqlCaching::from_entries(entries))
+            }
+            (None, "cdc", CqlOptionValue::Map(entries)) => CqlTableOption::CdcOptions(entries),
+            (None, "tombstone_gc", CqlOptionValue::Map(entries)) => {
+                CqlTableOption::TombstoneGc(entries)
+            }
+            (None, "per_partition_rate_limit", CqlOptionValue::Map(entries)) => {
+                CqlTableOption::PerPartitionRateLimit(entries)
+            }
+            (None, "caching", value) => match value.as_str().and_then(CqlCaching::from_legacy) {
+                Some(caching) => CqlTableOption::Caching(caching),
+                None => CqlTableOption::Other(name, value),
+            },
+            (None, _, value) => CqlTableOption::Other(name, value),
+        }
+    }
+
+    /// The name of the option, as written for an unknown option.
+    pub fn name(&self) -> &str {
+        match self {
+            CqlTableOption::Comment(_) => "comment",
+            CqlTableOption::GcGraceSeconds(_) => "gc_grace_seconds",
+            CqlTableOption::DefaultTimeToLive(_) => "default_time_to_live",
+            CqlTableOption::BloomFilterFpChance(_) => "bloom_filter_fp_chance",
+            CqlTableOption::CrcCheckChance(_) => "crc_check_chance",
+            CqlTableOption::MemtableFlushPeriodInMs(_) => "memtable_flush_period_in_ms",
+            CqlTableOption::MinIndexInterval(_) => "min_index_interval",
+            CqlTableOption::MaxIndexInterval(_) => "max_index_interval",
+            CqlTableOption::SpeculativeRetry(_) => "speculative_retry",
+            CqlTableOption::ReadRepair(_) => "read_repair",
+            CqlTableOption::Cdc(_) => "cdc",
+            CqlTableOption::Id(_) => "id",
+            CqlTableOption::CdcOptions(_) => "cdc",
+            CqlTableOption::PaxosGraceSeconds(_) => "paxos_grace_seconds",
+            CqlTableOption::TombstoneGc(_) => "tombstone_gc",
+            CqlTableOption::PerPartitionRateLimit(_) => "per_partition_rate_limit",
+            CqlTableOption::SynchronousUpdates(_) => "synchronous_updates",
+            CqlTableOption::Compaction(_) => "compaction",
+            CqlTableOption::Compression(_) => "compression",
+            CqlTableOption::Caching(_) => "caching",
+            CqlTableOption::Other(name, _) => name,
+        }
+    }
+}
+
+/// The value of an option, like `'users table'` or `{'class': 'LZ4Compressor'}`.
+#[derive(Debug, Clone, PartialEq, IsVariant)]
+pub enum CqlOptionValue<I> {
+    /// A string, with its escapes resolved.
+    String(String),
+    /// An integer, like `864000`.
+    Integer(I),
+    /// A floating point number, like `0.01`.
+    Float(I),
+    /// A boolean, `true` or `false`.
+    Boolean(bool),
+    /// An unquoted uuid, like `5a1c395e-b41f-11e5-9f22-ba0be0483c18`.
+    Uuid(I),
+    /// A map of string keys to values, like the `compaction` of a table.
+    Map(Vec<(String, CqlOptionValue<I>)>),
+    /// Another constant, like `99.0PERCENTILE`.
+    Constant(I),
+}
+
+impl<I> CqlOptionValue<I> {
+    /// The value of a boolean, or of a string `'true'` or `'false'` in any case.
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            CqlOptionValue::Boolean(boolean) => Some(*boolean),
+            CqlOptionValue::String(string) if string.eq_ignore_ascii_case("true") => Some(true),
+            CqlOptionValue::String(string) if string.eq_ignore_ascii_case("false") => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl<I: Deref<Target = str>> CqlOptionValue<I> {
+    /// The text of a string or another constant.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            CqlOptionValue::String(string) => Some(string),
+            CqlOptionValue::Constant(constant) => Some(constant),
+            _ => None,
+        }
+    }
+
+    /// The value of an integer, or of a string of an integer like the values of a compaction map.
+    pub fn as_i64(&self) -> Option<i64> {
+        match self {
+            CqlOptionValue::Integer(literal) => literal.parse().ok(),
+            CqlOptionValue::String(string) => string.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// The value of a number, or of a string of a number.
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            CqlOptionValue::Integer(literal) | CqlOptionValue::Float(literal) => {
+                literal.parse().ok()
+            }
+            CqlOptionValue::String(string) => string.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// The text of a uuid, or of a string like the quoted `id` of a table.
+    pub fn as_uuid(&self) -> Option<&str> {
+        match self {
+            CqlOptionValue::Uuid(literal) => Some(literal),
+            CqlOptionValue::String(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// The entries of a map.
+    pub fn as_map(&self) -> Option<&[(String, CqlOptionValue<I>)]> {
+        match self {
+            CqlOptionValue::Map(entries) => Some(entries),
+            _ => None,
+        }
+    }
+}
+
+/// A parameter of an option map with a value which can not be typed, like
+/// `'min_threshold': 'four'`.
+#[derive(Debug, Clone, PartialEq, Getters, CopyGetters, new)]
+pub struct CqlOptionDiagnostic<I> {
+    /// The key of the parameter.
+    #[getset(get = "pub")]
+    key: String,
+    /// The value of the parameter.
+    #[getset(get = "pub")]
+    value: CqlOptionValue<I>,
+    /// What the value was expected to be, like `an integer`.
+    #[getset(get_copy = "pub")]
+    expected: &'static str,
 }
 
 impl<I, ColumnRef> CqlTableOptions<I, ColumnRef> {
     pub(crate) fn reference_types<UdtType>(
         self,
         keyspace: Option<&CqlIdentifier<I>>,
-        table_context: &Vec<Rc<CqlColumn<I, Rc<UdtType>>>>,
-    ) -> Result<CqlTableOptions<I, Rc<CqlColumn<I, Rc<UdtType>>>>, CqlQualifiedIdentifier<I>>
+        table_context: &[SharedCqlColumn<I, UdtType>],
+    ) -> Result<CqlTableOptions<I, SharedCqlColumn<I, UdtType>>, CqlQualifiedIdentifier<I>>
     where
         I: Deref<Target = str> + Clone,
         ColumnRef: Identifiable<I>,
@@ -43,10 +455,61 @@ impl<I, ColumnRef> CqlTableOptions<I, ColumnRef> {
                     .ok_or_else(|| column.contextualized_identifier(keyspace))
             })
             .collect::<Result<Vec<_>, _>>()?;
-        Ok(CqlTableOptions::new(
-            self.compact_storage,
-            clustering_order,
-            self.options,
-        ))
+        Ok(
+            CqlTableOptions::new(self.compact_storage, clustering_order, self.options)
+                .with_duplicates(self.duplicates),
+        )
     }
 }
+
+/// Sets a typed parameter, or keeps the parameter as a diagnostic if it can not be typed.
+pub(crate) fn set<T, I>(
+    field: &mut Option<T>,
+    typed: Option<T>,
+    key: String,
+    value: CqlOptionValue<I>,
+    expected: &'static str,
+    diagnostics: &mut Vec<CqlOptionDiagnostic<I>>,
+) {
+    match typed {
+        Some(typed) => *field = Some(typed),
+        None => diagnostics.push(CqlOptionDiagnostic::new(key, value, expected)),
+    }
+}
+
+pub(crate) fn set_i64<I: Deref<Target = str>>(
+    field: &mut Option<i64>,
+    key: String,
+    value: CqlOptionValue<I>,
+    diagnostics: &mut Vec<CqlOptionDiagnostic<I>>,
+) {
+    set(field, value.as_i64(), key, value, "an integer", diagnostics)
+}
+
+pub(crate) fn set_f64<I: Deref<Target = str>>(
+    field: &mut Option<f64>,
+    key: String,
+    value: CqlOptionValue<I>,
+    diagnostics: &mut Vec<CqlOptionDiagnostic<I>>,
+) {
+    set(field, value.as_f64(), key, value, "a number", diagnostics)
+}
+
+pub(crate) fn set_bool<I: Deref<Target = str>>(
+    field: &mut Option<bool>,
+    key: String,
+    value: CqlOptionValue<I>,
+    diagnostics: &mut Vec<CqlOptionDiagnostic<I>>,
+) {
+    set(field, value.as_bool(), key, value, "a boolean", diagnostics)
+}
+
+pub(crate) fn set_string<I: Deref<Target = str>>(
+    field: &mut Option<String>,
+    key: String,
+    value: CqlOptionValue<I>,
+    diagnostics: &mut Vec<CqlOptionDiagnostic<I>>,
+) {
+    let typed = value.as_str().map(str::to_string);
+    set(field, typed, key, value, "a string", diagnostics)
+}
@@ -1,6 +1,7 @@
 use crate::model::*;
 use derive_new::new;
 use getset::Getters;
+use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::rc::Rc;
 
@@ -11,6 +12,12 @@ pub struct CqlPrimaryKey<ColumnRef> {
     /// The partition key.
     #[getset(get = "pub")]
     partition_key: Vec<ColumnRef>,
+    /// Whether the partition key is parenthesized, as in `((a), b)`.
+    ///
+    /// With clustering columns `(a, b)` and `((a), b)` have the same partition key, but
+    /// `((a, b))` and `(a, b)` do not, so the written form is kept for rendering.
+    #[getset(get = "pub")]
+    composite_partition: bool,
     /// The clustering columns.
     #[getset(get = "pub")]
     clustering_columns: Vec<ColumnRef>,
@@ -20,8 +27,8 @@ impl<ColumnRef> CqlPrimaryKey<ColumnRef> {
     pub(crate) fn reference_types<I, UdtType>(
         self,
         keyspace: Option<&CqlIdentifier<I>>,
-        table_context: &Vec<Rc<CqlColumn<I, Rc<UdtType>>>>,
-    ) -> Result<CqlPrimaryKey<Rc<CqlColumn<I, Rc<UdtType>>>>, CqlQualifiedIdentifier<I>>
+        table_context: &[SharedCqlColumn<I, UdtType>],
+    ) -> Result<CqlPrimaryKey<SharedCqlColumn<I, UdtType>>, CqlQualifiedIdentifier<I>>
     where
         I: Deref<Target = str> + Clone,
         ColumnRef: Identifiable<I>,
@@ -55,6 +62,41 @@ impl<ColumnRef> CqlPrimaryKey<ColumnRef> {
             })
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(CqlPrimaryKey::new(partition_key, clustering_columns))
+        Ok(CqlPrimaryKey::new(
+            partition_key,
+            self.composite_partition,
+            clustering_columns,
+        ))
+    }
+}
+
+impl<ColumnRef: Display> Display for CqlPrimaryKey<ColumnRef> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn write_list<ColumnRef: Display>(
+            f: &mut Formatter<'_>,
+            columns: &[ColumnRef],
+        ) -> std::fmt::Result {
+            for (i, column) in columns.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", column)?;
+            }
+            Ok(())
+        }
+
+        f.write_str("(")?;
+        if self.composite_partition {
+            f.write_str("(")?;
+            write_list(f, &self.partition_key)?;
+            f.write_str(")")?;
+        } else {
+            write_list(f, &self.partition_key)?;
+        }
+        if !self.clustering_columns.is_empty() {
+            f.write_str(", ")?;
+            write_list(f, &self.clustering_columns)?;
+        }
+        f.write_str(")")
     }
 }
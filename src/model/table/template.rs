@@ -0,0 +1,164 @@
+use crate::model::*;
+use std::fmt::Write;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// The columns of the partition key, and the clustering columns.
+type KeyColumns<'a, I, UdtType> = (
+    Vec<&'a CqlColumn<I, UdtType>>,
+    Vec<&'a CqlColumn<I, UdtType>>,
+);
+
+impl<I: Deref<Target = str>, UdtType>
+    CqlTable<I, Rc<CqlColumn<I, UdtType>>, Rc<CqlColumn<I, UdtType>>>
+{
+    /// Renders `INSERT INTO table (c1, c2, ...) VALUES (?, ?, ...)` over all columns, in
+    /// declaration order.
+    pub fn insert_template(&self) -> String {
+        let columns = self
+            .columns()
+            .iter()
+            .map(|column| column.name().to_string())
+            .collect::<Vec<_>>();
+        let markers = vec!["?"; columns.len()];
+
+        format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            self.name(),
+            columns.join(", "),
+            markers.join(", ")
+        )
+    }
+
+    /// Renders `SELECT * FROM table WHERE p1 = :p1 AND ...`, restricted by the partition key
+    /// and, if `clustering` is set, by the clustering columns.
+    pub fn select_by_key_template(&self, clustering: bool) -> String {
+        format!(
+            "SELECT * FROM {}{}",
+            self.name(),
+            self.key_restriction(clustering)
+        )
+    }
+
+    /// Renders `DELETE FROM table WHERE p1 = :p1 AND ...`, restricted by the partition key
+    /// and, if `clustering` is set, by the clustering columns.
+    pub fn delete_by_key_template(&self, clustering: bool) -> String {
+        format!(
+            "DELETE FROM {}{}",
+            self.name(),
+            self.key_restriction(clustering)
+        )
+    }
+
+    /// The columns of the partition key, and the clustering columns.
+    ///
+    /// Without a `PRIMARY KEY (...)` clause the column marked `PRIMARY KEY` is the partition key.
+    pub(crate) fn key_columns(&self) -> KeyColumns<'_, I, UdtType> {
+        match self.primary_key() {
+            Some(primary_key) => (
+                primary_key.partition_key().iter().map(Rc::deref).collect(),
+                primary_key
+                    .clustering_columns()
+                    .iter()
+                    .map(Rc::deref)
+                    .collect(),
+            ),
+            None => (
+                self.columns()
+                    .iter()
+                    .filter(|column| column.is_primary_key())
+                    .map(Rc::deref)
+                    .collect(),
+                Vec::new(),
+            ),
+        }
+    }
+
+    /// Renders ` WHERE c1 = :c1 AND ...` with named markers.
+    fn key_restriction(&self, clustering: bool) -> String {
+        let (mut columns, clustering_columns) = self.key_columns();
+        if clustering {
+            columns.extend(clustering_columns);
+        }
+
+        let mut restriction = String::new();
+        for (i, column) in columns.iter().enumerate() {
+            let keyword = if i == 0 { " WHERE" } else { " AND" };
+            let _ = write!(
+                restriction,
+                "{} {} = :{}",
+                keyword,
+                column.name(),
+                column.name()
+            );
+        }
+
+        restriction
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{parse_cql, resolve_references};
+
+    fn templates(clustering: bool) -> [String; 3] {
+        let input = include_str!("../../../tests/fixtures/composite_key.cql");
+        let (_, statements) = parse_cql(input).unwrap();
+        let statements = resolve_references(statements, None).unwrap();
+        let table = statements[0].create_table().unwrap();
+
+        [
+            table.insert_template(),
+            table.select_by_key_template(clustering),
+            table.delete_by_key_template(clustering),
+        ]
+    }
+
+    #[test]
+    fn test_templates() {
+        let [insert, select, delete] = templates(false);
+        assert_eq!(
+            insert,
+            "INSERT INTO zoo_ks.feedings_v1 \
+             (enclosure_id, \"Keeper Id\", fed_at, food_kind, amount_grams) \
+             VALUES (?, ?, ?, ?, ?)"
+        );
+        assert_eq!(
+            select,
+            "SELECT * FROM zoo_ks.feedings_v1 \
+             WHERE enclosure_id = :enclosure_id AND \"Keeper Id\" = :\"Keeper Id\""
+        );
+        assert_eq!(
+            delete,
+            "DELETE FROM zoo_ks.feedings_v1 \
+             WHERE enclosure_id = :enclosure_id AND \"Keeper Id\" = :\"Keeper Id\""
+        );
+    }
+
+    #[test]
+    fn test_templates_with_clustering_columns() {
+        let [_, select, delete] = templates(true);
+        let restriction = "WHERE enclosure_id = :enclosure_id AND \"Keeper Id\" = :\"Keeper Id\" \
+                           AND fed_at = :fed_at AND food_kind = :food_kind";
+        assert_eq!(
+            select,
+            format!("SELECT * FROM zoo_ks.feedings_v1 {}", restriction)
+        );
+        assert_eq!(
+            delete,
+            format!("DELETE FROM zoo_ks.feedings_v1 {}", restriction)
+        );
+    }
+
+    #[test]
+    fn test_templates_with_inline_primary_key() {
+        let input = "CREATE TABLE my_table (my_id int PRIMARY KEY, my_value text)";
+        let (_, statements) = parse_cql(input).unwrap();
+        let statements = resolve_references(statements, None).unwrap();
+        let table = statements[0].create_table().unwrap();
+        assert_eq!(
+            table.select_by_key_template(true),
+            "SELECT * FROM my_table WHERE my_id = :my_id"
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use crate::model::*;
+use derive_more::IsVariant;
+use derive_where::derive_where;
+use std::ops::Deref;
+
+/// A cql term, a literal as written in a statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/definitions.html#terms>
+///
+/// Constants keep their lexeme, they are only interpreted against a type, see
+/// [`CqlValue::from_term`].
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlTerm<I> {
+    /// A constant, like `42`, `'text'` or `NULL`.
+    Constant(CqlConstant<I>),
+    /// A list literal, `[1, 2]`.
+    List(Vec<CqlTerm<I>>),
+    /// A set literal, `{1, 2}`.
+    Set(Vec<CqlTerm<I>>),
+    /// A map literal, `{1: 'a', 2: 'b'}`.
+    Map(Vec<(CqlTerm<I>, CqlTerm<I>)>),
+    /// A tuple literal, `(1, 'a')`.
+    TupleLiteral(Vec<CqlTerm<I>>),
+    /// A user defined type literal, `{street: 'Main', number: 42}`.
+    UdtLiteral(Vec<(CqlIdentifier<I>, CqlTerm<I>)>),
+    /// A bind marker, its value is provided when the statement is executed.
+    BindMarker(CqlBindMarker<I>),
+    /// A function call, like `now()` or `token(42)`, evaluated when the statement is executed.
+    FunctionCall(CqlQualifiedIdentifier<I>, Vec<CqlTerm<I>>),
+}
+
+/// A constant of a term.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/definitions.html#constants>
+///
+/// Numbers keep their lexeme, so a `varint` or `decimal` beyond the range of `i64` and `f64`
+/// is not rounded.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlConstant<I> {
+    /// The `NULL` literal.
+    Null,
+    /// A string literal, with its escapes resolved.
+    String(String),
+    /// An integer literal, like `-42`.
+    Integer(I),
+    /// A floating point literal, like `1.5e3`, `NaN` or `-Infinity`.
+    Float(I),
+    /// A boolean literal.
+    Boolean(bool),
+    /// A uuid literal, like `5a1c395e-b41f-11e5-9f22-ba0be0483c18`.
+    Uuid(I),
+    /// A blob literal, like `0xCAFEBABE`.
+    Blob(I),
+    /// A duration literal, like `1h30m` or `P1DT2H`.
+    Duration(I),
+}
+
+impl<I> CqlConstant<I> {
+    /// A description of the kind of the constant, used in error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CqlConstant::Null => "null",
+            CqlConstant::String(_) => "string",
+            CqlConstant::Integer(_) => "integer",
+            CqlConstant::Float(_) => "float",
+            CqlConstant::Boolean(_) => "boolean",
+            CqlConstant::Uuid(_) => "uuid",
+            CqlConstant::Blob(_) => "blob",
+            CqlConstant::Duration(_) => "duration",
+        }
+    }
+}
+
+/// A bind marker of a prepared statement.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str>)]
+pub enum CqlBindMarker<I> {
+    /// A positional marker, `?`.
+    Positional,
+    /// A named marker, `:name`.
+    Named(CqlIdentifier<I>),
+}
+
+impl<I> CqlTerm<I> {
+    /// A description of the kind of the literal, used in error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CqlTerm::Constant(constant) => constant.kind(),
+            CqlTerm::List(_) => "list",
+            CqlTerm::Set(_) => "set",
+            CqlTerm::Map(_) => "map",
+            CqlTerm::TupleLiteral(_) => "tuple",
+            CqlTerm::UdtLiteral(_) => "user defined type literal",
+            CqlTerm::BindMarker(_) => "bind marker",
+            CqlTerm::FunctionCall(_, _) => "function call",
+        }
+    }
+
+    /// The bind markers of the term, also those nested in collections and function calls, in
+    /// the order they are written.
+    pub fn bind_markers(&self) -> impl Iterator<Item = &CqlBindMarker<I>> {
+        let mut terms = vec![self];
+        std::iter::from_fn(move || {
+            while let Some(term) = terms.pop() {
+                match term {
+                    CqlTerm::BindMarker(marker) => return Some(marker),
+                    CqlTerm::List(elements)
+                    | CqlTerm::Set(elements)
+                    | CqlTerm::TupleLiteral(elements)
+                    | CqlTerm::FunctionCall(_, elements) => terms.extend(elements.iter().rev()),
+                    CqlTerm::Map(entries) => {
+                        terms.extend(entries.iter().rev().flat_map(|(key, value)| [value, key]))
+                    }
+                    CqlTerm::UdtLiteral(fields) => {
+                        terms.extend(fields.iter().rev().map(|(_, term)| term))
+                    }
+                    _ => {}
+                }
+            }
+            None
+        })
+    }
+}
@@ -0,0 +1,85 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::Identifiable;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::{CopyGetters, Getters};
+use std::ops::Deref;
+
+/// The cql `CREATE TRIGGER` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/triggers.html#create-trigger>
+///
+/// Grammar:
+/// ```bnf
+/// create_trigger_statement::= CREATE TRIGGER [ IF NOT EXISTS ] trigger_name
+///         ON table_name
+///         USING string
+/// ```
+///
+/// Example:
+/// ```cql
+/// CREATE TRIGGER audit_trigger ON shop.orders USING 'org.mycompany.AuditTrigger';
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlCreateTrigger<I> {
+    /// If the trigger should only be created if it does not exist.
+    #[getset(get_copy = "pub")]
+    if_not_exists: bool,
+    /// The name of the trigger.
+    #[getset(get = "pub")]
+    name: CqlIdentifier<I>,
+    /// The table the trigger is created on.
+    #[getset(get = "pub")]
+    table: CqlQualifiedIdentifier<I>,
+    /// The class implementing the trigger, with its escapes resolved.
+    #[getset(get = "pub")]
+    class: String,
+}
+
+/// The cql `DROP TRIGGER` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/triggers.html#drop-trigger>
+///
+/// Grammar:
+/// ```bnf
+/// drop_trigger_statement::= DROP TRIGGER [ IF EXISTS ] trigger_name ON table_name
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlDropTrigger<I> {
+    /// If the trigger should only be dropped if it exists.
+    #[getset(get_copy = "pub")]
+    if_exists: bool,
+    /// The name of the trigger.
+    #[getset(get = "pub")]
+    name: CqlIdentifier<I>,
+    /// The table the trigger was created on.
+    #[getset(get = "pub")]
+    table: CqlQualifiedIdentifier<I>,
+}
+
+/// A trigger lives in the keyspace of its table.
+impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlCreateTrigger<I> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.table.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        &self.name
+    }
+}
+
+/// A trigger lives in the keyspace of its table.
+impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlDropTrigger<I> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.table.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        &self.name
+    }
+}
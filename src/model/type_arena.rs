@@ -0,0 +1,211 @@
+use crate::model::*;
+use derive_more::{IsVariant, Unwrap};
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// A reference to a type stored in a [`TypeArena`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct CqlTypeRef(u32);
+
+impl CqlTypeRef {
+    /// The position of the type in its arena.
+    #[inline(always)]
+    pub fn index(self) -> usize {
+        self.0 as usize
+    }
+}
+
+/// A type node of a [`TypeArena`], nested types are referenced by [`CqlTypeRef`].
+#[derive(Debug, Clone, PartialEq, IsVariant, Unwrap)]
+pub enum CqlTypeNode<UdtType> {
+    /// A type without nested types, either a native type or a user defined type.
+    Leaf(CqlType<UdtType>),
+    /// The frozen keyword is used to indicate that the type is immutable and can be used as a key in a map or set.
+    FROZEN(CqlTypeRef),
+    /// A map of keys to values.
+    MAP(CqlTypeRef, CqlTypeRef),
+    /// A set of values.
+    SET(CqlTypeRef),
+    /// A list of values.
+    LIST(CqlTypeRef),
+    /// The tuple type is used to indicate that the type is a tuple type.
+    TUPLE(Vec<CqlTypeRef>),
+    /// A vector of a fixed number of values, with its element type and dimension.
+    VECTOR(CqlTypeRef, u32),
+}
+
+/// An arena owning cql types.
+///
+/// Every nested [`CqlType`] allocates its own `Box`, the arena instead stores all nodes in a
+/// single vector and links them by index. This is an opt-in alternative for schemas with many
+/// deeply nested types, [`CqlType`] stays the default representation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeArena<UdtType> {
+    nodes: Vec<CqlTypeNode<UdtType>>,
+}
+
+impl<UdtType> Default for TypeArena<UdtType> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<UdtType> TypeArena<UdtType> {
+    /// Creates an empty arena.
+    pub fn new() -> Self {
+        Self { nodes: Vec::new() }
+    }
+
+    /// Creates an empty arena with space for `capacity` nodes.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            nodes: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// The number of nodes in the arena.
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Whether the arena contains no nodes.
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Adds a node to the arena.
+    ///
+    /// Nested references must point to nodes of this arena.
+    pub fn alloc(&mut self, node: CqlTypeNode<UdtType>) -> CqlTypeRef {
+        let index = self.nodes.len();
+        self.nodes.push(node);
+        CqlTypeRef(index as u32)
+    }
+
+    /// Returns the node of a reference.
+    ///
+    /// # Panics
+    /// Panics if the reference does not belong to this arena.
+    pub fn get(&self, reference: CqlTypeRef) -> &CqlTypeNode<UdtType> {
+        &self.nodes[reference.index()]
+    }
+
+    /// Drops all nodes allocated after the arena had `len` nodes.
+    pub(crate) fn truncate(&mut self, len: usize) {
+        self.nodes.truncate(len);
+    }
+
+    /// Moves a boxed type into the arena.
+    pub fn insert(&mut self, cql_type: CqlType<UdtType>) -> CqlTypeRef {
+        let node = match cql_type {
+            CqlType::FROZEN(inner) => CqlTypeNode::FROZEN(self.insert(*inner)),
+            CqlType::MAP(map) => {
+                let (key, value) = *map;
+                let key = self.insert(key);
+                let value = self.insert(value);
+                CqlTypeNode::MAP(key, value)
+            }
+            CqlType::SET(inner) => CqlTypeNode::SET(self.insert(*inner)),
+            CqlType::LIST(inner) => CqlTypeNode::LIST(self.insert(*inner)),
+            CqlType::TUPLE(types) => {
+                CqlTypeNode::TUPLE(types.into_iter().map(|ty| self.insert(ty)).collect())
+            }
+            CqlType::VECTOR(element, dimension) => {
+                CqlTypeNode::VECTOR(self.insert(*element), dimension)
+            }
+            leaf => CqlTypeNode::Leaf(leaf),
+        };
+
+        self.alloc(node)
+    }
+
+    /// Builds the boxed representation of a type of the arena.
+    pub fn to_boxed(&self, reference: CqlTypeRef) -> CqlType<UdtType>
+    where
+        UdtType: Clone,
+    {
+        match self.get(reference) {
+            CqlTypeNode::Leaf(leaf) => leaf.clone(),
+            CqlTypeNode::FROZEN(inner) => CqlType::FROZEN(Box::new(self.to_boxed(*inner))),
+            CqlTypeNode::MAP(key, value) => {
+                CqlType::MAP(Box::new((self.to_boxed(*key), self.to_boxed(*value))))
+            }
+            CqlTypeNode::SET(inner) => CqlType::SET(Box::new(self.to_boxed(*inner))),
+            CqlTypeNode::LIST(inner) => CqlType::LIST(Box::new(self.to_boxed(*inner))),
+            CqlTypeNode::TUPLE(types) => {
+                CqlType::TUPLE(types.iter().map(|ty| self.to_boxed(*ty)).collect())
+            }
+            CqlTypeNode::VECTOR(element, dimension) => {
+                CqlType::VECTOR(Box::new(self.to_boxed(*element)), *dimension)
+            }
+        }
+    }
+
+    /// Compares two types of the arena structurally.
+    pub fn type_eq(&self, left: CqlTypeRef, right: CqlTypeRef) -> bool
+    where
+        UdtType: PartialEq,
+    {
+        if left == right {
+            return true;
+        }
+
+        match (self.get(left), self.get(right)) {
+            (CqlTypeNode::Leaf(left), CqlTypeNode::Leaf(right)) => left == right,
+            (CqlTypeNode::FROZEN(left), CqlTypeNode::FROZEN(right))
+            | (CqlTypeNode::SET(left), CqlTypeNode::SET(right))
+            | (CqlTypeNode::LIST(left), CqlTypeNode::LIST(right)) => self.type_eq(*left, *right),
+            (CqlTypeNode::MAP(left_key, left_value), CqlTypeNode::MAP(right_key, right_value)) => {
+                self.type_eq(*left_key, *right_key) && self.type_eq(*left_value, *right_value)
+            }
+            (CqlTypeNode::TUPLE(left), CqlTypeNode::TUPLE(right)) => {
+                left.len() == right.len()
+                    && left
+                        .iter()
+                        .zip(right.iter())
+                        .all(|(left, right)| self.type_eq(*left, *right))
+            }
+            (
+                CqlTypeNode::VECTOR(left, left_dimension),
+                CqlTypeNode::VECTOR(right, right_dimension),
+            ) => left_dimension == right_dimension && self.type_eq(*left, *right),
+            _ => false,
+        }
+    }
+
+    /// Resolves the user defined types of all nodes against the already resolved statements.
+    ///
+    /// The node layout is kept, so all references into this arena stay valid for the result.
+    pub fn reference_types<I, Table, ResolvedUdtType>(
+        self,
+        keyspace: Option<&CqlIdentifier<I>>,
+        context: &[CqlStatement<I, Table, Rc<ResolvedUdtType>>],
+    ) -> Result<TypeArena<Rc<ResolvedUdtType>>, CqlQualifiedIdentifier<I>>
+    where
+        I: Deref<Target = str> + Clone,
+        UdtType: Identifiable<I>,
+        ResolvedUdtType: Identifiable<I>,
+    {
+        let nodes = self
+            .nodes
+            .into_iter()
+            .map(|node| {
+                Ok(match node {
+                    CqlTypeNode::Leaf(leaf) => {
+                        CqlTypeNode::Leaf(leaf.reference_types(keyspace, context)?)
+                    }
+                    CqlTypeNode::FROZEN(inner) => CqlTypeNode::FROZEN(inner),
+                    CqlTypeNode::MAP(key, value) => CqlTypeNode::MAP(key, value),
+                    CqlTypeNode::SET(inner) => CqlTypeNode::SET(inner),
+                    CqlTypeNode::LIST(inner) => CqlTypeNode::LIST(inner),
+                    CqlTypeNode::TUPLE(types) => CqlTypeNode::TUPLE(types),
+                    CqlTypeNode::VECTOR(element, dimension) => {
+                        CqlTypeNode::VECTOR(element, dimension)
+                    }
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(TypeArena { nodes })
+    }
+}
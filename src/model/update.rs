@@ -0,0 +1,165 @@
+use crate::model::condition::CqlCondition;
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::relation::CqlWhereClause;
+use crate::model::term::{CqlBindMarker, CqlTerm};
+use crate::model::using::CqlUsing;
+use crate::model::Identifiable;
+use derive_more::IsVariant;
+use derive_new::new;
+use derive_where::derive_where;
+use getset::Getters;
+use std::ops::Deref;
+
+/// The cql `UPDATE` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/dml.html#update-statement>
+///
+/// Grammar:
+/// ```bnf
+/// update_statement::= UPDATE table_name
+///         [ USING update_parameter ( AND update_parameter )* ]
+///         SET assignment ( ',' assignment )*
+///         WHERE where_clause
+///         [ IF ( EXISTS | condition ( AND condition )* ) ]
+/// assignment::= column_name '=' term
+///         | column_name '[' term ']' '=' term
+///         | column_name '.' field_name '=' term
+///         | column_name '=' column_name ( '+' | '-' ) term
+///         | column_name '=' term '+' column_name
+/// ```
+///
+/// Example:
+/// ```cql
+/// UPDATE page_views SET hits = hits + 1, misses = misses - ? WHERE url = '/';
+/// UPDATE users USING TTL 60 SET name = 'Alice', emails = emails + {'alice@example.com'}
+///     WHERE userid = 42 IF name = 'Al';
+/// ```
+#[derive(Debug, Clone, Getters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlUpdate<I> {
+    /// The table updated.
+    #[getset(get = "pub")]
+    table: CqlQualifiedIdentifier<I>,
+    /// The time to live and the timestamp of the written values.
+    #[getset(get = "pub")]
+    using: Option<CqlUsing<I>>,
+    /// The assignments of the columns.
+    #[getset(get = "pub")]
+    assignments: Vec<CqlAssignment<I>>,
+    /// The relations selecting the updated rows.
+    #[getset(get = "pub")]
+    where_clause: CqlWhereClause<I>,
+    /// The condition of a lightweight transaction.
+    #[getset(get = "pub")]
+    condition: Option<CqlCondition<I>>,
+}
+
+impl<I> CqlUpdate<I> {
+    /// The bind markers of the statement, in the order they are written.
+    pub fn bind_markers(&self) -> impl Iterator<Item = &CqlBindMarker<I>> {
+        self.using
+            .iter()
+            .flat_map(CqlUsing::bind_markers)
+            .chain(
+                self.assignments
+                    .iter()
+                    .flat_map(CqlAssignment::bind_markers),
+            )
+            .chain(self.where_clause.bind_markers())
+            .chain(self.condition.iter().flat_map(CqlCondition::bind_markers))
+    }
+
+    /// The assigned columns, in the order of their assignments.
+    pub fn assigned_columns(&self) -> impl Iterator<Item = &CqlIdentifier<I>> {
+        self.assignments.iter().map(CqlAssignment::column)
+    }
+}
+
+/// An assignment of the `SET` clause.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlAssignment<I> {
+    /// The column is set to the term, `name = 'Alice'`.
+    Column(CqlIdentifier<I>, CqlTerm<I>),
+    /// The element of a list or map column is set, `phones['home'] = '555'`.
+    Element(CqlIdentifier<I>, CqlTerm<I>, CqlTerm<I>),
+    /// The field of a user defined type column is set, `address.street = 'Main'`.
+    Field(CqlIdentifier<I>, CqlIdentifier<I>, CqlTerm<I>),
+    /// The elements are added to the end of the collection, `emails = emails + {'a@b.c'}`.
+    Append(CqlIdentifier<I>, CqlTerm<I>),
+    /// The elements are added to the start of the list, `tags = ['new'] + tags`.
+    Prepend(CqlIdentifier<I>, CqlTerm<I>),
+    /// The elements, or the keys of a map, are removed from the collection, `tags = tags - ['old']`.
+    Remove(CqlIdentifier<I>, CqlTerm<I>),
+    /// The counter is changed by an integer, `hits = hits + 1`.
+    CounterDelta(CqlIdentifier<I>, CqlCounterOperation, CqlTerm<I>),
+    /// The column is changed by a bind marker, `hits = hits + ?`.
+    ///
+    /// Whether a counter is changed or elements are added to or removed from a collection depends
+    /// on the type of the column, which is left to the schema.
+    BindMarkerDelta(CqlIdentifier<I>, CqlCounterOperation, CqlTerm<I>),
+}
+
+/// How a counter, or the column of a [`CqlAssignment::BindMarkerDelta`], is changed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, IsVariant)]
+pub enum CqlCounterOperation {
+    /// The delta is added, `+`.
+    Increment,
+    /// The delta is subtracted, `-`.
+    Decrement,
+}
+
+impl<I> CqlAssignment<I> {
+    /// The assigned column.
+    pub fn column(&self) -> &CqlIdentifier<I> {
+        match self {
+            CqlAssignment::Column(column, _)
+            | CqlAssignment::Element(column, _, _)
+            | CqlAssignment::Field(column, _, _)
+            | CqlAssignment::Append(column, _)
+            | CqlAssignment::Prepend(column, _)
+            | CqlAssignment::Remove(column, _)
+            | CqlAssignment::CounterDelta(column, _, _)
+            | CqlAssignment::BindMarkerDelta(column, _, _) => column,
+        }
+    }
+
+    /// The bind markers of the assignment, those of an element before those of the term.
+    pub fn bind_markers(&self) -> impl Iterator<Item = &CqlBindMarker<I>> {
+        let element = match self {
+            CqlAssignment::Element(_, element, _) => Some(element),
+            _ => None,
+        };
+        element
+            .into_iter()
+            .chain([self.term()])
+            .flat_map(CqlTerm::bind_markers)
+    }
+
+    /// The assigned term.
+    pub fn term(&self) -> &CqlTerm<I> {
+        match self {
+            CqlAssignment::Column(_, term)
+            | CqlAssignment::Element(_, _, term)
+            | CqlAssignment::Field(_, _, term)
+            | CqlAssignment::Append(_, term)
+            | CqlAssignment::Prepend(_, term)
+            | CqlAssignment::Remove(_, term)
+            | CqlAssignment::CounterDelta(_, _, term)
+            | CqlAssignment::BindMarkerDelta(_, _, term) => term,
+        }
+    }
+}
+
+/// An update is made in the keyspace of its table.
+impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlUpdate<I> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.table.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.table.name()
+    }
+}
@@ -3,12 +3,17 @@ use crate::model::identifier::CqlIdentifier;
 use crate::model::qualified_identifier::CqlQualifiedIdentifier;
 use crate::model::statement::CqlStatement;
 use crate::model::Identifiable;
+use derive_more::IsVariant;
 use derive_new::new;
 use derive_where::derive_where;
 use getset::{CopyGetters, Getters};
+use std::fmt::{Display, Formatter};
 use std::ops::Deref;
 use std::rc::Rc;
 
+/// A field of a user-defined type, its name and type.
+pub type CqlUserDefinedTypeField<I, UdtTypeRef> = (CqlIdentifier<I>, CqlType<UdtTypeRef>);
+
 /// User-defined type.
 /// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/types.html#user-defined-types>
 ///
@@ -33,24 +38,27 @@ use std::rc::Rc;
 ///    age int
 /// );
 /// ```
+///
+/// The name is wrapped in a [`Spanned`](crate::model::Spanned) by
+/// [`parse_cql_spanned`](crate::parse_cql_spanned) to keep its location.
 #[derive(Debug, Clone, Getters, CopyGetters, new)]
-#[derive_where(PartialEq; UdtTypeRef, I: std::ops::Deref<Target = str> + std::cmp::PartialEq)]
-pub struct ParsedCqlUserDefinedType<I, UdtTypeRef> {
+#[derive_where(PartialEq; UdtTypeRef, Name, I: std::ops::Deref<Target = str> + std::cmp::PartialEq)]
+pub struct ParsedCqlUserDefinedType<I, UdtTypeRef, Name = CqlQualifiedIdentifier<I>> {
     #[getset(get_copy = "pub")]
     if_not_exists: bool,
     /// The name of the user-defined type.
     #[getset(get = "pub")]
-    name: CqlQualifiedIdentifier<I>,
+    name: Name,
     /// The fields of the user-defined type.
     #[getset(get = "pub")]
-    fields: Vec<(CqlIdentifier<I>, CqlType<UdtTypeRef>)>,
+    fields: Vec<CqlUserDefinedTypeField<I, UdtTypeRef>>,
 }
 
-impl<I: Clone + Deref<Target = str>, UdtTypeRef> Identifiable<I>
-    for ParsedCqlUserDefinedType<I, UdtTypeRef>
+impl<I: Clone, UdtTypeRef, Name: Identifiable<I>> Identifiable<I>
+    for ParsedCqlUserDefinedType<I, UdtTypeRef, Name>
 {
     fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
-        self.name.keyspace().as_ref()
+        self.name.keyspace()
     }
 
     fn identifier(&self) -> &CqlIdentifier<I> {
@@ -58,11 +66,27 @@ impl<I: Clone + Deref<Target = str>, UdtTypeRef> Identifiable<I>
     }
 }
 
+impl<I, UdtTypeRef, Name> ParsedCqlUserDefinedType<I, UdtTypeRef, Name> {
+    /// The user defined types referenced by the fields, in order of occurrence and without
+    /// duplicates.
+    pub fn collect_udts(&self) -> Vec<&UdtTypeRef>
+    where
+        I: Clone + Deref<Target = str>,
+        UdtTypeRef: Identifiable<I>,
+    {
+        let mut udts = Vec::new();
+        for (_, cql_type) in &self.fields {
+            cql_type.extend_udts(&mut udts);
+        }
+        udts
+    }
+}
+
 impl<I, UdtTypeRef> ParsedCqlUserDefinedType<I, UdtTypeRef> {
     pub(crate) fn reference_types<Table>(
         self,
         keyspace: Option<&CqlIdentifier<I>>,
-        context: &Vec<CqlStatement<Table, Rc<CqlUserDefinedType<I>>>>,
+        context: &[CqlStatement<I, Table, Rc<CqlUserDefinedType<I>>>],
     ) -> Result<CqlUserDefinedType<I>, CqlQualifiedIdentifier<I>>
     where
         I: Deref<Target = str> + Clone,
@@ -78,17 +102,13 @@ impl<I, UdtTypeRef> ParsedCqlUserDefinedType<I, UdtTypeRef> {
                     .map(|cql_type| (name, cql_type))
             })
             .collect::<Result<Vec<_>, CqlQualifiedIdentifier<I>>>()?;
-        Ok(CqlUserDefinedType::new(
-            self.if_not_exists,
-            self.name,
-            fields,
-        ))
+        let name = self.name.contextualized_identifier(keyspace);
+        Ok(CqlUserDefinedType::new(self.if_not_exists, name, fields))
     }
 }
 
 /// User-defined type with resolved references.
 #[derive(Debug, Clone, Getters, CopyGetters, new)]
-#[derive_where(PartialEq; I: std::ops::Deref<Target = str> + std::cmp::PartialEq)]
 pub struct CqlUserDefinedType<I> {
     #[getset(get_copy = "pub")]
     if_not_exists: bool,
@@ -97,7 +117,39 @@ pub struct CqlUserDefinedType<I> {
     name: CqlQualifiedIdentifier<I>,
     /// The fields of the user-defined type.
     #[getset(get = "pub")]
-    fields: Vec<(CqlIdentifier<I>, CqlType<Rc<CqlUserDefinedType<I>>>)>,
+    fields: Vec<CqlUserDefinedTypeField<I, Rc<CqlUserDefinedType<I>>>>,
+}
+
+impl<I: Deref<Target = str> + PartialEq> PartialEq for CqlUserDefinedType<I> {
+    fn eq(&self, other: &Self) -> bool {
+        // Every usage of a resolved type points at the single `Rc` created for its definition.
+        std::ptr::eq(self, other)
+            || (self.if_not_exists == other.if_not_exists
+                && self.name == other.name
+                && self.fields == other.fields)
+    }
+}
+
+impl<I> CqlUserDefinedType<I> {
+    /// The user defined types referenced by the fields, in order of occurrence and without
+    /// duplicates. Their own references are found by calling this again.
+    pub fn collect_udts(&self) -> Vec<&Rc<CqlUserDefinedType<I>>>
+    where
+        I: Clone + Deref<Target = str>,
+    {
+        let mut udts = Vec::new();
+        for (_, cql_type) in &self.fields {
+            cql_type.extend_udts(&mut udts);
+        }
+        udts
+    }
+}
+
+/// Writes the qualified name of the type, as it is written where the type is used.
+impl<I: Deref<Target = str>> Display for CqlUserDefinedType<I> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
 }
 
 impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlUserDefinedType<I> {
@@ -111,3 +163,163 @@ impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlUserDefinedType<I> {
         self.name.identifier()
     }
 }
+
+/// The cql `ALTER TYPE` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/types.html#altering-a-udt>
+///
+/// Grammar:
+/// ```bnf
+/// alter_type_statement::= ALTER TYPE [ IF EXISTS ] udt_name alter_type_modification
+/// alter_type_modification::= ADD field_definition ( ',' field_definition )*
+///         | RENAME identifier TO identifier ( AND identifier TO identifier )*
+/// ```
+///
+/// Example:
+/// ```cql
+/// ALTER TYPE address ADD country text;
+///
+/// ALTER TYPE address RENAME zip TO zipcode;
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlAlterType<I> {
+    /// If the type should only be altered if it exists.
+    #[getset(get_copy = "pub")]
+    if_exists: bool,
+    /// The name of the user-defined type.
+    #[getset(get = "pub")]
+    name: CqlQualifiedIdentifier<I>,
+    /// The alteration of the fields.
+    #[getset(get = "pub")]
+    operation: CqlAlterTypeOperation<I>,
+}
+
+/// The alteration of the fields of a user-defined type.
+#[derive(Debug, Clone, IsVariant)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub enum CqlAlterTypeOperation<I> {
+    /// Adds fields to the type.
+    Add(Vec<(CqlIdentifier<I>, CqlType<CqlIdentifier<I>>)>),
+    /// Renames fields of the type, from the first to the second name.
+    Rename(Vec<(CqlIdentifier<I>, CqlIdentifier<I>)>),
+}
+
+impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlAlterType<I> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.name.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.name.identifier()
+    }
+}
+
+/// The cql `DROP TYPE` statement.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/types.html#dropping-a-udt>
+///
+/// Grammar:
+/// ```bnf
+/// drop_type_statement::= DROP TYPE [ IF EXISTS ] udt_name
+/// ```
+#[derive(Debug, Clone, Getters, CopyGetters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlDropType<I> {
+    /// If the type should only be dropped if it exists.
+    #[getset(get_copy = "pub")]
+    if_exists: bool,
+    /// The name of the user-defined type.
+    #[getset(get = "pub")]
+    name: CqlQualifiedIdentifier<I>,
+}
+
+impl<I: Clone + Deref<Target = str>> Identifiable<I> for CqlDropType<I> {
+    #[inline(always)]
+    fn keyspace(&self) -> Option<&CqlIdentifier<I>> {
+        self.name.keyspace().as_ref()
+    }
+
+    #[inline(always)]
+    fn identifier(&self) -> &CqlIdentifier<I> {
+        self.name.identifier()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    type ResolvedType = CqlType<Rc<CqlUserDefinedType<String>>>;
+
+    fn name() -> impl Strategy<Value = String> {
+        "[a-c]{1,2}"
+    }
+
+    fn cql_type() -> impl Strategy<Value = ResolvedType> {
+        let leaf = prop_oneof![Just(CqlType::INT), Just(CqlType::TEXT), Just(CqlType::UUID)];
+        leaf.prop_recursive(4, 32, 3, |inner| {
+            prop_oneof![
+                inner.clone().prop_map(|ty| CqlType::FROZEN(Box::new(ty))),
+                inner.clone().prop_map(|ty| CqlType::LIST(Box::new(ty))),
+                (inner.clone(), inner.clone())
+                    .prop_map(|(key, value)| CqlType::MAP(Box::new((key, value)))),
+                prop::collection::vec(inner.clone(), 1..3).prop_map(CqlType::TUPLE),
+                (name(), prop::collection::vec((name(), inner), 0..3)).prop_map(
+                    |(udt_name, fields)| {
+                        CqlType::UserDefined(Rc::new(CqlUserDefinedType::new(
+                            false,
+                            CqlQualifiedIdentifier::new(None, CqlIdentifier::new(udt_name)),
+                            fields
+                                .into_iter()
+                                .map(|(field, ty)| (CqlIdentifier::new(field), ty))
+                                .collect(),
+                        )))
+                    }
+                ),
+            ]
+        })
+    }
+
+    /// Rebuilds the type without sharing any `Rc` with the original.
+    fn deep_clone(ty: &ResolvedType) -> ResolvedType {
+        match ty {
+            CqlType::FROZEN(inner) => CqlType::FROZEN(Box::new(deep_clone(inner))),
+            CqlType::LIST(inner) => CqlType::LIST(Box::new(deep_clone(inner))),
+            CqlType::MAP(map) => CqlType::MAP(Box::new((deep_clone(&map.0), deep_clone(&map.1)))),
+            CqlType::TUPLE(types) => CqlType::TUPLE(types.iter().map(deep_clone).collect()),
+            CqlType::UserDefined(udt) => CqlType::UserDefined(Rc::new(CqlUserDefinedType::new(
+                udt.if_not_exists(),
+                udt.name().clone(),
+                udt.fields()
+                    .iter()
+                    .map(|(field, ty)| (field.clone(), deep_clone(ty)))
+                    .collect(),
+            ))),
+            ty => ty.clone(),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_eq_matches_structural_eq(left in cql_type(), right in cql_type()) {
+            // Generated names are lowercase and unquoted, so the debug output is structural.
+            prop_assert_eq!(left == right, format!("{:?}", left) == format!("{:?}", right));
+        }
+
+        #[test]
+        fn test_shared_and_deep_copies_are_equal(ty in cql_type()) {
+            let deep = deep_clone(&ty);
+            prop_assert_eq!(&ty, &ty.clone());
+            prop_assert_eq!(&ty, &deep);
+            prop_assert_eq!(&deep, &ty);
+        }
+
+        #[test]
+        fn test_shared_udt_with_different_wrapper(ty in cql_type()) {
+            let frozen = CqlType::FROZEN(Box::new(ty.clone()));
+            prop_assert_ne!(&frozen, &ty);
+        }
+    }
+}
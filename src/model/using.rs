@@ -0,0 +1,45 @@
+use crate::model::term::{CqlBindMarker, CqlTerm};
+use derive_new::new;
+use derive_where::derive_where;
+use getset::Getters;
+use std::ops::Deref;
+
+/// The `USING` clause of a statement modifying data.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/dml.html#update-parameters>
+///
+/// Grammar:
+/// ```bnf
+/// using_clause::= USING update_parameter ( AND update_parameter )*
+/// update_parameter::= ( TTL | TIMESTAMP ) ( integer | bind_marker )
+///         | TIMEOUT ( duration | bind_marker )
+/// ```
+///
+/// Example:
+/// ```cql
+/// USING TTL 86400 AND TIMESTAMP 1234567890
+/// USING TIMEOUT 5s
+/// ```
+#[derive(Debug, Clone, Getters, new)]
+#[derive_where(PartialEq; I: Deref<Target = str> + PartialEq)]
+pub struct CqlUsing<I> {
+    /// The seconds until the written values expire, `TTL`.
+    #[getset(get = "pub")]
+    ttl: Option<CqlTerm<I>>,
+    /// The microseconds since the unix epoch the values are written at, `TIMESTAMP`.
+    #[getset(get = "pub")]
+    timestamp: Option<CqlTerm<I>>,
+    /// How long the coordinator waits for the write, `TIMEOUT`, a ScyllaDB extension.
+    #[getset(get = "pub")]
+    timeout: Option<CqlTerm<I>>,
+}
+
+impl<I> CqlUsing<I> {
+    /// The bind markers of the parameters, those of the `TTL`, the `TIMESTAMP` and the `TIMEOUT`,
+    /// independent of the order they are written in.
+    pub fn bind_markers(&self) -> impl Iterator<Item = &CqlBindMarker<I>> {
+        [&self.ttl, &self.timestamp, &self.timeout]
+            .into_iter()
+            .flatten()
+            .flat_map(CqlTerm::bind_markers)
+    }
+}
@@ -0,0 +1,1290 @@
+use crate::error::CqlValueError;
+use crate::model::*;
+use derive_more::IsVariant;
+use derive_new::new;
+use getset::CopyGetters;
+use std::fmt::{Display, Formatter};
+use std::net::IpAddr;
+use std::ops::Deref;
+use std::rc::Rc;
+use std::str::FromStr;
+
+/// A cql duration, its components are independent of each other.
+/// More Information: <https://cassandra.apache.org/doc/latest/cassandra/cql/types.html#durations>
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, CopyGetters, new)]
+pub struct CqlDuration {
+    /// The months.
+    #[getset(get_copy = "pub")]
+    months: i32,
+    /// The days.
+    #[getset(get_copy = "pub")]
+    days: i32,
+    /// The nanoseconds.
+    #[getset(get_copy = "pub")]
+    nanoseconds: i64,
+}
+
+/// A typed cql value.
+///
+/// Created from a literal and the type it is assigned to, see [`CqlValue::from_term`].
+#[derive(Debug, Clone, PartialEq, IsVariant)]
+pub enum CqlValue {
+    /// The null value.
+    Null,
+    /// An `ascii`, `text` or `varchar` value.
+    Text(String),
+    /// A `tinyint` value.
+    TinyInt(i8),
+    /// A `smallint` value.
+    SmallInt(i16),
+    /// An `int` value.
+    Int(i32),
+    /// A `bigint` or `counter` value.
+    BigInt(i64),
+    /// A `varint` value, as its decimal digits.
+    Varint(String),
+    /// A `float` value.
+    Float(f32),
+    /// A `double` value.
+    Double(f64),
+    /// A `decimal` value, as written.
+    Decimal(String),
+    /// A `boolean` value.
+    Boolean(bool),
+    /// A `uuid` or `timeuuid` value.
+    Uuid(u128),
+    /// A `timestamp` value, in milliseconds since the unix epoch.
+    Timestamp(i64),
+    /// A `date` value, in days since the unix epoch.
+    Date(i32),
+    /// A `time` value, in nanoseconds since midnight.
+    Time(i64),
+    /// An `inet` value.
+    Inet(IpAddr),
+    /// A `blob` value.
+    Blob(Vec<u8>),
+    /// A `duration` value.
+    Duration(CqlDuration),
+    /// A `list` value.
+    List(Vec<CqlValue>),
+    /// A `set` value.
+    Set(Vec<CqlValue>),
+    /// A `map` value.
+    Map(Vec<(CqlValue, CqlValue)>),
+    /// A `tuple` value.
+    Tuple(Vec<CqlValue>),
+    /// A user defined type value, with all fields of the type in declaration order.
+    Udt(Vec<(String, CqlValue)>),
+}
+
+/// Defines an accessor returning the content of a variant.
+macro_rules! accessor {
+    ($(#[$doc:meta])* $name:ident, $variant:ident, $output:ty, |$value:ident| $map:expr) => {
+        $(#[$doc])*
+        pub fn $name(&self) -> Option<$output> {
+            match self {
+                CqlValue::$variant($value) => Some($map),
+                _ => None,
+            }
+        }
+    };
+}
+
+impl CqlValue {
+    /// Converts a literal into a value of the type.
+    ///
+    /// Fails if the literal can not be a value of the type, for example `'42'` is a `text`, but
+    /// not an `int`.
+    pub fn from_term<I: Deref<Target = str>>(
+        term: &CqlTerm<I>,
+        cql_type: &CqlType<Rc<CqlUserDefinedType<I>>>,
+    ) -> Result<Self, CqlValueError> {
+        let invalid = |literal: &str, reason: &'static str| CqlValueError::Invalid {
+            expected: cql_type.kind(),
+            literal: literal.to_string(),
+            reason,
+        };
+        let convert = |terms: &[CqlTerm<I>], cql_type| {
+            terms
+                .iter()
+                .map(|term| CqlValue::from_term(term, cql_type))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(match (cql_type, term) {
+            (_, CqlTerm::Constant(CqlConstant::Null)) => CqlValue::Null,
+            (CqlType::FROZEN(cql_type), term) => CqlValue::from_term(term, cql_type)?,
+            (CqlType::ASCII, CqlTerm::Constant(CqlConstant::String(text))) if !text.is_ascii() => {
+                return Err(invalid(text, "contains non-ascii characters"))
+            }
+            (
+                CqlType::ASCII | CqlType::TEXT | CqlType::VARCHAR,
+                CqlTerm::Constant(CqlConstant::String(text)),
+            ) => CqlValue::Text(text.clone()),
+            (CqlType::TINYINT, CqlTerm::Constant(CqlConstant::Integer(literal))) => {
+                CqlValue::TinyInt(integer(literal).map_err(|reason| invalid(literal, reason))?)
+            }
+            (CqlType::SMALLINT, CqlTerm::Constant(CqlConstant::Integer(literal))) => {
+                CqlValue::SmallInt(integer(literal).map_err(|reason| invalid(literal, reason))?)
+            }
+            (CqlType::INT, CqlTerm::Constant(CqlConstant::Integer(literal))) => {
+                CqlValue::Int(integer(literal).map_err(|reason| invalid(literal, reason))?)
+            }
+            (
+                CqlType::BIGINT | CqlType::COUNTER,
+                CqlTerm::Constant(CqlConstant::Integer(literal)),
+            ) => CqlValue::BigInt(integer(literal).map_err(|reason| invalid(literal, reason))?),
+            (CqlType::VARINT, CqlTerm::Constant(CqlConstant::Integer(literal))) => {
+                let digits = literal.strip_prefix('+').unwrap_or(literal);
+                let unsigned = digits.strip_prefix('-').unwrap_or(digits);
+                if unsigned.is_empty() || !unsigned.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(invalid(literal, "not an integer"));
+                }
+                CqlValue::Varint(digits.to_string())
+            }
+            (
+                CqlType::FLOAT,
+                CqlTerm::Constant(CqlConstant::Integer(literal))
+                | CqlTerm::Constant(CqlConstant::Float(literal)),
+            ) => CqlValue::Float(
+                literal
+                    .parse()
+                    .map_err(|_| invalid(literal, "not a number"))?,
+            ),
+            (
+                CqlType::DOUBLE,
+                CqlTerm::Constant(CqlConstant::Integer(literal))
+                | CqlTerm::Constant(CqlConstant::Float(literal)),
+            ) => CqlValue::Double(
+                literal
+                    .parse()
+                    .map_err(|_| invalid(literal, "not a number"))?,
+            ),
+            (
+                CqlType::DECIMAL,
+                CqlTerm::Constant(CqlConstant::Integer(literal))
+                | CqlTerm::Constant(CqlConstant::Float(literal)),
+            ) => {
+                // `NaN` and `Infinity` are the only literals with letters besides the exponent.
+                let finite = literal
+                    .bytes()
+                    .all(|b| !b.is_ascii_alphabetic() || b == b'e' || b == b'E');
+                if !finite || literal.parse::<f64>().is_err() {
+                    return Err(invalid(literal, "not a finite number"));
+                }
+                CqlValue::Decimal(literal.to_string())
+            }
+            (CqlType::BOOLEAN, CqlTerm::Constant(CqlConstant::Boolean(value))) => {
+                CqlValue::Boolean(*value)
+            }
+            (CqlType::UUID, CqlTerm::Constant(CqlConstant::Uuid(literal))) => {
+                CqlValue::Uuid(uuid(literal).ok_or_else(|| invalid(literal, "not a uuid"))?)
+            }
+            (CqlType::TIMEUUID, CqlTerm::Constant(CqlConstant::Uuid(literal))) => {
+                let uuid = uuid(literal).ok_or_else(|| invalid(literal, "not a uuid"))?;
+                if (uuid >> 76) & 0xf != 1 {
+                    return Err(invalid(literal, "not a version 1 uuid"));
+                }
+                CqlValue::Uuid(uuid)
+            }
+            (CqlType::TIMESTAMP, CqlTerm::Constant(CqlConstant::Integer(literal))) => {
+                CqlValue::Timestamp(integer(literal).map_err(|reason| invalid(literal, reason))?)
+            }
+            (CqlType::TIMESTAMP, CqlTerm::Constant(CqlConstant::String(text))) => {
+                CqlValue::Timestamp(
+                    timestamp(text).ok_or_else(|| invalid(text, "not a timestamp"))?,
+                )
+            }
+            (CqlType::DATE, CqlTerm::Constant(CqlConstant::Integer(literal))) => {
+                // Dates are unsigned days, with the epoch at 2^31.
+                let days: u32 = integer(literal).map_err(|reason| invalid(literal, reason))?;
+                CqlValue::Date((i64::from(days) - (1 << 31)) as i32)
+            }
+            (CqlType::DATE, CqlTerm::Constant(CqlConstant::String(text))) => {
+                CqlValue::Date(date(text).ok_or_else(|| invalid(text, "not a date"))?)
+            }
+            (CqlType::TIME, CqlTerm::Constant(CqlConstant::Integer(literal))) => {
+                let nanoseconds: i64 =
+                    integer(literal).map_err(|reason| invalid(literal, reason))?;
+                if !(0..NANOSECONDS_PER_DAY).contains(&nanoseconds) {
+                    return Err(invalid(literal, "out of range"));
+                }
+                CqlValue::Time(nanoseconds)
+            }
+            (CqlType::TIME, CqlTerm::Constant(CqlConstant::String(text))) => {
+                CqlValue::Time(time(text, true).ok_or_else(|| invalid(text, "not a time"))?)
+            }
+            (CqlType::INET, CqlTerm::Constant(CqlConstant::String(text))) => CqlValue::Inet(
+                text.parse()
+                    .map_err(|_| invalid(text, "not an ip address"))?,
+            ),
+            (CqlType::BLOB, CqlTerm::Constant(CqlConstant::Blob(literal))) => {
+                CqlValue::Blob(blob(literal).ok_or_else(|| invalid(literal, "not a blob"))?)
+            }
+            (CqlType::DURATION, CqlTerm::Constant(CqlConstant::Duration(literal))) => {
+                CqlValue::Duration(
+                    duration(literal).ok_or_else(|| invalid(literal, "not a duration"))?,
+                )
+            }
+            (CqlType::LIST(element), CqlTerm::List(elements)) => {
+                CqlValue::List(convert(elements, element)?)
+            }
+            (CqlType::SET(element), CqlTerm::Set(elements)) => {
+                CqlValue::Set(convert(elements, element)?)
+            }
+            (CqlType::VECTOR(element, dimension), CqlTerm::List(elements)) => {
+                if elements.len() != *dimension as usize {
+                    return Err(CqlValueError::VectorLength {
+                        expected: *dimension as usize,
+                        found: elements.len(),
+                    });
+                }
+                CqlValue::List(convert(elements, element)?)
+            }
+            // `{}` is both an empty set and an empty map.
+            (CqlType::SET(_), CqlTerm::Map(entries)) if entries.is_empty() => {
+                CqlValue::Set(Vec::new())
+            }
+            (CqlType::MAP(_), CqlTerm::Set(elements)) if elements.is_empty() => {
+                CqlValue::Map(Vec::new())
+            }
+            (CqlType::MAP(entry), CqlTerm::Map(entries)) => {
+                let (key, value) = entry.deref();
+                CqlValue::Map(
+                    entries
+                        .iter()
+                        .map(|(k, v)| {
+                            Ok((CqlValue::from_term(k, key)?, CqlValue::from_term(v, value)?))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+            }
+            (CqlType::TUPLE(types), CqlTerm::TupleLiteral(elements)) => {
+                if types.len() != elements.len() {
+                    return Err(CqlValueError::TupleLength {
+                        expected: types.len(),
+                        found: elements.len(),
+                    });
+                }
+                CqlValue::Tuple(
+                    elements
+                        .iter()
+                        .zip(types)
+                        .map(|(term, cql_type)| CqlValue::from_term(term, cql_type))
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+            }
+            (CqlType::UserDefined(udt), CqlTerm::UdtLiteral(fields)) => {
+                if let Some((unknown, _)) = fields
+                    .iter()
+                    .find(|(name, _)| !udt.fields().iter().any(|(field, _)| field == name))
+                {
+                    return Err(CqlValueError::UnknownField {
+                        field: unknown.to_string(),
+                    });
+                }
+                // Fields missing in the literal are null.
+                CqlValue::Udt(
+                    udt.fields()
+                        .iter()
+                        .map(|(field, cql_type)| {
+                            let value = match fields.iter().find(|(name, _)| name == field) {
+                                Some((_, term)) => CqlValue::from_term(term, cql_type)?,
+                                None => CqlValue::Null,
+                            };
+                            Ok((field.folded(), value))
+                        })
+                        .collect::<Result<Vec<_>, _>>()?,
+                )
+            }
+            (cql_type, term) => {
+                return Err(CqlValueError::Mismatch {
+                    expected: cql_type.kind(),
+                    found: term.kind(),
+                })
+            }
+        })
+    }
+
+    /// A description of the kind of the value, used in error messages.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CqlValue::Null => "null",
+            CqlValue::Text(_) => "text",
+            CqlValue::TinyInt(_) => "tinyint",
+            CqlValue::SmallInt(_) => "smallint",
+            CqlValue::Int(_) => "int",
+            CqlValue::BigInt(_) => "bigint",
+            CqlValue::Varint(_) => "varint",
+            CqlValue::Float(_) => "float",
+            CqlValue::Double(_) => "double",
+            CqlValue::Decimal(_) => "decimal",
+            CqlValue::Boolean(_) => "boolean",
+            CqlValue::Uuid(_) => "uuid",
+            CqlValue::Timestamp(_) => "timestamp",
+            CqlValue::Date(_) => "date",
+            CqlValue::Time(_) => "time",
+            CqlValue::Inet(_) => "inet",
+            CqlValue::Blob(_) => "blob",
+            CqlValue::Duration(_) => "duration",
+            CqlValue::List(_) => "list",
+            CqlValue::Set(_) => "set",
+            CqlValue::Map(_) => "map",
+            CqlValue::Tuple(_) => "tuple",
+            CqlValue::Udt(_) => "user defined type",
+        }
+    }
+
+    accessor!(
+        /// Returns the text value.
+        as_text, Text, &str, |text| text
+    );
+    accessor!(
+        /// Returns the tinyint value.
+        as_tinyint, TinyInt, i8, |value| *value
+    );
+    accessor!(
+        /// Returns the smallint value.
+        as_smallint, SmallInt, i16, |value| *value
+    );
+    accessor!(
+        /// Returns the int value.
+        as_int, Int, i32, |value| *value
+    );
+    accessor!(
+        /// Returns the bigint or counter value.
+        as_bigint, BigInt, i64, |value| *value
+    );
+    accessor!(
+        /// Returns the decimal digits of the varint value.
+        as_varint, Varint, &str, |digits| digits
+    );
+    accessor!(
+        /// Returns the float value.
+        as_float, Float, f32, |value| *value
+    );
+    accessor!(
+        /// Returns the double value.
+        as_double, Double, f64, |value| *value
+    );
+    accessor!(
+        /// Returns the decimal value, as written.
+        as_decimal, Decimal, &str, |literal| literal
+    );
+    accessor!(
+        /// Returns the boolean value.
+        as_boolean, Boolean, bool, |value| *value
+    );
+    accessor!(
+        /// Returns the uuid or timeuuid value.
+        as_uuid, Uuid, u128, |value| *value
+    );
+    accessor!(
+        /// Returns the timestamp value, in milliseconds since the unix epoch.
+        as_timestamp, Timestamp, i64, |value| *value
+    );
+    accessor!(
+        /// Returns the date value, in days since the unix epoch.
+        as_date, Date, i32, |value| *value
+    );
+    accessor!(
+        /// Returns the time value, in nanoseconds since midnight.
+        as_time, Time, i64, |value| *value
+    );
+    accessor!(
+        /// Returns the inet value.
+        as_inet, Inet, IpAddr, |value| *value
+    );
+    accessor!(
+        /// Returns the blob value.
+        as_blob, Blob, &[u8], |bytes| bytes
+    );
+    accessor!(
+        /// Returns the duration value.
+        as_duration, Duration, CqlDuration, |value| *value
+    );
+    accessor!(
+        /// Returns the elements of the list value.
+        as_list, List, &[CqlValue], |elements| elements
+    );
+    accessor!(
+        /// Returns the elements of the set value.
+        as_set, Set, &[CqlValue], |elements| elements
+    );
+    accessor!(
+        /// Returns the entries of the map value.
+        as_map, Map, &[(CqlValue, CqlValue)], |entries| entries
+    );
+    accessor!(
+        /// Returns the components of the tuple value.
+        as_tuple, Tuple, &[CqlValue], |components| components
+    );
+    accessor!(
+        /// Returns the fields of the user defined type value.
+        as_udt, Udt, &[(String, CqlValue)], |fields| fields
+    );
+
+    #[cfg(any(feature = "uuid", feature = "chrono", feature = "bigdecimal"))]
+    fn mismatch(&self, expected: &'static str) -> CqlValueError {
+        CqlValueError::Mismatch {
+            expected,
+            found: self.kind(),
+        }
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl TryFrom<&CqlValue> for uuid::Uuid {
+    type Error = CqlValueError;
+
+    fn try_from(value: &CqlValue) -> Result<Self, Self::Error> {
+        value
+            .as_uuid()
+            .map(uuid::Uuid::from_u128)
+            .ok_or_else(|| value.mismatch("uuid"))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<&CqlValue> for chrono::DateTime<chrono::Utc> {
+    type Error = CqlValueError;
+
+    fn try_from(value: &CqlValue) -> Result<Self, Self::Error> {
+        let milliseconds = value
+            .as_timestamp()
+            .ok_or_else(|| value.mismatch("timestamp"))?;
+        chrono::DateTime::from_timestamp_millis(milliseconds).ok_or(CqlValueError::Invalid {
+            expected: "timestamp",
+            literal: milliseconds.to_string(),
+            reason: "out of range",
+        })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<&CqlValue> for chrono::NaiveDate {
+    type Error = CqlValueError;
+
+    fn try_from(value: &CqlValue) -> Result<Self, Self::Error> {
+        /// The days from the first of January of year one to the unix epoch.
+        const UNIX_EPOCH_FROM_CE: i32 = 719_163;
+
+        let days = value.as_date().ok_or_else(|| value.mismatch("date"))?;
+        days.checked_add(UNIX_EPOCH_FROM_CE)
+            .and_then(chrono::NaiveDate::from_num_days_from_ce_opt)
+            .ok_or(CqlValueError::Invalid {
+                expected: "date",
+                literal: days.to_string(),
+                reason: "out of range",
+            })
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl TryFrom<&CqlValue> for chrono::NaiveTime {
+    type Error = CqlValueError;
+
+    fn try_from(value: &CqlValue) -> Result<Self, Self::Error> {
+        let nanoseconds = value.as_time().ok_or_else(|| value.mismatch("time"))?;
+        chrono::NaiveTime::from_num_seconds_from_midnight_opt(
+            (nanoseconds / 1_000_000_000) as u32,
+            (nanoseconds % 1_000_000_000) as u32,
+        )
+        .ok_or(CqlValueError::Invalid {
+            expected: "time",
+            literal: nanoseconds.to_string(),
+            reason: "out of range",
+        })
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+impl TryFrom<&CqlValue> for bigdecimal::BigDecimal {
+    type Error = CqlValueError;
+
+    fn try_from(value: &CqlValue) -> Result<Self, Self::Error> {
+        match value {
+            CqlValue::TinyInt(value) => Ok((*value).into()),
+            CqlValue::SmallInt(value) => Ok((*value).into()),
+            CqlValue::Int(value) => Ok((*value).into()),
+            CqlValue::BigInt(value) => Ok((*value).into()),
+            CqlValue::Varint(literal) | CqlValue::Decimal(literal) => {
+                literal.parse().map_err(|_| CqlValueError::Invalid {
+                    expected: "decimal",
+                    literal: literal.clone(),
+                    reason: "not a decimal",
+                })
+            }
+            _ => Err(value.mismatch("decimal")),
+        }
+    }
+}
+
+impl Display for CqlDuration {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        // The components of a duration share their sign.
+        if self.months < 0 || self.days < 0 || self.nanoseconds < 0 {
+            f.write_str("-")?;
+        }
+        if self.months == 0 && self.days == 0 && self.nanoseconds == 0 {
+            return f.write_str("0ns");
+        }
+        if self.months != 0 {
+            write!(f, "{}mo", self.months.unsigned_abs())?;
+        }
+        if self.days != 0 {
+            write!(f, "{}d", self.days.unsigned_abs())?;
+        }
+        if self.nanoseconds != 0 {
+            write!(f, "{}ns", self.nanoseconds.unsigned_abs())?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Renders the value as a cql literal.
+impl Display for CqlValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        fn write_list(
+            f: &mut Formatter<'_>,
+            values: &[CqlValue],
+            open: &str,
+            close: &str,
+        ) -> std::fmt::Result {
+            f.write_str(open)?;
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ")?;
+                }
+                write!(f, "{}", value)?;
+            }
+            f.write_str(close)
+        }
+
+        fn write_float(f: &mut Formatter<'_>, value: f64) -> std::fmt::Result {
+            match value {
+                value if value.is_nan() => f.write_str("NaN"),
+                value if value.is_infinite() && value > 0.0 => f.write_str("Infinity"),
+                value if value.is_infinite() => f.write_str("-Infinity"),
+                // Debug keeps the decimal point, so the literal stays a float.
+                value => write!(f, "{:?}", value),
+            }
+        }
+
+        match self {
+            CqlValue::Null => f.write_str("null"),
+            CqlValue::Text(text) => write!(f, "'{}'", text.replace('\'', "''")),
+            CqlValue::TinyInt(value) => write!(f, "{}", value),
+            CqlValue::SmallInt(value) => write!(f, "{}", value),
+            CqlValue::Int(value) => write!(f, "{}", value),
+            CqlValue::BigInt(value) => write!(f, "{}", value),
+            CqlValue::Varint(digits) => f.write_str(digits),
+            CqlValue::Float(value) => write_float(f, f64::from(*value)),
+            CqlValue::Double(value) => write_float(f, *value),
+            CqlValue::Decimal(literal) => f.write_str(literal),
+            CqlValue::Boolean(value) => write!(f, "{}", value),
+            CqlValue::Uuid(value) => {
+                let hex = format!("{:032x}", value);
+                write!(
+                    f,
+                    "{}-{}-{}-{}-{}",
+                    &hex[..8],
+                    &hex[8..12],
+                    &hex[12..16],
+                    &hex[16..20],
+                    &hex[20..]
+                )
+            }
+            CqlValue::Timestamp(milliseconds) => write!(f, "{}", milliseconds),
+            CqlValue::Date(days) => write!(f, "{}", i64::from(*days) + (1 << 31)),
+            CqlValue::Time(nanoseconds) => write!(f, "{}", nanoseconds),
+            CqlValue::Inet(address) => write!(f, "'{}'", address),
+            CqlValue::Blob(bytes) => {
+                f.write_str("0x")?;
+                bytes.iter().try_for_each(|byte| write!(f, "{:02x}", byte))
+            }
+            CqlValue::Duration(duration) => write!(f, "{}", duration),
+            CqlValue::List(elements) => write_list(f, elements, "[", "]"),
+            CqlValue::Set(elements) => write_list(f, elements, "{", "}"),
+            CqlValue::Tuple(elements) => write_list(f, elements, "(", ")"),
+            CqlValue::Map(entries) => {
+                f.write_str("{")?;
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}: {}", key, value)?;
+                }
+                f.write_str("}")
+            }
+            CqlValue::Udt(fields) => {
+                f.write_str("{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    let unquoted = name.starts_with(|c: char| c.is_ascii_lowercase())
+                        && name
+                            .chars()
+                            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+                    if unquoted {
+                        write!(f, "{}: {}", CqlIdentifier::new(name.as_str()), value)?;
+                    } else {
+                        let name = CqlIdentifier::<&str>::new_quoted(name.clone());
+                        write!(f, "{}: {}", name, value)?;
+                    }
+                }
+                f.write_str("}")
+            }
+        }
+    }
+}
+
+const NANOSECONDS_PER_DAY: i64 = 86_400 * 1_000_000_000;
+
+/// Parses an integer literal, distinguishing malformed from out of range literals.
+fn integer<T: FromStr>(literal: &str) -> Result<T, &'static str> {
+    literal.parse().map_err(|_| {
+        let digits = literal.trim_start_matches(['+', '-']);
+        if !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) {
+            "out of range"
+        } else {
+            "not an integer"
+        }
+    })
+}
+
+/// Parses the digits of a fixed width number.
+fn digits(input: &str) -> Option<i64> {
+    if input.is_empty() || !input.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    input.parse().ok()
+}
+
+/// Parses `xxxxxxxx-xxxx-xxxx-xxxx-xxxxxxxxxxxx`.
+pub(crate) fn uuid(literal: &str) -> Option<u128> {
+    let bytes = literal.as_bytes();
+    let shape = bytes.len() == 36
+        && bytes.iter().enumerate().all(|(i, b)| match i {
+            8 | 13 | 18 | 23 => *b == b'-',
+            _ => b.is_ascii_hexdigit(),
+        });
+    if !shape {
+        return None;
+    }
+
+    u128::from_str_radix(&literal.replace('-', ""), 16).ok()
+}
+
+/// Parses `0x` followed by pairs of hex digits.
+fn blob(literal: &str) -> Option<Vec<u8>> {
+    let hex = literal
+        .strip_prefix("0x")
+        .or_else(|| literal.strip_prefix("0X"))?;
+    if hex.len() % 2 != 0 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
+
+/// Parses `yyyy-mm-dd` into days since the unix epoch.
+fn date(input: &str) -> Option<i32> {
+    let bytes = input.as_bytes();
+    if bytes.len() != 10 || bytes[4] != b'-' || bytes[7] != b'-' {
+        return None;
+    }
+    let year = digits(&input[..4])?;
+    let month = digits(&input[5..7])?;
+    let day = digits(&input[8..])?;
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if leap => 29,
+        2 => 28,
+        _ => return None,
+    };
+    if !(1..=days_in_month).contains(&day) {
+        return None;
+    }
+
+    // Days from civil, with years starting in March so the leap day is the last day.
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+    Some((era * 146_097 + day_of_era - 719_468) as i32)
+}
+
+/// Parses `hh:mm[:ss[.fffffffff]]` into nanoseconds since midnight.
+fn time(input: &str, seconds_required: bool) -> Option<i64> {
+    let (input, fraction) = match input.split_once('.') {
+        Some((input, fraction)) if fraction.len() <= 9 => (input, Some(fraction)),
+        Some(_) => return None,
+        None => (input, None),
+    };
+    let mut parts = input.split(':');
+    let hours = parts.next().filter(|p| p.len() == 2).and_then(digits)?;
+    let minutes = parts.next().filter(|p| p.len() == 2).and_then(digits)?;
+    let seconds = match parts.next() {
+        Some(seconds) if seconds.len() == 2 => digits(seconds)?,
+        None if !seconds_required && fraction.is_none() => 0,
+        _ => return None,
+    };
+    if parts.next().is_some() || hours > 23 || minutes > 59 || seconds > 59 {
+        return None;
+    }
+    let fraction = match fraction {
+        Some(fraction) => digits(fraction)? * 10_i64.pow(9 - fraction.len() as u32),
+        None => 0,
+    };
+
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1_000_000_000 + fraction)
+}
+
+/// Parses `yyyy-mm-dd[( |T)hh:mm[:ss[.fff]]][Z|(+|-)hh[:]mm]` into milliseconds since the unix
+/// epoch.
+fn timestamp(input: &str) -> Option<i64> {
+    let days = date(input.get(..10)?)?;
+    let mut milliseconds = i64::from(days) * 86_400_000;
+    let mut zone = &input[10..];
+    if let Some(rest) = zone.strip_prefix([' ', 'T']) {
+        let end = rest.find(['Z', 'z', '+', '-']).unwrap_or(rest.len());
+        milliseconds += time(&rest[..end], false)? / 1_000_000;
+        zone = &rest[end..];
+    }
+    let (sign, offset) = match zone {
+        "" | "Z" | "z" => return Some(milliseconds),
+        _ => match zone.split_at(1) {
+            ("+", offset) => (1, offset),
+            ("-", offset) => (-1, offset),
+            _ => return None,
+        },
+    };
+    let offset = offset.replacen(':', "", 1);
+    if offset.len() != 4 {
+        return None;
+    }
+    let minutes = digits(&offset[..2])? * 60 + digits(&offset[2..])?;
+
+    Some(milliseconds - sign * minutes * 60_000)
+}
+
+/// Parses a duration, either in units like `1h30m` or in the ISO 8601 format like `PT1H30M`.
+fn duration(literal: &str) -> Option<CqlDuration> {
+    let (negative, input) = match literal.strip_prefix('-') {
+        Some(input) => (true, input),
+        None => (false, literal),
+    };
+    let (mut months, mut days, mut nanoseconds) = (0_i64, 0_i64, 0_i64);
+    let mut add = |unit: &str, amount: i64, time: bool| -> Option<()> {
+        let (total, factor) = match (unit, time) {
+            ("y", _) | ("Y", false) => (&mut months, 12),
+            ("mo", _) | ("M", false) => (&mut months, 1),
+            ("w", _) | ("W", false) => (&mut days, 7),
+            ("d", _) | ("D", false) => (&mut days, 1),
+            ("h", _) | ("H", true) => (&mut nanoseconds, 3_600_000_000_000),
+            ("m", _) | ("M", true) => (&mut nanoseconds, 60_000_000_000),
+            ("s", _) | ("S", true) => (&mut nanoseconds, 1_000_000_000),
+            ("ms", _) => (&mut nanoseconds, 1_000_000),
+            ("us" | "µs", _) => (&mut nanoseconds, 1_000),
+            ("ns", _) => (&mut nanoseconds, 1),
+            _ => return None,
+        };
+        *total = total.checked_add(amount.checked_mul(factor)?)?;
+        Some(())
+    };
+
+    if let Some(mut input) = input.strip_prefix('P') {
+        let mut time = false;
+        if input.is_empty() {
+            return None;
+        }
+        while !input.is_empty() {
+            if let Some(rest) = input.strip_prefix('T').filter(|_| !time) {
+                time = true;
+                input = rest;
+                if input.is_empty() {
+                    return None;
+                }
+                continue;
+            }
+            let end = input.find(|c: char| !c.is_ascii_digit())?;
+            let amount = digits(&input[..end])?;
+            let unit = input[end..].chars().next()?;
+            add(&unit.to_string(), amount, time)?;
+            input = &input[end + 1..];
+        }
+    } else {
+        let mut input = input;
+        if input.is_empty() {
+            return None;
+        }
+        while !input.is_empty() {
+            let end = input.find(|c: char| !c.is_ascii_digit())?;
+            let amount = digits(&input[..end])?;
+            let unit_end = input[end..]
+                .find(|c: char| c.is_ascii_digit())
+                .map(|unit_end| end + unit_end)
+                .unwrap_or(input.len());
+            add(&input[end..unit_end].to_lowercase(), amount, false)?;
+            input = &input[unit_end..];
+        }
+    }
+
+    let sign = if negative { -1 } else { 1 };
+    Some(CqlDuration::new(
+        i32::try_from(sign * months).ok()?,
+        i32::try_from(sign * days).ok()?,
+        sign * nanoseconds,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    type Type = CqlType<Rc<CqlUserDefinedType<&'static str>>>;
+
+    fn value(term: CqlTerm<&'static str>, cql_type: Type) -> Result<CqlValue, CqlValueError> {
+        CqlValue::from_term(&term, &cql_type)
+    }
+
+    fn text(text: &str) -> CqlTerm<&'static str> {
+        CqlTerm::Constant(CqlConstant::String(text.to_string()))
+    }
+
+    #[test]
+    fn test_scalars() {
+        assert_eq!(
+            value(text("hello"), CqlType::TEXT),
+            Ok(CqlValue::Text("hello".to_string()))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Integer("-7")),
+                CqlType::TINYINT
+            ),
+            Ok(CqlValue::TinyInt(-7))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Integer("300")),
+                CqlType::SMALLINT
+            ),
+            Ok(CqlValue::SmallInt(300))
+        );
+        assert_eq!(
+            value(CqlTerm::Constant(CqlConstant::Integer("42")), CqlType::INT),
+            Ok(CqlValue::Int(42))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Integer("9000000000")),
+                CqlType::BIGINT
+            ),
+            Ok(CqlValue::BigInt(9_000_000_000))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Integer("1")),
+                CqlType::COUNTER
+            ),
+            Ok(CqlValue::BigInt(1))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Integer("123456789012345678901234567890")),
+                CqlType::VARINT
+            ),
+            Ok(CqlValue::Varint(
+                "123456789012345678901234567890".to_string()
+            ))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Float("1.5e3")),
+                CqlType::FLOAT
+            ),
+            Ok(CqlValue::Float(1500.0))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Integer("2")),
+                CqlType::DOUBLE
+            ),
+            Ok(CqlValue::Double(2.0))
+        );
+        assert!(value(
+            CqlTerm::Constant(CqlConstant::Float("NaN")),
+            CqlType::DOUBLE
+        )
+        .unwrap()
+        .as_double()
+        .unwrap()
+        .is_nan());
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Float("-Infinity")),
+                CqlType::DOUBLE
+            ),
+            Ok(CqlValue::Double(f64::NEG_INFINITY))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Float("0.1")),
+                CqlType::DECIMAL
+            ),
+            Ok(CqlValue::Decimal("0.1".to_string()))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Boolean(true)),
+                CqlType::BOOLEAN
+            ),
+            Ok(CqlValue::Boolean(true))
+        );
+        assert_eq!(
+            value(CqlTerm::Constant(CqlConstant::Null), CqlType::INT),
+            Ok(CqlValue::Null)
+        );
+    }
+
+    #[test]
+    fn test_uuids() {
+        let literal = "5a1c395e-b41f-11e5-9f22-ba0be0483c18";
+        let expected = CqlValue::Uuid(0x5a1c395e_b41f_11e5_9f22_ba0be0483c18);
+        assert_eq!(
+            value(CqlTerm::Constant(CqlConstant::Uuid(literal)), CqlType::UUID),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Uuid(literal)),
+                CqlType::TIMEUUID
+            ),
+            Ok(expected)
+        );
+        assert!(value(
+            CqlTerm::Constant(CqlConstant::Uuid("5a1c395e-b41f-41e5-9f22-ba0be0483c18")),
+            CqlType::TIMEUUID
+        )
+        .unwrap_err()
+        .is_invalid());
+    }
+
+    #[test]
+    fn test_dates_and_times() {
+        assert_eq!(
+            value(text("2024-02-29 12:30:00.5+01:00"), CqlType::TIMESTAMP),
+            Ok(CqlValue::Timestamp(1_709_206_200_500))
+        );
+        assert_eq!(
+            value(text("1970-01-01T00:00Z"), CqlType::TIMESTAMP),
+            Ok(CqlValue::Timestamp(0))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Integer("1000")),
+                CqlType::TIMESTAMP
+            ),
+            Ok(CqlValue::Timestamp(1000))
+        );
+        assert_eq!(
+            value(text("1969-12-31"), CqlType::DATE),
+            Ok(CqlValue::Date(-1))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Integer("2147483648")),
+                CqlType::DATE
+            ),
+            Ok(CqlValue::Date(0))
+        );
+        assert_eq!(
+            value(text("01:02:03.000000004"), CqlType::TIME),
+            Ok(CqlValue::Time(3_723_000_000_004))
+        );
+        assert!(value(text("2023-02-29"), CqlType::DATE).is_err());
+        assert!(value(text("24:00:00"), CqlType::TIME).is_err());
+    }
+
+    #[test]
+    fn test_inet_blob_duration() {
+        assert_eq!(
+            value(text("::1"), CqlType::INET),
+            Ok(CqlValue::Inet("::1".parse().unwrap()))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Blob("0xCAFEbabe")),
+                CqlType::BLOB
+            ),
+            Ok(CqlValue::Blob(vec![0xca, 0xfe, 0xba, 0xbe]))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Duration("1y2mo3w4d5h6m7s8ms9us10ns")),
+                CqlType::DURATION
+            ),
+            Ok(CqlValue::Duration(CqlDuration::new(
+                14,
+                25,
+                5 * 3_600_000_000_000 + 6 * 60_000_000_000 + 7_008_009_010
+            )))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Duration("-P1MT2H")),
+                CqlType::DURATION
+            ),
+            Ok(CqlValue::Duration(CqlDuration::new(
+                -1,
+                0,
+                -2 * 3_600_000_000_000
+            )))
+        );
+        assert!(value(CqlTerm::Constant(CqlConstant::Blob("0xABC")), CqlType::BLOB).is_err());
+    }
+
+    #[test]
+    fn test_collections() {
+        let list = CqlType::LIST(Box::new(CqlType::INT));
+        assert_eq!(
+            value(
+                CqlTerm::List(vec![
+                    CqlTerm::Constant(CqlConstant::Integer("1")),
+                    CqlTerm::Constant(CqlConstant::Integer("2"))
+                ]),
+                list
+            ),
+            Ok(CqlValue::List(vec![CqlValue::Int(1), CqlValue::Int(2)]))
+        );
+        let set = CqlType::FROZEN(Box::new(CqlType::SET(Box::new(CqlType::TEXT))));
+        assert_eq!(
+            value(CqlTerm::Set(vec![text("a")]), set.clone()),
+            Ok(CqlValue::Set(vec![CqlValue::Text("a".to_string())]))
+        );
+        assert_eq!(value(CqlTerm::Map(vec![]), set), Ok(CqlValue::Set(vec![])));
+        let map = CqlType::MAP(Box::new((CqlType::TEXT, CqlType::BIGINT)));
+        assert_eq!(
+            value(
+                CqlTerm::Map(vec![(
+                    text("k"),
+                    CqlTerm::Constant(CqlConstant::Integer("3"))
+                )]),
+                map
+            ),
+            Ok(CqlValue::Map(vec![(
+                CqlValue::Text("k".to_string()),
+                CqlValue::BigInt(3)
+            )]))
+        );
+        let tuple = CqlType::TUPLE(vec![CqlType::INT, CqlType::TEXT]);
+        assert_eq!(
+            value(
+                CqlTerm::TupleLiteral(vec![
+                    CqlTerm::Constant(CqlConstant::Integer("1")),
+                    CqlTerm::Constant(CqlConstant::Null)
+                ]),
+                tuple.clone()
+            ),
+            Ok(CqlValue::Tuple(vec![CqlValue::Int(1), CqlValue::Null]))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::TupleLiteral(vec![CqlTerm::Constant(CqlConstant::Integer("1"))]),
+                tuple
+            ),
+            Err(CqlValueError::TupleLength {
+                expected: 2,
+                found: 1
+            })
+        );
+        let vector = CqlType::VECTOR(Box::new(CqlType::FLOAT), 2);
+        assert_eq!(
+            value(
+                CqlTerm::List(vec![
+                    CqlTerm::Constant(CqlConstant::Float("0.5")),
+                    CqlTerm::Constant(CqlConstant::Integer("1"))
+                ]),
+                vector.clone()
+            ),
+            Ok(CqlValue::List(vec![
+                CqlValue::Float(0.5),
+                CqlValue::Float(1.0)
+            ]))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::List(vec![CqlTerm::Constant(CqlConstant::Float("0.5"))]),
+                vector
+            ),
+            Err(CqlValueError::VectorLength {
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_udt() {
+        let udt = Rc::new(CqlUserDefinedType::new(
+            false,
+            CqlQualifiedIdentifier::new(None, CqlIdentifier::new("address_type")),
+            vec![
+                (CqlIdentifier::new("street"), CqlType::TEXT),
+                (CqlIdentifier::new("number"), CqlType::INT),
+            ],
+        ));
+        assert_eq!(
+            value(
+                CqlTerm::UdtLiteral(vec![(
+                    CqlIdentifier::new("number"),
+                    CqlTerm::Constant(CqlConstant::Integer("42"))
+                )]),
+                CqlType::UserDefined(udt.clone())
+            ),
+            Ok(CqlValue::Udt(vec![
+                ("street".to_string(), CqlValue::Null),
+                ("number".to_string(), CqlValue::Int(42)),
+            ]))
+        );
+        assert_eq!(
+            value(
+                CqlTerm::UdtLiteral(vec![(CqlIdentifier::new("zip"), text("1234"))]),
+                CqlType::UserDefined(udt)
+            ),
+            Err(CqlValueError::UnknownField {
+                field: "zip".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn test_mismatches() {
+        assert_eq!(
+            value(text("42"), CqlType::INT),
+            Err(CqlValueError::Mismatch {
+                expected: "int",
+                found: "string"
+            })
+        );
+        assert_eq!(
+            value(
+                CqlTerm::Constant(CqlConstant::Integer("128")),
+                CqlType::TINYINT
+            ),
+            Err(CqlValueError::Invalid {
+                expected: "tinyint",
+                literal: "128".to_string(),
+                reason: "out of range"
+            })
+        );
+        assert!(value(text("héllo"), CqlType::ASCII)
+            .unwrap_err()
+            .is_invalid());
+        assert!(value(text("not an ip"), CqlType::INET)
+            .unwrap_err()
+            .is_invalid());
+        assert!(value(
+            CqlTerm::Constant(CqlConstant::Float("NaN")),
+            CqlType::DECIMAL
+        )
+        .is_err());
+        assert!(
+            value(CqlTerm::Constant(CqlConstant::Integer("1")), CqlType::TEXT)
+                .unwrap_err()
+                .is_mismatch()
+        );
+        assert!(value(
+            CqlTerm::List(vec![text("a")]),
+            CqlType::SET(Box::new(CqlType::TEXT))
+        )
+        .unwrap_err()
+        .is_mismatch());
+    }
+
+    #[test]
+    fn test_accessors() {
+        let value = CqlValue::Int(1);
+        assert_eq!(value.as_int(), Some(1));
+        assert_eq!(value.as_bigint(), None);
+        assert_eq!(CqlValue::Text("a".to_string()).as_text(), Some("a"));
+        assert_eq!(CqlValue::Blob(vec![1]).as_blob(), Some(&[1][..]));
+    }
+
+    #[cfg(feature = "uuid")]
+    #[test]
+    fn test_uuid_conversion() {
+        let value = CqlValue::Uuid(0x5a1c395e_b41f_11e5_9f22_ba0be0483c18);
+        assert_eq!(
+            uuid::Uuid::try_from(&value).unwrap().to_string(),
+            "5a1c395e-b41f-11e5-9f22-ba0be0483c18"
+        );
+        assert!(uuid::Uuid::try_from(&CqlValue::Int(1)).is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_conversion() {
+        let timestamp = chrono::DateTime::<chrono::Utc>::try_from(&CqlValue::Timestamp(1000));
+        assert_eq!(timestamp.unwrap().timestamp_millis(), 1000);
+        assert_eq!(
+            chrono::NaiveDate::try_from(&CqlValue::Date(-1)).unwrap(),
+            chrono::NaiveDate::from_ymd_opt(1969, 12, 31).unwrap()
+        );
+        assert_eq!(
+            chrono::NaiveTime::try_from(&CqlValue::Time(3_723_000_000_004)).unwrap(),
+            chrono::NaiveTime::from_hms_nano_opt(1, 2, 3, 4).unwrap()
+        );
+    }
+
+    #[cfg(feature = "bigdecimal")]
+    #[test]
+    fn test_bigdecimal_conversion() {
+        let decimal = bigdecimal::BigDecimal::try_from(&CqlValue::Decimal("0.1".to_string()));
+        assert_eq!(decimal.unwrap().to_string(), "0.1");
+        assert!(bigdecimal::BigDecimal::try_from(&CqlValue::Boolean(true)).is_err());
+    }
+
+    #[test]
+    fn test_display() {
+        let values = [
+            (CqlValue::Text("it's".to_string()), "'it''s'"),
+            (CqlValue::Double(2.0), "2.0"),
+            (CqlValue::Float(f32::NEG_INFINITY), "-Infinity"),
+            (
+                CqlValue::Uuid(0x5a1c395e_b41f_11e5_9f22_ba0be0483c18),
+                "5a1c395e-b41f-11e5-9f22-ba0be0483c18",
+            ),
+            (CqlValue::Date(0), "2147483648"),
+            (CqlValue::Blob(vec![0xca, 0xfe]), "0xcafe"),
+            (CqlValue::Duration(CqlDuration::new(-1, 0, -5)), "-1mo5ns"),
+            (
+                CqlValue::Map(vec![(CqlValue::Int(1), CqlValue::Null)]),
+                "{1: null}",
+            ),
+            (
+                CqlValue::Udt(vec![
+                    ("street".to_string(), CqlValue::Text("Main".to_string())),
+                    ("Zip Code".to_string(), CqlValue::Int(1)),
+                ]),
+                "{street: 'Main', \"Zip Code\": 1}",
+            ),
+        ];
+        for (value, literal) in values {
+            assert_eq!(value.to_string(), literal);
+        }
+        assert_eq!(duration("-1mo5ns"), Some(CqlDuration::new(-1, 0, -5)));
+    }
+}
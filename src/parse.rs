@@ -1,11 +1,35 @@
+use crate::model::table::primary_key::CqlPrimaryKey;
 use nom::IResult;
 
-mod cql_type;
+mod condition;
+pub(crate) mod cql_type;
+mod delete;
+mod documented;
+mod function;
 mod identifier;
+mod index;
+mod insert;
+mod keyspace;
+mod materialized_view;
+mod permission;
 mod qualified_identifier;
+mod relation;
+mod role;
+mod select;
+pub(crate) mod span;
 mod statement;
-mod table;
+pub(crate) mod table;
+mod term;
+mod trigger;
+mod type_arena;
+mod update;
 mod user_defined_type;
+mod using;
+
+/// The deepest nesting of terms and selectors accepted by the parser, `[[1]]` has a depth of 2.
+///
+/// Deeper nesting is a failure instead of overflowing the stack.
+pub(crate) const MAX_NESTING_DEPTH: usize = 64;
 
 pub trait Parse<I, E> {
     /// A parser takes in input type, and returns a `Result` containing
@@ -13,4 +37,26 @@ pub trait Parse<I, E> {
     fn parse(input: I) -> IResult<I, Self, E>
     where
         Self: Sized;
+
+    /// Parses the node after `leading`, the whitespace and comments skipped in front of it
+    /// following a token. Nodes keeping their doc comment override this, the others ignore
+    /// `leading`.
+    fn parse_after(leading: I, input: I) -> IResult<I, Self, E>
+    where
+        Self: Sized,
+    {
+        let _ = leading;
+        Self::parse(input)
+    }
+}
+
+/// A column definition of a table, which may mark the column as the primary key.
+pub trait ColumnDefinition<ColumnRef, PrimaryKey = CqlPrimaryKey<ColumnRef>> {
+    /// Whether the column is marked `PRIMARY KEY`.
+    fn is_marked_primary_key(&self) -> bool;
+    /// The reference to the column, as in the `PRIMARY KEY (...)` clause.
+    fn column_ref(&self) -> ColumnRef;
+    /// The primary key declared by marking the column `PRIMARY KEY`, with the column as the
+    /// partition key.
+    fn primary_key(&self) -> PrimaryKey;
 }
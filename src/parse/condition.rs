@@ -0,0 +1,165 @@
+use crate::model::condition::{CqlColumnCondition, CqlCondition};
+use crate::model::identifier::CqlIdentifier;
+use crate::model::relation::CqlOperator;
+use crate::model::term::CqlTerm;
+use crate::parse::relation::{in_values, operator};
+use crate::parse::term::identifier_char;
+use crate::parse::Parse;
+use crate::utils::{keyword, space1_before, ws0, ws1, Whitespace};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::{cut, map, not, opt, value};
+use nom::error::{ContextError, ErrorKind, ParseError};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlCondition<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        alt((
+            // A column can not be followed by another word, `NOT` has to be the keyword.
+            value(
+                CqlCondition::NotExists,
+                tuple((keyword("NOT"), ws1, keyword("EXISTS"), not(identifier_char))),
+            ),
+            value(
+                CqlCondition::Exists,
+                terminated(keyword("EXISTS"), not(identifier_char)),
+            ),
+            map(
+                separated_list1(delimited(ws1, keyword("AND"), ws1), column_condition),
+                CqlCondition::Columns,
+            ),
+        ))(input)
+    }
+}
+
+/// Parses the `IF` clause of a statement, fails if the statement does not support the condition.
+pub(crate) fn if_clause<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    supported: fn(&CqlCondition<&'de str>) -> bool,
+    unsupported: &'static str,
+) -> impl FnMut(&'de str) -> IResult<&'de str, Option<CqlCondition<&'de str>>, E> {
+    move |input| {
+        let (input, condition) =
+            opt(pair(ws1, terminated(keyword("IF"), not(identifier_char))))(input)?;
+        if condition.is_none() {
+            return Ok((input, None));
+        }
+        let (condition_start, _) = Whitespace::split_whitespace(input);
+        let (input, condition) = cut(space1_before(CqlCondition::parse))(input)?;
+        if !supported(&condition) {
+            return Err(nom::Err::Failure(E::add_context(
+                condition_start,
+                unsupported,
+                E::from_error_kind(condition_start, ErrorKind::Verify),
+            )));
+        }
+
+        Ok((input, Some(condition)))
+    }
+}
+
+fn column_condition<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlColumnCondition<&'de str>, E> {
+    map(
+        tuple((
+            CqlIdentifier::parse,
+            opt(preceded(tag("."), CqlIdentifier::parse)),
+            alt((
+                pair(delimited(ws0, operator, ws0), CqlTerm::parse),
+                pair(
+                    value(
+                        CqlOperator::In,
+                        preceded(ws1, terminated(keyword("IN"), not(identifier_char))),
+                    ),
+                    preceded(ws0, in_values),
+                ),
+            )),
+        )),
+        |(column, field, (operator, term))| CqlColumnCondition::new(column, field, operator, term),
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::term::{CqlBindMarker, CqlConstant};
+    use nom::error::VerboseError;
+
+    fn parse(input: &str) -> IResult<&str, CqlCondition<&str>, VerboseError<&str>> {
+        CqlCondition::parse(input)
+    }
+
+    #[test]
+    fn test_parse_condition() {
+        assert_eq!(parse("exists;"), Ok((";", CqlCondition::Exists)));
+        assert_eq!(parse("NOT  EXISTS"), Ok(("", CqlCondition::NotExists)));
+        assert_eq!(
+            parse("version_1 = 3 AND \"Owner\" IN ('a', 'b') AND addr_1.city_1!='Paris'"),
+            Ok((
+                "",
+                CqlCondition::Columns(vec![
+                    CqlColumnCondition::new(
+                        CqlIdentifier::Unquoted("version_1"),
+                        None,
+                        CqlOperator::Eq,
+                        CqlTerm::Constant(CqlConstant::Integer("3"))
+                    ),
+                    CqlColumnCondition::new(
+                        CqlIdentifier::Quoted("Owner".to_string()),
+                        None,
+                        CqlOperator::In,
+                        CqlTerm::TupleLiteral(vec![
+                            CqlTerm::Constant(CqlConstant::String("a".to_string())),
+                            CqlTerm::Constant(CqlConstant::String("b".to_string()))
+                        ])
+                    ),
+                    CqlColumnCondition::new(
+                        CqlIdentifier::Unquoted("addr_1"),
+                        Some(CqlIdentifier::Unquoted("city_1")),
+                        CqlOperator::Ne,
+                        CqlTerm::Constant(CqlConstant::String("Paris".to_string()))
+                    ),
+                ])
+            ))
+        );
+        assert_eq!(
+            parse("owner_1 in :owners_1"),
+            Ok((
+                "",
+                CqlCondition::Columns(vec![CqlColumnCondition::new(
+                    CqlIdentifier::Unquoted("owner_1"),
+                    None,
+                    CqlOperator::In,
+                    CqlTerm::BindMarker(CqlBindMarker::Named(CqlIdentifier::Unquoted("owners_1")))
+                )])
+            ))
+        );
+        // Columns starting like the keywords are columns.
+        for input in ["exists_1 = 1", "not_1 = 1"] {
+            let (remaining, condition) = parse(input).unwrap();
+            assert_eq!(remaining, "");
+            assert!(condition.is_columns());
+        }
+    }
+
+    #[test]
+    fn test_parse_if_clause() {
+        let mut if_clause = if_clause::<VerboseError<&str>>(
+            |condition| !condition.is_not_exists(),
+            "IF NOT EXISTS is not supported",
+        );
+        assert_eq!(
+            if_clause(" IF EXISTS"),
+            Ok(("", Some(CqlCondition::Exists)))
+        );
+        assert_eq!(if_clause(" IFFY"), Ok((" IFFY", None)));
+        assert!(matches!(
+            if_clause(" IF NOT EXISTS"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+}
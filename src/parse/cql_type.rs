@@ -1,78 +1,145 @@
 use crate::model::cql_type::CqlType;
 use crate::model::identifier::CqlIdentifier;
+use crate::parse::term::identifier_char;
 use crate::parse::Parse;
-use crate::utils::{angle_bracket, seperated, space0_around};
+use crate::utils::{angle_bracket, keyword, seperated, space0_around, space0_tag};
+use crate::DEFAULT_MAX_TYPE_DEPTH;
 use nom::branch::alt;
-use nom::bytes::complete::{tag, tag_no_case};
-use nom::combinator::map;
-use nom::error::ParseError;
+use nom::bytes::complete::tag;
+use nom::character::complete::digit1;
+use nom::combinator::{map, map_opt, not};
+use nom::error::{ContextError, ErrorKind, ParseError};
 use nom::multi::separated_list1;
+use nom::sequence::terminated;
 use nom::IResult;
 
-impl<'de, E: ParseError<&'de str>> Parse<&'de str, E> for CqlType<CqlIdentifier<&'de str>> {
+/// Matches the keyword of a native type, which must not be the start of a longer identifier.
+fn native<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    name: &'static str,
+) -> impl FnMut(&'de str) -> IResult<&'de str, &'de str, E> {
+    terminated(keyword(name), not(identifier_char))
+}
+
+/// Parses the types without nested types, except for user defined types.
+pub(crate) fn parse_native_type<'de, UdtType, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlType<UdtType>, E> {
+    alt((
+        map(native("ASCII"), |_| CqlType::ASCII),
+        map(native("BIGINT"), |_| CqlType::BIGINT),
+        map(native("BLOB"), |_| CqlType::BLOB),
+        map(native("BOOLEAN"), |_| CqlType::BOOLEAN),
+        map(native("COUNTER"), |_| CqlType::COUNTER),
+        map(native("DATE"), |_| CqlType::DATE),
+        map(native("DECIMAL"), |_| CqlType::DECIMAL),
+        map(native("DOUBLE"), |_| CqlType::DOUBLE),
+        map(native("DURATION"), |_| CqlType::DURATION),
+        map(native("FLOAT"), |_| CqlType::FLOAT),
+        map(native("INET"), |_| CqlType::INET),
+        map(native("INT"), |_| CqlType::INT),
+        map(native("SMALLINT"), |_| CqlType::SMALLINT),
+        map(native("TEXT"), |_| CqlType::TEXT),
+        map(native("TIMESTAMP"), |_| CqlType::TIMESTAMP),
+        map(native("TIMEUUID"), |_| CqlType::TIMEUUID),
+        map(native("TIME"), |_| CqlType::TIME),
+        map(native("TINYINT"), |_| CqlType::TINYINT),
+        map(native("UUID"), |_| CqlType::UUID),
+        map(native("VARCHAR"), |_| CqlType::VARCHAR),
+        map(native("VARINT"), |_| CqlType::VARINT),
+    ))(input)
+}
+
+/// Parses the dimension of a vector, which must be positive.
+pub(crate) fn parse_dimension<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, u32, E> {
+    let (remaining, dimension) = map_opt(digit1, |digits: &str| digits.parse().ok())(input)?;
+    if dimension == 0 {
+        return Err(nom::Err::Failure(E::add_context(
+            input,
+            "vector dimension must be positive",
+            E::from_error_kind(input, ErrorKind::Verify),
+        )));
+    }
+
+    Ok((remaining, dimension))
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlType<CqlIdentifier<&'de str>>
+{
     fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        parse_type(input, 0, DEFAULT_MAX_TYPE_DEPTH)
+    }
+}
+
+/// Parses a type nested at `depth`, types nested deeper than `max_depth` are a failure.
+pub(crate) fn parse_type<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+    depth: usize,
+    max_depth: usize,
+) -> IResult<&'de str, CqlType<CqlIdentifier<&'de str>>, E> {
+    if depth > max_depth {
+        return Err(nom::Err::Failure(E::add_context(
+            input,
+            "type nested too deeply",
+            E::from_error_kind(input, ErrorKind::Verify),
+        )));
+    }
+    let nested = |input| parse_type(input, depth + 1, max_depth);
+
+    alt((
+        parse_native_type,
         alt((
-            alt((
-                map(tag_no_case("ASCII"), |_| Self::ASCII),
-                map(tag_no_case("BIGINT"), |_| Self::BIGINT),
-                map(tag_no_case("BLOB"), |_| Self::BLOB),
-                map(tag_no_case("BOOLEAN"), |_| Self::BOOLEAN),
-                map(tag_no_case("COUNTER"), |_| Self::COUNTER),
-                map(tag_no_case("DATE"), |_| Self::DATE),
-                map(tag_no_case("DECIMAL"), |_| Self::DECIMAL),
-                map(tag_no_case("DOUBLE"), |_| Self::DOUBLE),
-                map(tag_no_case("DURATION"), |_| Self::DURATION),
-                map(tag_no_case("FLOAT"), |_| Self::FLOAT),
-                map(tag_no_case("INET"), |_| Self::INET),
-                map(tag_no_case("INT"), |_| Self::INT),
-                map(tag_no_case("SMALLINT"), |_| Self::SMALLINT),
-                map(tag_no_case("TEXT"), |_| Self::TEXT),
-                map(tag_no_case("TIMESTAMP"), |_| Self::TIMESTAMP),
-                map(tag_no_case("TIMEUUID"), |_| Self::TIMEUUID),
-                map(tag_no_case("TIME"), |_| Self::TIME),
-                map(tag_no_case("TINYINT"), |_| Self::TINYINT),
-                map(tag_no_case("UUID"), |_| Self::UUID),
-                map(tag_no_case("VARCHAR"), |_| Self::VARCHAR),
-                map(tag_no_case("VARINT"), |_| Self::VARINT),
-            )),
-            alt((
-                map(
-                    // FROZEN '<' cql_type '>'
-                    angle_bracket(tag_no_case("FROZEN"), Self::parse),
-                    |(_, ty)| Self::FROZEN(Box::new(ty)),
+            map(
+                // FROZEN '<' cql_type '>'
+                angle_bracket(keyword("FROZEN"), nested),
+                |(_, ty)| CqlType::FROZEN(Box::new(ty)),
+            ),
+            map(
+                // MAP '<' (cql_type ',' cql_type) '>'
+                angle_bracket(
+                    keyword("MAP"),
+                    // cql_type ',' cql_type
+                    seperated(nested, tag(","), nested),
                 ),
-                map(
-                    // MAP '<' (cql_type ',' cql_type) '>'
-                    angle_bracket(
-                        tag_no_case("MAP"),
-                        // cql_type ',' cql_type
-                        seperated(Self::parse, tag(","), Self::parse),
-                    ),
-                    |(_, (key, _, value))| Self::MAP(Box::new((key, value))),
+                |(_, (key, _, value))| CqlType::MAP(Box::new((key, value))),
+            ),
+            map(
+                // SET '<' cql_type '>'
+                angle_bracket(keyword("SET"), nested),
+                |(_, ty)| CqlType::SET(Box::new(ty)),
+            ),
+            map(
+                // LIST '<' cql_type '>'
+                angle_bracket(keyword("LIST"), nested),
+                |(_, ty)| CqlType::LIST(Box::new(ty)),
+            ),
+            map(
+                // TUPLE '<' cql_type ( ',' cql_type )* '>'
+                angle_bracket(
+                    keyword("TUPLE"),
+                    // cql_type ( ',' cql_type )*
+                    separated_list1(tag(","), space0_around(nested)),
                 ),
-                map(
-                    // SET '<' cql_type '>'
-                    angle_bracket(tag_no_case("SET"), Self::parse),
-                    |(_, ty)| Self::SET(Box::new(ty)),
+                |(_, ty)| CqlType::TUPLE(ty),
+            ),
+            map(
+                // VECTOR '<' cql_type ',' integer '>'
+                angle_bracket(
+                    keyword("VECTOR"),
+                    // cql_type ',' integer
+                    seperated(nested, tag(","), parse_dimension),
                 ),
-                map(
-                    // LIST '<' cql_type '>'
-                    angle_bracket(tag_no_case("LIST"), Self::parse),
-                    |(_, ty)| Self::LIST(Box::new(ty)),
-                ),
-                map(
-                    // TUPLE '<' cql_type ( ',' cql_type )* '>'
-                    angle_bracket(
-                        tag_no_case("TUPLE"),
-                        // cql_type ( ',' cql_type )*
-                        separated_list1(tag(","), space0_around(Self::parse)),
-                    ),
-                    |(_, ty)| Self::TUPLE(ty),
-                ),
-                map(CqlIdentifier::parse, |ident| Self::UserDefined(ident)),
-            )),
-        ))(input)
-    }
+                |(_, (ty, _, dimension))| CqlType::VECTOR(Box::new(ty), dimension),
+            ),
+            // A nested type with an unknown name or invalid parameters is no user defined type.
+            map(
+                terminated(CqlIdentifier::parse, not(space0_tag("<"))),
+                CqlType::UserDefined,
+            ),
+        )),
+    ))(input)
 }
 
 #[cfg(test)]
@@ -268,6 +335,40 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_parse_type_vector() {
+        let input = "vector<float, 1536>";
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(input);
+        assert_eq!(
+            result,
+            Ok(("", CqlType::VECTOR(Box::new(CqlType::FLOAT), 1536)))
+        );
+        let input = "VECTOR < FROZEN<my_udt> , 8 >";
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                "",
+                CqlType::VECTOR(
+                    Box::new(CqlType::FROZEN(Box::new(CqlType::UserDefined(
+                        CqlIdentifier::Unquoted("my_udt")
+                    )))),
+                    8
+                )
+            ))
+        );
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse("vector<float>");
+        assert_ne!(
+            result,
+            Ok(("", CqlType::VECTOR(Box::new(CqlType::FLOAT), 0)))
+        );
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse("vector<float, 0>");
+        assert!(matches!(result, Err(nom::Err::Failure(_))));
+        let error = crate::parse_cql_type("vector<float, 0>").unwrap_err();
+        assert_eq!(error.offset(), 14);
+        assert_eq!(error.message(), "vector dimension must be positive");
+    }
+
     #[test]
     fn test_parse_type_udt() {
         let input = "user_defined_type";
@@ -280,4 +381,113 @@ mod test {
             ))
         );
     }
+
+    #[test]
+    fn test_parse_type_keyword_prefix() {
+        let keywords = [
+            "ascii",
+            "bigint",
+            "blob",
+            "boolean",
+            "counter",
+            "date",
+            "decimal",
+            "double",
+            "duration",
+            "float",
+            "inet",
+            "int",
+            "smallint",
+            "text",
+            "time",
+            "timestamp",
+            "timeuuid",
+            "tinyint",
+            "uuid",
+            "varchar",
+            "varint",
+            "frozen",
+            "map",
+            "set",
+            "list",
+            "tuple",
+            "vector",
+        ];
+        for keyword in keywords {
+            for name in [
+                format!("{}_1", keyword),
+                format!("{}x1", keyword.to_uppercase()),
+            ] {
+                let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(&name);
+                assert_eq!(
+                    result,
+                    Ok(("", CqlType::UserDefined(CqlIdentifier::Unquoted(&*name)))),
+                    "{}",
+                    name
+                );
+            }
+        }
+        for name in [
+            "intx", "mapping", "seth", "listing", "datetime", "timer", "Blobs",
+        ] {
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(name);
+            assert_eq!(
+                result,
+                Ok(("", CqlType::UserDefined(CqlIdentifier::Unquoted(name))))
+            );
+        }
+        let input = "time_series, date_time1>";
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                ", date_time1>",
+                CqlType::UserDefined(CqlIdentifier::Unquoted("time_series"))
+            ))
+        );
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse("int, text");
+        assert_eq!(result, Ok((", text", CqlType::INT)));
+        // A name followed by parameters is no user defined type.
+        let result: IResult<_, CqlType<_>, nom::error::Error<&str>> =
+            CqlType::parse("mapping<int>");
+        assert!(result.is_err());
+    }
+
+    fn nest(open: &str, leaf: &str, close: &str, depth: usize) -> String {
+        format!("{}{}{}", open.repeat(depth), leaf, close.repeat(depth))
+    }
+
+    #[test]
+    fn test_parse_type_depth() {
+        let input = nest("frozen<", "int", ">", 50);
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(&input);
+        let (remaining, mut cql_type) = result.unwrap();
+        assert_eq!(remaining, "");
+        for _ in 0..50 {
+            cql_type = *cql_type.unwrap_frozen();
+        }
+        assert_eq!(cql_type, CqlType::INT);
+
+        let input = nest("frozen<", "int", ">", DEFAULT_MAX_TYPE_DEPTH);
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(&input);
+        assert!(result.is_ok());
+        let input = nest("frozen<", "int", ">", DEFAULT_MAX_TYPE_DEPTH + 1);
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(&input);
+        assert!(matches!(result, Err(nom::Err::Failure(_))));
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_parse_type_depth_exceeded() {
+        for (open, close) in [
+            ("frozen<", ">"),
+            ("map<int, ", ">"),
+            ("tuple<text, ", ", int>"),
+            ("vector<", ", 2>"),
+        ] {
+            let input = nest(open, "int", close, 100_000);
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(&input);
+            assert!(matches!(result, Err(nom::Err::Failure(_))), "{}", open);
+        }
+    }
 }
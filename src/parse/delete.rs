@@ -0,0 +1,204 @@
+use crate::model::delete::{CqlDelete, CqlDeletion};
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::relation::CqlWhereClause;
+use crate::model::term::CqlTerm;
+use crate::model::using::CqlUsing;
+use crate::parse::condition::if_clause;
+use crate::parse::term::identifier_char;
+use crate::parse::Parse;
+use crate::utils::{debug_event, keyword, space1_before, ws0, ws1, Whitespace};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::{cut, map, not, opt, success};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlDelete<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = keyword("DELETE")(input)?;
+        // No other statement starts with DELETE.
+        let (input, (deletions, table)) = cut(pair(
+            map(
+                opt(preceded(
+                    pair(ws1, not(terminated(keyword("FROM"), not(identifier_char)))),
+                    separated_list1(delimited(ws0, tag(","), ws0), deletion),
+                )),
+                Option::unwrap_or_default,
+            ),
+            preceded(
+                pair(ws0, keyword("FROM")),
+                space1_before(CqlQualifiedIdentifier::parse),
+            ),
+        ))(input)?;
+        debug_event!(name = &**table.name(), "delete");
+        let (using_start, _) = Whitespace::split_whitespace(input);
+        let (input, (using, where_clause, condition)) = cut(tuple((
+            opt(preceded(ws0, CqlUsing::parse)),
+            preceded(
+                pair(ws0, keyword("WHERE")),
+                space1_before(CqlWhereClause::parse),
+            ),
+            if_clause(
+                |condition| !condition.is_not_exists(),
+                "IF NOT EXISTS is only supported by INSERT",
+            ),
+        )))(input)?;
+        if using.as_ref().is_some_and(|using| using.ttl().is_some()) {
+            return Err(nom::Err::Failure(E::add_context(
+                using_start,
+                "a deletion has no TTL",
+                E::from_error_kind(using_start, ErrorKind::Verify),
+            )));
+        }
+
+        Ok((
+            input,
+            CqlDelete::new(deletions, table, using, where_clause, condition),
+        ))
+    }
+}
+
+fn deletion<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlDeletion<&'de str>, E> {
+    let (input, column) = CqlIdentifier::parse(input)?;
+    let mut deletion = alt((
+        map(
+            preceded(
+                ws0,
+                delimited(
+                    pair(tag("["), ws0),
+                    CqlTerm::parse,
+                    pair(ws0, context("]", tag("]"))),
+                ),
+            ),
+            |element| CqlDeletion::Element(column.clone(), element),
+        ),
+        map(preceded(tag("."), CqlIdentifier::parse), |field| {
+            CqlDeletion::Field(column.clone(), field)
+        }),
+        success(CqlDeletion::Column(column.clone())),
+    ));
+    deletion(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::condition::CqlCondition;
+    use crate::model::relation::{CqlOperator, CqlRelation};
+    use crate::model::term::{CqlBindMarker, CqlConstant};
+    use nom::error::{VerboseError, VerboseErrorKind};
+
+    fn parse(input: &str) -> IResult<&str, CqlDelete<&str>, VerboseError<&str>> {
+        CqlDelete::parse(input)
+    }
+
+    #[test]
+    fn test_parse_delete_rows() {
+        let (remaining, delete) =
+            parse("DELETE FROM my_ks.events_1 WHERE pk_1 = 1 AND ts_1 >= 10 AND ts_1 < 20;")
+                .unwrap();
+        assert_eq!(remaining, ";");
+        assert!(delete.deletions().is_empty());
+        assert_eq!(
+            delete.table(),
+            &CqlQualifiedIdentifier::new(
+                Some(CqlIdentifier::Unquoted("my_ks")),
+                CqlIdentifier::Unquoted("events_1")
+            )
+        );
+        assert_eq!(
+            delete
+                .where_clause()
+                .relations()
+                .iter()
+                .map(CqlRelation::operator)
+                .collect::<Vec<_>>(),
+            vec![CqlOperator::Eq, CqlOperator::Ge, CqlOperator::Lt]
+        );
+        assert_eq!(delete.condition(), &None);
+    }
+
+    #[test]
+    fn test_parse_delete_values() {
+        let (remaining, delete) = parse(
+            "delete col_1, map_1 [ 'key' ], udt_1.field_1 from events_1 \
+             using timestamp 1234 where pk_1 = 1 if exists",
+        )
+        .unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(
+            delete.deletions(),
+            &vec![
+                CqlDeletion::Column(CqlIdentifier::Unquoted("col_1")),
+                CqlDeletion::Element(
+                    CqlIdentifier::Unquoted("map_1"),
+                    CqlTerm::Constant(CqlConstant::String("key".to_string()))
+                ),
+                CqlDeletion::Field(
+                    CqlIdentifier::Unquoted("udt_1"),
+                    CqlIdentifier::Unquoted("field_1")
+                ),
+            ]
+        );
+        assert_eq!(
+            delete.using(),
+            &Some(CqlUsing::new(
+                None,
+                Some(CqlTerm::Constant(CqlConstant::Integer("1234"))),
+                None
+            ))
+        );
+        assert_eq!(delete.condition(), &Some(CqlCondition::Exists));
+        // A column starting like FROM is deleted.
+        let (_, delete) = parse("DELETE from_1 FROM events_1 WHERE pk_1 = 1").unwrap();
+        assert_eq!(
+            delete.deletions(),
+            &vec![CqlDeletion::Column(CqlIdentifier::Unquoted("from_1"))]
+        );
+    }
+
+    #[test]
+    fn test_bind_markers() {
+        let (_, delete) = parse(
+            "DELETE map_1[:key_1] FROM events_1 USING TIMESTAMP ? WHERE pk_1 = ? IF col_1 = :old_1",
+        )
+        .unwrap();
+        let named = |name| CqlBindMarker::Named(CqlIdentifier::Unquoted(name));
+        assert_eq!(
+            delete.bind_markers().collect::<Vec<_>>(),
+            vec![
+                &named("key_1"),
+                &CqlBindMarker::Positional,
+                &CqlBindMarker::Positional,
+                &named("old_1"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_delete() {
+        let input = "DELETE FROM events_1 USING TTL 5 WHERE pk_1 = 1";
+        let Err(nom::Err::Failure(error)) = parse(input) else {
+            panic!("TTL accepted");
+        };
+        assert!(error.errors.contains(&(
+            &input[21..],
+            VerboseErrorKind::Context("a deletion has no TTL")
+        )));
+        assert!(matches!(
+            parse("DELETE FROM events_1"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(matches!(
+            parse("DELETE col_1 events_1 WHERE pk_1 = 1"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+}
@@ -0,0 +1,39 @@
+use crate::model::documented::Documented;
+use crate::parse::{ColumnDefinition, Parse};
+use crate::utils::doc_comment;
+use nom::error::ParseError;
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str>, T: Parse<&'de str, E>> Parse<&'de str, E> for Documented<T> {
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (remaining, node) = T::parse(input)?;
+
+        Ok((remaining, Documented::new(None, node)))
+    }
+
+    fn parse_after(leading: &'de str, input: &'de str) -> IResult<&'de str, Self, E> {
+        let (remaining, node) = T::parse(input)?;
+
+        Ok((
+            remaining,
+            Documented::new(doc_comment(leading, false), node),
+        ))
+    }
+}
+
+impl<ColumnRef, PrimaryKey, T> ColumnDefinition<ColumnRef, PrimaryKey> for Documented<T>
+where
+    T: ColumnDefinition<ColumnRef, PrimaryKey>,
+{
+    fn is_marked_primary_key(&self) -> bool {
+        self.node().is_marked_primary_key()
+    }
+
+    fn column_ref(&self) -> ColumnRef {
+        self.node().column_ref()
+    }
+
+    fn primary_key(&self) -> PrimaryKey {
+        self.node().primary_key()
+    }
+}
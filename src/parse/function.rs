@@ -0,0 +1,126 @@
+use crate::model::cql_type::CqlType;
+use crate::model::function::{CqlDropAggregate, CqlFunctionSignature};
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::parse::Parse;
+use crate::utils::{
+    debug_event, keyword, space1_before, space1_between, space1_tags_no_case, traced, ws0,
+    ErrorInput,
+};
+use nom::bytes::complete::tag;
+use nom::combinator::{cut, opt};
+use nom::error::{context, ContextError, ParseError};
+use nom::multi::separated_list0;
+use nom::sequence::{delimited, preceded};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>>
+    Parse<&'de str, E> for CqlDropAggregate<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("DROP"), keyword("AGGREGATE")))(input)?;
+        let (input, if_exists) = opt(space1_before(space1_tags_no_case(["IF", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlQualifiedIdentifier::parse)(input)?;
+        debug_event!(
+            keyspace = name.keyspace().as_ref().map(|keyspace| &**keyspace),
+            name = &**name.name(),
+            "drop aggregate"
+        );
+        let (input, signature) = opt(preceded(ws0, parse_signature))(input)?;
+
+        Ok((
+            input,
+            CqlDropAggregate::new(if_exists.is_some(), name, signature),
+        ))
+    }
+}
+
+/// Parses the parenthesized argument types of a function or aggregate.
+pub(crate) fn parse_signature<
+    'de,
+    E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>,
+>(
+    input: &'de str,
+) -> IResult<&'de str, CqlFunctionSignature<&'de str>, E> {
+    preceded(
+        tag("("),
+        cut(delimited(
+            ws0,
+            separated_list0(
+                tag(","),
+                delimited(ws0, traced("type", CqlType::parse), ws0),
+            ),
+            context(")", tag(")")),
+        )),
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::identifier::CqlIdentifier;
+
+    fn parse(input: &str) -> IResult<&str, CqlDropAggregate<&str>, nom::error::Error<&str>> {
+        CqlDropAggregate::parse(input)
+    }
+
+    fn name<'a>(keyspace: Option<&'a str>, name: &'a str) -> CqlQualifiedIdentifier<&'a str> {
+        CqlQualifiedIdentifier::new(
+            keyspace.map(CqlIdentifier::Unquoted),
+            CqlIdentifier::Unquoted(name),
+        )
+    }
+
+    #[test]
+    fn test_parse_drop_aggregate() {
+        assert_eq!(
+            parse("DROP AGGREGATE IF EXISTS my_ks.average_1(int);"),
+            Ok((
+                ";",
+                CqlDropAggregate::new(
+                    true,
+                    name(Some("my_ks"), "average_1"),
+                    Some(vec![CqlType::INT])
+                )
+            ))
+        );
+        assert_eq!(
+            parse("drop aggregate average_1"),
+            Ok((
+                "",
+                CqlDropAggregate::new(false, name(None, "average_1"), None)
+            ))
+        );
+        assert_eq!(
+            parse("DROP AGGREGATE average_1 ()"),
+            Ok((
+                "",
+                CqlDropAggregate::new(false, name(None, "average_1"), Some(vec![]))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_overloaded_signature() {
+        assert_eq!(
+            parse("DROP AGGREGATE my_ks.merge_1( map<text, frozen<list<int>>> , frozen<address_type>, set<bigint>)"),
+            Ok((
+                "",
+                CqlDropAggregate::new(
+                    false,
+                    name(Some("my_ks"), "merge_1"),
+                    Some(vec![
+                        CqlType::MAP(Box::new((
+                            CqlType::TEXT,
+                            CqlType::FROZEN(Box::new(CqlType::LIST(Box::new(CqlType::INT))))
+                        ))),
+                        CqlType::FROZEN(Box::new(CqlType::UserDefined(CqlIdentifier::Unquoted(
+                            "address_type"
+                        )))),
+                        CqlType::SET(Box::new(CqlType::BIGINT)),
+                    ])
+                )
+            ))
+        );
+        assert!(parse("DROP AGGREGATE merge_1(int, list<int>").is_err());
+    }
+}
@@ -1,44 +1,202 @@
-use crate::model::identifier::CqlIdentifier;
+use crate::model::identifier::{is_reserved_keyword, CqlIdentifier};
 use crate::parse::Parse;
+use memchr::memchr;
 use nom::branch::alt;
-use nom::bytes::complete::{tag, take_until, take_while1};
-use nom::character::complete::alpha1;
-use nom::error::ParseError;
+use nom::bytes::complete::{tag, take_while};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
 use nom::{AsChar, IResult, InputTake};
 
-impl<'de, E: ParseError<&'de str>> Parse<&'de str, E> for CqlIdentifier<&'de str> {
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlIdentifier<&'de str>
+{
     fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
-        fn parse_quoted<'de, E: ParseError<&'de str>>(
+        fn parse_quoted<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
             input: &'de str,
-        ) -> IResult<&str, CqlIdentifier<&'de str>, E> {
+        ) -> IResult<&'de str, CqlIdentifier<&'de str>, E> {
             let (input, _) = tag("\"")(input)?;
             let mut acc = String::new();
             let mut input = input;
             loop {
-                let (i, s) = take_until("\"")(input)?;
-                acc.push_str(s);
-                let (i, _) = tag("\"")(i)?;
-                input = i;
-                if !i.starts_with("\"") {
+                let end = memchr(b'"', input.as_bytes()).ok_or_else(|| {
+                    nom::Err::Error(E::from_error_kind(input, ErrorKind::TakeUntil))
+                })?;
+                acc.push_str(&input[..end]);
+                input = &input[end + 1..];
+                // A doubled quote is an escaped quote, a single one closes the identifier.
+                if !input.starts_with('"') {
                     break;
                 }
                 acc.push('"');
+                input = &input[1..];
             }
 
             Ok((input, CqlIdentifier::Quoted(acc)))
         }
 
-        fn parse_unquoted<'de, E: ParseError<&'de str>>(
+        fn parse_unquoted<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
             input: &'de str,
-        ) -> IResult<&str, CqlIdentifier<&'de str>, E> {
-            let (i, first) = alpha1(input)?;
-            let (i, rest) = take_while1(|c: char| c.is_alpha() || c.is_dec_digit() || c == '_')(i)?;
-            Ok((
-                i,
-                CqlIdentifier::Unquoted(input.take(first.len() + rest.len())),
-            ))
+        ) -> IResult<&'de str, CqlIdentifier<&'de str>, E> {
+            // A letter followed by any letters, digits and underscores.
+            if !input.starts_with(|c: char| c.is_alpha()) {
+                return Err(nom::Err::Error(E::from_error_kind(input, ErrorKind::Alpha)));
+            }
+            let (i, rest) =
+                take_while(|c: char| c.is_alpha() || c.is_dec_digit() || c == '_')(&input[1..])?;
+            let identifier = input.take(1 + rest.len());
+            // Reserved keywords are only identifiers when quoted.
+            if is_reserved_keyword(identifier) {
+                return Err(nom::Err::Error(E::from_error_kind(input, ErrorKind::Tag)));
+            }
+            Ok((i, CqlIdentifier::Unquoted(identifier)))
+        }
+
+        context("identifier", alt((parse_quoted, parse_unquoted)))(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse_unquoted() {
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse("my_table1 (");
+        assert_eq!(result, Ok((" (", CqlIdentifier::Unquoted("my_table1"))));
+    }
+
+    #[test]
+    fn test_parse_unquoted_letters() {
+        for (input, remaining, name) in [
+            ("a", "", "a"),
+            ("a,", ",", "a"),
+            ("species)", ")", "species"),
+            ("userid text", " text", "userid"),
+            ("Body\n", "\n", "Body"),
+            ("x1_", "", "x1_"),
+        ] {
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse(input);
+            assert_eq!(result, Ok((remaining, CqlIdentifier::Unquoted(name))));
+        }
+        for input in ["1a", "_a", "", " a"] {
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse(input);
+            assert!(result.is_err(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_reserved_keyword() {
+        for input in ["select", "FROM x", "Primary"] {
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse(input);
+            assert_eq!(
+                result,
+                Err(nom::Err::Error(nom::error::Error::new(
+                    input,
+                    ErrorKind::Tag
+                ))),
+            );
         }
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse("\"select\"");
+        assert_eq!(
+            result,
+            Ok(("", CqlIdentifier::Quoted("select".to_string())))
+        );
+        // Non-reserved keywords and words starting with a keyword stay identifiers.
+        for name in ["ttl", "key", "selected", "order_id"] {
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse(name);
+            assert_eq!(result, Ok(("", CqlIdentifier::Unquoted(name))));
+        }
+    }
+
+    #[test]
+    fn test_parse_quoted() {
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse("\"My Table\" (");
+        assert_eq!(
+            result,
+            Ok((" (", CqlIdentifier::Quoted("My Table".to_string())))
+        );
+    }
 
-        alt((parse_quoted, parse_unquoted))(input)
+    #[test]
+    fn test_parse_quoted_multibyte() {
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse("\"tählé\"");
+        assert_eq!(result, Ok(("", CqlIdentifier::Quoted("tählé".to_string()))));
+    }
+
+    #[test]
+    fn test_parse_quoted_empty_remaining() {
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse("\"a\"");
+        assert_eq!(result, Ok(("", CqlIdentifier::Quoted("a".to_string()))));
+    }
+
+    #[test]
+    fn test_parse_quoted_escaped() {
+        let result: IResult<_, _, nom::error::Error<&str>> =
+            CqlIdentifier::parse("\"my\"\"table\" (");
+        assert_eq!(
+            result,
+            Ok((" (", CqlIdentifier::Quoted("my\"table".to_string())))
+        );
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse("\"a\"\"b\"");
+        assert_eq!(result, Ok(("", CqlIdentifier::Quoted("a\"b".to_string()))));
+    }
+
+    #[test]
+    fn test_parse_quoted_escaped_at_end() {
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse("\"a\"\"\" (");
+        assert_eq!(result, Ok((" (", CqlIdentifier::Quoted("a\"".to_string()))));
+    }
+
+    #[test]
+    fn test_parse_quoted_consecutive_escapes() {
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse("\"a\"\"\"\"b\"");
+        assert_eq!(
+            result,
+            Ok(("", CqlIdentifier::Quoted("a\"\"b".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_parse_quoted_unterminated() {
+        let input = "\"unterminated";
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse(input);
+        assert_eq!(
+            result,
+            Err(nom::Err::Error(nom::error::Error::new(
+                input,
+                ErrorKind::Alpha
+            )))
+        );
+    }
+
+    #[test]
+    fn test_display_parses_back() {
+        for input in [
+            "my_table",
+            "MyTable",
+            "\"MyTable\"",
+            "\"we\"\"ird\"",
+            "\"select\"",
+            "\"a b\"",
+        ] {
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse(input);
+            let (_, identifier) = result.unwrap();
+            assert_eq!(identifier.to_string(), input);
+        }
+        for identifier in [CqlIdentifier::new("Select"), CqlIdentifier::new("my table")] {
+            let cql = identifier.to_cql();
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse(&cql);
+            assert_eq!(result, Ok(("", identifier)));
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_quoted_display_parses_back(name in "\\PC+") {
+            let identifier = CqlIdentifier::<&str>::new_quoted(name);
+            let cql = identifier.to_cql();
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlIdentifier::parse(&cql);
+            prop_assert_eq!(result, Ok(("", identifier)));
+        }
     }
 }
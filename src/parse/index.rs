@@ -0,0 +1,199 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::index::{CqlCreateIndex, CqlIndexClass, CqlIndexTarget};
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::parse::Parse;
+use crate::utils::{
+    braced, debug_event, keyword, space0_between, space1_before, space1_tags_no_case,
+    string_constant, ws0, ws1,
+};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::{cut, map, not, opt};
+use nom::error::{context, ContextError, ParseError};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlCreateIndex<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = terminated(keyword("CREATE"), ws1)(input)?;
+        let (input, custom) = opt(terminated(keyword("CUSTOM"), ws1))(input)?;
+        let (input, _) = keyword("INDEX")(input)?;
+        let (input, if_not_exists) =
+            opt(space1_before(space1_tags_no_case(["IF", "NOT", "EXISTS"])))(input)?;
+        // The name is optional, `ON` starts the table instead.
+        let (input, name) = opt(space1_before(preceded(
+            not(pair(keyword("ON"), ws1)),
+            CqlIdentifier::parse,
+        )))(input)?;
+        let (input, table) = preceded(
+            space1_before(keyword("ON")),
+            space1_before(CqlQualifiedIdentifier::parse),
+        )(input)?;
+        debug_event!(
+            keyspace = table.keyspace().as_ref().map(|keyspace| &**keyspace),
+            table = &**table.name(),
+            "index"
+        );
+        let (input, _) = preceded(ws0, context("(", tag("(")))(input)?;
+        // Once the columns are opened the statement is an index.
+        let (input, (partition_key, target)) = cut(terminated(
+            pair(
+                opt(terminated(
+                    delimited(
+                        pair(ws0, tag("(")),
+                        separated_list1(tag(","), delimited(ws0, CqlIdentifier::parse, ws0)),
+                        tag(")"),
+                    ),
+                    pair(ws0, tag(",")),
+                )),
+                delimited(ws0, CqlIndexTarget::parse, ws0),
+            ),
+            context(")", tag(")")),
+        ))(input)?;
+        let (input, using) = opt(preceded(
+            pair(ws0, keyword("USING")),
+            cut(map(
+                pair(
+                    space1_before(string_constant),
+                    opt(preceded(
+                        tuple((
+                            space1_before(keyword("WITH")),
+                            space1_before(keyword("OPTIONS")),
+                            ws0,
+                            tag("="),
+                            ws0,
+                        )),
+                        braced(map(
+                            space0_between((string_constant, tag(":"), string_constant)),
+                            |(key, _, value)| (key, value),
+                        )),
+                    )),
+                ),
+                |(class, options)| CqlIndexClass::new(class, options),
+            )),
+        ))(input)?;
+
+        Ok((
+            input,
+            CqlCreateIndex::new(
+                custom.is_some(),
+                if_not_exists.is_some(),
+                name,
+                table,
+                partition_key,
+                target,
+                using,
+            ),
+        ))
+    }
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlIndexTarget<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        fn function<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+            name: &'static str,
+        ) -> impl FnMut(&'de str) -> IResult<&'de str, CqlIdentifier<&'de str>, E> {
+            preceded(
+                pair(keyword(name), pair(ws0, tag("("))),
+                terminated(delimited(ws0, CqlIdentifier::parse, ws0), tag(")")),
+            )
+        }
+
+        alt((
+            map(function("KEYS"), CqlIndexTarget::Keys),
+            map(function("VALUES"), CqlIndexTarget::Values),
+            map(function("ENTRIES"), CqlIndexTarget::Entries),
+            map(function("FULL"), CqlIndexTarget::Full),
+            map(CqlIdentifier::parse, CqlIndexTarget::Column),
+        ))(input)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(input: &str) -> IResult<&str, CqlCreateIndex<&str>, nom::error::Error<&str>> {
+        CqlCreateIndex::parse(input)
+    }
+
+    fn unquoted(name: &str) -> CqlIdentifier<&str> {
+        CqlIdentifier::Unquoted(name)
+    }
+
+    #[test]
+    fn test_parse_create_index() {
+        assert_eq!(
+            parse("CREATE INDEX IF NOT EXISTS orders_by_user ON my_ks.orders_v1 (user_id);"),
+            Ok((
+                ";",
+                CqlCreateIndex::new(
+                    false,
+                    true,
+                    Some(unquoted("orders_by_user")),
+                    CqlQualifiedIdentifier::new(Some(unquoted("my_ks")), unquoted("orders_v1")),
+                    None,
+                    CqlIndexTarget::Column(unquoted("user_id")),
+                    None,
+                )
+            ))
+        );
+
+        let (remaining, index) = parse("create index on orders_v1(keys ( tag_map ))").unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(index.name(), &None);
+        assert!(!index.is_local());
+        assert_eq!(index.target(), &CqlIndexTarget::Keys(unquoted("tag_map")));
+        assert_eq!(index.target().column(), &unquoted("tag_map"));
+    }
+
+    #[test]
+    fn test_parse_local_index() {
+        let (remaining, index) =
+            parse("CREATE INDEX ON my_ks.orders_v1 ((shop_id, region_1), user_id)").unwrap();
+        assert_eq!(remaining, "");
+        assert!(index.is_local());
+        assert_eq!(
+            index.partition_key(),
+            &Some(vec![unquoted("shop_id"), unquoted("region_1")])
+        );
+        assert_eq!(index.target(), &CqlIndexTarget::Column(unquoted("user_id")));
+
+        let (_, index) =
+            parse("CREATE INDEX by_user ON orders_v1 ( ( \"Shop Id\" ) , \"User Id\" )").unwrap();
+        assert_eq!(
+            index.partition_key(),
+            &Some(vec![CqlIdentifier::Quoted("Shop Id".to_string())])
+        );
+        assert_eq!(
+            index.target(),
+            &CqlIndexTarget::Column(CqlIdentifier::Quoted("User Id".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_custom_index() {
+        let (remaining, index) = parse(
+            "CREATE CUSTOM INDEX by_name ON orders_v1 (name_1) USING 'StorageAttachedIndex' WITH OPTIONS = {'case_sensitive': 'false'}",
+        )
+        .unwrap();
+        assert_eq!(remaining, "");
+        assert!(index.custom());
+        assert_eq!(
+            index.using(),
+            &Some(CqlIndexClass::new(
+                "StorageAttachedIndex".to_string(),
+                Some(vec![("case_sensitive".to_string(), "false".to_string())])
+            ))
+        );
+        assert!(matches!(
+            parse("CREATE INDEX ON orders_v1 ((shop_id), )"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+}
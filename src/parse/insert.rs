@@ -0,0 +1,262 @@
+use crate::model::condition::CqlCondition;
+use crate::model::identifier::CqlIdentifier;
+use crate::model::insert::{CqlInsert, CqlInsertValues, CqlJsonDefault};
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::term::{CqlConstant, CqlTerm};
+use crate::model::using::CqlUsing;
+use crate::parse::condition::if_clause;
+use crate::parse::term::{arguments, bind_marker};
+use crate::parse::Parse;
+use crate::utils::{
+    debug_event, keyword, space1_before, space1_between, string_constant, ws0, ws1, Whitespace,
+};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::{cut, map, opt, value};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlInsert<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("INSERT"), keyword("INTO")))(input)?;
+        // No other statement starts with INSERT INTO.
+        let (input, table) = cut(space1_before(CqlQualifiedIdentifier::parse))(input)?;
+        debug_event!(name = &**table.name(), "insert");
+        let (input, values) = match json(input)? {
+            (input, Some(values)) => (input, values),
+            (input, None) => {
+                let (values_start, _) = Whitespace::split_whitespace(input);
+                let (input, (columns, values)) = cut(names_values)(input)?;
+                if columns.len() != values.len() {
+                    return Err(nom::Err::Failure(E::add_context(
+                        values_start,
+                        "the number of values does not match the number of columns",
+                        E::from_error_kind(values_start, ErrorKind::Verify),
+                    )));
+                }
+                (
+                    input,
+                    CqlInsertValues::Values(columns.into_iter().zip(values).collect()),
+                )
+            }
+        };
+
+        let (input, condition) = if_clause(
+            CqlCondition::is_not_exists,
+            "INSERT only supports IF NOT EXISTS",
+        )(input)?;
+        let (input, using) = opt(preceded(ws0, CqlUsing::parse))(input)?;
+
+        Ok((input, CqlInsert::new(table, values, condition, using)))
+    }
+}
+
+/// The column names and the values assigned to them.
+type NamesValues<'de> = (Vec<CqlIdentifier<&'de str>>, Vec<CqlTerm<&'de str>>);
+
+/// Parses the column names and the values assigned to them.
+fn names_values<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, NamesValues<'de>, E> {
+    pair(
+        delimited(
+            pair(ws0, tag("(")),
+            separated_list1(tag(","), delimited(ws0, CqlIdentifier::parse, ws0)),
+            context(")", tag(")")),
+        ),
+        preceded(pair(ws0, keyword("VALUES")), arguments),
+    )(input)
+}
+
+/// Parses a json encoded row with its `DEFAULT` behavior, only if it starts with `JSON`.
+fn json<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, Option<CqlInsertValues<&'de str>>, E> {
+    let (input, json) = opt(pair(ws0, keyword("JSON")))(input)?;
+    if json.is_none() {
+        return Ok((input, None));
+    }
+    let (input, (row, default)) = cut(pair(
+        context(
+            "json",
+            space1_before(alt((
+                map(string_constant, |text| {
+                    CqlTerm::Constant(CqlConstant::String(text))
+                }),
+                map(bind_marker, CqlTerm::BindMarker),
+            ))),
+        ),
+        opt(preceded(
+            pair(ws1, keyword("DEFAULT")),
+            cut(space1_before(alt((
+                value(CqlJsonDefault::Null, keyword("NULL")),
+                value(CqlJsonDefault::Unset, keyword("UNSET")),
+            )))),
+        )),
+    ))(input)?;
+
+    Ok((input, Some(CqlInsertValues::Json(row, default))))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::term::CqlBindMarker;
+    use nom::error::{VerboseError, VerboseErrorKind};
+
+    fn parse(input: &str) -> IResult<&str, CqlInsert<&str>, VerboseError<&str>> {
+        CqlInsert::parse(input)
+    }
+
+    #[test]
+    fn test_parse_insert_values() {
+        assert_eq!(
+            parse("INSERT INTO my_ks.users_1 (user_id, \"Name\")\n  VALUES (42, 'it''s me');"),
+            Ok((
+                ";",
+                CqlInsert::new(
+                    CqlQualifiedIdentifier::new(
+                        Some(CqlIdentifier::Unquoted("my_ks")),
+                        CqlIdentifier::Unquoted("users_1")
+                    ),
+                    CqlInsertValues::Values(vec![
+                        (
+                            CqlIdentifier::Unquoted("user_id"),
+                            CqlTerm::Constant(CqlConstant::Integer("42"))
+                        ),
+                        (
+                            CqlIdentifier::Quoted("Name".to_string()),
+                            CqlTerm::Constant(CqlConstant::String("it's me".to_string()))
+                        ),
+                    ]),
+                    None,
+                    None
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_insert_json() {
+        let table = CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted("users_1"));
+        assert_eq!(
+            parse("insert into users_1 json '{\"name\": \"it''s me\"}' default unset"),
+            Ok((
+                "",
+                CqlInsert::new(
+                    table.clone(),
+                    CqlInsertValues::Json(
+                        CqlTerm::Constant(CqlConstant::String(
+                            "{\"name\": \"it's me\"}".to_string()
+                        )),
+                        Some(CqlJsonDefault::Unset)
+                    ),
+                    None,
+                    None
+                )
+            ))
+        );
+        assert_eq!(
+            parse("INSERT INTO users_1 JSON ? DEFAULT NULL IF NOT EXISTS;"),
+            Ok((
+                ";",
+                CqlInsert::new(
+                    table.clone(),
+                    CqlInsertValues::Json(
+                        CqlTerm::BindMarker(CqlBindMarker::Positional),
+                        Some(CqlJsonDefault::Null)
+                    ),
+                    Some(CqlCondition::NotExists),
+                    None
+                )
+            ))
+        );
+        assert_eq!(
+            parse("INSERT INTO users_1 JSON :row_1"),
+            Ok((
+                "",
+                CqlInsert::new(
+                    table,
+                    CqlInsertValues::Json(
+                        CqlTerm::BindMarker(CqlBindMarker::Named(CqlIdentifier::Unquoted("row_1"))),
+                        None
+                    ),
+                    None,
+                    None
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_insert_using() {
+        let (remaining, insert) = parse(
+            "INSERT INTO users_1 (id_1) VALUES (1) IF NOT EXISTS USING TTL 60 AND TIMESTAMP 7;",
+        )
+        .unwrap();
+        assert_eq!(remaining, ";");
+        assert_eq!(insert.condition(), &Some(CqlCondition::NotExists));
+        assert_eq!(
+            insert.using(),
+            &Some(CqlUsing::new(
+                Some(CqlTerm::Constant(CqlConstant::Integer("60"))),
+                Some(CqlTerm::Constant(CqlConstant::Integer("7"))),
+                None
+            ))
+        );
+        let (_, insert) = parse("INSERT INTO users_1 JSON ? USING TIMEOUT 5s").unwrap();
+        assert_eq!(
+            insert.using().as_ref().map(CqlUsing::timeout),
+            Some(&Some(CqlTerm::Constant(CqlConstant::Duration("5s"))))
+        );
+    }
+
+    #[test]
+    fn test_bind_markers() {
+        let (_, insert) = parse(
+            "INSERT INTO users_1 (id_1, tags_1, name_1) VALUES (?, {?: 1}, :name_1) USING TTL ?",
+        )
+        .unwrap();
+        assert_eq!(
+            insert.bind_markers().collect::<Vec<_>>(),
+            vec![
+                &CqlBindMarker::Positional,
+                &CqlBindMarker::Positional,
+                &CqlBindMarker::Named(CqlIdentifier::Unquoted("name_1")),
+                &CqlBindMarker::Positional,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_insert() {
+        let input = "INSERT INTO users_1 (user_id, name_1) VALUES (42)";
+        let Err(nom::Err::Failure(error)) = parse(input) else {
+            panic!("missing value accepted");
+        };
+        assert!(error.errors.contains(&(
+            &input[20..],
+            VerboseErrorKind::Context("the number of values does not match the number of columns")
+        )));
+        assert!(matches!(
+            parse("INSERT INTO users_1 JSON 42"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(matches!(
+            parse("INSERT INTO users_1 JSON '{}' DEFAULT zero_1"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(matches!(
+            parse("INSERT INTO users_1 VALUES (42)"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(matches!(
+            parse("INSERT INTO users_1 (id_1) VALUES (42) IF EXISTS"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+}
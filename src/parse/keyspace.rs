@@ -0,0 +1,128 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::keyspace::CqlCreateKeyspace;
+use crate::model::replication::CqlReplication;
+use crate::model::table::options::CqlOptionValue;
+use crate::parse::table::options::{option_name, option_value};
+use crate::parse::Parse;
+use crate::utils::{
+    debug_event, keyword, space0_between, space1_before, space1_between, space1_tags_no_case, ws0,
+    ws1,
+};
+use nom::bytes::complete::tag;
+use nom::combinator::{cut, opt};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
+use nom::multi::separated_list1;
+use nom::sequence::{pair, preceded};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlCreateKeyspace<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("CREATE"), keyword("KEYSPACE")))(input)?;
+        let (input, if_not_exists) =
+            opt(space1_before(space1_tags_no_case(["IF", "NOT", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlIdentifier::parse)(input)?;
+        debug_event!(name = &*name, "keyspace");
+        // Once the name is read the statement is a keyspace, its options are mandatory.
+        let (remaining, options) = cut(preceded(
+            pair(ws0, keyword("WITH")),
+            preceded(
+                ws1,
+                separated_list1(
+                    pair(space1_before(keyword("AND")), ws0),
+                    space0_between((option_name, context("=", tag("=")), option_value)),
+                ),
+            ),
+        ))(input)?;
+
+        let mut replication = None;
+        let mut durable_writes = None;
+        let mut others = Vec::new();
+        for (name, _, value) in options {
+            match (name.to_ascii_lowercase().as_str(), value) {
+                ("replication", CqlOptionValue::Map(entries)) => {
+                    replication = Some(CqlReplication::from_entries(entries))
+                }
+                ("durable_writes", value) if value.as_bool().is_some() => {
+                    durable_writes = value.as_bool()
+                }
+                (_, value) => others.push((name, value)),
+            }
+        }
+        let replication = replication.ok_or_else(|| {
+            nom::Err::Failure(E::add_context(
+                input,
+                "replication",
+                E::from_error_kind(input, ErrorKind::Verify),
+            ))
+        })?;
+
+        Ok((
+            remaining,
+            CqlCreateKeyspace::new(
+                if_not_exists.is_some(),
+                name,
+                replication,
+                durable_writes,
+                others,
+            ),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_create_keyspace() {
+        let input = "CREATE KEYSPACE IF NOT EXISTS shop_ks WITH replication = \
+            {'class': 'NetworkTopologyStrategy', 'dc1': 3, 'dc2': '2'}  AND durable_writes = true";
+        let result: IResult<_, _, nom::error::Error<_>> = CqlCreateKeyspace::parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                "",
+                CqlCreateKeyspace::new(
+                    true,
+                    CqlIdentifier::Unquoted("shop_ks"),
+                    CqlReplication::NetworkTopologyStrategy(vec![
+                        ("dc1".to_string(), 3),
+                        ("dc2".to_string(), 2),
+                    ]),
+                    Some(true),
+                    vec![],
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_simple_strategy() {
+        let input = "create keyspace \"Shop\" with REPLICATION = \
+            {'class': 'org.apache.cassandra.locator.SimpleStrategy', 'replication_factor': '1'} \
+            and graph_engine = 'Core';";
+        let result: IResult<_, _, nom::error::Error<_>> = CqlCreateKeyspace::parse(input);
+        let (remaining, keyspace) = result.unwrap();
+        assert_eq!(remaining, ";");
+        assert!(!keyspace.if_not_exists());
+        assert_eq!(keyspace.name(), &CqlIdentifier::Quoted("Shop".to_string()));
+        assert_eq!(keyspace.replication(), &CqlReplication::SimpleStrategy(1));
+        assert_eq!(keyspace.durable_writes(), None);
+        assert_eq!(
+            keyspace.options(),
+            &vec![("graph_engine", CqlOptionValue::String("Core".to_string()))]
+        );
+    }
+
+    #[test]
+    fn test_replication_required() {
+        let result: IResult<_, CqlCreateKeyspace<_>, nom::error::Error<_>> =
+            CqlCreateKeyspace::parse("CREATE KEYSPACE shop_ks WITH durable_writes = false");
+        assert!(matches!(result, Err(nom::Err::Failure(_))));
+        let result: IResult<_, CqlCreateKeyspace<_>, nom::error::Error<_>> =
+            CqlCreateKeyspace::parse("CREATE KEYSPACE shop_ks");
+        assert!(matches!(result, Err(nom::Err::Failure(_))));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::materialized_view::CqlAlterMaterializedView;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::table::options::CqlTableOptions;
+use crate::parse::Parse;
+use crate::utils::{
+    debug_event, keyword, space1_before, space1_between, space1_tags_no_case, ws0, ws1,
+};
+use nom::combinator::{cut, opt};
+use nom::error::{ContextError, ParseError};
+use nom::sequence::{pair, preceded};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlAlterMaterializedView<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) =
+            space1_between((keyword("ALTER"), keyword("MATERIALIZED"), keyword("VIEW")))(input)?;
+        let (input, if_exists) = opt(space1_before(space1_tags_no_case(["IF", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlQualifiedIdentifier::parse)(input)?;
+        debug_event!(
+            keyspace = name.keyspace().as_ref().map(|keyspace| &**keyspace),
+            name = &**name.name(),
+            "materialized view"
+        );
+        // Once `WITH` is read the options are mandatory.
+        let (input, options) = preceded(
+            pair(ws0, keyword("WITH")),
+            cut(preceded(ws1, CqlTableOptions::<_, CqlIdentifier<_>>::parse)),
+        )(input)?;
+
+        Ok((
+            input,
+            CqlAlterMaterializedView::new(if_exists.is_some(), name, options),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::order::CqlOrder;
+    use crate::model::table::compaction::CqlCompaction;
+    use crate::model::table::options::{CqlOptionValue, CqlTableOption};
+
+    #[test]
+    fn test_parse_alter_materialized_view() {
+        let input = "ALTER MATERIALIZED VIEW IF EXISTS my_ks.my_view \
+            WITH compaction = {'class': 'LeveledCompactionStrategy'} AND gc_grace_seconds = 3600";
+        let result: IResult<_, _, nom::error::Error<_>> = CqlAlterMaterializedView::parse(input);
+        assert_eq!(
+            result,
+            Ok((
+                "",
+                CqlAlterMaterializedView::new(
+                    true,
+                    CqlQualifiedIdentifier::new(
+                        Some(CqlIdentifier::Unquoted("my_ks")),
+                        CqlIdentifier::Unquoted("my_view"),
+                    ),
+                    CqlTableOptions::new(
+                        false,
+                        vec![],
+                        vec![
+                            CqlTableOption::Compaction(Box::new(CqlCompaction::from_entries(
+                                vec![(
+                                    "class".to_string(),
+                                    CqlOptionValue::String("LeveledCompactionStrategy".to_string())
+                                )]
+                            ))),
+                            CqlTableOption::GcGraceSeconds(3600),
+                        ],
+                    ),
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_clustering_order() {
+        let input = "alter materialized view my_view with CLUSTERING ORDER BY (my_field ASC)";
+        let result: IResult<_, _, nom::error::Error<_>> = CqlAlterMaterializedView::parse(input);
+        let (_, view) = result.unwrap();
+        assert!(!view.if_exists());
+        assert_eq!(
+            view.options().clustering_order(),
+            &vec![(CqlIdentifier::Unquoted("my_field"), CqlOrder::Asc)]
+        );
+    }
+
+    #[test]
+    fn test_options_required() {
+        let result: IResult<_, _, nom::error::Error<_>> =
+            CqlAlterMaterializedView::parse("ALTER MATERIALIZED VIEW my_view");
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,314 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::permission::{CqlGrant, CqlPermission, CqlResource, CqlRevoke};
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::parse::function::parse_signature;
+use crate::parse::Parse;
+use crate::utils::{
+    keyword, space1_before, space1_tags_no_case, string_constant, ws0, ws1, ErrorInput,
+};
+use nom::branch::alt;
+use nom::combinator::{cut, map, opt, value};
+use nom::error::{context, ContextError, ParseError};
+use nom::sequence::{pair, preceded, terminated};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>>
+    Parse<&'de str, E> for CqlGrant<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = keyword("GRANT")(input)?;
+        let (input, (permission, resource)) = cut(permission_on_resource)(input)?;
+        let (input, role) = cut(preceded(
+            pair(ws0, keyword("TO")),
+            space1_before(CqlIdentifier::parse),
+        ))(input)?;
+
+        Ok((input, CqlGrant::new(permission, resource, role)))
+    }
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>>
+    Parse<&'de str, E> for CqlRevoke<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = keyword("REVOKE")(input)?;
+        let (input, (permission, resource)) = cut(permission_on_resource)(input)?;
+        let (input, role) = cut(preceded(
+            pair(ws0, keyword("FROM")),
+            space1_before(CqlIdentifier::parse),
+        ))(input)?;
+
+        Ok((input, CqlRevoke::new(permission, resource, role)))
+    }
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E> for CqlPermission {
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        alt((
+            terminated(
+                value(CqlPermission::All, keyword("ALL")),
+                opt(space1_before(keyword("PERMISSIONS"))),
+            ),
+            terminated(
+                alt((
+                    value(CqlPermission::Create, keyword("CREATE")),
+                    value(CqlPermission::Alter, keyword("ALTER")),
+                    value(CqlPermission::Drop, keyword("DROP")),
+                    // Before `SELECT`, which is its prefix.
+                    value(CqlPermission::SelectMasked, keyword("SELECT_MASKED")),
+                    value(CqlPermission::Select, keyword("SELECT")),
+                    value(CqlPermission::Modify, keyword("MODIFY")),
+                    value(CqlPermission::Authorize, keyword("AUTHORIZE")),
+                    value(CqlPermission::Describe, keyword("DESCRIBE")),
+                    value(CqlPermission::Execute, keyword("EXECUTE")),
+                    value(CqlPermission::Unmask, keyword("UNMASK")),
+                )),
+                opt(space1_before(keyword("PERMISSION"))),
+            ),
+        ))(input)
+    }
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>>
+    Parse<&'de str, E> for CqlResource<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        alt((
+            value(
+                CqlResource::AllKeyspaces,
+                space1_tags_no_case(["ALL", "KEYSPACES"]),
+            ),
+            value(CqlResource::AllRoles, space1_tags_no_case(["ALL", "ROLES"])),
+            map(
+                preceded(
+                    space1_tags_no_case(["ALL", "FUNCTIONS"]),
+                    opt(preceded(
+                        space1_before(space1_tags_no_case(["IN", "KEYSPACE"])),
+                        space1_before(CqlIdentifier::parse),
+                    )),
+                ),
+                CqlResource::AllFunctions,
+            ),
+            value(
+                CqlResource::AllMBeans,
+                space1_tags_no_case(["ALL", "MBEANS"]),
+            ),
+            map(
+                preceded(pair(keyword("KEYSPACE"), ws1), CqlIdentifier::parse),
+                CqlResource::Keyspace,
+            ),
+            map(
+                preceded(pair(keyword("ROLE"), ws1), CqlIdentifier::parse),
+                CqlResource::Role,
+            ),
+            map(
+                preceded(
+                    pair(keyword("FUNCTION"), ws1),
+                    pair(
+                        CqlQualifiedIdentifier::parse,
+                        preceded(ws0, cut(parse_signature)),
+                    ),
+                ),
+                |(name, signature)| CqlResource::Function(name, signature),
+            ),
+            map(
+                preceded(pair(keyword("MBEANS"), ws1), string_constant),
+                CqlResource::MBeans,
+            ),
+            map(
+                preceded(pair(keyword("MBEAN"), ws1), string_constant),
+                CqlResource::MBean,
+            ),
+            map(
+                preceded(
+                    opt(pair(keyword("TABLE"), ws1)),
+                    CqlQualifiedIdentifier::parse,
+                ),
+                CqlResource::Table,
+            ),
+        ))(input)
+    }
+}
+
+/// Parses the permission and the resource of a `GRANT` or `REVOKE` statement, after its keyword.
+fn permission_on_resource<
+    'de,
+    E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>,
+>(
+    input: &'de str,
+) -> IResult<&'de str, (CqlPermission, CqlResource<&'de str>), E> {
+    let (input, permission) = space1_before(context("permission", CqlPermission::parse))(input)?;
+    let (input, resource) = preceded(
+        space1_before(keyword("ON")),
+        space1_before(context("resource", CqlResource::parse)),
+    )(input)?;
+
+    Ok((input, (permission, resource)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::cql_type::CqlType;
+
+    fn parse(input: &str) -> IResult<&str, CqlGrant<&str>, nom::error::Error<&str>> {
+        CqlGrant::parse(input)
+    }
+
+    fn resource(input: &str) -> CqlResource<&str> {
+        let result: IResult<_, _, nom::error::Error<&str>> = CqlResource::parse(input);
+        let (remaining, resource) = result.unwrap();
+        assert_eq!(remaining, "");
+        resource
+    }
+
+    #[test]
+    fn test_parse_grant() {
+        assert_eq!(
+            parse("GRANT SELECT ON KEYSPACE my_ks TO analyst_1;"),
+            Ok((
+                ";",
+                CqlGrant::new(
+                    CqlPermission::Select,
+                    CqlResource::Keyspace(CqlIdentifier::Unquoted("my_ks")),
+                    CqlIdentifier::Unquoted("analyst_1")
+                )
+            ))
+        );
+        assert_eq!(
+            parse("grant modify on table my_ks.orders_v1 to app_1"),
+            Ok((
+                "",
+                CqlGrant::new(
+                    CqlPermission::Modify,
+                    CqlResource::Table(CqlQualifiedIdentifier::new(
+                        Some(CqlIdentifier::Unquoted("my_ks")),
+                        CqlIdentifier::Unquoted("orders_v1")
+                    )),
+                    CqlIdentifier::Unquoted("app_1")
+                )
+            ))
+        );
+        assert_eq!(
+            parse("GRANT ALL PERMISSIONS ON ALL KEYSPACES TO \"Site Admin\""),
+            Ok((
+                "",
+                CqlGrant::new(
+                    CqlPermission::All,
+                    CqlResource::AllKeyspaces,
+                    CqlIdentifier::Quoted("Site Admin".to_string())
+                )
+            ))
+        );
+        assert!(matches!(
+            parse("GRANT SELECT ON KEYSPACE my_ks"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_revoke() {
+        fn parse(input: &str) -> IResult<&str, CqlRevoke<&str>, nom::error::Error<&str>> {
+            CqlRevoke::parse(input)
+        }
+
+        assert_eq!(
+            parse("REVOKE SELECT ON my_ks.orders_v1 FROM analyst_1;"),
+            Ok((
+                ";",
+                CqlRevoke::new(
+                    CqlPermission::Select,
+                    CqlResource::Table(CqlQualifiedIdentifier::new(
+                        Some(CqlIdentifier::Unquoted("my_ks")),
+                        CqlIdentifier::Unquoted("orders_v1")
+                    )),
+                    CqlIdentifier::Unquoted("analyst_1")
+                )
+            ))
+        );
+        assert_eq!(
+            parse("revoke all permissions on all keyspaces from \"Site Admin\""),
+            Ok((
+                "",
+                CqlRevoke::new(
+                    CqlPermission::All,
+                    CqlResource::AllKeyspaces,
+                    CqlIdentifier::Quoted("Site Admin".to_string())
+                )
+            ))
+        );
+        assert_eq!(
+            parse("REVOKE ALL ON ALL KEYSPACES FROM \"Team \"\"A\"\"\""),
+            Ok((
+                "",
+                CqlRevoke::new(
+                    CqlPermission::All,
+                    CqlResource::AllKeyspaces,
+                    CqlIdentifier::Quoted("Team \"A\"".to_string())
+                )
+            ))
+        );
+        assert!(matches!(
+            parse("REVOKE SELECT ON my_ks.orders_v1 TO analyst_1"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_permission() {
+        for (input, expected) in [
+            ("ALL", CqlPermission::All),
+            ("alter", CqlPermission::Alter),
+            ("AUTHORIZE PERMISSION", CqlPermission::Authorize),
+            ("SELECT_MASKED", CqlPermission::SelectMasked),
+            ("Select", CqlPermission::Select),
+            ("EXECUTE", CqlPermission::Execute),
+        ] {
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlPermission::parse(input);
+            assert_eq!(result, Ok(("", expected)), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_resource() {
+        let table = CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted("table_1"));
+        assert_eq!(resource("table_1"), CqlResource::Table(table.clone()));
+        assert_eq!(resource("TABLE table_1"), CqlResource::Table(table));
+        assert_eq!(
+            resource("keyspace_1"),
+            CqlResource::Table(CqlQualifiedIdentifier::new(
+                None,
+                CqlIdentifier::Unquoted("keyspace_1")
+            ))
+        );
+        assert_eq!(resource("ALL ROLES"), CqlResource::AllRoles);
+        assert_eq!(
+            resource("ROLE admin_1"),
+            CqlResource::Role(CqlIdentifier::Unquoted("admin_1"))
+        );
+        assert_eq!(resource("ALL FUNCTIONS"), CqlResource::AllFunctions(None));
+        assert_eq!(
+            resource("all functions in keyspace my_ks"),
+            CqlResource::AllFunctions(Some(CqlIdentifier::Unquoted("my_ks")))
+        );
+        assert_eq!(
+            resource("FUNCTION my_ks.average_1(int, list<text>)"),
+            CqlResource::Function(
+                CqlQualifiedIdentifier::new(
+                    Some(CqlIdentifier::Unquoted("my_ks")),
+                    CqlIdentifier::Unquoted("average_1")
+                ),
+                vec![CqlType::INT, CqlType::LIST(Box::new(CqlType::TEXT))]
+            )
+        );
+        assert_eq!(resource("ALL MBEANS"), CqlResource::AllMBeans);
+        assert_eq!(
+            resource("MBEAN 'org.apache.cassandra.db:type=Tables'"),
+            CqlResource::MBean("org.apache.cassandra.db:type=Tables".to_string())
+        );
+        assert_eq!(
+            resource("MBEANS 'org.apache.cassandra.db:*'"),
+            CqlResource::MBeans("org.apache.cassandra.db:*".to_string())
+        );
+    }
+}
@@ -1,20 +1,23 @@
 use crate::model::identifier::CqlIdentifier;
 use crate::model::qualified_identifier::CqlQualifiedIdentifier;
 use crate::parse::Parse;
+use crate::utils::ws0;
 use nom::bytes::complete::tag;
-use nom::character::complete::multispace0;
 use nom::combinator::opt;
-use nom::error::ParseError;
+use nom::error::{ContextError, ParseError};
+use nom::sequence::pair;
 use nom::IResult;
 
-impl<'de, E: ParseError<&'de str>> Parse<&'de str, E> for CqlQualifiedIdentifier<&'de str> {
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlQualifiedIdentifier<&'de str>
+{
     fn parse(input: &'de str) -> IResult<&'de str, CqlQualifiedIdentifier<&'de str>, E> {
         let (input, name_or_keyspace) = CqlIdentifier::parse(input)?;
-        let (input, _) = multispace0(input)?;
-        let (input, dot) = opt(tag("."))(input)?;
+        // The whitespace after the name is only consumed together with a dot.
+        let (input, dot) = opt(pair(ws0, tag(".")))(input)?;
 
         if dot.is_some() {
-            let (input, _) = multispace0(input)?;
+            let (input, _) = ws0(input)?;
             let (input, name) = CqlIdentifier::parse(input)?;
             Ok((
                 input,
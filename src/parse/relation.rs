@@ -0,0 +1,427 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::relation::{CqlOperator, CqlRelation, CqlWhereClause};
+use crate::model::term::{CqlConstant, CqlTerm};
+use crate::parse::term::{arguments, bind_marker, identifier_char};
+use crate::parse::Parse;
+use crate::utils::{keyword, space1_tags_no_case, string_constant, ws0, ws1};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::{map, not, value};
+use nom::error::{context, ContextError, ParseError};
+use nom::multi::{separated_list0, separated_list1};
+use nom::sequence::{delimited, pair, preceded, tuple};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlWhereClause<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        map(
+            separated_list1(delimited(ws1, keyword("AND"), ws1), CqlRelation::parse),
+            CqlWhereClause::new,
+        )(input)
+    }
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlRelation<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        alt((
+            map(
+                tuple((
+                    preceded(pair(keyword("TOKEN"), ws0), columns),
+                    delimited(ws0, operator, ws0),
+                    CqlTerm::parse,
+                )),
+                |(columns, operator, term)| CqlRelation::Token(columns, operator, term),
+            ),
+            map(
+                pair(
+                    columns,
+                    alt((
+                        pair(delimited(ws0, operator, ws0), tuple_literal),
+                        pair(
+                            value(
+                                CqlOperator::In,
+                                tuple((ws0, keyword("IN"), not(identifier_char), ws0)),
+                            ),
+                            alt((
+                                map(
+                                    delimited(
+                                        pair(tag("("), ws0),
+                                        separated_list0(
+                                            tag(","),
+                                            delimited(ws0, tuple_literal, ws0),
+                                        ),
+                                        context(")", tag(")")),
+                                    ),
+                                    CqlTerm::TupleLiteral,
+                                ),
+                                map(bind_marker, CqlTerm::BindMarker),
+                            )),
+                        ),
+                    )),
+                ),
+                |(columns, (operator, term))| CqlRelation::Tuple(columns, operator, term),
+            ),
+            map(
+                pair(
+                    CqlIdentifier::parse,
+                    alt((
+                        pair(delimited(ws0, operator, ws0), CqlTerm::parse),
+                        pair(word_operator(CqlOperator::In, keyword("IN")), in_values),
+                        // CONTAINS is a prefix of CONTAINS KEY.
+                        pair(
+                            word_operator(
+                                CqlOperator::ContainsKey,
+                                space1_tags_no_case(["CONTAINS", "KEY"]),
+                            ),
+                            CqlTerm::parse,
+                        ),
+                        pair(
+                            word_operator(CqlOperator::Contains, keyword("CONTAINS")),
+                            CqlTerm::parse,
+                        ),
+                        pair(
+                            word_operator(CqlOperator::Like, keyword("LIKE")),
+                            alt((
+                                map(string_constant, |text| {
+                                    CqlTerm::Constant(CqlConstant::String(text))
+                                }),
+                                map(bind_marker, CqlTerm::BindMarker),
+                            )),
+                        ),
+                    )),
+                ),
+                |(column, (operator, term))| CqlRelation::Column(column, operator, term),
+            ),
+        ))(input)
+    }
+}
+
+/// Parses a parenthesized list of columns, like `(day, hour)`.
+///
+/// A parenthesized relation, like `(day = 1)`, is no list of columns.
+fn columns<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, Vec<CqlIdentifier<&'de str>>, E> {
+    delimited(
+        tag("("),
+        separated_list1(tag(","), delimited(ws0, CqlIdentifier::parse, ws0)),
+        context(")", tag(")")),
+    )(input)
+}
+
+/// Parses an operator written as a word, like `IN`, surrounded by whitespace.
+fn word_operator<'de, O, E: ParseError<&'de str> + ContextError<&'de str>>(
+    operator: CqlOperator,
+    word: impl FnMut(&'de str) -> IResult<&'de str, O, E>,
+) -> impl FnMut(&'de str) -> IResult<&'de str, CqlOperator, E> {
+    value(operator, tuple((ws1, word, not(identifier_char), ws0)))
+}
+
+/// Parses the values of an `IN` relation, a parenthesized list of terms or a bind marker.
+pub(crate) fn in_values<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlTerm<&'de str>, E> {
+    alt((
+        map(arguments, CqlTerm::TupleLiteral),
+        map(bind_marker, CqlTerm::BindMarker),
+    ))(input)
+}
+
+/// Parses a tuple literal, like `(1, 'a')`, or a bind marker.
+fn tuple_literal<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlTerm<&'de str>, E> {
+    alt((
+        map(arguments, CqlTerm::TupleLiteral),
+        map(bind_marker, CqlTerm::BindMarker),
+    ))(input)
+}
+
+pub(crate) fn operator<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlOperator, E> {
+    context(
+        "operator",
+        // The two character operators first, `<` is a prefix of `<=`.
+        alt((
+            value(CqlOperator::Le, tag("<=")),
+            value(CqlOperator::Ge, tag(">=")),
+            value(CqlOperator::Ne, tag("!=")),
+            value(CqlOperator::Eq, tag("=")),
+            value(CqlOperator::Lt, tag("<")),
+            value(CqlOperator::Gt, tag(">")),
+        )),
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+    use crate::model::term::CqlBindMarker;
+    use nom::error::VerboseError;
+
+    fn parse(input: &str) -> IResult<&str, CqlWhereClause<&str>, VerboseError<&str>> {
+        CqlWhereClause::parse(input)
+    }
+
+    #[test]
+    fn test_parse_where_clause() {
+        assert_eq!(
+            parse("user_id=42 and\n  \"Created At\" >= '2024-01-01' AND score_1 != 1.5;"),
+            Ok((
+                ";",
+                CqlWhereClause::new(vec![
+                    CqlRelation::Column(
+                        CqlIdentifier::Unquoted("user_id"),
+                        CqlOperator::Eq,
+                        CqlTerm::Constant(CqlConstant::Integer("42"))
+                    ),
+                    CqlRelation::Column(
+                        CqlIdentifier::Quoted("Created At".to_string()),
+                        CqlOperator::Ge,
+                        CqlTerm::Constant(CqlConstant::String("2024-01-01".to_string()))
+                    ),
+                    CqlRelation::Column(
+                        CqlIdentifier::Unquoted("score_1"),
+                        CqlOperator::Ne,
+                        CqlTerm::Constant(CqlConstant::Float("1.5"))
+                    ),
+                ])
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_operators() {
+        for (input, operator) in [
+            ("my_field < 1", CqlOperator::Lt),
+            ("my_field > 1", CqlOperator::Gt),
+            ("my_field <= 1", CqlOperator::Le),
+            ("my_field<1", CqlOperator::Lt),
+        ] {
+            let (_, clause) = parse(input).unwrap();
+            assert_eq!(
+                clause.relations(),
+                &vec![CqlRelation::Column(
+                    CqlIdentifier::Unquoted("my_field"),
+                    operator,
+                    CqlTerm::Constant(CqlConstant::Integer("1"))
+                )]
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_trailing_and() {
+        assert_eq!(
+            parse("my_field = 1 AND LIMIT")
+                .map(|(remaining, clause)| (remaining, clause.relations().len())),
+            Ok((" AND LIMIT", 1))
+        );
+        assert!(parse("my_field 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_token_and_function_calls() {
+        let call = |name, arguments| {
+            CqlTerm::FunctionCall(
+                CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted(name)),
+                arguments,
+            )
+        };
+        let (remaining, clause) = parse(
+            "TOKEN( user_id , shop_id ) > token(?, ?) AND created_at < to_timestamp(now_1())",
+        )
+        .unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(
+            clause.relations(),
+            &vec![
+                CqlRelation::Token(
+                    vec![
+                        CqlIdentifier::Unquoted("user_id"),
+                        CqlIdentifier::Unquoted("shop_id")
+                    ],
+                    CqlOperator::Gt,
+                    call(
+                        "token",
+                        vec![
+                            CqlTerm::BindMarker(CqlBindMarker::Positional),
+                            CqlTerm::BindMarker(CqlBindMarker::Positional)
+                        ]
+                    )
+                ),
+                CqlRelation::Column(
+                    CqlIdentifier::Unquoted("created_at"),
+                    CqlOperator::Lt,
+                    call("to_timestamp", vec![call("now_1", vec![])])
+                ),
+            ]
+        );
+        assert_eq!(clause.relations()[0].columns().len(), 2);
+
+        let (remaining, clause) = parse("token(a) > token(?)").unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(
+            clause.relations(),
+            &vec![CqlRelation::Token(
+                vec![CqlIdentifier::Unquoted("a")],
+                CqlOperator::Gt,
+                call(
+                    "token",
+                    vec![CqlTerm::BindMarker(CqlBindMarker::Positional)]
+                )
+            )]
+        );
+
+        // A column starting with token is no token relation.
+        let (_, clause) = parse("token_id = 1").unwrap();
+        assert_eq!(
+            clause.relations()[0].columns(),
+            &[CqlIdentifier::Unquoted("token_id")]
+        );
+    }
+
+    #[test]
+    fn test_parse_tuple_relations() {
+        let column = |name| CqlIdentifier::Unquoted(name);
+        let (remaining, clause) = parse(
+            "pk_1 = 1 AND (day_1, \"Hour\") > (?, ?) AND ( day_1 , hour_1 )IN((1, 2), (3,4)) \
+             AND (day_1) <= :day_1;",
+        )
+        .unwrap();
+        assert_eq!(remaining, ";");
+        assert_eq!(
+            clause.relations()[1..],
+            [
+                CqlRelation::Tuple(
+                    vec![column("day_1"), CqlIdentifier::Quoted("Hour".to_string())],
+                    CqlOperator::Gt,
+                    CqlTerm::TupleLiteral(vec![
+                        CqlTerm::BindMarker(CqlBindMarker::Positional),
+                        CqlTerm::BindMarker(CqlBindMarker::Positional),
+                    ])
+                ),
+                CqlRelation::Tuple(
+                    vec![column("day_1"), column("hour_1")],
+                    CqlOperator::In,
+                    CqlTerm::TupleLiteral(vec![
+                        CqlTerm::TupleLiteral(vec![
+                            CqlTerm::Constant(CqlConstant::Integer("1")),
+                            CqlTerm::Constant(CqlConstant::Integer("2"))
+                        ]),
+                        CqlTerm::TupleLiteral(vec![
+                            CqlTerm::Constant(CqlConstant::Integer("3")),
+                            CqlTerm::Constant(CqlConstant::Integer("4"))
+                        ]),
+                    ])
+                ),
+                CqlRelation::Tuple(
+                    vec![column("day_1")],
+                    CqlOperator::Le,
+                    CqlTerm::BindMarker(CqlBindMarker::Named(column("day_1")))
+                ),
+            ]
+        );
+        assert_eq!(
+            clause.relations()[2].columns(),
+            &[column("day_1"), column("hour_1")]
+        );
+        assert_eq!(clause.bind_markers().count(), 3);
+
+        let (_, clause) = parse("(day_1, hour_1) IN ?").unwrap();
+        assert_eq!(
+            clause.relations()[0].term(),
+            &CqlTerm::BindMarker(CqlBindMarker::Positional)
+        );
+        // A parenthesized relation is no tuple relation.
+        assert!(parse("(day_1 = 1)").is_err());
+        assert!(parse("(day_1, hour_1) > 1").is_err());
+    }
+
+    #[test]
+    fn test_parse_like() {
+        let (remaining, clause) =
+            parse("name_1 LIKE 'Ali%' AND \"like\" like:pattern_1 AND like_1 = 1;").unwrap();
+        assert_eq!(remaining, ";");
+        assert_eq!(
+            clause.relations(),
+            &vec![
+                CqlRelation::Column(
+                    CqlIdentifier::Unquoted("name_1"),
+                    CqlOperator::Like,
+                    CqlTerm::Constant(CqlConstant::String("Ali%".to_string()))
+                ),
+                CqlRelation::Column(
+                    CqlIdentifier::Quoted("like".to_string()),
+                    CqlOperator::Like,
+                    CqlTerm::BindMarker(CqlBindMarker::Named(CqlIdentifier::Unquoted("pattern_1")))
+                ),
+                CqlRelation::Column(
+                    CqlIdentifier::Unquoted("like_1"),
+                    CqlOperator::Eq,
+                    CqlTerm::Constant(CqlConstant::Integer("1"))
+                ),
+            ]
+        );
+        // LIKE is a keyword only on its own and only matches strings.
+        assert!(parse("name_1 like_2 'Ali%'").is_err());
+        assert!(parse("name_1 LIKE 42").is_err());
+    }
+
+    #[test]
+    fn test_parse_in_and_contains() {
+        let column = |name| CqlIdentifier::Unquoted(name);
+        let (remaining, clause) = parse(
+            "pk_1 IN (1, 2, 3) AND pk_2 in ? AND tags_1 CONTAINS 'rust' \
+             AND map_1 contains  key 'k' AND \"contains\" CONTAINS key_1(1);",
+        )
+        .unwrap();
+        assert_eq!(remaining, ";");
+        assert_eq!(
+            clause.relations(),
+            &vec![
+                CqlRelation::Column(
+                    column("pk_1"),
+                    CqlOperator::In,
+                    CqlTerm::TupleLiteral(vec![
+                        CqlTerm::Constant(CqlConstant::Integer("1")),
+                        CqlTerm::Constant(CqlConstant::Integer("2")),
+                        CqlTerm::Constant(CqlConstant::Integer("3"))
+                    ])
+                ),
+                CqlRelation::Column(
+                    column("pk_2"),
+                    CqlOperator::In,
+                    CqlTerm::BindMarker(CqlBindMarker::Positional)
+                ),
+                CqlRelation::Column(
+                    column("tags_1"),
+                    CqlOperator::Contains,
+                    CqlTerm::Constant(CqlConstant::String("rust".to_string()))
+                ),
+                CqlRelation::Column(
+                    column("map_1"),
+                    CqlOperator::ContainsKey,
+                    CqlTerm::Constant(CqlConstant::String("k".to_string()))
+                ),
+                CqlRelation::Column(
+                    CqlIdentifier::Quoted("contains".to_string()),
+                    CqlOperator::Contains,
+                    CqlTerm::FunctionCall(
+                        CqlQualifiedIdentifier::new(None, column("key_1")),
+                        vec![CqlTerm::Constant(CqlConstant::Integer("1"))]
+                    )
+                ),
+            ]
+        );
+        assert!(clause.relations()[3].operator().is_contains_key());
+        assert!(parse("pk_1 IN 1").is_err());
+        assert!(parse("tags_1 CONTAINS").is_err());
+    }
+}
@@ -0,0 +1,326 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::role::{
+    CqlAlterRole, CqlCreateRole, CqlDatacenters, CqlDropRole, CqlRoleOptions,
+};
+use crate::parse::Parse;
+use crate::utils::{
+    braced, keyword, space0_between, space1_before, space1_between, space1_tags_no_case,
+    string_constant, ws0,
+};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::{cut, map, opt, value};
+use nom::error::{context, ContextError, ParseError};
+use nom::sequence::{delimited, pair, preceded};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlCreateRole<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("CREATE"), keyword("ROLE")))(input)?;
+        let (input, if_not_exists) =
+            opt(space1_before(space1_tags_no_case(["IF", "NOT", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlIdentifier::parse)(input)?;
+        // Once `WITH` is read the options are mandatory.
+        let (input, options) = opt(preceded(
+            space1_before(keyword("WITH")),
+            cut(space1_before(CqlRoleOptions::parse)),
+        ))(input)?;
+
+        Ok((
+            input,
+            CqlCreateRole::new(if_not_exists.is_some(), name, options.unwrap_or_default()),
+        ))
+    }
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlAlterRole<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("ALTER"), keyword("ROLE")))(input)?;
+        let (input, if_exists) = opt(space1_before(space1_tags_no_case(["IF", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlIdentifier::parse)(input)?;
+        let (input, options) = cut(preceded(
+            space1_before(keyword("WITH")),
+            space1_before(CqlRoleOptions::parse),
+        ))(input)?;
+
+        Ok((input, CqlAlterRole::new(if_exists.is_some(), name, options)))
+    }
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlDropRole<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("DROP"), keyword("ROLE")))(input)?;
+        let (input, if_exists) = opt(space1_before(space1_tags_no_case(["IF", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlIdentifier::parse)(input)?;
+
+        Ok((input, CqlDropRole::new(if_exists.is_some(), name)))
+    }
+}
+
+/// A single option of a role.
+enum RoleOption {
+    Password(String),
+    HashedPassword(String),
+    Login(bool),
+    Superuser(bool),
+    Options(Vec<(String, String)>),
+    Datacenters(CqlDatacenters),
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E> for CqlRoleOptions {
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let mut input = input;
+        let mut password = None;
+        let mut hashed_password = None;
+        let mut login = None;
+        let mut superuser = None;
+        let mut options = None;
+        let mut datacenters = None;
+
+        loop {
+            let (i, option) = alt((
+                map(
+                    preceded(pair(keyword("PASSWORD"), equals), string_constant),
+                    RoleOption::Password,
+                ),
+                map(
+                    preceded(
+                        pair(space1_tags_no_case(["HASHED", "PASSWORD"]), equals),
+                        string_constant,
+                    ),
+                    RoleOption::HashedPassword,
+                ),
+                map(
+                    preceded(pair(keyword("LOGIN"), equals), boolean),
+                    RoleOption::Login,
+                ),
+                map(
+                    preceded(pair(keyword("SUPERUSER"), equals), boolean),
+                    RoleOption::Superuser,
+                ),
+                map(
+                    preceded(
+                        pair(keyword("OPTIONS"), equals),
+                        braced(space0_between((string_constant, tag(":"), string_constant))),
+                    ),
+                    |entries| {
+                        RoleOption::Options(
+                            entries
+                                .into_iter()
+                                .map(|(key, _, value)| (key, value))
+                                .collect(),
+                        )
+                    },
+                ),
+                map(
+                    preceded(
+                        space1_tags_no_case(["ACCESS", "TO"]),
+                        space1_before(alt((
+                            value(
+                                CqlDatacenters::All,
+                                space1_tags_no_case(["ALL", "DATACENTERS"]),
+                            ),
+                            map(
+                                preceded(
+                                    keyword("DATACENTERS"),
+                                    preceded(ws0, braced(string_constant)),
+                                ),
+                                CqlDatacenters::Only,
+                            ),
+                        ))),
+                    ),
+                    RoleOption::Datacenters,
+                ),
+            ))(input)?;
+            match option {
+                RoleOption::Password(value) => password = Some(value),
+                RoleOption::HashedPassword(value) => hashed_password = Some(value),
+                RoleOption::Login(value) => login = Some(value),
+                RoleOption::Superuser(value) => superuser = Some(value),
+                RoleOption::Options(value) => options = Some(value),
+                RoleOption::Datacenters(value) => datacenters = Some(value),
+            }
+
+            match opt(space1_before(keyword("AND")))(i)? {
+                (i, Some(_)) => {
+                    let (i, _) = ws0(i)?;
+                    input = i;
+                }
+                (i, None) => {
+                    input = i;
+                    break;
+                }
+            }
+        }
+
+        Ok((
+            input,
+            CqlRoleOptions::new(
+                password,
+                hashed_password,
+                login,
+                superuser,
+                options,
+                datacenters,
+            ),
+        ))
+    }
+}
+
+/// Recognizes the `=` between the name and the value of an option.
+fn equals<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, &'de str, E> {
+    delimited(ws0, context("=", tag("=")), ws0)(input)
+}
+
+fn boolean<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, bool, E> {
+    alt((value(true, keyword("TRUE")), value(false, keyword("FALSE"))))(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(input: &str) -> IResult<&str, CqlCreateRole<&str>, nom::error::Error<&str>> {
+        CqlCreateRole::parse(input)
+    }
+
+    #[test]
+    fn test_parse_create_role() {
+        assert_eq!(
+            parse("CREATE ROLE IF NOT EXISTS admin_1 WITH PASSWORD = 'secret' AND LOGIN = true AND SUPERUSER = false AND OPTIONS = {'custom': 'x'};"),
+            Ok((
+                ";",
+                CqlCreateRole::new(
+                    true,
+                    CqlIdentifier::Unquoted("admin_1"),
+                    CqlRoleOptions::new(
+                        Some("secret".to_string()),
+                        None,
+                        Some(true),
+                        Some(false),
+                        Some(vec![("custom".to_string(), "x".to_string())]),
+                        None,
+                    )
+                )
+            ))
+        );
+        assert_eq!(
+            parse("create role \"Site Admin\""),
+            Ok((
+                "",
+                CqlCreateRole::new(
+                    false,
+                    CqlIdentifier::Quoted("Site Admin".to_string()),
+                    CqlRoleOptions::default()
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_role_options() {
+        fn options(input: &str) -> CqlRoleOptions {
+            let result: IResult<_, _, nom::error::Error<&str>> = CqlRoleOptions::parse(input);
+            let (remaining, options) = result.unwrap();
+            assert_eq!(remaining, "");
+            options
+        }
+
+        assert_eq!(
+            options("hashed password='$2a$10$x' and access to datacenters {'dc_1' , 'dc_2'}"),
+            CqlRoleOptions::new(
+                None,
+                Some("$2a$10$x".to_string()),
+                None,
+                None,
+                None,
+                Some(CqlDatacenters::Only(vec![
+                    "dc_1".to_string(),
+                    "dc_2".to_string()
+                ])),
+            )
+        );
+        assert_eq!(
+            options("ACCESS TO ALL DATACENTERS AND LOGIN = FALSE"),
+            CqlRoleOptions::new(
+                None,
+                None,
+                Some(false),
+                None,
+                None,
+                Some(CqlDatacenters::All)
+            )
+        );
+        assert_eq!(options("OPTIONS = {}").options(), &Some(vec![]));
+        assert!(matches!(
+            parse("CREATE ROLE admin_1 WITH LOGIN = maybe"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_alter_role() {
+        fn parse(input: &str) -> IResult<&str, CqlAlterRole<&str>, nom::error::Error<&str>> {
+            CqlAlterRole::parse(input)
+        }
+
+        assert_eq!(
+            parse("ALTER ROLE admin_1 WITH PASSWORD = 'new';"),
+            Ok((
+                ";",
+                CqlAlterRole::new(
+                    false,
+                    CqlIdentifier::Unquoted("admin_1"),
+                    CqlRoleOptions::new(Some("new".to_string()), None, None, None, None, None)
+                )
+            ))
+        );
+        assert_eq!(
+            parse("alter role if exists \"Site Admin\" with superuser = true"),
+            Ok((
+                "",
+                CqlAlterRole::new(
+                    true,
+                    CqlIdentifier::Quoted("Site Admin".to_string()),
+                    CqlRoleOptions::new(None, None, None, Some(true), None, None)
+                )
+            ))
+        );
+        assert!(matches!(
+            parse("ALTER ROLE admin_1"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_drop_role() {
+        fn parse(input: &str) -> IResult<&str, CqlDropRole<&str>, nom::error::Error<&str>> {
+            CqlDropRole::parse(input)
+        }
+
+        assert_eq!(
+            parse("DROP ROLE IF EXISTS admin_1;"),
+            Ok((
+                ";",
+                CqlDropRole::new(true, CqlIdentifier::Unquoted("admin_1"))
+            ))
+        );
+        assert_eq!(
+            parse("drop role \"Site Admin\""),
+            Ok((
+                "",
+                CqlDropRole::new(false, CqlIdentifier::Quoted("Site Admin".to_string()))
+            ))
+        );
+    }
+}
@@ -0,0 +1,607 @@
+use crate::model::cql_type::CqlType;
+use crate::model::identifier::CqlIdentifier;
+use crate::model::order::CqlOrder;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::relation::CqlWhereClause;
+use crate::model::select::{CqlOrdering, CqlSelect, CqlSelection, CqlSelector};
+use crate::model::term::{CqlConstant, CqlTerm};
+use crate::parse::term::{bind_marker, function_name, integer};
+use crate::parse::{Parse, MAX_NESTING_DEPTH};
+use crate::utils::{
+    debug_event, keyword, space1_before, space1_tags_no_case, ws0, ws1, Whitespace,
+};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::combinator::{cut, map, opt, peek, value, verify};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
+use nom::multi::{separated_list0, separated_list1};
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlSelect<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = keyword("SELECT")(input)?;
+        let (after_json, json) = opt(select_modifier("JSON"))(input)?;
+        let (modifier, _) = Whitespace::split_whitespace(after_json);
+        let (after_distinct, distinct) = opt(select_modifier("DISTINCT"))(after_json)?;
+        // A modifier may as well be the name of the first selected column, like in
+        // `SELECT json FROM t`, the clauses are parsed again without it if they fail.
+        let mut retries = Vec::new();
+        if distinct.is_some() {
+            retries.push((after_json, json.is_some(), false));
+        }
+        if json.is_some() {
+            retries.push((input, false, false));
+        }
+        let mut result = clauses(after_distinct)
+            .map(|(input, select)| (input, select, json.is_some(), distinct.is_some()));
+        for (retry, json, distinct) in retries {
+            if result.is_err() {
+                if let Ok((input, select)) = clauses::<E>(retry) {
+                    result = Ok((input, select, json, distinct));
+                }
+            }
+        }
+        let (input, select, json, distinct) = result?;
+        if json && distinct {
+            return Err(nom::Err::Failure(E::add_context(
+                modifier,
+                "JSON and DISTINCT can not be combined",
+                E::from_error_kind(modifier, ErrorKind::Verify),
+            )));
+        }
+        debug_event!(name = &**select.table().name(), "select");
+
+        Ok((input, select.with_json(json).with_distinct(distinct)))
+    }
+}
+
+/// Parses the clauses of a select following SELECT and its modifiers.
+fn clauses<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlSelect<&'de str>, E> {
+    // No other statement starts with SELECT.
+    let (
+        input,
+        (
+            selection,
+            table,
+            where_clause,
+            group_by,
+            order_by,
+            per_partition_limit,
+            limit,
+            allow_filtering,
+        ),
+    ) = cut(tuple((
+        preceded(ws1, selection),
+        preceded(
+            pair(ws0, keyword("FROM")),
+            space1_before(CqlQualifiedIdentifier::parse),
+        ),
+        opt(preceded(
+            pair(ws0, keyword("WHERE")),
+            space1_before(CqlWhereClause::parse),
+        )),
+        map(
+            opt(preceded(
+                pair(ws0, space1_tags_no_case(["GROUP", "BY"])),
+                space1_before(separated_list1(
+                    delimited(ws0, tag(","), ws0),
+                    CqlIdentifier::parse,
+                )),
+            )),
+            Option::unwrap_or_default,
+        ),
+        map(
+            opt(preceded(
+                pair(ws0, space1_tags_no_case(["ORDER", "BY"])),
+                space1_before(ordering),
+            )),
+            Option::unwrap_or_default,
+        ),
+        opt(preceded(
+            pair(ws0, space1_tags_no_case(["PER", "PARTITION", "LIMIT"])),
+            space1_before(limit),
+        )),
+        opt(preceded(pair(ws0, keyword("LIMIT")), space1_before(limit))),
+        map(
+            opt(pair(ws0, space1_tags_no_case(["ALLOW", "FILTERING"]))),
+            |allow_filtering| allow_filtering.is_some(),
+        ),
+    )))(input)?;
+    Ok((
+        input,
+        CqlSelect::new(
+            selection,
+            table,
+            where_clause,
+            group_by,
+            order_by,
+            limit,
+            allow_filtering,
+        )
+        .with_per_partition_limit(per_partition_limit),
+    ))
+}
+
+/// Parses a modifier following SELECT, only if another word follows it.
+fn select_modifier<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    modifier: &'static str,
+) -> impl FnMut(&'de str) -> IResult<&'de str, &'de str, E> {
+    terminated(preceded(ws1, keyword(modifier)), peek(ws1))
+}
+
+/// Parses the selected items, each may be renamed with `AS`.
+fn selection<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlSelection<&'de str>, E> {
+    alt((
+        value(CqlSelection::All, tag("*")),
+        map(
+            separated_list1(
+                delimited(ws0, tag(","), ws0),
+                map(
+                    pair(
+                        |input| selector(input, 0),
+                        opt(preceded(
+                            pair(ws1, keyword("AS")),
+                            space1_before(CqlIdentifier::parse),
+                        )),
+                    ),
+                    |(selector, alias)| match alias {
+                        Some(alias) => CqlSelector::Alias(Box::new(selector), alias),
+                        None => selector,
+                    },
+                ),
+            ),
+            CqlSelection::Selectors,
+        ),
+    ))(input)
+}
+
+/// Parses the arguments of a function nested at `depth`, which can not be renamed.
+fn arguments<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+    depth: usize,
+) -> IResult<&'de str, CqlSelection<&'de str>, E> {
+    alt((
+        value(CqlSelection::All, tag("*")),
+        map(
+            separated_list0(delimited(ws0, tag(","), ws0), |input| {
+                selector(input, depth + 1)
+            }),
+            CqlSelection::Selectors,
+        ),
+    ))(input)
+}
+
+/// Parses a selector nested at `depth`, selectors nested deeper than [`MAX_NESTING_DEPTH`] are
+/// a failure.
+fn selector<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+    depth: usize,
+) -> IResult<&'de str, CqlSelector<&'de str>, E> {
+    if depth > MAX_NESTING_DEPTH {
+        return Err(nom::Err::Failure(E::add_context(
+            input,
+            "selector nested too deeply",
+            E::from_error_kind(input, ErrorKind::Verify),
+        )));
+    }
+    let nested = |input| selector(input, depth + 1);
+
+    alt((
+        map(
+            // Once `CAST (` is read it is a cast, the selector is not parsed again as a call.
+            preceded(
+                tuple((keyword("CAST"), ws0, tag("("))),
+                cut(terminated(
+                    pair(
+                        preceded(ws0, nested),
+                        preceded(pair(ws1, keyword("AS")), space1_before(CqlType::parse)),
+                    ),
+                    pair(ws0, context(")", tag(")"))),
+                )),
+            ),
+            |(selector, cql_type)| CqlSelector::Cast(Box::new(selector), cql_type),
+        ),
+        map(
+            pair(
+                function_name,
+                delimited(
+                    pair(ws0, tag("(")),
+                    delimited(ws0, |input| arguments(input, depth), ws0),
+                    context(")", tag(")")),
+                ),
+            ),
+            |(name, arguments)| CqlSelector::Function(name, arguments),
+        ),
+        map(CqlIdentifier::parse, CqlSelector::Column),
+    ))(input)
+}
+
+/// Parses the columns of an `ORDER BY` clause, columns without an order are ascending.
+fn ordering<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, Vec<CqlOrdering<&'de str>>, E> {
+    separated_list1(delimited(ws0, tag(","), ws0), |input| {
+        let (input, column) = CqlIdentifier::parse(input)?;
+        let mut ordering = alt((
+            map(
+                preceded(
+                    tuple((ws1, keyword("ANN"), ws1, keyword("OF"), ws1)),
+                    cut(context(
+                        "vector",
+                        verify(CqlTerm::parse, |vector| {
+                            vector.is_list() || vector.is_bind_marker()
+                        }),
+                    )),
+                ),
+                |vector| CqlOrdering::Ann(column.clone(), vector),
+            ),
+            map(
+                opt(preceded(
+                    ws1,
+                    alt((
+                        value(CqlOrder::Asc, keyword("ASC")),
+                        value(CqlOrder::Desc, keyword("DESC")),
+                    )),
+                )),
+                |order| CqlOrdering::Column(column.clone(), order.unwrap_or(CqlOrder::Asc)),
+            ),
+        ));
+        ordering(input)
+    })(input)
+}
+
+fn limit<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlTerm<&'de str>, E> {
+    context(
+        "limit",
+        alt((
+            map(integer, |literal| {
+                CqlTerm::Constant(CqlConstant::Integer(literal))
+            }),
+            map(bind_marker, CqlTerm::BindMarker),
+        )),
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::relation::{CqlOperator, CqlRelation};
+    use crate::model::term::CqlBindMarker;
+    use nom::error::{VerboseError, VerboseErrorKind};
+
+    fn parse(input: &str) -> IResult<&str, CqlSelect<&str>, VerboseError<&str>> {
+        CqlSelect::parse(input)
+    }
+
+    #[test]
+    fn test_parse_select_all() {
+        assert_eq!(
+            parse("SELECT * FROM my_ks.my_table;"),
+            Ok((
+                ";",
+                CqlSelect::new(
+                    CqlSelection::All,
+                    CqlQualifiedIdentifier::new(
+                        Some(CqlIdentifier::Unquoted("my_ks")),
+                        CqlIdentifier::Unquoted("my_table")
+                    ),
+                    None,
+                    vec![],
+                    vec![],
+                    None,
+                    false
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_select_clauses() {
+        assert_eq!(
+            parse(
+                "select user_id, \"Name\" from my_table where user_id = 42 \
+                 order by created_at desc, seq_1 limit 10 allow  filtering"
+            ),
+            Ok((
+                "",
+                CqlSelect::new(
+                    CqlSelection::Selectors(vec![
+                        CqlSelector::Column(CqlIdentifier::Unquoted("user_id")),
+                        CqlSelector::Column(CqlIdentifier::Quoted("Name".to_string())),
+                    ]),
+                    CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted("my_table")),
+                    Some(CqlWhereClause::new(vec![CqlRelation::Column(
+                        CqlIdentifier::Unquoted("user_id"),
+                        CqlOperator::Eq,
+                        CqlTerm::Constant(CqlConstant::Integer("42"))
+                    )])),
+                    vec![],
+                    vec![
+                        CqlOrdering::Column(CqlIdentifier::Unquoted("created_at"), CqlOrder::Desc),
+                        CqlOrdering::Column(CqlIdentifier::Unquoted("seq_1"), CqlOrder::Asc),
+                    ],
+                    Some(CqlTerm::Constant(CqlConstant::Integer("10"))),
+                    true
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_limit_bind_marker() {
+        let (_, select) = parse("SELECT * FROM my_table LIMIT ?").unwrap();
+        assert_eq!(
+            select.limit(),
+            &Some(CqlTerm::BindMarker(CqlBindMarker::Positional))
+        );
+        let (_, select) = parse("SELECT * FROM my_table LIMIT :page_size").unwrap();
+        assert_eq!(
+            select.limit(),
+            &Some(CqlTerm::BindMarker(CqlBindMarker::Named(
+                CqlIdentifier::Unquoted("page_size")
+            )))
+        );
+    }
+
+    #[test]
+    fn test_bind_markers() {
+        let (_, select) =
+            parse("SELECT * FROM my_table WHERE pk_1 = :pk_1 AND ck_1 > ? LIMIT ?").unwrap();
+        assert_eq!(
+            select.bind_markers().collect::<Vec<_>>(),
+            vec![
+                &CqlBindMarker::Named(CqlIdentifier::Unquoted("pk_1")),
+                &CqlBindMarker::Positional,
+                &CqlBindMarker::Positional,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_ann_ordering() {
+        let (remaining, select) =
+            parse("SELECT * FROM products_1 ORDER BY embedding_1 ANN OF [0.1, 0.2, 0.3] LIMIT 10;")
+                .unwrap();
+        assert_eq!(remaining, ";");
+        assert_eq!(
+            select.order_by(),
+            &vec![CqlOrdering::Ann(
+                CqlIdentifier::Unquoted("embedding_1"),
+                CqlTerm::List(vec![
+                    CqlTerm::Constant(CqlConstant::Float("0.1")),
+                    CqlTerm::Constant(CqlConstant::Float("0.2")),
+                    CqlTerm::Constant(CqlConstant::Float("0.3"))
+                ])
+            )]
+        );
+        assert_eq!(
+            select.order_by()[0].column(),
+            &CqlIdentifier::Unquoted("embedding_1")
+        );
+        let (_, select) =
+            parse("select * from products_1 order by embedding_1 ann  of ? limit ?").unwrap();
+        assert_eq!(select.bind_markers().count(), 2);
+        // The vector has to be a list.
+        assert!(matches!(
+            parse("SELECT * FROM products_1 ORDER BY embedding_1 ANN OF 1"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_per_partition_limit() {
+        let (remaining, select) =
+            parse("SELECT * FROM my_table PER  PARTITION\nLIMIT 3 LIMIT 100;").unwrap();
+        assert_eq!(remaining, ";");
+        assert_eq!(
+            select.per_partition_limit(),
+            &Some(CqlTerm::Constant(CqlConstant::Integer("3")))
+        );
+        assert_eq!(
+            select.limit(),
+            &Some(CqlTerm::Constant(CqlConstant::Integer("100")))
+        );
+        let (_, select) =
+            parse("select * from my_table where pk_1 = 1 per partition limit ?").unwrap();
+        assert_eq!(
+            select.per_partition_limit(),
+            &Some(CqlTerm::BindMarker(CqlBindMarker::Positional))
+        );
+        assert_eq!(select.limit(), &None);
+        let (_, select) = parse("SELECT * FROM my_table LIMIT 5").unwrap();
+        assert_eq!(select.per_partition_limit(), &None);
+        // PER PARTITION LIMIT comes before LIMIT.
+        let (remaining, _) = parse("SELECT * FROM my_table LIMIT 5 PER PARTITION LIMIT 3").unwrap();
+        assert_eq!(remaining, " PER PARTITION LIMIT 3");
+    }
+
+    #[test]
+    fn test_parse_token() {
+        let token = |arguments| {
+            CqlSelector::Function(
+                CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted("token")),
+                CqlSelection::Selectors(arguments),
+            )
+        };
+        let (remaining, select) = parse("SELECT token(pk) FROM t").unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(
+            select.selection(),
+            &CqlSelection::Selectors(vec![token(vec![CqlSelector::Column(
+                CqlIdentifier::Unquoted("pk")
+            )])])
+        );
+        for input in [
+            "SELECT a FROM t WHERE token(a) > token(?)",
+            "SELECT a FROM t WHERE token(a, b) > token(?, ?)",
+            "SELECT TOKEN(a, b) AS t_1 FROM t WHERE TOKEN(a, b) <= TOKEN(1, 'x')",
+        ] {
+            assert_eq!(
+                crate::parse_cql_complete(input).map(|statements| statements.len()),
+                Ok(1),
+                "{}",
+                input
+            );
+        }
+        // Only a call is the token function, the word stays reserved as a column.
+        assert!(crate::parse_cql_complete("SELECT token FROM t").is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_select() {
+        assert!(matches!(
+            parse("SELECT * my_table"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(matches!(
+            parse("SELECT FROM my_table"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_group_by() {
+        let (remaining, select) = parse(
+            "SELECT shop_id, \"count\"(*), max_1(price_1), my_ks.avg_2(to_double(price_1), day_2) \
+             FROM orders_v1 GROUP BY shop_id , day_1 ORDER BY day_1",
+        )
+        .unwrap();
+        assert_eq!(remaining, "");
+        let function = |keyspace, name, arguments| {
+            CqlSelector::Function(
+                CqlQualifiedIdentifier::new(keyspace, CqlIdentifier::Unquoted(name)),
+                arguments,
+            )
+        };
+        let column = |name| CqlSelector::Column(CqlIdentifier::Unquoted(name));
+        assert_eq!(
+            select.selection(),
+            &CqlSelection::Selectors(vec![
+                column("shop_id"),
+                CqlSelector::Function(
+                    CqlQualifiedIdentifier::new(None, CqlIdentifier::Quoted("count".to_string())),
+                    CqlSelection::All
+                ),
+                function(
+                    None,
+                    "max_1",
+                    CqlSelection::Selectors(vec![column("price_1")])
+                ),
+                function(
+                    Some(CqlIdentifier::Unquoted("my_ks")),
+                    "avg_2",
+                    CqlSelection::Selectors(vec![
+                        function(
+                            None,
+                            "to_double",
+                            CqlSelection::Selectors(vec![column("price_1")])
+                        ),
+                        column("day_2"),
+                    ])
+                ),
+            ])
+        );
+        assert_eq!(
+            select.group_by(),
+            &vec![
+                CqlIdentifier::Unquoted("shop_id"),
+                CqlIdentifier::Unquoted("day_1")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_json_and_distinct() {
+        let (_, select) = parse("SELECT JSON * FROM my_table").unwrap();
+        assert!(select.json() && !select.distinct());
+        let (_, select) = parse("select distinct\n  shop_id FROM my_table").unwrap();
+        assert!(!select.json() && select.distinct());
+        assert_eq!(
+            select.selection(),
+            &CqlSelection::Selectors(vec![CqlSelector::Column(CqlIdentifier::Unquoted(
+                "shop_id"
+            ))])
+        );
+        // A column starting like a modifier is no modifier.
+        let (_, select) = parse("SELECT json_data FROM my_table").unwrap();
+        assert!(!select.json());
+        // A column named like a modifier.
+        let (_, select) = parse("SELECT json FROM my_table").unwrap();
+        assert!(!select.json());
+        assert_eq!(
+            select.selection(),
+            &CqlSelection::Selectors(vec![CqlSelector::Column(CqlIdentifier::Unquoted("json"))])
+        );
+        let (_, select) = parse("SELECT distinct FROM my_table").unwrap();
+        assert!(!select.distinct());
+        let (_, select) = parse("SELECT json AS j FROM my_table").unwrap();
+        assert!(!select.json());
+        let (_, select) = parse("SELECT JSON distinct FROM my_table").unwrap();
+        assert!(select.json() && !select.distinct());
+        assert_eq!(
+            select.selection(),
+            &CqlSelection::Selectors(vec![CqlSelector::Column(CqlIdentifier::Unquoted(
+                "distinct"
+            ))])
+        );
+        let (_, select) = parse("SELECT DISTINCT json FROM my_table").unwrap();
+        assert!(!select.json() && select.distinct());
+
+        let input = "SELECT JSON DISTINCT shop_id FROM my_table";
+        let Err(nom::Err::Failure(error)) = parse(input) else {
+            panic!("JSON and DISTINCT accepted");
+        };
+        assert!(error.errors.contains(&(
+            &input[12..],
+            VerboseErrorKind::Context("JSON and DISTINCT can not be combined")
+        )));
+    }
+
+    #[test]
+    fn test_parse_alias_and_cast() {
+        let (remaining, select) = parse(
+            "SELECT \"writetime\"(value_1) AS written_at, CAST( user_id AS text ), \
+             CAST(to_map(value_1) AS frozen<list<int>>) as \"Values\" FROM my_table",
+        )
+        .unwrap();
+        assert_eq!(remaining, "");
+        let column = |name| Box::new(CqlSelector::Column(CqlIdentifier::Unquoted(name)));
+        assert_eq!(
+            select.selection(),
+            &CqlSelection::Selectors(vec![
+                CqlSelector::Alias(
+                    Box::new(CqlSelector::Function(
+                        CqlQualifiedIdentifier::new(
+                            None,
+                            CqlIdentifier::Quoted("writetime".to_string())
+                        ),
+                        CqlSelection::Selectors(vec![*column("value_1")])
+                    )),
+                    CqlIdentifier::Unquoted("written_at")
+                ),
+                CqlSelector::Cast(column("user_id"), CqlType::TEXT),
+                CqlSelector::Alias(
+                    Box::new(CqlSelector::Cast(
+                        Box::new(CqlSelector::Function(
+                            CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted("to_map")),
+                            CqlSelection::Selectors(vec![*column("value_1")])
+                        )),
+                        CqlType::FROZEN(Box::new(CqlType::LIST(Box::new(CqlType::INT))))
+                    )),
+                    CqlIdentifier::Quoted("Values".to_string())
+                ),
+            ])
+        );
+        // Arguments can not be renamed.
+        assert!(parse("SELECT max_1(value_1 AS other_1) FROM my_table").is_err());
+    }
+}
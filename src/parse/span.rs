@@ -0,0 +1,148 @@
+use crate::model::span::Spanned;
+use crate::model::table::primary_key::CqlPrimaryKey;
+use crate::parse::{ColumnDefinition, Parse};
+use nom::error::ParseError;
+use nom::{IResult, Offset};
+
+impl<'de, E: ParseError<&'de str>, T: Parse<&'de str, E>> Parse<&'de str, E>
+    for Spanned<&'de str, T>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (remaining, node) = T::parse(input)?;
+        let span = &input[..input.offset(remaining)];
+
+        Ok((remaining, Spanned::new(span, node)))
+    }
+}
+
+/// The primary key declared by a spanned column spans the column definition.
+impl<I: Clone, ColumnRef, T> ColumnDefinition<ColumnRef, Spanned<I, CqlPrimaryKey<ColumnRef>>>
+    for Spanned<I, T>
+where
+    T: ColumnDefinition<ColumnRef>,
+{
+    fn is_marked_primary_key(&self) -> bool {
+        self.node().is_marked_primary_key()
+    }
+
+    fn column_ref(&self) -> ColumnRef {
+        self.node().column_ref()
+    }
+
+    fn primary_key(&self) -> Spanned<I, CqlPrimaryKey<ColumnRef>> {
+        Spanned::new(self.span().clone(), self.node().primary_key())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::model::span::CqlSpan;
+    use crate::parse_cql_spanned;
+
+    // A static, the spans must point into the same string.
+    static INPUT: &str = r#"USE my_ks;
+
+CREATE TABLE my_ks."My Table" (
+    id uuid,
+    PRIMARY KEY ((id), "Näme"),
+  "Näme" text STATIC
+) WITH comment = 'Some; text'
+    AND CLUSTERING ORDER BY ("Näme" DESC) AND COMPACT STORAGE;
+  CREATE TYPE IF NOT EXISTS address (street text);
+CREATE TABLE t (a int PRIMARY KEY, b text) WITH gc_grace_seconds = 10;
+CREATE TABLE u (a int)"#;
+
+    /// The start line, start column and text of a span.
+    fn location(span: &str) -> (usize, usize, &str) {
+        let location = CqlSpan::locate(INPUT, span).unwrap();
+        assert_eq!(&INPUT[location.range()], span);
+        (location.line(), location.column(), span)
+    }
+
+    #[test]
+    fn test_statement_spans() {
+        let (remaining, statements) = parse_cql_spanned(INPUT).unwrap();
+        assert_eq!(remaining, "");
+        let locations = statements
+            .iter()
+            .map(|statement| {
+                let span = statement.locate(INPUT).unwrap();
+                (span.line(), span.column(), span.end() - span.start())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            locations,
+            vec![(1, 1, 9), (3, 1, 192), (9, 3, 47), (10, 1, 69), (11, 1, 22)]
+        );
+        assert_eq!(statements[1].locate(INPUT).unwrap().start(), 12);
+        assert!(statements[1].span().ends_with("COMPACT STORAGE"));
+        assert!(statements[1].is_create_table());
+        // A copy of the input does not contain the span.
+        let copy = INPUT.to_string();
+        assert_eq!(statements[1].locate(&copy), None);
+    }
+
+    #[test]
+    fn test_table_spans() {
+        let (_, statements) = parse_cql_spanned(INPUT).unwrap();
+        let table = statements[1].create_table().unwrap();
+        assert_eq!(location(table.span()).0, 3);
+        assert_eq!(location(table.name().span()), (3, 14, "my_ks.\"My Table\""));
+        assert_eq!(
+            table
+                .columns()
+                .iter()
+                .map(|column| location(column.span()))
+                .collect::<Vec<_>>(),
+            vec![(4, 5, "id uuid"), (6, 3, "\"Näme\" text STATIC")]
+        );
+        assert_eq!(
+            location(table.columns()[1].name().span()),
+            (6, 3, "\"Näme\"")
+        );
+        let primary_key = table.primary_key().as_ref().unwrap();
+        assert_eq!(location(primary_key.span()), (5, 17, "((id), \"Näme\")"));
+        assert_eq!(
+            location(primary_key.clustering_columns()[0].span()),
+            (5, 24, "\"Näme\"")
+        );
+        let options = table.options().as_ref().unwrap();
+        assert_eq!(
+            options
+                .options()
+                .iter()
+                .map(|option| location(option.span()))
+                .collect::<Vec<_>>(),
+            vec![(7, 8, "comment = 'Some; text'")]
+        );
+        let (column, _) = &options.clustering_order()[0];
+        assert_eq!(location(column.span()), (8, 30, "\"Näme\""));
+    }
+
+    #[test]
+    fn test_inline_primary_key_spans() {
+        let (_, statements) = parse_cql_spanned(INPUT).unwrap();
+        let table = statements[3].create_table().unwrap();
+        let primary_key = table.primary_key().as_ref().unwrap();
+        assert_eq!(location(primary_key.span()), (10, 17, "a int PRIMARY KEY"));
+        assert_eq!(
+            location(primary_key.partition_key()[0].span()),
+            (10, 17, "a")
+        );
+        let options = table.options().as_ref().unwrap();
+        assert_eq!(*options.options()[0].span(), "gc_grace_seconds = 10");
+
+        let table = statements[4].create_table().unwrap();
+        assert_eq!(location(table.name().span()), (11, 14, "u"));
+        assert_eq!(table.primary_key(), &None);
+        assert_eq!(table.options(), &None);
+    }
+
+    #[test]
+    fn test_user_defined_type_spans() {
+        let (_, statements) = parse_cql_spanned(INPUT).unwrap();
+        let udt = statements[2].create_user_defined_type().unwrap();
+        assert_eq!(location(udt.span()).0, 9);
+        assert_eq!(location(udt.name().span()), (9, 29, "address"));
+    }
+}
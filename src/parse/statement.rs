@@ -1,27 +1,100 @@
 use nom::branch::alt;
 use nom::combinator::map;
-use nom::error::ParseError;
+use nom::error::{ContextError, ParseError};
 use nom::IResult;
 
+use crate::model::delete::CqlDelete;
+use crate::model::function::CqlDropAggregate;
 use crate::model::identifier::CqlIdentifier;
+use crate::model::index::CqlCreateIndex;
+use crate::model::insert::CqlInsert;
+use crate::model::keyspace::CqlCreateKeyspace;
+use crate::model::materialized_view::CqlAlterMaterializedView;
+use crate::model::permission::{CqlGrant, CqlRevoke};
+use crate::model::role::{CqlAlterRole, CqlCreateRole, CqlDropRole};
+use crate::model::select::CqlSelect;
 use crate::model::statement::CqlStatement;
-use crate::model::table::column::CqlColumn;
-use crate::model::table::CqlTable;
-use crate::model::user_defined_type::ParsedCqlUserDefinedType;
+use crate::model::table::like::CqlCreateTableLike;
+use crate::model::trigger::{CqlCreateTrigger, CqlDropTrigger};
+use crate::model::update::CqlUpdate;
+use crate::model::user_defined_type::{CqlAlterType, CqlDropType};
 use crate::parse::Parse;
+use crate::utils::{keyword, space1_between, traced, ErrorInput};
 
-impl<'de, E: ParseError<&'de str>> Parse<&'de str, E>
-    for CqlStatement<
-        CqlTable<&'de str, CqlColumn<&'de str, CqlIdentifier<&'de str>>, CqlIdentifier<&'de str>>,
-        ParsedCqlUserDefinedType<&'de str, CqlIdentifier<&'de str>>,
-    >
+impl<'de, E, Table, UdtType> Parse<&'de str, E> for CqlStatement<&'de str, Table, UdtType>
+where
+    E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>,
+    Table: Parse<&'de str, E>,
+    UdtType: Parse<&'de str, E>,
 {
     fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
         alt((
-            map(ParsedCqlUserDefinedType::parse, |user_defined_type| {
-                CqlStatement::CreateUserDefinedType(user_defined_type)
-            }),
-            map(CqlTable::parse, |table| CqlStatement::CreateTable(table)),
+            map(
+                traced("user_defined_type", UdtType::parse),
+                CqlStatement::CreateUserDefinedType,
+            ),
+            map(traced("table", Table::parse), CqlStatement::CreateTable),
+            map(
+                traced("table_like", CqlCreateTableLike::parse),
+                CqlStatement::CreateTableLike,
+            ),
+            map(
+                traced(
+                    "use",
+                    space1_between((keyword("USE"), CqlIdentifier::parse)),
+                ),
+                |(_, keyspace)| CqlStatement::Use(keyspace),
+            ),
+            map(
+                traced("create_keyspace", CqlCreateKeyspace::parse),
+                CqlStatement::CreateKeyspace,
+            ),
+            map(
+                traced("materialized_view", CqlAlterMaterializedView::parse),
+                CqlStatement::AlterMaterializedView,
+            ),
+            map(
+                traced("alter_type", CqlAlterType::parse),
+                CqlStatement::AlterType,
+            ),
+            map(
+                traced("drop_type", CqlDropType::parse),
+                CqlStatement::DropType,
+            ),
+            map(
+                traced("drop_aggregate", CqlDropAggregate::parse),
+                CqlStatement::DropAggregate,
+            ),
+            map(
+                traced("create_trigger", CqlCreateTrigger::parse),
+                CqlStatement::CreateTrigger,
+            ),
+            map(
+                traced("drop_trigger", CqlDropTrigger::parse),
+                CqlStatement::DropTrigger,
+            ),
+            map(
+                traced("create_role", CqlCreateRole::parse),
+                CqlStatement::CreateRole,
+            ),
+            map(
+                traced("alter_role", CqlAlterRole::parse),
+                CqlStatement::AlterRole,
+            ),
+            map(
+                traced("drop_role", CqlDropRole::parse),
+                CqlStatement::DropRole,
+            ),
+            map(traced("grant", CqlGrant::parse), CqlStatement::Grant),
+            map(traced("revoke", CqlRevoke::parse), CqlStatement::Revoke),
+            map(
+                traced("create_index", CqlCreateIndex::parse),
+                CqlStatement::CreateIndex,
+            ),
+            map(traced("select", CqlSelect::parse), CqlStatement::Select),
+            map(traced("insert", CqlInsert::parse), CqlStatement::Insert),
+            map(traced("update", CqlUpdate::parse), CqlStatement::Update),
+            map(traced("delete", CqlDelete::parse), CqlStatement::Delete),
         ))(input)
     }
 }
@@ -29,15 +102,24 @@ impl<'de, E: ParseError<&'de str>> Parse<&'de str, E>
 #[cfg(test)]
 mod test {
     use crate::model::cql_type::CqlType;
+    use crate::model::identifier::CqlIdentifier;
+    use crate::model::materialized_view::CqlAlterMaterializedView;
     use crate::model::order::CqlOrder;
     use crate::model::qualified_identifier::CqlQualifiedIdentifier;
     use crate::model::table::column::CqlColumn;
-    use crate::model::table::options::CqlTableOptions;
+    use crate::model::table::options::{CqlTableOption, CqlTableOptions};
     use crate::model::table::primary_key::CqlPrimaryKey;
     use crate::model::table::CqlTable;
+    use crate::model::user_defined_type::{CqlAlterTypeOperation, ParsedCqlUserDefinedType};
 
     use super::*;
 
+    type Statement<'a> = CqlStatement<
+        &'a str,
+        CqlTable<&'a str, CqlColumn<&'a str, CqlIdentifier<&'a str>>, CqlIdentifier<&'a str>>,
+        ParsedCqlUserDefinedType<&'a str, CqlIdentifier<&'a str>>,
+    >;
+
     #[test]
     fn test_parse_table() {
         let input = r#"CREATE TABLE IF NOT EXISTS my_keyspace.my_table (
@@ -46,7 +128,7 @@ mod test {
             PRIMARY KEY (my_field1)
         ) WITH CLUSTERING ORDER BY (my_field2 DESC)"#;
         assert_eq!(
-            CqlStatement::parse(input),
+            Statement::parse(input),
             Ok::<_, nom::Err<nom::error::Error<_>>>((
                 "",
                 CqlStatement::CreateTable(CqlTable::new(
@@ -71,6 +153,7 @@ mod test {
                     ],
                     Some(CqlPrimaryKey::new(
                         vec![CqlIdentifier::Unquoted("my_field1")],
+                        false,
                         vec![]
                     )),
                     Some(CqlTableOptions::new(
@@ -93,7 +176,7 @@ mod test {
             my_field5 some_udt
         )"#;
         assert_eq!(
-            CqlStatement::parse(input),
+            Statement::parse(input),
             Ok::<_, nom::Err<nom::error::Error<_>>>((
                 "",
                 CqlStatement::CreateUserDefinedType(ParsedCqlUserDefinedType::new(
@@ -125,4 +208,57 @@ mod test {
             ))
         );
     }
+
+    #[test]
+    fn test_parse_use() {
+        assert_eq!(
+            Statement::parse("USE my_keyspace"),
+            Ok::<_, nom::Err<nom::error::Error<_>>>((
+                "",
+                CqlStatement::Use(CqlIdentifier::Unquoted("my_keyspace"))
+            ))
+        );
+        assert_eq!(
+            Statement::parse("use\n  \"My Keyspace\""),
+            Ok::<_, nom::Err<nom::error::Error<_>>>((
+                "",
+                CqlStatement::Use(CqlIdentifier::Quoted("My Keyspace".to_string()))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_alter_materialized_view() {
+        let input = "ALTER MATERIALIZED VIEW my_view WITH gc_grace_seconds = 3600";
+        assert_eq!(
+            Statement::parse(input),
+            Ok::<_, nom::Err<nom::error::Error<_>>>((
+                "",
+                CqlStatement::AlterMaterializedView(CqlAlterMaterializedView::new(
+                    false,
+                    CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted("my_view")),
+                    CqlTableOptions::new(false, vec![], vec![CqlTableOption::GcGraceSeconds(3600)]),
+                ))
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_alter_type() {
+        let input = "ALTER TYPE my_type RENAME my_field1 TO my_field2";
+        assert_eq!(
+            Statement::parse(input),
+            Ok::<_, nom::Err<nom::error::Error<_>>>((
+                "",
+                CqlStatement::AlterType(CqlAlterType::new(
+                    false,
+                    CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted("my_type")),
+                    CqlAlterTypeOperation::Rename(vec![(
+                        CqlIdentifier::Unquoted("my_field1"),
+                        CqlIdentifier::Unquoted("my_field2")
+                    )]),
+                ))
+            ))
+        );
+    }
 }
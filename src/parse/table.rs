@@ -1,57 +1,372 @@
 mod column;
-mod options;
+mod like;
+pub(crate) mod options;
 mod primary_key;
 
-use crate::model::identifier::CqlIdentifier;
-use crate::model::qualified_identifier::CqlQualifiedIdentifier;
-use crate::model::table::column::CqlColumn;
 use crate::model::table::options::CqlTableOptions;
 use crate::model::table::primary_key::CqlPrimaryKey;
 use crate::model::table::CqlTable;
-use crate::parse::Parse;
+use crate::model::Identifiable;
+use crate::parse::term::identifier_char;
+use crate::parse::{ColumnDefinition, Parse};
 use crate::utils::{
-    space0_around, space0_between, space1_before, space1_between, space1_tags_no_case,
+    debug_event, keyword, or_error, separated_list0_with_error, space1_before, space1_between,
+    space1_tags_no_case, ws0, ws1,
 };
+use nom::branch::alt;
 use nom::bytes::complete::{tag, tag_no_case};
-use nom::character::complete::multispace0;
-use nom::combinator::opt;
-use nom::error::ParseError;
-use nom::multi::separated_list0;
+use nom::combinator::{cut, map, not, opt};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
+use nom::sequence::{pair, preceded, terminated};
 use nom::IResult;
+use std::borrow::Borrow;
 
-impl<'de, E: ParseError<&'de str>> Parse<&'de str, E>
-    for CqlTable<&'de str, CqlColumn<&'de str, CqlIdentifier<&'de str>>, CqlIdentifier<&'de str>>
+/// An element of the parenthesized definition list of a table.
+enum Definition<Column, PrimaryKey> {
+    Column(Column),
+    PrimaryKey(PrimaryKey),
+}
+
+impl<'de, E, Column, ColumnRef, Name, PrimaryKey, OptionEntry> Parse<&'de str, E>
+    for CqlTable<&'de str, Column, ColumnRef, Name, PrimaryKey, OptionEntry>
+where
+    E: ParseError<&'de str> + ContextError<&'de str>,
+    Column: Parse<&'de str, E> + ColumnDefinition<ColumnRef, PrimaryKey>,
+    ColumnRef: Parse<&'de str, E> + Identifiable<&'de str>,
+    Name: Parse<&'de str, E> + Identifiable<&'de str>,
+    PrimaryKey: Parse<&'de str, E> + Borrow<CqlPrimaryKey<ColumnRef>>,
+    CqlTableOptions<&'de str, ColumnRef, OptionEntry>: Parse<&'de str, E>,
 {
     fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
-        let (input, _) = space1_tags_no_case(["CREATE", "TABLE"])(input)?;
+        let (input, _) = space1_between((keyword("CREATE"), keyword("TABLE")))(input)?;
         let (input, if_not_exists) =
             opt(space1_before(space1_tags_no_case(["IF", "NOT", "EXISTS"])))(input)?;
-        let (input, name) = space1_before(CqlQualifiedIdentifier::parse)(input)?;
-        let (input, _) = multispace0(input)?;
-        let (input, _) = tag("(")(input)?;
-        let (input, columns) = separated_list0(tag(","), space0_around(CqlColumn::parse))(input)?;
-        let (input, primary_key) = opt(space0_between((
-            tag(","),
-            space1_tags_no_case(["PRIMARY", "KEY"]),
-            CqlPrimaryKey::parse,
-        )))(input)?;
-        let (input, _) = multispace0(input)?;
-        let (input, _) = tag(")")(input)?;
-        let (input, _) = multispace0(input)?;
-        let (input, options) = opt(space1_between((
-            tag_no_case("WITH"),
-            CqlTableOptions::parse,
-        )))(input)?;
+        let (input, name) = space1_before(Name::parse)(input)?;
+        debug_event!(
+            keyspace = name.keyspace().map(|keyspace| &**keyspace),
+            name = &**name.identifier(),
+            "table"
+        );
+        let (input, _) = ws0(input)?;
+        let (input, _) = context("(", tag("("))(input)?;
+        // The PRIMARY KEY clause may appear anywhere between the column definitions.
+        let mut primary_key = None;
+        let (input, (definitions, definition_error)) =
+            separated_list0_with_error(tag(","), |input| {
+                // The skipped comments are kept by columns documented by them.
+                let (input, leading) = ws0(input)?;
+                // `PRIMARY` is reserved, so an element starting with `PRIMARY KEY` is never
+                // parsed as a column named `PRIMARY` of type `KEY`.
+                let (remaining, definition) = alt((
+                    map(
+                        preceded(
+                            context(
+                                "PRIMARY KEY",
+                                terminated(
+                                    space1_tags_no_case(["PRIMARY", "KEY"]),
+                                    not(identifier_char),
+                                ),
+                            ),
+                            cut(preceded(ws0, context("(", PrimaryKey::parse))),
+                        ),
+                        Definition::PrimaryKey,
+                    ),
+                    map(
+                        |input| Column::parse_after(leading, input),
+                        Definition::Column,
+                    ),
+                    unquoted_primary,
+                ))(input)?;
+                let (remaining, _) = ws0(remaining)?;
+                match definition {
+                    Definition::Column(column) => Ok((remaining, Some(column))),
+                    Definition::PrimaryKey(_) if primary_key.is_some() => {
+                        Err(nom::Err::Failure(E::add_context(
+                            input,
+                            "duplicate PRIMARY KEY clause",
+                            E::from_error_kind(input, ErrorKind::Verify),
+                        )))
+                    }
+                    Definition::PrimaryKey(clause) => {
+                        primary_key = Some(clause);
+                        Ok((remaining, None))
+                    }
+                }
+            })(input)?;
+        let columns: Vec<Column> = definitions.into_iter().flatten().collect();
+        let (input, _) = ws0(input)?;
+        let (input, _) =
+            context(")", tag(")"))(input).map_err(|e| e.map(or_error(definition_error)))?;
+        // Without a clause a single column marked `PRIMARY KEY` is the partition key. Other
+        // marked columns conflict with the clause or with each other, they are kept for
+        // validation.
+        let mut marked = columns
+            .iter()
+            .filter(|column| column.is_marked_primary_key())
+            .collect::<Vec<_>>();
+        let primary_key = match primary_key {
+            None if marked.len() == 1 => Some(marked.remove(0).primary_key()),
+            // The clause may repeat a single marked column.
+            Some(clause) if is_single_column(clause.borrow(), &marked) => {
+                marked.clear();
+                Some(clause)
+            }
+            primary_key => primary_key,
+        };
+        let marked = marked
+            .into_iter()
+            .map(ColumnDefinition::column_ref)
+            .collect();
+        // Whitespace is only consumed together with the options, it is not part of the table.
+        // Once `WITH` is read the options are mandatory.
+        let (input, options) = opt(preceded(
+            pair(ws0, keyword("WITH")),
+            cut(preceded(ws1, CqlTableOptions::parse)),
+        ))(input)?;
 
         Ok((
             input,
-            CqlTable::new(
-                if_not_exists.is_some(),
-                name,
-                columns,
-                primary_key.map(|(_, _, pk)| pk),
-                options.map(|(_, options)| options),
-            ),
+            CqlTable::new(if_not_exists.is_some(), name, columns, primary_key, options)
+                .with_conflicting_primary_key(marked),
         ))
     }
 }
+
+/// Whether the `PRIMARY KEY (...)` clause is the single column of `columns` as partition key.
+fn is_single_column<'de, Column, ColumnRef, PrimaryKey>(
+    clause: &CqlPrimaryKey<ColumnRef>,
+    columns: &[&Column],
+) -> bool
+where
+    Column: ColumnDefinition<ColumnRef, PrimaryKey>,
+    ColumnRef: Identifiable<&'de str>,
+{
+    match (clause.partition_key().as_slice(), columns) {
+        ([partition], [column]) => {
+            clause.clustering_columns().is_empty()
+                && partition.identifier() == column.column_ref().identifier()
+        }
+        _ => false,
+    }
+}
+
+/// Fails on a column named `primary` without quotes, which the column parser rejects as a
+/// reserved keyword.
+fn unquoted_primary<'de, E, T>(input: &'de str) -> IResult<&'de str, T, E>
+where
+    E: ParseError<&'de str> + ContextError<&'de str>,
+{
+    let (_, _) = terminated(tag_no_case("PRIMARY"), not(identifier_char))(input)?;
+
+    Err(nom::Err::Failure(E::add_context(
+        input,
+        "the column name PRIMARY is a reserved keyword, quote it as \"primary\"",
+        E::from_error_kind(input, ErrorKind::Verify),
+    )))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::identifier::CqlIdentifier;
+    use crate::model::table::column::CqlColumn;
+    use nom::error::{VerboseError, VerboseErrorKind};
+    use nom::Offset;
+
+    type Table<'a> =
+        CqlTable<&'a str, CqlColumn<&'a str, CqlIdentifier<&'a str>>, CqlIdentifier<&'a str>>;
+
+    fn parse(input: &str) -> IResult<&str, Table<'_>, VerboseError<&str>> {
+        Table::parse(input)
+    }
+
+    fn column_names<'a>(table: &'a Table<'a>) -> Vec<&'a str> {
+        table
+            .columns()
+            .iter()
+            .map(|column| &**column.name())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_primary_key_anywhere() {
+        for input in [
+            "CREATE TABLE my_table (PRIMARY KEY (a1), a1 int, b1 text)",
+            "CREATE TABLE my_table (a1 int, PRIMARY KEY (a1), b1 text)",
+            "CREATE TABLE my_table (a1 int, b1 text, PRIMARY KEY (a1))",
+        ] {
+            let (remaining, table) = parse(input).unwrap();
+            assert_eq!(remaining, "", "{}", input);
+            assert_eq!(column_names(&table), vec!["a1", "b1"], "{}", input);
+            assert_eq!(
+                table.primary_key().as_ref().unwrap().partition_key(),
+                &vec![CqlIdentifier::Unquoted("a1")],
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_inline_primary_key() {
+        let (_, table) = parse("CREATE TABLE my_table (a1 int, b1 text PRIMARY KEY)").unwrap();
+        assert_eq!(
+            table.primary_key(),
+            &Some(CqlPrimaryKey::new(
+                vec![CqlIdentifier::Unquoted("b1")],
+                false,
+                vec![]
+            ))
+        );
+        let (_, table) = parse("CREATE TABLE my_table (\"A 1\" int PRIMARY KEY, b1 text)").unwrap();
+        assert_eq!(
+            table.primary_key().as_ref().unwrap().partition_key(),
+            &vec![CqlIdentifier::Quoted("A 1".to_string())]
+        );
+
+        // An explicit clause is kept, the conflict is recorded for the validation.
+        let (_, table) =
+            parse("CREATE TABLE my_table (a1 int PRIMARY KEY, b1 text, PRIMARY KEY (b1))").unwrap();
+        assert_eq!(
+            table.primary_key().as_ref().unwrap().partition_key(),
+            &vec![CqlIdentifier::Unquoted("b1")]
+        );
+        assert_eq!(
+            table.conflicting_primary_key(),
+            &vec![CqlIdentifier::Unquoted("a1")]
+        );
+        let (_, table) =
+            parse("CREATE TABLE my_table (a1 int PRIMARY KEY, b1 text, PRIMARY KEY (a1))").unwrap();
+        assert!(table.conflicting_primary_key().is_empty());
+        let (_, table) =
+            parse("CREATE TABLE my_table (a1 int PRIMARY KEY, b1 text PRIMARY KEY)").unwrap();
+        assert_eq!(table.primary_key(), &None);
+        assert_eq!(
+            table.conflicting_primary_key(),
+            &vec![CqlIdentifier::Unquoted("a1"), CqlIdentifier::Unquoted("b1")]
+        );
+        let (_, table) = parse("CREATE TABLE my_table (a1 int, b1 text)").unwrap();
+        assert_eq!(table.primary_key(), &None);
+        assert!(table.conflicting_primary_key().is_empty());
+    }
+
+    #[test]
+    fn test_parse_inline_primary_key_spanned() {
+        use crate::lookup::SpannedTable;
+
+        let input = "CREATE TABLE my_table (a1 int, \"B \"\"1\"\"\" text PRIMARY KEY)";
+        let result: IResult<_, SpannedTable, VerboseError<&str>> = SpannedTable::parse(input);
+        let (_, table) = result.unwrap();
+        let partition_key = table.primary_key().as_ref().unwrap().partition_key();
+        assert_eq!(partition_key.len(), 1);
+        assert_eq!(input.offset(partition_key[0].span()), 31);
+        assert_eq!(partition_key[0].span(), &"\"B \"\"1\"\"\"");
+    }
+
+    #[test]
+    fn test_parse_composite_primary_key_anywhere() {
+        for input in [
+            "CREATE TABLE t (PRIMARY KEY ((a1, b1), c1), a1 int, b1 int, c1 text) WITH comment = 'x'",
+            "CREATE TABLE t (a1 int, b1 int,\n  PRIMARY KEY ((a1, b1), c1) ,\n  c1 text) WITH comment = 'x'",
+            "CREATE TABLE t (a1 int, b1 int, c1 text, PRIMARY KEY ((a1, b1), c1)) WITH comment = 'x'",
+        ] {
+            let (remaining, table) = parse(input).unwrap();
+            assert_eq!(remaining, "", "{}", input);
+            assert_eq!(column_names(&table), vec!["a1", "b1", "c1"], "{}", input);
+            assert_eq!(
+                table.primary_key(),
+                &Some(CqlPrimaryKey::new(
+                    vec![CqlIdentifier::Unquoted("a1"), CqlIdentifier::Unquoted("b1")],
+                    true,
+                    vec![CqlIdentifier::Unquoted("c1")]
+                )),
+                "{}",
+                input
+            );
+            assert!(table.options().is_some(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn test_parse_primary_key_not_a_column() {
+        for input in [
+            "CREATE TABLE t (a1 int STATIC, PRIMARY KEY (a1), b1 text)",
+            "CREATE TABLE t (primary KEY (a1), a1 int STATIC, b1 text STATIC)",
+            "CREATE TABLE t (a1 int, b1 text STATIC,PRIMARY\n  KEY(a1))",
+        ] {
+            let (remaining, table) = parse(input).unwrap();
+            assert_eq!(remaining, "", "{}", input);
+            assert_eq!(column_names(&table), vec!["a1", "b1"], "{}", input);
+            assert_eq!(
+                table.primary_key().as_ref().unwrap().partition_key(),
+                &vec![CqlIdentifier::Unquoted("a1")],
+                "{}",
+                input
+            );
+        }
+
+        // A quoted column may be named primary.
+        let (_, table) =
+            parse("CREATE TABLE t (\"primary\" int STATIC, a1 int, PRIMARY KEY (a1, \"primary\"))")
+                .unwrap();
+        assert_eq!(column_names(&table), vec!["primary", "a1"]);
+        assert!(table.columns()[0].is_static());
+        assert_eq!(
+            table.primary_key().as_ref().unwrap().clustering_columns(),
+            &vec![CqlIdentifier::Quoted("primary".to_string())]
+        );
+        let (_, table) = parse("CREATE TABLE t (primary_id int PRIMARY KEY, a1 int)").unwrap();
+        assert_eq!(column_names(&table), vec!["primary_id", "a1"]);
+    }
+
+    #[test]
+    fn test_parse_unquoted_primary_column() {
+        for (input, position, message) in [
+            (
+                "CREATE TABLE t (a1 int, primary text STATIC)",
+                24,
+                "the column name PRIMARY is a reserved keyword, quote it as \"primary\"",
+            ),
+            (
+                "CREATE TABLE t (Primary int PRIMARY KEY)",
+                16,
+                "the column name PRIMARY is a reserved keyword, quote it as \"primary\"",
+            ),
+            ("CREATE TABLE t (a1 int, PRIMARY KEY)", 35, "("),
+            ("CREATE TABLE t (a1 int, PRIMARY KEY a1)", 36, "("),
+        ] {
+            let Err(nom::Err::Failure(error)) = parse(input) else {
+                panic!("{} accepted", input);
+            };
+            assert!(
+                error
+                    .errors
+                    .contains(&(&input[position..], VerboseErrorKind::Context(message))),
+                "{}: {:?}",
+                input,
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_duplicate_primary_key() {
+        let input = "CREATE TABLE my_table (a1 int, PRIMARY KEY (a1), b1 text, PRIMARY KEY (b1))";
+        let Err(nom::Err::Failure(error)) = parse(input) else {
+            panic!("duplicate PRIMARY KEY clause accepted");
+        };
+        assert!(error.errors.contains(&(
+            &input[58..],
+            VerboseErrorKind::Context("duplicate PRIMARY KEY clause")
+        )));
+
+        let input = "CREATE TABLE my_table (PRIMARY KEY (a1), a1 int, PRIMARY KEY (a1))";
+        let Err(nom::Err::Failure(error)) = parse(input) else {
+            panic!("duplicate PRIMARY KEY clause accepted");
+        };
+        assert!(error.errors.contains(&(
+            &input[49..],
+            VerboseErrorKind::Context("duplicate PRIMARY KEY clause")
+        )));
+    }
+}
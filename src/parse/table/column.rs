@@ -1,31 +1,265 @@
 use crate::model::cql_type::CqlType;
 use crate::model::identifier::CqlIdentifier;
-use crate::model::table::column::CqlColumn;
-use crate::parse::Parse;
-use crate::utils::{space0_between, space1_before, space1_tags_no_case};
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::table::column::{CqlColumn, CqlColumnMask};
+use crate::model::table::primary_key::CqlPrimaryKey;
+use crate::parse::term::{arguments, identifier_char};
+use crate::parse::{ColumnDefinition, Parse};
+use crate::utils::{
+    keyword, space0_between, space1_before, space1_tags_no_case, traced, ErrorInput, Whitespace,
+};
+use nom::branch::alt;
 use nom::bytes::complete::tag_no_case;
-use nom::combinator::opt;
-use nom::error::ParseError;
+use nom::combinator::{cut, map, not, value};
+use nom::error::{ContextError, ErrorKind, ParseError};
+use nom::sequence::{pair, terminated};
 use nom::IResult;
 
-impl<'de, E: ParseError<&'de str>> Parse<&'de str, E>
-    for CqlColumn<&'de str, CqlIdentifier<&'de str>>
+impl<I, UdtType, Name: Clone> ColumnDefinition<Name> for CqlColumn<I, UdtType, Name> {
+    fn is_marked_primary_key(&self) -> bool {
+        self.is_primary_key()
+    }
+
+    fn column_ref(&self) -> Name {
+        self.name().clone()
+    }
+
+    fn primary_key(&self) -> CqlPrimaryKey<Name> {
+        CqlPrimaryKey::new(vec![self.column_ref()], false, Vec::new())
+    }
+}
+
+#[derive(Clone)]
+enum Modifier<'de> {
+    Static,
+    PrimaryKey,
+    Mask(CqlColumnMask<&'de str>),
+}
+
+impl<'de, E, Name> Parse<&'de str, E> for CqlColumn<&'de str, CqlIdentifier<&'de str>, Name>
+where
+    E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>,
+    Name: Parse<&'de str, E>,
 {
     fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
-        let (input, (name, cql_type)) =
-            space0_between((CqlIdentifier::parse, CqlType::parse))(input)?;
-        let (input, is_static) = opt(space1_before(tag_no_case("STATIC")))(input)?;
-        let (input, is_primary_key) =
-            opt(space1_before(space1_tags_no_case(["PRIMARY", "KEY"])))(input)?;
+        let (mut input, (name, cql_type)) =
+            space0_between((Name::parse, traced("type", CqlType::parse)))(input)?;
+
+        // The modifiers may appear in any order, but each at most once.
+        let mut is_static = false;
+        let mut is_primary_key = false;
+        let mut mask = None;
+        loop {
+            let (remaining, modifier) = match space1_before::<_, _, _, E>(alt((
+                value(
+                    Modifier::Static,
+                    terminated(tag_no_case("STATIC"), not(identifier_char)),
+                ),
+                value(
+                    Modifier::PrimaryKey,
+                    terminated(
+                        space1_tags_no_case(["PRIMARY", "KEY"]),
+                        not(identifier_char),
+                    ),
+                ),
+                map(column_mask, Modifier::Mask),
+            )))(input)
+            {
+                Ok(result) => result,
+                // An incomplete mask fails the column, anything else ends the modifiers.
+                Err(nom::Err::Failure(error)) => return Err(nom::Err::Failure(error)),
+                Err(_) => break,
+            };
+            let (seen, message) = match modifier {
+                Modifier::Static => (is_static, "duplicate STATIC modifier"),
+                Modifier::PrimaryKey => (is_primary_key, "duplicate PRIMARY KEY modifier"),
+                Modifier::Mask(_) => (mask.is_some(), "duplicate MASKED WITH modifier"),
+            };
+            if seen {
+                let (duplicate, _) = Whitespace::split_whitespace(input);
+                return Err(nom::Err::Failure(E::add_context(
+                    duplicate,
+                    message,
+                    E::from_error_kind(duplicate, ErrorKind::Verify),
+                )));
+            }
+            match modifier {
+                Modifier::Static => is_static = true,
+                Modifier::PrimaryKey => is_primary_key = true,
+                Modifier::Mask(column_mask) => mask = Some(column_mask),
+            }
+            input = remaining;
+        }
 
+        let column = CqlColumn::new(name, cql_type, is_static, is_primary_key);
         Ok((
             input,
-            CqlColumn::new(
-                name,
-                cql_type,
-                is_static.is_some(),
-                is_primary_key.is_some(),
-            ),
+            match mask {
+                Some(mask) => column.with_mask(mask),
+                None => column,
+            },
         ))
     }
 }
+
+/// Parses `MASKED WITH DEFAULT` or `MASKED WITH name(arguments)`.
+fn column_mask<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlColumnMask<&'de str>, E> {
+    let (input, _) = space1_tags_no_case(["MASKED", "WITH"])(input)?;
+    // Once MASKED WITH is read the mask must follow.
+    cut(space1_before(alt((
+        value(
+            CqlColumnMask::Default,
+            terminated(keyword("DEFAULT"), not(identifier_char)),
+        ),
+        map(
+            pair(CqlQualifiedIdentifier::parse, arguments),
+            |(name, arguments)| CqlColumnMask::Function(name, arguments),
+        ),
+    ))))(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::term::{CqlConstant, CqlTerm};
+    use nom::error::{VerboseError, VerboseErrorKind};
+
+    fn parse(
+        input: &str,
+    ) -> IResult<&str, CqlColumn<&str, CqlIdentifier<&str>>, VerboseError<&str>> {
+        CqlColumn::parse(input)
+    }
+
+    #[test]
+    fn test_parse_without_modifiers() {
+        assert_eq!(
+            parse("my_field int,"),
+            Ok((
+                ",",
+                CqlColumn::new(
+                    CqlIdentifier::Unquoted("my_field"),
+                    CqlType::INT,
+                    false,
+                    false
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_modifiers_in_any_order() {
+        let expected = CqlColumn::new(
+            CqlIdentifier::Unquoted("my_field"),
+            CqlType::INT,
+            true,
+            true,
+        );
+        assert_eq!(
+            parse("my_field int STATIC PRIMARY KEY"),
+            Ok(("", expected.clone()))
+        );
+        assert_eq!(
+            parse("my_field int primary\n  key\n  static,"),
+            Ok((",", expected))
+        );
+        assert_eq!(
+            parse("my_field int PRIMARY KEY"),
+            Ok((
+                "",
+                CqlColumn::new(
+                    CqlIdentifier::Unquoted("my_field"),
+                    CqlType::INT,
+                    false,
+                    true
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_modifier_prefix() {
+        // A word starting with a modifier ends the modifiers.
+        let column = CqlColumn::new(CqlIdentifier::Unquoted("a"), CqlType::INT, false, false);
+        assert_eq!(parse("a int STATICX"), Ok((" STATICX", column.clone())));
+        assert_eq!(parse("a int PRIMARY KEYS"), Ok((" PRIMARY KEYS", column)));
+    }
+
+    #[test]
+    fn test_parse_duplicate_modifier() {
+        let input = "my_field int STATIC PRIMARY KEY STATIC";
+        let Err(nom::Err::Failure(error)) = parse(input) else {
+            panic!("duplicate modifier accepted");
+        };
+        assert!(error.errors.contains(&(
+            &input[32..],
+            VerboseErrorKind::Context("duplicate STATIC modifier")
+        )));
+        assert!(matches!(
+            parse("my_field int PRIMARY KEY PRIMARY KEY"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_mask() {
+        let column = |is_primary_key, mask| {
+            CqlColumn::new(
+                CqlIdentifier::Unquoted("email_1"),
+                CqlType::TEXT,
+                false,
+                is_primary_key,
+            )
+            .with_mask(mask)
+        };
+        assert_eq!(
+            parse("email_1 text MASKED WITH DEFAULT,"),
+            Ok((",", column(false, CqlColumnMask::Default)))
+        );
+        assert_eq!(
+            parse("email_1 text masked with mask_inner (2, null) PRIMARY KEY)"),
+            Ok((
+                ")",
+                column(
+                    true,
+                    CqlColumnMask::Function(
+                        CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted("mask_inner")),
+                        vec![
+                            CqlTerm::Constant(CqlConstant::Integer("2")),
+                            CqlTerm::Constant(CqlConstant::Null)
+                        ]
+                    )
+                )
+            ))
+        );
+        assert_eq!(
+            parse("email_1 text MASKED WITH \"system\".mask_replace('***')"),
+            Ok((
+                "",
+                column(
+                    false,
+                    CqlColumnMask::Function(
+                        CqlQualifiedIdentifier::new(
+                            Some(CqlIdentifier::Quoted("system".to_string())),
+                            CqlIdentifier::Unquoted("mask_replace")
+                        ),
+                        vec![CqlTerm::Constant(CqlConstant::String("***".to_string()))]
+                    )
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_mask() {
+        assert!(matches!(
+            parse("email_1 text MASKED WITH mask_inner(2"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(matches!(
+            parse("email_1 text MASKED WITH DEFAULT MASKED WITH DEFAULT"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+}
@@ -0,0 +1,102 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::table::like::CqlCreateTableLike;
+use crate::model::table::options::CqlTableOptions;
+use crate::parse::Parse;
+use crate::utils::{
+    debug_event, keyword, space1_before, space1_between, space1_tags_no_case, ws0, ws1,
+};
+use nom::combinator::{cut, opt};
+use nom::error::{ContextError, ParseError};
+use nom::sequence::{pair, preceded};
+use nom::IResult;
+
+impl<'de, E, Table> Parse<&'de str, E> for CqlCreateTableLike<&'de str, Table>
+where
+    E: ParseError<&'de str> + ContextError<&'de str>,
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("CREATE"), keyword("TABLE")))(input)?;
+        let (input, if_not_exists) =
+            opt(space1_before(space1_tags_no_case(["IF", "NOT", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlQualifiedIdentifier::parse)(input)?;
+        let (input, _) = pair(ws0, keyword("LIKE"))(input)?;
+        // Once `LIKE` is read the statement copies a table.
+        let (input, source) = cut(space1_before(CqlQualifiedIdentifier::parse))(input)?;
+        debug_event!(
+            keyspace = name.keyspace().as_ref().map(|keyspace| &**keyspace),
+            name = &**name.name(),
+            source = &**source.name(),
+            "table like"
+        );
+        // Once `WITH` is read the options are mandatory.
+        let (input, options) = opt(preceded(
+            pair(ws0, keyword("WITH")),
+            cut(preceded(ws1, CqlTableOptions::<_, CqlIdentifier<_>>::parse)),
+        ))(input)?;
+
+        Ok((
+            input,
+            CqlCreateTableLike::new(if_not_exists.is_some(), name, source, options),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::table::options::CqlTableOption;
+
+    type TableLike<'a> = CqlCreateTableLike<&'a str, ()>;
+
+    fn parse(input: &str) -> IResult<&str, TableLike<'_>, nom::error::Error<&str>> {
+        TableLike::parse(input)
+    }
+
+    fn name<'a>(keyspace: Option<&'a str>, name: &'a str) -> CqlQualifiedIdentifier<&'a str> {
+        CqlQualifiedIdentifier::new(
+            keyspace.map(CqlIdentifier::Unquoted),
+            CqlIdentifier::Unquoted(name),
+        )
+    }
+
+    #[test]
+    fn test_parse_create_table_like() {
+        assert_eq!(
+            parse("CREATE TABLE my_ks.copy_1 LIKE my_ks.original_1;"),
+            Ok((
+                ";",
+                CqlCreateTableLike::new(
+                    false,
+                    name(Some("my_ks"), "copy_1"),
+                    name(Some("my_ks"), "original_1"),
+                    None
+                )
+            ))
+        );
+
+        let (remaining, like) =
+            parse("create table if not exists copy_1 like original_1 with gc_grace_seconds = 0")
+                .unwrap();
+        assert_eq!(remaining, "");
+        assert!(like.if_not_exists());
+        assert_eq!(like.source(), &name(None, "original_1"));
+        assert_eq!(
+            like.options().as_ref().unwrap().options(),
+            &vec![CqlTableOption::GcGraceSeconds(0)]
+        );
+        assert!(like.source_table().is_none());
+    }
+
+    #[test]
+    fn test_parse_table_is_not_like() {
+        assert!(matches!(
+            parse("CREATE TABLE copy_1 (id_1 int PRIMARY KEY)"),
+            Err(nom::Err::Error(_))
+        ));
+        assert!(matches!(
+            parse("CREATE TABLE copy_1 LIKE"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+}
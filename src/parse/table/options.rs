@@ -1,83 +1,612 @@
-use crate::model::identifier::CqlIdentifier;
 use crate::model::order::CqlOrder;
-use crate::model::table::options::CqlTableOptions;
-use crate::parse::Parse;
-use crate::utils::{space0_around, space0_between, space1_before, space1_between, space1_tags};
+use crate::model::table::options::{CqlOptionValue, CqlTableOption, CqlTableOptions};
+use crate::parse::term::{float, identifier_char, integer, uuid};
+use crate::parse::{Parse, MAX_NESTING_DEPTH};
+use crate::utils::{
+    braced, keyword, space0_around, space0_between, space1_before, space1_between, space1_tags,
+    string_constant, ws0,
+};
 use nom::branch::alt;
-use nom::bytes::complete::{tag, tag_no_case};
-use nom::character::complete::multispace0;
-use nom::combinator::{map, opt};
-use nom::error::ParseError;
+use nom::bytes::complete::{tag, tag_no_case, take_while, take_while1};
+use nom::character::complete::alpha1;
+use nom::combinator::{map, not, opt, recognize, value};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
 use nom::multi::separated_list1;
-use nom::sequence::delimited;
+use nom::sequence::{delimited, pair, separated_pair, terminated};
 use nom::IResult;
+use std::borrow::Borrow;
 
-impl<'de, E: ParseError<&'de str>> Parse<&'de str, E>
-    for CqlTableOptions<&'de str, CqlIdentifier<&'de str>>
+impl<'de, E, ColumnRef, Entry> Parse<&'de str, E> for CqlTableOptions<&'de str, ColumnRef, Entry>
+where
+    E: ParseError<&'de str> + ContextError<&'de str>,
+    ColumnRef: Parse<&'de str, E>,
+    Entry: Parse<&'de str, E> + Borrow<CqlTableOption<&'de str>>,
 {
     fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
         let mut input = input;
         let mut compact_storage = false;
         let mut clustering_order = None;
         let mut options = Vec::new();
+        let mut names = Vec::new();
+        let mut duplicates = Vec::new();
 
         loop {
-            let (i, _) = multispace0(input)?;
-            let (i, option) = opt(|input| {
-                alt((
-                    map(space1_tags(["COMPACT", "STORAGE"]), |_| {
-                        compact_storage = true;
-                    }),
-                    map(
-                        space0_between((
-                            space1_tags(["CLUSTERING", "ORDER", "BY"]),
-                            delimited(
-                                tag("("),
-                                separated_list1(
-                                    tag(","),
-                                    space0_around(space1_between((
-                                        CqlIdentifier::parse,
-                                        alt((
-                                            map(tag_no_case("ASC"), |_| CqlOrder::Asc),
-                                            map(tag_no_case("DESC"), |_| CqlOrder::Desc),
-                                        )),
-                                    ))),
-                                ),
-                                tag(")"),
-                            ),
-                        )),
-                        |order| {
-                            clustering_order = Some(order);
-                        },
-                    ),
-                    // TODO: parse options.
-                ))(input)
-            })(i)?;
-
-            if option.is_none() {
-                input = i;
-                break;
+            let (i, _) = ws0(input)?;
+            let (i, name) = match table_option::<_, _, Entry>(i) {
+                Ok((i, TableOption::CompactStorage)) => {
+                    compact_storage = true;
+                    (i, "COMPACT STORAGE".to_string())
+                }
+                Ok((i, TableOption::ClusteringOrder(order))) => {
+                    clustering_order = Some(order);
+                    (i, "CLUSTERING ORDER".to_string())
+                }
+                Ok((i, TableOption::Option(entry))) => {
+                    let name = entry.borrow().name().to_ascii_lowercase();
+                    options.push(entry);
+                    (i, name)
+                }
+                // A key was read but the option is broken, the error points at its key.
+                Err(nom::Err::Error(_)) if option_name::<E>(i).is_ok() => {
+                    return Err(nom::Err::Failure(E::add_context(
+                        i,
+                        "invalid option",
+                        E::from_error_kind(i, ErrorKind::Verify),
+                    )))
+                }
+                // At least one option is required.
+                Err(nom::Err::Error(error)) if names.is_empty() => {
+                    return Err(nom::Err::Error(error))
+                }
+                // After an `AND` another option is required.
+                Err(nom::Err::Error(error)) => return Err(nom::Err::Failure(error)),
+                Err(error) => return Err(error),
+            };
+            if names.contains(&name) && !duplicates.contains(&name) {
+                duplicates.push(name.clone());
             }
+            names.push(name);
 
-            let (i, option) = opt(space1_before(tag_no_case("AND")))(i)?;
-
-            if option.is_none() {
-                input = i;
+            let (i, and) = opt(space1_before(tag_no_case("AND")))(i)?;
+            input = i;
+            if and.is_none() {
                 break;
             }
-
-            input = i;
         }
 
         Ok((
             input,
             CqlTableOptions::new(
                 compact_storage,
-                clustering_order
-                    .map(|(_, clustering_order)| clustering_order)
-                    .unwrap_or_default(),
+                clustering_order.unwrap_or_default(),
                 options,
-            ),
+            )
+            .with_duplicates(duplicates),
         ))
     }
 }
+
+/// An entry of the table options.
+enum TableOption<ColumnRef, Entry> {
+    /// `COMPACT STORAGE`.
+    CompactStorage,
+    /// `CLUSTERING ORDER BY (...)`.
+    ClusteringOrder(Vec<(ColumnRef, CqlOrder)>),
+    /// `name = value`.
+    Option(Entry),
+}
+
+/// Parses an entry of the table options.
+fn table_option<'de, E, ColumnRef, Entry>(
+    input: &'de str,
+) -> IResult<&'de str, TableOption<ColumnRef, Entry>, E>
+where
+    E: ParseError<&'de str> + ContextError<&'de str>,
+    ColumnRef: Parse<&'de str, E>,
+    Entry: Parse<&'de str, E>,
+{
+    alt((
+        map(
+            context("COMPACT", space1_tags(["COMPACT", "STORAGE"])),
+            |_| TableOption::CompactStorage,
+        ),
+        map(
+            space0_between((
+                context("CLUSTERING", space1_tags(["CLUSTERING", "ORDER", "BY"])),
+                delimited(
+                    tag("("),
+                    separated_list1(
+                        tag(","),
+                        space0_around(space1_between((
+                            ColumnRef::parse,
+                            alt((
+                                map(tag_no_case("ASC"), |_| CqlOrder::Asc),
+                                map(tag_no_case("DESC"), |_| CqlOrder::Desc),
+                            )),
+                        ))),
+                    ),
+                    tag(")"),
+                ),
+            )),
+            |(_, order)| TableOption::ClusteringOrder(order),
+        ),
+        map(Entry::parse, TableOption::Option),
+    ))(input)
+}
+
+impl<'de, E> Parse<&'de str, E> for CqlTableOption<&'de str>
+where
+    E: ParseError<&'de str> + ContextError<&'de str>,
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        map(
+            space0_between((known_option_name, context("=", tag("=")), option_value)),
+            |(name, _, value)| CqlTableOption::from_value(name, value),
+        )(input)
+    }
+}
+
+/// The names of the table options known to Cassandra and ScyllaDB, suggested when no option
+/// name could be read.
+const OPTION_NAMES: [&str; 19] = [
+    "bloom_filter_fp_chance",
+    "caching",
+    "cdc",
+    "comment",
+    "compaction",
+    "compression",
+    "crc_check_chance",
+    "default_time_to_live",
+    "gc_grace_seconds",
+    "id",
+    "max_index_interval",
+    "memtable_flush_period_in_ms",
+    "min_index_interval",
+    "paxos_grace_seconds",
+    "per_partition_rate_limit",
+    "read_repair",
+    "speculative_retry",
+    "synchronous_updates",
+    "tombstone_gc",
+];
+
+/// Like [`option_name`], but expects one of the known table options on failure.
+fn known_option_name<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, &'de str, E> {
+    option_name(input).map_err(|error| {
+        error.map(|error| {
+            OPTION_NAMES
+                .iter()
+                .fold(error, |error, name| E::add_context(input, name, error))
+        })
+    })
+}
+
+/// Recognizes the name of an option, like `gc_grace_seconds`.
+pub(crate) fn option_name<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, &'de str, E> {
+    context(
+        "option",
+        recognize(pair(
+            alpha1,
+            take_while(|c: char| c.is_ascii_alphanumeric() || c == '_'),
+        )),
+    )(input)
+}
+
+/// Parses the value of an option, a string, a uuid, a number, a boolean, a map or another constant
+/// like `99.0PERCENTILE`.
+pub(crate) fn option_value<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlOptionValue<&'de str>, E> {
+    nested_option_value(input, 0)
+}
+
+/// Parses an option value nested in maps at `depth`, values nested deeper than
+/// [`MAX_NESTING_DEPTH`] are a failure.
+fn nested_option_value<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+    depth: usize,
+) -> IResult<&'de str, CqlOptionValue<&'de str>, E> {
+    if depth > MAX_NESTING_DEPTH {
+        return Err(nom::Err::Failure(E::add_context(
+            input,
+            "option value nested too deeply",
+            E::from_error_kind(input, ErrorKind::Verify),
+        )));
+    }
+
+    context(
+        "option value",
+        alt((
+            map(string_constant, CqlOptionValue::String),
+            map(
+                braced(separated_pair(
+                    string_constant,
+                    delimited(ws0, tag(":"), ws0),
+                    |input| nested_option_value(input, depth + 1),
+                )),
+                CqlOptionValue::Map,
+            ),
+            value(
+                CqlOptionValue::Boolean(true),
+                terminated(keyword("TRUE"), not(identifier_char)),
+            ),
+            value(
+                CqlOptionValue::Boolean(false),
+                terminated(keyword("FALSE"), not(identifier_char)),
+            ),
+            // Before the numbers, a uuid can start with digits.
+            map(uuid, CqlOptionValue::Uuid),
+            map(float, CqlOptionValue::Float),
+            // `99.0PERCENTILE` is no number.
+            map(terminated(integer, not(tag("."))), CqlOptionValue::Integer),
+            map(
+                take_while1(|c: char| {
+                    c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-' | '+')
+                }),
+                CqlOptionValue::Constant,
+            ),
+        )),
+    )(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::table::caching::{CqlCaching, CqlCachingKeys, CqlRowsPerPartition};
+    use crate::model::table::compaction::{
+        CqlCompaction, CqlCompactionStrategy, CqlCompactionWindowUnit,
+    };
+    use crate::model::table::compression::CqlCompressor;
+    use crate::model::CqlIdentifier;
+
+    type Options<'a> = CqlTableOptions<&'a str, CqlIdentifier<&'a str>>;
+
+    #[test]
+    fn test_parse_options() {
+        let input = "compaction = { 'class' : 'LeveledCompactionStrategy', 'sub': {'a': '}'} } \
+            AND CLUSTERING ORDER BY (my_field DESC) AND comment='It''s' \
+            AND gc_grace_seconds=3600 AND caching = $$all$$ AND speculative_retry = 99.0PERCENTILE \
+            AND extensions = {  };";
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse(input);
+        let (remaining, options) = result.unwrap();
+        assert_eq!(remaining, ";");
+        assert_eq!(
+            options.clustering_order(),
+            &vec![(CqlIdentifier::Unquoted("my_field"), CqlOrder::Desc)]
+        );
+        assert_eq!(
+            options.options(),
+            &vec![
+                CqlTableOption::Compaction(Box::new(CqlCompaction::from_entries(vec![
+                    (
+                        "class".to_string(),
+                        CqlOptionValue::String("LeveledCompactionStrategy".to_string())
+                    ),
+                    (
+                        "sub".to_string(),
+                        CqlOptionValue::Map(vec![(
+                            "a".to_string(),
+                            CqlOptionValue::String("}".to_string())
+                        )])
+                    ),
+                ]))),
+                CqlTableOption::Comment("It's".to_string()),
+                CqlTableOption::GcGraceSeconds(3600),
+                // The single string form of old dumps.
+                CqlTableOption::Caching(CqlCaching::from_legacy("ALL").unwrap()),
+                CqlTableOption::SpeculativeRetry("99.0PERCENTILE".to_string()),
+                CqlTableOption::Other("extensions", CqlOptionValue::Map(vec![])),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_options_in_any_order() {
+        let input = "comment = 'users table' AND COMPACT STORAGE\n  AND default_time_to_live = 0 \
+            AND CLUSTERING ORDER BY (my_field ASC) AND memtable_flush_period_in_ms = 0";
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse(input);
+        let (remaining, options) = result.unwrap();
+        assert_eq!(remaining, "");
+        assert!(options.compact_storage());
+        assert_eq!(options.clustering_order().len(), 1);
+        assert_eq!(
+            options.options(),
+            &vec![
+                CqlTableOption::Comment("users table".to_string()),
+                CqlTableOption::DefaultTimeToLive(0),
+                CqlTableOption::MemtableFlushPeriodInMs(0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_unterminated_map() {
+        let result: IResult<_, Options, nom::error::Error<_>> =
+            Options::parse("compaction = { 'class' : 'x'");
+        assert!(result.is_err());
+        let result: IResult<_, Options, nom::error::Error<_>> =
+            Options::parse("compaction = { 'class' 'x' }");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_parse_typed_values() {
+        let input = "gc_grace_seconds = 864000 AND bloom_filter_fp_chance = 0.01 AND cdc = TRUE \
+            AND crc_check_chance = 1.0 AND speculative_retry = '99p' AND read_repair = 'BLOCKING' \
+            AND compaction = {'class': 'SizeTieredCompactionStrategy', 'max_threshold': '32'} \
+            AND synchronous_updates = false";
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse(input);
+        let (remaining, options) = result.unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(options.gc_grace_seconds(), Some(864000));
+        assert_eq!(options.bloom_filter_fp_chance(), Some(0.01));
+        assert_eq!(options.cdc(), Some(true));
+        assert_eq!(options.crc_check_chance(), Some(1.0));
+        assert_eq!(options.speculative_retry(), Some("99p"));
+        assert_eq!(options.read_repair(), Some("BLOCKING"));
+        assert_eq!(options.comment(), None);
+        let compaction = options.compaction().unwrap();
+        assert_eq!(compaction.class(), &Some(CqlCompactionStrategy::SizeTiered));
+        assert_eq!(compaction.max_threshold(), Some(32));
+        let other = &options.options()[7];
+        assert_eq!(other.name(), "synchronous_updates");
+        assert_eq!(other, &CqlTableOption::SynchronousUpdates(false));
+    }
+
+    #[test]
+    fn test_parse_mistyped_option() {
+        // A well-known option with a value of another type is no parse failure.
+        let result: IResult<_, _, nom::error::Error<_>> =
+            Options::parse("gc_grace_seconds = 'soon' AND Default_Time_To_Live = 60");
+        let (_, options) = result.unwrap();
+        assert_eq!(options.gc_grace_seconds(), None);
+        assert_eq!(
+            options.options()[0],
+            CqlTableOption::Other(
+                "gc_grace_seconds",
+                CqlOptionValue::String("soon".to_string())
+            )
+        );
+        assert_eq!(options.default_ttl(), Some(60));
+    }
+
+    #[test]
+    fn test_parse_table_id() {
+        let result: IResult<_, _, nom::error::Error<_>> =
+            Options::parse("ID = '5a1c395e-b41f-11e5-9f22-ba0be0483c18' AND comment = 'x'");
+        let (remaining, options) = result.unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(options.id(), Some(0x5a1c395e_b41f_11e5_9f22_ba0be0483c18));
+
+        // Unquoted, starting with digits like a number.
+        let result: IResult<_, _, nom::error::Error<_>> =
+            Options::parse("id = 123e4567-e89b-12d3-a456-426614174000;");
+        let (remaining, options) = result.unwrap();
+        assert_eq!(remaining, ";");
+        assert_eq!(
+            options.options(),
+            &vec![CqlTableOption::Id(0x123e4567_e89b_12d3_a456_426614174000)]
+        );
+
+        // No uuid, so no typed id.
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse("id = 'my_table'");
+        let (_, options) = result.unwrap();
+        assert_eq!(options.id(), None);
+    }
+
+    #[test]
+    fn test_parse_compaction() {
+        let input = "compaction = {\
+            'class': 'org.apache.cassandra.db.compaction.TimeWindowCompactionStrategy', \
+            'compaction_window_unit': 'DAYS', 'compaction_window_size': '1', \
+            'max_threshold': '32', 'min_threshold': 'four', 'tombstone_threshold': 0.2, \
+            'enabled': 'true', 'provide_overlapping_tombstones': 'NONE'}";
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse(input);
+        let (remaining, options) = result.unwrap();
+        assert_eq!(remaining, "");
+        let compaction = options.compaction().unwrap();
+        assert_eq!(compaction.class(), &Some(CqlCompactionStrategy::TimeWindow));
+        assert_eq!(
+            compaction.compaction_window_unit(),
+            Some(CqlCompactionWindowUnit::Days)
+        );
+        assert_eq!(compaction.compaction_window_size(), Some(1));
+        assert_eq!(compaction.max_threshold(), Some(32));
+        assert_eq!(compaction.tombstone_threshold(), Some(0.2));
+        assert_eq!(compaction.enabled(), Some(true));
+        // A value which can not be typed is no parse failure.
+        assert_eq!(compaction.min_threshold(), None);
+        assert_eq!(compaction.diagnostics().len(), 1);
+        assert_eq!(compaction.diagnostics()[0].key(), "min_threshold");
+        assert_eq!(compaction.diagnostics()[0].expected(), "an integer");
+        assert_eq!(
+            compaction.extras(),
+            &vec![(
+                "provide_overlapping_tombstones".to_string(),
+                CqlOptionValue::String("NONE".to_string())
+            )]
+        );
+        assert_eq!(
+            compaction.to_entries()[..2],
+            [
+                (
+                    "class".to_string(),
+                    CqlOptionValue::String("TimeWindowCompactionStrategy".to_string())
+                ),
+                (
+                    "enabled".to_string(),
+                    CqlOptionValue::String("true".to_string())
+                ),
+            ]
+        );
+
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse(
+            "compaction = {'class': 'com.example.MyStrategy', 'sstable_size_in_mb': '160'}",
+        );
+        let (_, options) = result.unwrap();
+        let compaction = options.compaction().unwrap();
+        assert_eq!(
+            compaction.class(),
+            &Some(CqlCompactionStrategy::Other(
+                "com.example.MyStrategy".to_string()
+            ))
+        );
+        assert_eq!(compaction.sstable_size_in_mb(), Some(160));
+    }
+
+    #[test]
+    fn test_parse_compression() {
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse(
+            "compression = {'class': 'org.apache.cassandra.io.compress.LZ4Compressor', \
+            'chunk_length_in_kb': 64, 'crc_check_chance': '0.5'}",
+        );
+        let (_, options) = result.unwrap();
+        let compression = options.compression().unwrap();
+        assert_eq!(compression.class(), &Some(CqlCompressor::Lz4));
+        assert_eq!(compression.chunk_length_in_kb(), Some(64));
+        assert_eq!(compression.crc_check_chance(), Some(0.5));
+        assert!(compression.is_enabled());
+
+        // The deprecated keys of old dumps.
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse(
+            "compression = {'sstable_compression': 'SnappyCompressor', 'chunk_length_kb': '16', \
+            'compression_level': '3'}",
+        );
+        let (_, options) = result.unwrap();
+        let compression = options.compression().unwrap();
+        assert_eq!(compression.class(), &Some(CqlCompressor::Snappy));
+        assert_eq!(compression.chunk_length_in_kb(), Some(16));
+        assert_eq!(compression.extras().len(), 1);
+        assert_eq!(
+            compression.to_entries()[..2],
+            [
+                (
+                    "class".to_string(),
+                    CqlOptionValue::String("SnappyCompressor".to_string())
+                ),
+                (
+                    "chunk_length_in_kb".to_string(),
+                    CqlOptionValue::String("16".to_string())
+                ),
+            ]
+        );
+
+        let result: IResult<_, _, nom::error::Error<_>> =
+            Options::parse("compression = {'enabled': 'false'} AND comment = ''");
+        let (_, options) = result.unwrap();
+        let compression = options.compression().unwrap();
+        assert_eq!(compression.enabled(), Some(false));
+        assert!(!compression.is_enabled());
+        assert_eq!(compression.class(), &None);
+
+        let result: IResult<_, _, nom::error::Error<_>> =
+            Options::parse("compression = {'sstable_compression': ''}");
+        let (_, options) = result.unwrap();
+        assert!(!options.compression().unwrap().is_enabled());
+    }
+
+    #[test]
+    fn test_parse_caching() {
+        let result: IResult<_, _, nom::error::Error<_>> =
+            Options::parse("caching = {'keys': 'ALL', 'rows_per_partition': '120'}");
+        let (_, options) = result.unwrap();
+        let caching = options.caching().unwrap();
+        assert_eq!(caching.keys(), Some(CqlCachingKeys::All));
+        assert_eq!(
+            caching.rows_per_partition(),
+            Some(CqlRowsPerPartition::Count(120))
+        );
+
+        let result: IResult<_, _, nom::error::Error<_>> =
+            Options::parse("caching = {'keys': 'some', 'rows_per_partition': 'none'}");
+        let (_, options) = result.unwrap();
+        let caching = options.caching().unwrap();
+        assert_eq!(caching.keys(), None);
+        assert_eq!(caching.diagnostics()[0].key(), "keys");
+        assert_eq!(
+            caching.rows_per_partition(),
+            Some(CqlRowsPerPartition::None)
+        );
+
+        // The single string form of Cassandra 2.x.
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse("caching = 'KEYS_ONLY'");
+        let (_, options) = result.unwrap();
+        let caching = options.caching().unwrap();
+        assert_eq!(caching.keys(), Some(CqlCachingKeys::All));
+        assert_eq!(
+            caching.rows_per_partition(),
+            Some(CqlRowsPerPartition::None)
+        );
+        assert_eq!(
+            caching.to_entries(),
+            vec![
+                (
+                    "keys".to_string(),
+                    CqlOptionValue::String("ALL".to_string())
+                ),
+                (
+                    "rows_per_partition".to_string(),
+                    CqlOptionValue::String("NONE".to_string())
+                ),
+            ]
+        );
+
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse("caching = 'SOME'");
+        let (_, options) = result.unwrap();
+        assert_eq!(options.caching(), None);
+    }
+
+    #[test]
+    fn test_parse_duplicate_options() {
+        let input = "CLUSTERING ORDER BY (my_field ASC) AND COMPACT STORAGE AND comment = 'x' \
+            AND Comment = 'y' AND COMPACT STORAGE AND comment = 'z'";
+        let result: IResult<_, _, nom::error::Error<_>> = Options::parse(input);
+        let (remaining, options) = result.unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(
+            options.duplicates(),
+            &vec!["comment".to_string(), "COMPACT STORAGE".to_string()]
+        );
+        assert_eq!(options.options().len(), 3);
+
+        let result: IResult<_, _, nom::error::Error<_>> =
+            Options::parse("comment = 'x' AND gc_grace_seconds = 1");
+        assert!(result.unwrap().1.duplicates().is_empty());
+    }
+
+    #[test]
+    fn test_parse_unparseable_option() {
+        let input = "comment = 'x' AND 'oops' = 1 AND gc_grace_seconds = 1";
+        let result: IResult<_, Options, nom::error::Error<_>> = Options::parse(input);
+        match result {
+            Err(nom::Err::Failure(error)) => assert_eq!(error.input, &input[18..]),
+            result => panic!("expected a failure, got {:?}", result),
+        }
+        let result: IResult<_, Options, nom::error::Error<_>> =
+            Options::parse("comment = 'x' AND gc_grace_seconds = ");
+        assert!(matches!(result, Err(nom::Err::Failure(_))));
+    }
+
+    #[test]
+    fn test_invalid_option_points_at_key() {
+        for (input, key) in [
+            (
+                "CREATE TABLE t (k int PRIMARY KEY) WITH comment = 'x' AND bogus",
+                58,
+            ),
+            ("CREATE TABLE t (k int PRIMARY KEY) WITH bogus 5", 40),
+            (
+                "CREATE TABLE t (k int PRIMARY KEY) WITH gc_grace_seconds = ",
+                40,
+            ),
+        ] {
+            let error = crate::parse_cql_complete(input).unwrap_err();
+            assert_eq!(error.offset(), key, "{}", input);
+            assert_eq!(error.message(), "invalid option");
+        }
+    }
+}
@@ -1,38 +1,46 @@
-use crate::model::identifier::CqlIdentifier;
 use crate::model::table::primary_key::CqlPrimaryKey;
 use crate::parse::Parse;
 use crate::utils::{space0_around, space0_between};
 use nom::branch::alt;
 use nom::bytes::complete::tag;
 use nom::combinator::{map, opt};
-use nom::error::ParseError;
+use nom::error::{ContextError, ParseError};
 use nom::multi::separated_list1;
 use nom::sequence::delimited;
 use nom::IResult;
 
-impl<'de, E: ParseError<&'de str>> Parse<&'de str, E> for CqlPrimaryKey<CqlIdentifier<&'de str>> {
+impl<'de, E, ColumnRef> Parse<&'de str, E> for CqlPrimaryKey<ColumnRef>
+where
+    E: ParseError<&'de str> + ContextError<&'de str>,
+    ColumnRef: Parse<&'de str, E>,
+{
     fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
-        let (input, (_, partition_key, clustering_columns, _)) = space0_between((
-            tag("("),
-            alt((
-                map(CqlIdentifier::parse, |name| vec![name]),
-                delimited(
-                    tag("("),
-                    separated_list1(tag(","), space0_around(CqlIdentifier::parse)),
-                    tag(")"),
-                ),
-            )),
-            opt(space0_between((
-                tag(","),
-                separated_list1(tag(","), space0_around(CqlIdentifier::parse)),
-            ))),
-            tag(")"),
-        ))(input)?;
+        let (input, (_, (partition_key, composite_partition), clustering_columns, _)) =
+            space0_between((
+                tag("("),
+                alt((
+                    map(ColumnRef::parse, |name| (vec![name], false)),
+                    map(
+                        delimited(
+                            tag("("),
+                            separated_list1(tag(","), space0_around(ColumnRef::parse)),
+                            tag(")"),
+                        ),
+                        |names| (names, true),
+                    ),
+                )),
+                opt(space0_between((
+                    tag(","),
+                    separated_list1(tag(","), space0_around(ColumnRef::parse)),
+                ))),
+                tag(")"),
+            ))(input)?;
 
         Ok((
             input,
             CqlPrimaryKey::new(
                 partition_key,
+                composite_partition,
                 clustering_columns
                     .map(|(_, clustering_columns)| clustering_columns)
                     .unwrap_or_default(),
@@ -40,3 +48,69 @@ impl<'de, E: ParseError<&'de str>> Parse<&'de str, E> for CqlPrimaryKey<CqlIdent
         ))
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::identifier::CqlIdentifier;
+
+    fn round_trip(input: &str) -> CqlPrimaryKey<CqlIdentifier<&str>> {
+        let (remaining, primary_key) = CqlPrimaryKey::parse(input)
+            .map_err(|e: nom::Err<nom::error::Error<&str>>| e)
+            .unwrap();
+        assert_eq!(remaining, "");
+        assert_eq!(primary_key.to_string(), input);
+
+        primary_key
+    }
+
+    #[test]
+    fn test_single_partition_column() {
+        let primary_key = round_trip("(a1)");
+        assert!(!primary_key.composite_partition());
+        assert_eq!(primary_key.partition_key(), &vec![CqlIdentifier::new("a1")]);
+        assert!(primary_key.clustering_columns().is_empty());
+    }
+
+    #[test]
+    fn test_parenthesized_single_partition_column() {
+        let primary_key = round_trip("((a1))");
+        assert!(primary_key.composite_partition());
+        assert_eq!(primary_key.partition_key(), &vec![CqlIdentifier::new("a1")]);
+    }
+
+    #[test]
+    fn test_parenthesized_partition_with_clustering() {
+        let primary_key = round_trip("((a1), b1)");
+        assert!(primary_key.composite_partition());
+        assert_eq!(primary_key.partition_key(), &vec![CqlIdentifier::new("a1")]);
+        assert_eq!(
+            primary_key.clustering_columns(),
+            &vec![CqlIdentifier::new("b1")]
+        );
+
+        // Without the parentheses the same columns form the same partition key.
+        let unparenthesized = round_trip("(a1, b1)");
+        assert!(!unparenthesized.composite_partition());
+        assert_ne!(unparenthesized, primary_key);
+    }
+
+    #[test]
+    fn test_composite_partition() {
+        let primary_key = round_trip("((a1, b1), c1)");
+        assert!(primary_key.composite_partition());
+        assert_eq!(
+            primary_key.partition_key(),
+            &vec![CqlIdentifier::new("a1"), CqlIdentifier::new("b1")]
+        );
+        assert_eq!(
+            primary_key.clustering_columns(),
+            &vec![CqlIdentifier::new("c1")]
+        );
+    }
+
+    #[test]
+    fn test_quoted_columns() {
+        round_trip("((\"a\"\"1\", b1), \"C1\")");
+    }
+}
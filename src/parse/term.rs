@@ -0,0 +1,625 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::term::{CqlBindMarker, CqlConstant, CqlTerm};
+use crate::parse::{Parse, MAX_NESTING_DEPTH};
+use crate::utils::{keyword, string_constant, ws0};
+use nom::branch::alt;
+use nom::bytes::complete::{tag, tag_no_case, take_while_m_n};
+use nom::character::complete::{digit0, digit1, hex_digit0, one_of, satisfy};
+use nom::combinator::{map, map_opt, not, opt, recognize, value};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
+use nom::multi::{many1, separated_list0};
+use nom::sequence::{delimited, pair, preceded, separated_pair, terminated, tuple};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlTerm<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        parse_term(input, 0)
+    }
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlConstant<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        alt((
+            value(
+                CqlConstant::Null,
+                terminated(keyword("NULL"), not(identifier_char)),
+            ),
+            value(
+                CqlConstant::Boolean(true),
+                terminated(keyword("TRUE"), not(identifier_char)),
+            ),
+            value(
+                CqlConstant::Boolean(false),
+                terminated(keyword("FALSE"), not(identifier_char)),
+            ),
+            map(string_constant, CqlConstant::String),
+            // A uuid can start like a float, `123e4567-...`.
+            map(uuid, CqlConstant::Uuid),
+            map(blob, CqlConstant::Blob),
+            map(float, CqlConstant::Float),
+            map(integer, CqlConstant::Integer),
+            map(duration, CqlConstant::Duration),
+        ))(input)
+    }
+}
+
+/// Parses a term nested at `depth`, terms nested deeper than [`MAX_NESTING_DEPTH`] are a failure.
+fn parse_term<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+    depth: usize,
+) -> IResult<&'de str, CqlTerm<&'de str>, E> {
+    if depth > MAX_NESTING_DEPTH {
+        return Err(nom::Err::Failure(E::add_context(
+            input,
+            "term nested too deeply",
+            E::from_error_kind(input, ErrorKind::Verify),
+        )));
+    }
+
+    context(
+        "term",
+        alt((
+            map(CqlConstant::parse, CqlTerm::Constant),
+            |input| collection(input, depth),
+            map(bind_marker, CqlTerm::BindMarker),
+            |input| function_call(input, depth),
+        )),
+    )(input)
+}
+
+/// Parses a function call, like `to_timestamp(now())`, only if a `(` follows the name.
+fn function_call<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+    depth: usize,
+) -> IResult<&'de str, CqlTerm<&'de str>, E> {
+    map(
+        pair(function_name, |input| nested_arguments(input, depth)),
+        |(name, arguments)| CqlTerm::FunctionCall(name, arguments),
+    )(input)
+}
+
+/// Parses the name of a function, the reserved `TOKEN` names the token function.
+pub(crate) fn function_name<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlQualifiedIdentifier<&'de str>, E> {
+    alt((
+        CqlQualifiedIdentifier::parse,
+        map(terminated(keyword("TOKEN"), not(identifier_char)), |name| {
+            CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted(name))
+        }),
+    ))(input)
+}
+
+/// Parses a list, set, map, user defined type or tuple literal, an empty `{}` is an empty map.
+///
+/// The keys of a map are terms, the fields of a user defined type are names. The elements of
+/// braces are parsed once, whether they are a map or a set is decided after.
+fn collection<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+    depth: usize,
+) -> IResult<&'de str, CqlTerm<&'de str>, E> {
+    let nested = |input| parse_term(input, depth + 1);
+    let colon = || delimited(ws0, tag(":"), ws0);
+    alt((
+        map(list("[", nested, "]"), CqlTerm::List),
+        map(list("(", nested, ")"), CqlTerm::TupleLiteral),
+        map_opt(
+            list("{", pair(nested, opt(preceded(colon(), nested))), "}"),
+            |elements| {
+                if elements.iter().all(|(_, value)| value.is_none()) && !elements.is_empty() {
+                    Some(CqlTerm::Set(
+                        elements.into_iter().map(|(key, _)| key).collect(),
+                    ))
+                } else {
+                    elements
+                        .into_iter()
+                        .map(|(key, value)| value.map(|value| (key, value)))
+                        .collect::<Option<Vec<_>>>()
+                        .map(CqlTerm::Map)
+                }
+            },
+        ),
+        map(
+            list(
+                "{",
+                separated_pair(CqlIdentifier::parse, colon(), nested),
+                "}",
+            ),
+            CqlTerm::UdtLiteral,
+        ),
+    ))(input)
+}
+
+/// Parses a comma separated list between the delimiters, like `[1, 2]`.
+fn list<'de, O, E: ParseError<&'de str> + ContextError<&'de str>>(
+    open: &'static str,
+    element: impl FnMut(&'de str) -> IResult<&'de str, O, E>,
+    close: &'static str,
+) -> impl FnMut(&'de str) -> IResult<&'de str, Vec<O>, E> {
+    delimited(
+        tag(open),
+        separated_list0(tag(","), delimited(ws0, element, ws0)),
+        pair(ws0, context(close, tag(close))),
+    )
+}
+
+/// Parses the parenthesized arguments of a function call, like `(2, null)`.
+pub(crate) fn arguments<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, Vec<CqlTerm<&'de str>>, E> {
+    nested_arguments(input, 0)
+}
+
+/// Parses the arguments of a function call nested in a term at `depth`.
+fn nested_arguments<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+    depth: usize,
+) -> IResult<&'de str, Vec<CqlTerm<&'de str>>, E> {
+    delimited(
+        pair(ws0, tag("(")),
+        separated_list0(
+            tag(","),
+            delimited(ws0, |input| parse_term(input, depth + 1), ws0),
+        ),
+        pair(ws0, context(")", tag(")"))),
+    )(input)
+}
+
+/// Recognizes an integer literal, like `-42`.
+pub(crate) fn integer<'de, E: ParseError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, &'de str, E> {
+    terminated(recognize(pair(opt(tag("-")), digit1)), not(identifier_char))(input)
+}
+
+/// Recognizes a floating point literal with a fraction or an exponent, like `1.5e3`, or one of
+/// `NaN`, `Infinity` and `-Infinity`.
+///
+/// A dot followed by a name, like in `1.foo`, is not part of the literal.
+pub(crate) fn float<'de, E: ParseError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, &'de str, E> {
+    let exponent = || tuple((one_of("eE"), opt(one_of("+-")), digit1));
+    terminated(
+        alt((
+            recognize(tuple((
+                opt(tag("-")),
+                digit1,
+                alt((
+                    recognize(pair(pair(tag("."), digit0), opt(exponent()))),
+                    recognize(exponent()),
+                )),
+            ))),
+            tag_no_case("NaN"),
+            recognize(pair(opt(tag("-")), tag_no_case("Infinity"))),
+        )),
+        not(identifier_char),
+    )(input)
+}
+
+/// Recognizes a uuid literal of hex digit groups, like `123e4567-e89b-12d3-a456-426614174000`.
+pub(crate) fn uuid<'de, E: ParseError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, &'de str, E> {
+    let group = |length| take_while_m_n(length, length, |c: char| c.is_ascii_hexdigit());
+    terminated(
+        recognize(tuple((
+            group(8),
+            tag("-"),
+            group(4),
+            tag("-"),
+            group(4),
+            tag("-"),
+            group(4),
+            tag("-"),
+            group(12),
+        ))),
+        not(identifier_char),
+    )(input)
+}
+
+/// Recognizes a blob literal of hex digits, like `0xCAFEBABE`.
+fn blob<'de, E: ParseError<&'de str>>(input: &'de str) -> IResult<&'de str, &'de str, E> {
+    terminated(
+        recognize(pair(tag_no_case("0x"), hex_digit0)),
+        not(identifier_char),
+    )(input)
+}
+
+/// Recognizes a duration literal of numbers with units, like `1h30m` or `-500ms`.
+pub(crate) fn duration<'de, E: ParseError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, &'de str, E> {
+    // The two character units first, `m` is a prefix of `mo` and `ms`.
+    let unit = alt((
+        tag_no_case("mo"),
+        tag_no_case("ms"),
+        tag_no_case("us"),
+        tag("µs"),
+        tag_no_case("ns"),
+        recognize(one_of("yYwWdDhHmMsS")),
+    ));
+    terminated(
+        recognize(pair(opt(tag("-")), many1(pair(digit1, unit)))),
+        not(identifier_char),
+    )(input)
+}
+
+/// Parses a bind marker, `?` or `:name`.
+pub(crate) fn bind_marker<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlBindMarker<&'de str>, E> {
+    alt((
+        value(CqlBindMarker::Positional, tag("?")),
+        map(
+            preceded(tag(":"), CqlIdentifier::parse),
+            CqlBindMarker::Named,
+        ),
+    ))(input)
+}
+
+pub(crate) fn identifier_char<'de, E: ParseError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, char, E> {
+    satisfy(|c| c.is_ascii_alphanumeric() || c == '_')(input)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use nom::error::{VerboseError, VerboseErrorKind};
+
+    fn parse(input: &str) -> IResult<&str, CqlTerm<&str>, VerboseError<&str>> {
+        CqlTerm::parse(input)
+    }
+
+    #[test]
+    fn test_parse_literals() {
+        assert_eq!(
+            parse("null)"),
+            Ok((")", CqlTerm::Constant(CqlConstant::Null)))
+        );
+        assert_eq!(
+            parse("'it''s',"),
+            Ok((
+                ",",
+                CqlTerm::Constant(CqlConstant::String("it's".to_string()))
+            ))
+        );
+        assert_eq!(
+            parse("-42)"),
+            Ok((")", CqlTerm::Constant(CqlConstant::Integer("-42"))))
+        );
+        assert_eq!(
+            parse("1.5e3"),
+            Ok(("", CqlTerm::Constant(CqlConstant::Float("1.5e3"))))
+        );
+        assert_eq!(
+            parse("2."),
+            Ok(("", CqlTerm::Constant(CqlConstant::Float("2."))))
+        );
+        assert_eq!(
+            parse("7E-2"),
+            Ok(("", CqlTerm::Constant(CqlConstant::Float("7E-2"))))
+        );
+        assert_eq!(
+            parse("1h30m,"),
+            Ok((",", CqlTerm::Constant(CqlConstant::Duration("1h30m"))))
+        );
+        assert_eq!(
+            parse("-500ms"),
+            Ok(("", CqlTerm::Constant(CqlConstant::Duration("-500ms"))))
+        );
+        assert_eq!(
+            parse("2mo1us"),
+            Ok(("", CqlTerm::Constant(CqlConstant::Duration("2mo1us"))))
+        );
+    }
+
+    #[test]
+    fn test_parse_constant() {
+        let constant = |input| CqlConstant::parse(input) as IResult<_, _, VerboseError<&str>>;
+        assert_eq!(constant("42 "), Ok((" ", CqlConstant::Integer("42"))));
+        assert_eq!(
+            constant("0xCAFEBABE"),
+            Ok(("", CqlConstant::Blob("0xCAFEBABE")))
+        );
+        assert_eq!(
+            constant("-Infinity"),
+            Ok(("", CqlConstant::Float("-Infinity")))
+        );
+        // The dot of a name after a number is left to the caller.
+        assert_eq!(constant("1.foo"), Ok((".foo", CqlConstant::Integer("1"))));
+        assert_eq!(constant("null"), Ok(("", CqlConstant::Null)));
+        assert_eq!(constant("True)"), Ok((")", CqlConstant::Boolean(true))));
+        assert_eq!(
+            constant("123e4567-e89b-12d3-a456-426614174000"),
+            Ok((
+                "",
+                CqlConstant::Uuid("123e4567-e89b-12d3-a456-426614174000")
+            ))
+        );
+        assert!(constant("foo").is_err());
+    }
+
+    #[test]
+    fn test_parse_uuids_and_booleans() {
+        assert_eq!(
+            parse("123e4567-e89b-12d3-a456-426614174000)"),
+            Ok((
+                ")",
+                CqlTerm::Constant(CqlConstant::Uuid("123e4567-e89b-12d3-a456-426614174000"))
+            ))
+        );
+        assert_eq!(
+            parse("5A1C395E-B41F-11E5-9F22-BA0BE0483C18"),
+            Ok((
+                "",
+                CqlTerm::Constant(CqlConstant::Uuid("5A1C395E-B41F-11E5-9F22-BA0BE0483C18"))
+            ))
+        );
+        assert_eq!(
+            parse("true,"),
+            Ok((",", CqlTerm::Constant(CqlConstant::Boolean(true))))
+        );
+        assert_eq!(
+            parse("FALSE"),
+            Ok(("", CqlTerm::Constant(CqlConstant::Boolean(false))))
+        );
+        assert_eq!(
+            parse("Null"),
+            Ok(("", CqlTerm::Constant(CqlConstant::Null)))
+        );
+        // Names starting like a keyword or a uuid are function calls.
+        assert_eq!(
+            parse("true_1()"),
+            Ok((
+                "",
+                CqlTerm::FunctionCall(
+                    CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted("true_1")),
+                    vec![]
+                )
+            ))
+        );
+        assert_eq!(
+            parse("cafe_1()").map(|(_, term)| term.is_function_call()),
+            Ok(true)
+        );
+        // A group too short, too long or with another character is no uuid.
+        for input in [
+            "123e4567-e89b-12d3-a456-42661417400",
+            "123e4567-e89b-12d3-a456-4266141740001",
+            "123e4567-e89b-12d3-a456-42661417400g",
+        ] {
+            assert!(!parse(input)
+                .is_ok_and(|(_, term)| matches!(term, CqlTerm::Constant(CqlConstant::Uuid(_)))));
+        }
+    }
+
+    #[test]
+    fn test_parse_numbers_and_blobs() {
+        assert_eq!(
+            parse("42"),
+            Ok(("", CqlTerm::Constant(CqlConstant::Integer("42"))))
+        );
+        assert_eq!(
+            parse("-7,"),
+            Ok((",", CqlTerm::Constant(CqlConstant::Integer("-7"))))
+        );
+        // Larger than an i64, the lexeme is kept.
+        assert_eq!(
+            parse("123456789012345678901234567890"),
+            Ok((
+                "",
+                CqlTerm::Constant(CqlConstant::Integer("123456789012345678901234567890"))
+            ))
+        );
+        assert_eq!(
+            parse("NaN)"),
+            Ok((")", CqlTerm::Constant(CqlConstant::Float("NaN"))))
+        );
+        assert_eq!(
+            parse("Infinity"),
+            Ok(("", CqlTerm::Constant(CqlConstant::Float("Infinity"))))
+        );
+        assert_eq!(
+            parse("-infinity"),
+            Ok(("", CqlTerm::Constant(CqlConstant::Float("-infinity"))))
+        );
+        assert_eq!(
+            parse("0xCAFEbabe,"),
+            Ok((",", CqlTerm::Constant(CqlConstant::Blob("0xCAFEbabe"))))
+        );
+        assert_eq!(
+            parse("0x"),
+            Ok(("", CqlTerm::Constant(CqlConstant::Blob("0x"))))
+        );
+        // A dot followed by a name is not part of the number.
+        assert_eq!(
+            parse("1.foo"),
+            Ok((".foo", CqlTerm::Constant(CqlConstant::Integer("1"))))
+        );
+        assert!(parse("0xCAFEG").is_err());
+        assert!(parse("NaN_1").is_err());
+    }
+
+    #[test]
+    fn test_parse_function_call() {
+        let call = |keyspace, name, arguments| {
+            CqlTerm::FunctionCall(
+                CqlQualifiedIdentifier::new(keyspace, CqlIdentifier::Unquoted(name)),
+                arguments,
+            )
+        };
+        assert_eq!(
+            parse("to_timestamp ( now_1( ) ) AND"),
+            Ok((
+                " AND",
+                call(None, "to_timestamp", vec![call(None, "now_1", vec![])])
+            ))
+        );
+        assert_eq!(
+            parse("my_ks.my_fn(1, 'a')"),
+            Ok((
+                "",
+                call(
+                    Some(CqlIdentifier::Unquoted("my_ks")),
+                    "my_fn",
+                    vec![
+                        CqlTerm::Constant(CqlConstant::Integer("1")),
+                        CqlTerm::Constant(CqlConstant::String("a".to_string()))
+                    ]
+                )
+            ))
+        );
+        assert_eq!(
+            parse("my_ks.uuid_1 \n()"),
+            Ok((
+                "",
+                call(Some(CqlIdentifier::Unquoted("my_ks")), "uuid_1", vec![])
+            ))
+        );
+        // A column is no term.
+        assert!(parse("my_column AND").is_err());
+        assert!(parse("my_ks.my_column").is_err());
+    }
+
+    #[test]
+    fn test_parse_udts_and_tuples() {
+        assert_eq!(
+            parse("{ street_1: 'Main', \"Number\" :42 }"),
+            Ok((
+                "",
+                CqlTerm::UdtLiteral(vec![
+                    (
+                        CqlIdentifier::Unquoted("street_1"),
+                        CqlTerm::Constant(CqlConstant::String("Main".to_string()))
+                    ),
+                    (
+                        CqlIdentifier::Quoted("Number".to_string()),
+                        CqlTerm::Constant(CqlConstant::Integer("42"))
+                    ),
+                ])
+            ))
+        );
+        assert_eq!(
+            parse("(1, 'a', ( 3.0 ),{k_1: ?})"),
+            Ok((
+                "",
+                CqlTerm::TupleLiteral(vec![
+                    CqlTerm::Constant(CqlConstant::Integer("1")),
+                    CqlTerm::Constant(CqlConstant::String("a".to_string())),
+                    CqlTerm::TupleLiteral(vec![CqlTerm::Constant(CqlConstant::Float("3.0"))]),
+                    CqlTerm::UdtLiteral(vec![(
+                        CqlIdentifier::Unquoted("k_1"),
+                        CqlTerm::BindMarker(CqlBindMarker::Positional)
+                    )]),
+                ])
+            ))
+        );
+        // A key which is a term makes a map.
+        assert!(parse("{f_1(): 1}").is_ok_and(|(_, term)| term.is_map()));
+        assert!(parse("(1, 2").is_err());
+    }
+
+    #[test]
+    fn test_parse_collections() {
+        assert_eq!(
+            parse("[1, [ ], 'a'] +"),
+            Ok((
+                " +",
+                CqlTerm::List(vec![
+                    CqlTerm::Constant(CqlConstant::Integer("1")),
+                    CqlTerm::List(vec![]),
+                    CqlTerm::Constant(CqlConstant::String("a".to_string()))
+                ])
+            ))
+        );
+        assert_eq!(
+            parse("{ 'k' : 1.5, 'l':null }"),
+            Ok((
+                "",
+                CqlTerm::Map(vec![
+                    (
+                        CqlTerm::Constant(CqlConstant::String("k".to_string())),
+                        CqlTerm::Constant(CqlConstant::Float("1.5"))
+                    ),
+                    (
+                        CqlTerm::Constant(CqlConstant::String("l".to_string())),
+                        CqlTerm::Constant(CqlConstant::Null)
+                    ),
+                ])
+            ))
+        );
+        assert_eq!(
+            parse("{2,3}"),
+            Ok((
+                "",
+                CqlTerm::Set(vec![
+                    CqlTerm::Constant(CqlConstant::Integer("2")),
+                    CqlTerm::Constant(CqlConstant::Integer("3"))
+                ])
+            ))
+        );
+        assert_eq!(parse("{ }"), Ok(("", CqlTerm::Map(vec![]))));
+        assert!(parse("[1, 2").is_err());
+        assert!(parse("{1: 2, 3}").is_err());
+        assert!(parse("{1, 2: 3}").is_err());
+    }
+
+    #[test]
+    fn test_parse_nesting_depth() {
+        let nested = |depth| format!("{}1{}", "[".repeat(depth), "]".repeat(depth));
+        assert!(parse(&nested(MAX_NESTING_DEPTH)).is_ok());
+        match parse(&nested(MAX_NESTING_DEPTH + 1)) {
+            Err(nom::Err::Failure(error)) => assert!(error
+                .errors
+                .iter()
+                .any(|(_, kind)| kind == &VerboseErrorKind::Context("term nested too deeply"))),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn test_parse_bind_markers() {
+        assert_eq!(
+            parse("?,"),
+            Ok((",", CqlTerm::BindMarker(CqlBindMarker::Positional)))
+        );
+        assert_eq!(
+            parse(":\"User Id\")"),
+            Ok((
+                ")",
+                CqlTerm::BindMarker(CqlBindMarker::Named(CqlIdentifier::Quoted(
+                    "User Id".to_string()
+                )))
+            ))
+        );
+        assert_eq!(
+            parse("[?, :item_1]"),
+            Ok((
+                "",
+                CqlTerm::List(vec![
+                    CqlTerm::BindMarker(CqlBindMarker::Positional),
+                    CqlTerm::BindMarker(CqlBindMarker::Named(CqlIdentifier::Unquoted("item_1"))),
+                ])
+            ))
+        );
+        // The name of a marker is an identifier.
+        assert!(parse(":1st").is_err());
+        assert!(parse(": user_id").is_err());
+    }
+
+    #[test]
+    fn test_parse_invalid_literals() {
+        assert!(parse("nullable").is_err());
+        assert!(parse("42abc").is_err());
+        assert!(parse("-").is_err());
+        assert!(parse("5min").is_err());
+    }
+}
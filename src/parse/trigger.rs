@@ -0,0 +1,147 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::trigger::{CqlCreateTrigger, CqlDropTrigger};
+use crate::parse::Parse;
+use crate::utils::{
+    debug_event, keyword, space1_before, space1_between, space1_tags_no_case, string_constant, ws0,
+};
+use nom::combinator::{cut, opt};
+use nom::error::{ContextError, ParseError};
+use nom::sequence::{pair, preceded};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlCreateTrigger<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("CREATE"), keyword("TRIGGER")))(input)?;
+        let (input, if_not_exists) =
+            opt(space1_before(space1_tags_no_case(["IF", "NOT", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlIdentifier::parse)(input)?;
+        debug_event!(name = &*name, "trigger");
+        // Once the name is read the statement is a trigger.
+        let (input, (table, class)) = cut(pair(
+            preceded(
+                space1_before(keyword("ON")),
+                space1_before(CqlQualifiedIdentifier::parse),
+            ),
+            preceded(pair(ws0, keyword("USING")), space1_before(string_constant)),
+        ))(input)?;
+
+        Ok((
+            input,
+            CqlCreateTrigger::new(if_not_exists.is_some(), name, table, class),
+        ))
+    }
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlDropTrigger<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("DROP"), keyword("TRIGGER")))(input)?;
+        let (input, if_exists) = opt(space1_before(space1_tags_no_case(["IF", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlIdentifier::parse)(input)?;
+        let (input, table) = cut(preceded(
+            space1_before(keyword("ON")),
+            space1_before(CqlQualifiedIdentifier::parse),
+        ))(input)?;
+
+        Ok((input, CqlDropTrigger::new(if_exists.is_some(), name, table)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn table<'a>(keyspace: Option<&'a str>, name: &'a str) -> CqlQualifiedIdentifier<&'a str> {
+        CqlQualifiedIdentifier::new(
+            keyspace.map(CqlIdentifier::Unquoted),
+            CqlIdentifier::Unquoted(name),
+        )
+    }
+
+    #[test]
+    fn test_parse_create_trigger() {
+        fn parse(input: &str) -> IResult<&str, CqlCreateTrigger<&str>, nom::error::Error<&str>> {
+            CqlCreateTrigger::parse(input)
+        }
+
+        assert_eq!(
+            parse("CREATE TRIGGER trig_1 ON my_ks.orders_v1 USING 'org.mycompany.Trigger';"),
+            Ok((
+                ";",
+                CqlCreateTrigger::new(
+                    false,
+                    CqlIdentifier::Unquoted("trig_1"),
+                    table(Some("my_ks"), "orders_v1"),
+                    "org.mycompany.Trigger".to_string()
+                )
+            ))
+        );
+        assert_eq!(
+            parse("create trigger if not exists \"Audit\" on orders_v1 using $$it's.Trigger$$"),
+            Ok((
+                "",
+                CqlCreateTrigger::new(
+                    true,
+                    CqlIdentifier::Quoted("Audit".to_string()),
+                    table(None, "orders_v1"),
+                    "it's.Trigger".to_string()
+                )
+            ))
+        );
+        assert_eq!(
+            parse("CREATE TRIGGER trig_1 ON orders_v1 USING 'it''s'"),
+            Ok((
+                "",
+                CqlCreateTrigger::new(
+                    false,
+                    CqlIdentifier::Unquoted("trig_1"),
+                    table(None, "orders_v1"),
+                    "it's".to_string()
+                )
+            ))
+        );
+        assert!(matches!(
+            parse("CREATE TRIGGER trig_1 ON orders_v1"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(matches!(
+            parse("CREATE TABLE orders_v1 (id_1 int PRIMARY KEY)"),
+            Err(nom::Err::Error(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_drop_trigger() {
+        fn parse(input: &str) -> IResult<&str, CqlDropTrigger<&str>, nom::error::Error<&str>> {
+            CqlDropTrigger::parse(input)
+        }
+
+        assert_eq!(
+            parse("DROP TRIGGER IF EXISTS trig_1 ON my_ks.orders_v1;"),
+            Ok((
+                ";",
+                CqlDropTrigger::new(
+                    true,
+                    CqlIdentifier::Unquoted("trig_1"),
+                    table(Some("my_ks"), "orders_v1")
+                )
+            ))
+        );
+        assert_eq!(
+            parse("drop trigger trig_1 on orders_v1"),
+            Ok((
+                "",
+                CqlDropTrigger::new(
+                    false,
+                    CqlIdentifier::Unquoted("trig_1"),
+                    table(None, "orders_v1")
+                )
+            ))
+        );
+        assert!(parse("DROP TRIGGER trig_1").is_err());
+    }
+}
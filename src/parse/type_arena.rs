@@ -0,0 +1,259 @@
+use crate::model::cql_type::CqlType;
+use crate::model::identifier::CqlIdentifier;
+use crate::model::type_arena::{CqlTypeNode, CqlTypeRef, TypeArena};
+use crate::parse::cql_type::{parse_dimension, parse_native_type};
+use crate::parse::Parse;
+use crate::utils::{keyword, space0_tag, ws0};
+use crate::DEFAULT_MAX_TYPE_DEPTH;
+use nom::combinator::not;
+use nom::error::{ContextError, ErrorKind, ParseError};
+use nom::IResult;
+
+/// Parses `name '<'`.
+fn open<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    name: &'static str,
+) -> impl FnMut(&'de str) -> IResult<&'de str, (), E> {
+    move |input: &'de str| {
+        let (input, _) = keyword(name)(input)?;
+        let (input, _) = space0_tag("<")(input)?;
+        let (input, _) = ws0(input)?;
+        Ok((input, ()))
+    }
+}
+
+impl<'de> TypeArena<CqlIdentifier<&'de str>> {
+    /// Parses a cql type directly into the arena.
+    ///
+    /// Accepts the same grammar as the boxed [`CqlType`]. On failure, nodes allocated by the
+    /// failed attempt are removed again.
+    pub fn parse<E: ParseError<&'de str> + ContextError<&'de str>>(
+        &mut self,
+        input: &'de str,
+    ) -> IResult<&'de str, CqlTypeRef, E> {
+        let len = self.len();
+        let result = self.parse_node(input, 0);
+        if result.is_err() {
+            self.truncate(len);
+        }
+
+        result
+    }
+
+    fn parse_node<E: ParseError<&'de str> + ContextError<&'de str>>(
+        &mut self,
+        input: &'de str,
+        depth: usize,
+    ) -> IResult<&'de str, CqlTypeRef, E> {
+        if depth > DEFAULT_MAX_TYPE_DEPTH {
+            return Err(nom::Err::Failure(E::add_context(
+                input,
+                "type nested too deeply",
+                E::from_error_kind(input, ErrorKind::Verify),
+            )));
+        }
+        if let Ok((input, native)) = parse_native_type::<_, E>(input) {
+            return Ok((input, self.alloc(CqlTypeNode::Leaf(native))));
+        }
+        let len = self.len();
+        match self.parse_nested(input, depth) {
+            Err(nom::Err::Error(_)) => self.truncate(len),
+            result => return result,
+        }
+
+        let (input, identifier) = CqlIdentifier::parse(input)?;
+        let (input, _) = not(space0_tag("<"))(input)?;
+        Ok((
+            input,
+            self.alloc(CqlTypeNode::Leaf(CqlType::UserDefined(identifier))),
+        ))
+    }
+
+    fn parse_nested<E: ParseError<&'de str> + ContextError<&'de str>>(
+        &mut self,
+        input: &'de str,
+        depth: usize,
+    ) -> IResult<&'de str, CqlTypeRef, E> {
+        // FROZEN '<' cql_type '>'
+        if let Ok((input, _)) = open::<E>("FROZEN")(input) {
+            let (input, inner) = self.parse_node(input, depth + 1)?;
+            let (input, _) = space0_tag(">")(input)?;
+            return Ok((input, self.alloc(CqlTypeNode::FROZEN(inner))));
+        }
+        // MAP '<' cql_type ',' cql_type '>'
+        if let Ok((input, _)) = open::<E>("MAP")(input) {
+            let (input, key) = self.parse_node(input, depth + 1)?;
+            let (input, _) = space0_tag(",")(input)?;
+            let (input, _) = ws0(input)?;
+            let (input, value) = self.parse_node(input, depth + 1)?;
+            let (input, _) = space0_tag(">")(input)?;
+            return Ok((input, self.alloc(CqlTypeNode::MAP(key, value))));
+        }
+        // SET '<' cql_type '>'
+        if let Ok((input, _)) = open::<E>("SET")(input) {
+            let (input, inner) = self.parse_node(input, depth + 1)?;
+            let (input, _) = space0_tag(">")(input)?;
+            return Ok((input, self.alloc(CqlTypeNode::SET(inner))));
+        }
+        // LIST '<' cql_type '>'
+        if let Ok((input, _)) = open::<E>("LIST")(input) {
+            let (input, inner) = self.parse_node(input, depth + 1)?;
+            let (input, _) = space0_tag(">")(input)?;
+            return Ok((input, self.alloc(CqlTypeNode::LIST(inner))));
+        }
+        // TUPLE '<' cql_type ( ',' cql_type )* '>'
+        if let Ok((mut input, _)) = open::<E>("TUPLE")(input) {
+            let mut types = Vec::new();
+            loop {
+                let (i, ty) = self.parse_node(input, depth + 1)?;
+                types.push(ty);
+                let (i, _) = ws0(i)?;
+                match space0_tag::<_, _, E>(",")(i) {
+                    Ok((i, _)) => {
+                        let (i, _) = ws0(i)?;
+                        input = i;
+                    }
+                    Err(_) => {
+                        input = i;
+                        break;
+                    }
+                }
+            }
+            let (input, _) = space0_tag(">")(input)?;
+            return Ok((input, self.alloc(CqlTypeNode::TUPLE(types))));
+        }
+        // VECTOR '<' cql_type ',' integer '>'
+        if let Ok((input, _)) = open::<E>("VECTOR")(input) {
+            let (input, element) = self.parse_node(input, depth + 1)?;
+            let (input, _) = space0_tag(",")(input)?;
+            let (input, _) = ws0(input)?;
+            let (input, dimension) = parse_dimension(input)?;
+            let (input, _) = space0_tag(">")(input)?;
+            return Ok((input, self.alloc(CqlTypeNode::VECTOR(element, dimension))));
+        }
+
+        Err(nom::Err::Error(E::from_error_kind(input, ErrorKind::Alt)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse_both(input: &str) {
+        let mut arena = TypeArena::new();
+        let arena_result: IResult<_, _, nom::error::Error<&str>> = arena.parse(input);
+        let boxed_result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse(input);
+        let (arena_remaining, reference) = arena_result.unwrap();
+        let (boxed_remaining, boxed) = boxed_result.unwrap();
+        assert_eq!(arena_remaining, boxed_remaining);
+        assert_eq!(arena.to_boxed(reference), boxed);
+    }
+
+    #[test]
+    fn test_parse_native() {
+        parse_both("TIMESTAMP");
+        parse_both("int");
+        parse_both("int_1");
+        parse_both("time_series");
+    }
+
+    #[test]
+    fn test_parse_nested() {
+        parse_both("frozen<map<text, frozen<list<frozen<my_udt>>>>>");
+        parse_both("MAP < INT , TEXT >");
+        parse_both("SET<INT>");
+        parse_both("tuple<int, text, frozen<tuple<my_udt, blob>>>");
+        parse_both("vector<frozen<my_udt>, 8>");
+    }
+
+    #[test]
+    fn test_parse_incomplete_nested() {
+        let mut arena = TypeArena::new();
+        let arena_result: IResult<_, _, nom::error::Error<&str>> = arena.parse("frozen<>");
+        let boxed_result: IResult<_, _, nom::error::Error<&str>> = CqlType::parse("frozen<>");
+        assert!(arena_result.is_err());
+        assert!(boxed_result.is_err());
+        assert!(arena.is_empty());
+    }
+
+    #[test]
+    fn test_parse_failure_leaves_arena_untouched() {
+        let mut arena = TypeArena::new();
+        let first: IResult<_, _, nom::error::Error<&str>> = arena.parse("int");
+        assert!(first.is_ok());
+        let result: IResult<_, _, nom::error::Error<&str>> = arena.parse("map<int, text");
+        assert!(result.is_err());
+        assert_eq!(arena.len(), 1);
+    }
+
+    #[test]
+    fn test_insert_round_trip() {
+        let ty: CqlType<CqlIdentifier<&str>> = CqlType::FROZEN(Box::new(CqlType::MAP(Box::new((
+            CqlType::TEXT,
+            CqlType::TUPLE(vec![
+                CqlType::INT,
+                CqlType::UserDefined(CqlIdentifier::new("my_udt")),
+            ]),
+        )))));
+        let mut arena = TypeArena::new();
+        let reference = arena.insert(ty.clone());
+        assert_eq!(arena.len(), 6);
+        assert_eq!(arena.to_boxed(reference), ty);
+    }
+
+    #[test]
+    fn test_type_eq() {
+        let mut arena = TypeArena::new();
+        let (_, left) = arena
+            .parse::<nom::error::Error<&str>>("frozen<list<my_udt>>")
+            .unwrap();
+        let (_, right) = arena
+            .parse::<nom::error::Error<&str>>("FROZEN<LIST<\"my_udt\">>")
+            .unwrap();
+        let (_, other) = arena
+            .parse::<nom::error::Error<&str>>("frozen<set<my_udt>>")
+            .unwrap();
+        assert!(arena.type_eq(left, right));
+        assert!(!arena.type_eq(left, other));
+    }
+
+    #[test]
+    fn test_reference_types() {
+        let (_, statements) = crate::parse_cql("CREATE TYPE my_ks.my_udt (my_field int)").unwrap();
+        let context = crate::resolve_references(statements, None).unwrap();
+        let udt = context[0].create_user_defined_type().unwrap();
+
+        let mut arena = TypeArena::new();
+        let (_, reference) = arena
+            .parse::<nom::error::Error<&str>>("frozen<map<text, my_udt>>")
+            .unwrap();
+        let keyspace = CqlIdentifier::new("my_ks");
+        let resolved = arena.reference_types(Some(&keyspace), &context).unwrap();
+        assert_eq!(
+            resolved.to_boxed(reference),
+            CqlType::FROZEN(Box::new(CqlType::MAP(Box::new((
+                CqlType::TEXT,
+                CqlType::UserDefined(udt.clone())
+            )))))
+        );
+
+        let mut arena = TypeArena::new();
+        let _ = arena
+            .parse::<nom::error::Error<&str>>("list<unknown_udt>")
+            .unwrap();
+        assert!(arena.reference_types(Some(&keyspace), &context).is_err());
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    fn test_parse_depth_exceeded() {
+        let mut arena = TypeArena::new();
+        let input = format!("{}int{}", "list<".repeat(50), ">".repeat(50));
+        let result: IResult<_, _, nom::error::Error<&str>> = arena.parse(&input);
+        assert!(result.is_ok());
+        let input = format!("{}int{}", "map<int, ".repeat(100_000), ">".repeat(100_000));
+        let result: IResult<_, _, nom::error::Error<&str>> = arena.parse(&input);
+        assert!(matches!(result, Err(nom::Err::Failure(_))));
+        assert_eq!(arena.len(), 51);
+    }
+}
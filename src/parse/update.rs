@@ -0,0 +1,368 @@
+use crate::model::identifier::CqlIdentifier;
+use crate::model::qualified_identifier::CqlQualifiedIdentifier;
+use crate::model::relation::CqlWhereClause;
+use crate::model::term::{CqlConstant, CqlTerm};
+use crate::model::update::{CqlAssignment, CqlCounterOperation, CqlUpdate};
+use crate::model::using::CqlUsing;
+use crate::parse::condition::if_clause;
+use crate::parse::Parse;
+use crate::utils::{debug_event, keyword, space1_before, ws0};
+use nom::branch::alt;
+use nom::bytes::complete::tag;
+use nom::character::complete::one_of;
+use nom::combinator::{cut, map, opt, verify};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded, terminated, tuple};
+use nom::IResult;
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlUpdate<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = keyword("UPDATE")(input)?;
+        // No other statement starts with UPDATE.
+        let (input, (table, using, assignments, where_clause, condition)) = cut(tuple((
+            space1_before(CqlQualifiedIdentifier::parse),
+            opt(preceded(ws0, CqlUsing::parse)),
+            preceded(
+                pair(ws0, keyword("SET")),
+                space1_before(separated_list1(delimited(ws0, tag(","), ws0), assignment)),
+            ),
+            preceded(
+                pair(ws0, keyword("WHERE")),
+                space1_before(CqlWhereClause::parse),
+            ),
+            if_clause(
+                |condition| !condition.is_not_exists(),
+                "IF NOT EXISTS is only supported by INSERT",
+            ),
+        )))(input)?;
+        debug_event!(name = &**table.name(), "update");
+
+        Ok((
+            input,
+            CqlUpdate::new(table, using, assignments, where_clause, condition),
+        ))
+    }
+}
+
+/// Parses an assignment, the column on the right side of an append, prepend, removal or delta
+/// has to be the assigned column.
+fn assignment<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlAssignment<&'de str>, E> {
+    let (input, column) = CqlIdentifier::parse(input)?;
+    let equals = || tuple((ws0, tag("="), ws0));
+    let same_column = || verify(CqlIdentifier::parse, |other| other == &column);
+    let mut assignment = alt((
+        map(
+            pair(
+                preceded(
+                    ws0,
+                    delimited(
+                        pair(tag("["), ws0),
+                        CqlTerm::parse,
+                        pair(ws0, context("]", tag("]"))),
+                    ),
+                ),
+                preceded(equals(), CqlTerm::parse),
+            ),
+            |(element, term)| CqlAssignment::Element(column.clone(), element, term),
+        ),
+        map(
+            pair(
+                preceded(tag("."), CqlIdentifier::parse),
+                preceded(equals(), CqlTerm::parse),
+            ),
+            |(field, term)| CqlAssignment::Field(column.clone(), field, term),
+        ),
+        preceded(
+            equals(),
+            alt((
+                preceded(same_column(), delta(&column)),
+                map(
+                    terminated(CqlTerm::parse, tuple((ws0, tag("+"), ws0, same_column()))),
+                    |term| CqlAssignment::Prepend(column.clone(), term),
+                ),
+                map(CqlTerm::parse, |term| {
+                    CqlAssignment::Column(column.clone(), term)
+                }),
+            )),
+        ),
+    ));
+    assignment(input)
+}
+
+/// Parses the `+ term` or `- term` following the assigned column on the right side.
+///
+/// A collection literal is appended or removed and an integer changes a counter. A bind marker
+/// may stand for either, anything else fails.
+fn delta<'de, 'a, E: ParseError<&'de str> + ContextError<&'de str>>(
+    column: &'a CqlIdentifier<&'de str>,
+) -> impl FnMut(&'de str) -> IResult<&'de str, CqlAssignment<&'de str>, E> + 'a {
+    move |input| {
+        let (input, operation) = delimited(ws0, one_of("+-"), ws0)(input)?;
+        let operation = if operation == '+' {
+            CqlCounterOperation::Increment
+        } else {
+            CqlCounterOperation::Decrement
+        };
+        let (remaining, term) = CqlTerm::parse(input)?;
+        let column = column.clone();
+        let assignment = match (operation, term) {
+            (
+                CqlCounterOperation::Increment,
+                term @ (CqlTerm::List(_) | CqlTerm::Set(_) | CqlTerm::Map(_)),
+            ) => CqlAssignment::Append(column, term),
+            (
+                CqlCounterOperation::Decrement,
+                term @ (CqlTerm::List(_) | CqlTerm::Set(_) | CqlTerm::Map(_)),
+            ) => CqlAssignment::Remove(column, term),
+            (operation, term @ CqlTerm::Constant(CqlConstant::Integer(_))) => {
+                CqlAssignment::CounterDelta(column, operation, term)
+            }
+            (operation, term @ CqlTerm::BindMarker(_)) => {
+                CqlAssignment::BindMarkerDelta(column, operation, term)
+            }
+            _ => return Err(nom::Err::Failure(E::add_context(
+                input,
+                "only a collection literal, an integer or a bind marker can be added or subtracted",
+                E::from_error_kind(input, ErrorKind::Verify),
+            ))),
+        };
+        Ok((remaining, assignment))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::condition::{CqlColumnCondition, CqlCondition};
+    use crate::model::relation::{CqlOperator, CqlRelation};
+    use crate::model::term::CqlBindMarker;
+
+    use nom::error::{VerboseError, VerboseErrorKind};
+
+    fn parse(input: &str) -> IResult<&str, CqlUpdate<&str>, VerboseError<&str>> {
+        CqlUpdate::parse(input)
+    }
+
+    #[test]
+    fn test_parse_update() {
+        let (remaining, update) = parse(
+            "UPDATE my_ks.users_1 USING TTL 60 SET map_1['k'] = 'v', list_1 = list_1 + [1], \
+             set_1 = set_1 - {2}, list_1=[0]+list_1, addr_1.street_1 = 'Main', col_3 = 3 \
+             WHERE pk_1 = 1 IF col_3 = 2;",
+        )
+        .unwrap();
+        assert_eq!(remaining, ";");
+        let column = |name| CqlIdentifier::Unquoted(name);
+        assert_eq!(
+            update,
+            CqlUpdate::new(
+                CqlQualifiedIdentifier::new(Some(column("my_ks")), column("users_1")),
+                Some(CqlUsing::new(
+                    Some(CqlTerm::Constant(CqlConstant::Integer("60"))),
+                    None,
+                    None
+                )),
+                vec![
+                    CqlAssignment::Element(
+                        column("map_1"),
+                        CqlTerm::Constant(CqlConstant::String("k".to_string())),
+                        CqlTerm::Constant(CqlConstant::String("v".to_string()))
+                    ),
+                    CqlAssignment::Append(
+                        column("list_1"),
+                        CqlTerm::List(vec![CqlTerm::Constant(CqlConstant::Integer("1"))])
+                    ),
+                    CqlAssignment::Remove(
+                        column("set_1"),
+                        CqlTerm::Set(vec![CqlTerm::Constant(CqlConstant::Integer("2"))])
+                    ),
+                    CqlAssignment::Prepend(
+                        column("list_1"),
+                        CqlTerm::List(vec![CqlTerm::Constant(CqlConstant::Integer("0"))])
+                    ),
+                    CqlAssignment::Field(
+                        column("addr_1"),
+                        column("street_1"),
+                        CqlTerm::Constant(CqlConstant::String("Main".to_string()))
+                    ),
+                    CqlAssignment::Column(
+                        column("col_3"),
+                        CqlTerm::Constant(CqlConstant::Integer("3"))
+                    ),
+                ],
+                CqlWhereClause::new(vec![CqlRelation::Column(
+                    column("pk_1"),
+                    CqlOperator::Eq,
+                    CqlTerm::Constant(CqlConstant::Integer("1"))
+                )]),
+                Some(CqlCondition::Columns(vec![CqlColumnCondition::new(
+                    column("col_3"),
+                    None,
+                    CqlOperator::Eq,
+                    CqlTerm::Constant(CqlConstant::Integer("2"))
+                )]))
+            )
+        );
+        assert_eq!(
+            update
+                .assigned_columns()
+                .map(|column| &**column)
+                .collect::<Vec<_>>(),
+            vec!["map_1", "list_1", "set_1", "list_1", "addr_1", "col_3"]
+        );
+    }
+
+    #[test]
+    fn test_parse_update_if_exists() {
+        let (_, update) =
+            parse("update users_1 set name_1 = null where pk_1 = 1 if exists").unwrap();
+        assert!(update.using().is_none());
+        assert_eq!(update.condition(), &Some(CqlCondition::Exists));
+    }
+
+    #[test]
+    fn test_parse_counter_update() {
+        let (_, update) = parse(
+            "UPDATE counts_1 SET hits_1 = hits_1 + 1, misses_1 = misses_1 - ? WHERE id_1 = 1",
+        )
+        .unwrap();
+        assert_eq!(
+            update.assignments(),
+            &vec![
+                CqlAssignment::CounterDelta(
+                    CqlIdentifier::Unquoted("hits_1"),
+                    CqlCounterOperation::Increment,
+                    CqlTerm::Constant(CqlConstant::Integer("1"))
+                ),
+                CqlAssignment::BindMarkerDelta(
+                    CqlIdentifier::Unquoted("misses_1"),
+                    CqlCounterOperation::Decrement,
+                    CqlTerm::BindMarker(CqlBindMarker::Positional)
+                ),
+            ]
+        );
+        // A collection literal is no counter delta.
+        let (_, update) = parse("UPDATE t_1 SET ids_1 = ids_1 - {1} WHERE id_1 = 1").unwrap();
+        assert!(update.assignments()[0].is_remove());
+    }
+
+    #[test]
+    fn test_parse_bind_marker_delta() {
+        // Without the schema a bind marker may change a counter or a collection.
+        let (_, update) = parse(
+            "UPDATE t_1 SET l = l + ?, tags = tags + ?, m = m + :x, s = s - ? WHERE id_1 = 1",
+        )
+        .unwrap();
+        let delta = |column, operation, marker| {
+            CqlAssignment::BindMarkerDelta(
+                CqlIdentifier::Unquoted(column),
+                operation,
+                CqlTerm::BindMarker(marker),
+            )
+        };
+        assert_eq!(
+            update.assignments(),
+            &vec![
+                delta(
+                    "l",
+                    CqlCounterOperation::Increment,
+                    CqlBindMarker::Positional
+                ),
+                delta(
+                    "tags",
+                    CqlCounterOperation::Increment,
+                    CqlBindMarker::Positional
+                ),
+                delta(
+                    "m",
+                    CqlCounterOperation::Increment,
+                    CqlBindMarker::Named(CqlIdentifier::Unquoted("x"))
+                ),
+                delta(
+                    "s",
+                    CqlCounterOperation::Decrement,
+                    CqlBindMarker::Positional
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_delta() {
+        for (input, operand) in [
+            ("UPDATE t_1 SET c = c + 'a' WHERE id_1 = 1", 23),
+            ("UPDATE t_1 SET c = c - 1.5 WHERE id_1 = 1", 23),
+            (
+                "UPDATE t_1 SET c = c + 5a1c395e-b41f-11e5-9f22-ba0be0483c18 WHERE id_1 = 1",
+                23,
+            ),
+        ] {
+            let Err(nom::Err::Failure(error)) = parse(input) else {
+                panic!("{} accepted", input);
+            };
+            assert!(
+                error.errors.contains(&(
+                    &input[operand..],
+                    VerboseErrorKind::Context(
+                        "only a collection literal, an integer or a bind marker can be added or subtracted"
+                    )
+                )),
+                "{:?}",
+                error
+            );
+        }
+    }
+
+    #[test]
+    fn test_bind_markers() {
+        let (_, update) = parse(
+            "UPDATE users_1 USING TTL :ttl_1 SET map_1[?] = ?, list_1 = list_1 + [:item_1, ?] \
+             WHERE pk_1 = :pk_1 AND ck_1 = to_ck(?) IF col_1 IN (?, 1)",
+        )
+        .unwrap();
+        let named = |name| CqlBindMarker::Named(CqlIdentifier::Unquoted(name));
+        assert_eq!(
+            update.bind_markers().collect::<Vec<_>>(),
+            vec![
+                &named("ttl_1"),
+                &CqlBindMarker::Positional,
+                &CqlBindMarker::Positional,
+                &named("item_1"),
+                &CqlBindMarker::Positional,
+                &named("pk_1"),
+                &CqlBindMarker::Positional,
+                &CqlBindMarker::Positional,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_update() {
+        let input = "UPDATE users_1 SET col_1 = 1 WHERE pk_1 = 1 IF NOT EXISTS";
+        let Err(nom::Err::Failure(error)) = parse(input) else {
+            panic!("IF NOT EXISTS accepted");
+        };
+        assert!(error.errors.contains(&(
+            &input[47..],
+            VerboseErrorKind::Context("IF NOT EXISTS is only supported by INSERT")
+        )));
+        // Only the assigned column can be appended to.
+        assert!(matches!(
+            parse("UPDATE users_1 SET list_1 = list_2 + [1] WHERE pk_1 = 1"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(matches!(
+            parse("UPDATE users_1 SET col_1 = 1"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(matches!(
+            parse("UPDATE users_1 WHERE pk_1 = 1"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+}
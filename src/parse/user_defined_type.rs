@@ -1,51 +1,236 @@
 use crate::model::cql_type::CqlType;
 use crate::model::identifier::CqlIdentifier;
 use crate::model::qualified_identifier::CqlQualifiedIdentifier;
-use crate::model::user_defined_type::ParsedCqlUserDefinedType;
+use crate::model::user_defined_type::{
+    CqlAlterType, CqlAlterTypeOperation, CqlDropType, CqlUserDefinedTypeField,
+    ParsedCqlUserDefinedType,
+};
+use crate::model::Identifiable;
 use crate::parse::Parse;
-use crate::utils::{space1_before, space1_tags_no_case};
+use crate::utils::{
+    debug_event, keyword, or_error, separated_list0_with_error, space1_before, space1_between,
+    space1_tags_no_case, traced, ws0, ws1, ErrorInput,
+};
+use nom::branch::alt;
 use nom::bytes::complete::tag;
-use nom::character::complete::{multispace0, multispace1};
-use nom::combinator::opt;
-use nom::error::ParseError;
-use nom::multi::separated_list0;
-use nom::sequence::delimited;
+use nom::combinator::{cut, map, opt};
+use nom::error::{context, ContextError, ParseError};
+use nom::multi::separated_list1;
+use nom::sequence::preceded;
 use nom::IResult;
 
-impl<'de, E: ParseError<&'de str>> Parse<&'de str, E>
-    for ParsedCqlUserDefinedType<&'de str, CqlIdentifier<&'de str>>
+impl<'de, E, Name> Parse<&'de str, E>
+    for ParsedCqlUserDefinedType<&'de str, CqlIdentifier<&'de str>, Name>
+where
+    E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>,
+    Name: Parse<&'de str, E> + Identifiable<&'de str>,
 {
     fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
-        let (input, _) = space1_tags_no_case(["CREATE", "TYPE"])(input)?;
+        let (input, _) = space1_between((keyword("CREATE"), keyword("TYPE")))(input)?;
 
         let (input, if_not_exists) =
             opt(space1_before(space1_tags_no_case(["IF", "NOT", "EXISTS"])))(input)?;
         let if_not_exists = if_not_exists.is_some();
 
-        let (input, _) = multispace1(input)?;
-        let (input, name) = CqlQualifiedIdentifier::parse(input)?;
+        let (input, _) = ws1(input)?;
+        let (input, name) = Name::parse(input)?;
+        debug_event!(
+            keyspace = name.keyspace().map(|keyspace| &**keyspace),
+            name = &**name.identifier(),
+            "user defined type"
+        );
 
-        let (input, _) = multispace0(input)?;
+        let (input, _) = ws0(input)?;
 
-        fn parse_field<'de, E: ParseError<&'de str>>(
-            input: &'de str,
-        ) -> IResult<&'de str, (CqlIdentifier<&'de str>, CqlType<CqlIdentifier<&'de str>>), E>
-        {
-            let (input, _) = multispace0(input)?;
-            let (input, name) = CqlIdentifier::parse(input)?;
-            let (input, _) = multispace1(input)?;
-            let (input, ty) = CqlType::parse(input)?;
-            let (input, _) = multispace0(input)?;
+        let (input, _) = context("(", tag("("))(input)?;
+        let (input, (fields, field_error)) =
+            separated_list0_with_error(tag(","), parse_field)(input)?;
+        let (input, _) = context(")", tag(")"))(input).map_err(|e| e.map(or_error(field_error)))?;
 
-            Ok((input, (name, ty)))
-        }
+        Ok((
+            input,
+            ParsedCqlUserDefinedType::new(if_not_exists, name, fields),
+        ))
+    }
+}
+
+/// Parses a field definition, surrounded by optional whitespace.
+fn parse_field<'de, E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, CqlUserDefinedTypeField<&'de str, CqlIdentifier<&'de str>>, E> {
+    let (input, _) = ws0(input)?;
+    let (input, name) = CqlIdentifier::parse(input)?;
+    let (input, _) = ws1(input)?;
+    let (input, ty) = traced("type", CqlType::parse)(input)?;
+    let (input, _) = ws0(input)?;
+
+    Ok((input, (name, ty)))
+}
 
-        let (input, fields) =
-            delimited(tag("("), separated_list0(tag(","), parse_field), tag(")"))(input)?;
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str> + ErrorInput<&'de str>>
+    Parse<&'de str, E> for CqlAlterType<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("ALTER"), keyword("TYPE")))(input)?;
+        let (input, if_exists) = opt(space1_before(space1_tags_no_case(["IF", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlQualifiedIdentifier::parse)(input)?;
+        debug_event!(
+            keyspace = name.keyspace().as_ref().map(|keyspace| &**keyspace),
+            name = &**name.name(),
+            "alter type"
+        );
+        let (input, _) = ws0(input)?;
+        let (input, operation) = alt((
+            map(
+                preceded(keyword("ADD"), cut(separated_list1(tag(","), parse_field))),
+                CqlAlterTypeOperation::Add,
+            ),
+            map(
+                preceded(
+                    keyword("RENAME"),
+                    cut(separated_list1(
+                        space1_before(keyword("AND")),
+                        preceded(
+                            ws1,
+                            map(
+                                space1_between((
+                                    CqlIdentifier::parse,
+                                    keyword("TO"),
+                                    CqlIdentifier::parse,
+                                )),
+                                |(from, _, to)| (from, to),
+                            ),
+                        ),
+                    )),
+                ),
+                CqlAlterTypeOperation::Rename,
+            ),
+        ))(input)?;
 
         Ok((
             input,
-            ParsedCqlUserDefinedType::new(if_not_exists, name, fields),
+            CqlAlterType::new(if_exists.is_some(), name, operation),
         ))
     }
 }
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlDropType<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (input, _) = space1_between((keyword("DROP"), keyword("TYPE")))(input)?;
+        let (input, if_exists) = opt(space1_before(space1_tags_no_case(["IF", "EXISTS"])))(input)?;
+        let (input, name) = space1_before(CqlQualifiedIdentifier::parse)(input)?;
+
+        Ok((input, CqlDropType::new(if_exists.is_some(), name)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(input: &str) -> IResult<&str, CqlAlterType<&str>, nom::error::Error<&str>> {
+        CqlAlterType::parse(input)
+    }
+
+    fn name(keyspace: &'static str, name: &'static str) -> CqlQualifiedIdentifier<&'static str> {
+        CqlQualifiedIdentifier::new(
+            Some(CqlIdentifier::Unquoted(keyspace)),
+            CqlIdentifier::Unquoted(name),
+        )
+    }
+
+    #[test]
+    fn test_parse_add() {
+        assert_eq!(
+            parse("ALTER TYPE my_ks.address_type ADD zip_code text, geo_1 frozen<tuple<float, float>>"),
+            Ok((
+                "",
+                CqlAlterType::new(
+                    false,
+                    name("my_ks", "address_type"),
+                    CqlAlterTypeOperation::Add(vec![
+                        (CqlIdentifier::Unquoted("zip_code"), CqlType::TEXT),
+                        (
+                            CqlIdentifier::Unquoted("geo_1"),
+                            CqlType::FROZEN(Box::new(CqlType::TUPLE(vec![
+                                CqlType::FLOAT,
+                                CqlType::FLOAT
+                            ]))),
+                        ),
+                    ]),
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_rename() {
+        assert_eq!(
+            parse("alter type if exists my_ks.address_type rename street_1 to street_name and \"Zip\" TO zip_code;"),
+            Ok((
+                ";",
+                CqlAlterType::new(
+                    true,
+                    name("my_ks", "address_type"),
+                    CqlAlterTypeOperation::Rename(vec![
+                        (
+                            CqlIdentifier::Unquoted("street_1"),
+                            CqlIdentifier::Unquoted("street_name")
+                        ),
+                        (
+                            CqlIdentifier::Quoted("Zip".to_string()),
+                            CqlIdentifier::Unquoted("zip_code")
+                        ),
+                    ]),
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_parse_missing_operation() {
+        assert!(parse("ALTER TYPE my_ks.address_type DROP zip_code").is_err());
+        assert!(matches!(
+            parse("ALTER TYPE my_ks.address_type ADD"),
+            Err(nom::Err::Failure(_))
+        ));
+    }
+
+    #[test]
+    fn test_parse_drop_type() {
+        fn parse(input: &str) -> IResult<&str, CqlDropType<&str>, nom::error::Error<&str>> {
+            CqlDropType::parse(input)
+        }
+
+        assert_eq!(
+            parse("DROP TYPE IF EXISTS my_ks.address_type;"),
+            Ok((";", CqlDropType::new(true, name("my_ks", "address_type"))))
+        );
+        assert_eq!(
+            parse("drop type \"My Keyspace\".\"Address Type\""),
+            Ok((
+                "",
+                CqlDropType::new(
+                    false,
+                    CqlQualifiedIdentifier::new(
+                        Some(CqlIdentifier::Quoted("My Keyspace".to_string())),
+                        CqlIdentifier::Quoted("Address Type".to_string()),
+                    )
+                )
+            ))
+        );
+        assert_eq!(
+            parse("Drop Type If Exists address_type"),
+            Ok((
+                "",
+                CqlDropType::new(
+                    true,
+                    CqlQualifiedIdentifier::new(None, CqlIdentifier::Unquoted("address_type"))
+                )
+            ))
+        );
+        assert!(parse("DROP TYPE IF EXISTS").is_err());
+    }
+}
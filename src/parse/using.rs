@@ -0,0 +1,134 @@
+use crate::model::term::{CqlConstant, CqlTerm};
+use crate::model::using::CqlUsing;
+use crate::parse::term::{bind_marker, duration, integer};
+use crate::parse::Parse;
+use crate::utils::{keyword, space1_before, ws1};
+use nom::branch::alt;
+use nom::combinator::{map, value};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
+use nom::multi::separated_list1;
+use nom::sequence::{delimited, pair, preceded};
+use nom::IResult;
+
+#[derive(Clone, Copy)]
+enum Parameter {
+    Ttl,
+    Timestamp,
+    Timeout,
+}
+
+impl<'de, E: ParseError<&'de str> + ContextError<&'de str>> Parse<&'de str, E>
+    for CqlUsing<&'de str>
+{
+    fn parse(input: &'de str) -> IResult<&'de str, Self, E> {
+        let (remaining, parameters) = preceded(
+            pair(keyword("USING"), ws1),
+            separated_list1(
+                delimited(ws1, keyword("AND"), ws1),
+                alt((
+                    pair(
+                        alt((
+                            value(Parameter::Ttl, keyword("TTL")),
+                            value(Parameter::Timestamp, keyword("TIMESTAMP")),
+                        )),
+                        space1_before(parameter_value(map(integer, |literal| {
+                            CqlTerm::Constant(CqlConstant::Integer(literal))
+                        }))),
+                    ),
+                    pair(
+                        value(Parameter::Timeout, keyword("TIMEOUT")),
+                        space1_before(parameter_value(map(duration, |literal| {
+                            CqlTerm::Constant(CqlConstant::Duration(literal))
+                        }))),
+                    ),
+                )),
+            ),
+        )(input)?;
+
+        let (mut ttl, mut timestamp, mut timeout) = (None, None, None);
+        for (parameter, term) in parameters {
+            let slot = match parameter {
+                Parameter::Ttl => &mut ttl,
+                Parameter::Timestamp => &mut timestamp,
+                Parameter::Timeout => &mut timeout,
+            };
+            if slot.replace(term).is_some() {
+                return Err(nom::Err::Failure(E::add_context(
+                    input,
+                    "each update parameter can only be given once",
+                    E::from_error_kind(input, ErrorKind::Verify),
+                )));
+            }
+        }
+
+        Ok((remaining, CqlUsing::new(ttl, timestamp, timeout)))
+    }
+}
+
+/// Parses the value of an update parameter, the literal or a bind marker.
+fn parameter_value<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    literal: impl FnMut(&'de str) -> IResult<&'de str, CqlTerm<&'de str>, E>,
+) -> impl FnMut(&'de str) -> IResult<&'de str, CqlTerm<&'de str>, E> {
+    context(
+        "update parameter",
+        alt((literal, map(bind_marker, CqlTerm::BindMarker))),
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::model::identifier::CqlIdentifier;
+    use crate::model::term::CqlBindMarker;
+    use nom::error::VerboseError;
+
+    fn parse(input: &str) -> IResult<&str, CqlUsing<&str>, VerboseError<&str>> {
+        CqlUsing::parse(input)
+    }
+
+    #[test]
+    fn test_parse_using() {
+        assert_eq!(
+            parse("USING TTL 86400 AND TIMESTAMP 1234567890 SET"),
+            Ok((
+                " SET",
+                CqlUsing::new(
+                    Some(CqlTerm::Constant(CqlConstant::Integer("86400"))),
+                    Some(CqlTerm::Constant(CqlConstant::Integer("1234567890"))),
+                    None
+                )
+            ))
+        );
+        assert_eq!(
+            parse("using timestamp :written_at"),
+            Ok((
+                "",
+                CqlUsing::new(
+                    None,
+                    Some(CqlTerm::BindMarker(CqlBindMarker::Named(
+                        CqlIdentifier::Unquoted("written_at")
+                    ))),
+                    None
+                )
+            ))
+        );
+        assert!(matches!(
+            parse("USING TTL 1 AND TTL 2"),
+            Err(nom::Err::Failure(_))
+        ));
+        assert!(parse("USING TTL 'forever'").is_err());
+        assert_eq!(
+            parse("USING TIMEOUT 500ms AND TTL ?"),
+            Ok((
+                "",
+                CqlUsing::new(
+                    Some(CqlTerm::BindMarker(CqlBindMarker::Positional)),
+                    None,
+                    Some(CqlTerm::Constant(CqlConstant::Duration("500ms")))
+                )
+            ))
+        );
+        assert!(parse("USING TIMEOUT 5").is_err());
+        assert!(parse("USING TTL 5s").is_err());
+    }
+}
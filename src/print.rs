@@ -0,0 +1,1075 @@
+use crate::model::*;
+use derive_new::new;
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// The kind of a name, each kind has its own replacements.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+enum Kind {
+    Keyspace,
+    Table,
+    Type,
+    Column,
+    Field,
+    Function,
+    Trigger,
+    Role,
+    Index,
+}
+
+impl Kind {
+    fn prefix(self) -> &'static str {
+        match self {
+            Kind::Keyspace => "keyspace",
+            Kind::Table => "table",
+            Kind::Type => "type",
+            Kind::Column => "column",
+            Kind::Field => "field",
+            Kind::Function => "function",
+            Kind::Trigger => "trigger",
+            Kind::Role => "role",
+            Kind::Index => "index",
+        }
+    }
+}
+
+/// The replacement of a name.
+#[derive(new)]
+struct Replacement {
+    /// The generated name.
+    name: String,
+    /// The spellings of the name, unquoted names are lowercase.
+    #[new(default)]
+    spellings: Vec<String>,
+}
+
+/// Renders statements as cql, optionally renaming them and laying them out over several lines.
+pub(crate) struct Printer {
+    /// The seed of the generated names, names and strings are kept without a seed.
+    seed: Option<u64>,
+    /// The replacements, by kind and the name as cql compares it.
+    names: HashMap<(Kind, String), Replacement>,
+    /// The indentation of column definitions, fields and options, which are written on their
+    /// own lines. Without an indentation a statement is written on a single line.
+    indent: Option<String>,
+    output: String,
+}
+
+impl Printer {
+    /// A printer renaming every name and emptying every string, see
+    /// [`anonymize`](crate::anonymize::anonymize).
+    pub(crate) fn anonymizing(seed: u64) -> Self {
+        Self {
+            seed: Some(seed),
+            names: HashMap::new(),
+            indent: None,
+            output: String::new(),
+        }
+    }
+
+    /// A printer keeping the statements, writing definitions on their own lines indented by
+    /// `indent` spaces.
+    pub(crate) fn formatting(indent: usize) -> Self {
+        Self {
+            seed: None,
+            names: HashMap::new(),
+            indent: Some(" ".repeat(indent)),
+            output: String::new(),
+        }
+    }
+
+    /// Renders the statements, each terminated by a semicolon and a newline.
+    pub(crate) fn print(&mut self, statements: &[ParsedCqlStatement<&str>]) -> String {
+        self.output.clear();
+        for statement in statements {
+            self.statement(statement);
+            self.output.push_str(";\n");
+        }
+
+        self.output.clone()
+    }
+
+    fn statement(&mut self, statement: &ParsedCqlStatement<&str>) {
+        match statement {
+            CqlStatement::CreateTable(table) => self.table(table),
+            CqlStatement::CreateUserDefinedType(udt) => self.user_defined_type(udt),
+            CqlStatement::AlterMaterializedView(view) => self.alter_materialized_view(view),
+            CqlStatement::AlterType(alter_type) => self.alter_type(alter_type),
+            CqlStatement::DropType(drop_type) => {
+                self.output.push_str("DROP TYPE ");
+                if drop_type.if_exists() {
+                    self.output.push_str("IF EXISTS ");
+                }
+                self.qualified_name(Kind::Type, drop_type.name());
+            }
+            CqlStatement::DropAggregate(drop_aggregate) => self.drop_aggregate(drop_aggregate),
+            CqlStatement::CreateTrigger(create_trigger) => {
+                self.create("TRIGGER", create_trigger.if_not_exists());
+                let name = self.name(Kind::Trigger, create_trigger.name());
+                write!(self.output, "{} ON ", name).unwrap();
+                self.qualified_name(Kind::Table, create_trigger.table());
+                let class = self.string(create_trigger.class());
+                write!(self.output, " USING {}", class).unwrap();
+            }
+            CqlStatement::DropTrigger(drop_trigger) => {
+                self.output.push_str("DROP TRIGGER ");
+                if drop_trigger.if_exists() {
+                    self.output.push_str("IF EXISTS ");
+                }
+                let name = self.name(Kind::Trigger, drop_trigger.name());
+                write!(self.output, "{} ON ", name).unwrap();
+                self.qualified_name(Kind::Table, drop_trigger.table());
+            }
+            CqlStatement::CreateRole(create_role) => {
+                self.create("ROLE", create_role.if_not_exists());
+                let name = self.name(Kind::Role, create_role.name());
+                self.output.push_str(&name);
+                self.role_options(create_role.options());
+            }
+            CqlStatement::AlterRole(alter_role) => {
+                self.output.push_str("ALTER ROLE ");
+                if alter_role.if_exists() {
+                    self.output.push_str("IF EXISTS ");
+                }
+                let name = self.name(Kind::Role, alter_role.name());
+                self.output.push_str(&name);
+                self.role_options(alter_role.options());
+            }
+            CqlStatement::DropRole(drop_role) => {
+                self.output.push_str("DROP ROLE ");
+                if drop_role.if_exists() {
+                    self.output.push_str("IF EXISTS ");
+                }
+                let name = self.name(Kind::Role, drop_role.name());
+                self.output.push_str(&name);
+            }
+            CqlStatement::Grant(grant) => {
+                write!(self.output, "GRANT {} ON ", grant.permission().keyword()).unwrap();
+                self.resource(grant.resource());
+                let role = self.name(Kind::Role, grant.role());
+                write!(self.output, " TO {}", role).unwrap();
+            }
+            CqlStatement::Revoke(revoke) => {
+                write!(self.output, "REVOKE {} ON ", revoke.permission().keyword()).unwrap();
+                self.resource(revoke.resource());
+                let role = self.name(Kind::Role, revoke.role());
+                write!(self.output, " FROM {}", role).unwrap();
+            }
+            CqlStatement::CreateTableLike(like) => {
+                self.create("TABLE", like.if_not_exists());
+                self.qualified_name(Kind::Table, like.name());
+                self.output.push_str(" LIKE ");
+                self.qualified_name(Kind::Table, like.source());
+                if let Some(options) = like.options() {
+                    self.options(options);
+                }
+            }
+            CqlStatement::CreateIndex(create_index) => self.index(create_index),
+            CqlStatement::Select(select) => self.select(select),
+            CqlStatement::Insert(insert) => self.insert(insert),
+            CqlStatement::Update(update) => self.update(update),
+            CqlStatement::Delete(delete) => self.delete(delete),
+            CqlStatement::Use(keyspace) => {
+                let keyspace = self.name(Kind::Keyspace, keyspace);
+                write!(self.output, "USE {}", keyspace).unwrap();
+            }
+            CqlStatement::CreateKeyspace(create_keyspace) => self.create_keyspace(create_keyspace),
+        }
+    }
+
+    /// Returns the replacement of the name, or the name itself without a seed.
+    fn name(&mut self, kind: Kind, identifier: &CqlIdentifier<&str>) -> String {
+        let Some(seed) = self.seed else {
+            return identifier.to_cql();
+        };
+        // Unquoted names are case insensitive, quoted names are not.
+        let spelling = match identifier {
+            CqlIdentifier::Unquoted(name) => name.to_lowercase(),
+            CqlIdentifier::Quoted(name) => name.clone(),
+        };
+        let count = self.names.len() as u64;
+        let replacement = self
+            .names
+            .entry((kind, spelling.to_lowercase()))
+            .or_insert_with(|| {
+                Replacement::new(format!("{}_{:016x}", kind.prefix(), mix(seed, count)))
+            });
+        if !replacement.spellings.contains(&spelling) {
+            replacement.spellings.push(spelling.clone());
+        }
+
+        // A lowercase spelling keeps the generated name, the others get their own variant,
+        // unless they are the only spelling.
+        let has_lowercase = replacement
+            .spellings
+            .iter()
+            .any(|spelling| *spelling == spelling.to_lowercase());
+        let variant = if spelling == spelling.to_lowercase() {
+            0
+        } else {
+            replacement
+                .spellings
+                .iter()
+                .filter(|spelling| **spelling != spelling.to_lowercase())
+                .position(|s| *s == spelling)
+                .unwrap()
+                + usize::from(has_lowercase)
+        };
+        if variant == 0 {
+            return replacement.name.clone();
+        }
+
+        // Differs from the generated name only in its case, so it needs to be quoted.
+        let mut letter = 0;
+        let name = replacement
+            .name
+            .chars()
+            .map(|c| {
+                if !c.is_ascii_lowercase() {
+                    return c;
+                }
+                letter += 1;
+                if variant & (1 << (letter - 1)) != 0 {
+                    c.to_ascii_uppercase()
+                } else {
+                    c
+                }
+            })
+            .collect::<String>();
+        format!("\"{}\"", name)
+    }
+
+    fn qualified_name(&mut self, kind: Kind, identifier: &CqlQualifiedIdentifier<&str>) {
+        if let Some(keyspace) = identifier.keyspace() {
+            let keyspace = self.name(Kind::Keyspace, keyspace);
+            write!(self.output, "{}.", keyspace).unwrap();
+        }
+        let name = self.name(kind, identifier.name());
+        self.output.push_str(&name);
+    }
+
+    /// Renders a string, which is emptied by a seed.
+    fn string(&self, string: &str) -> String {
+        match self.seed {
+            Some(_) => "''".to_string(),
+            None => quote(string),
+        }
+    }
+
+    /// Starts the `index`th element of a parenthesized list, like a column definition.
+    fn element(&mut self, index: usize) {
+        match &self.indent {
+            Some(indent) => {
+                if index > 0 {
+                    self.output.push(',');
+                }
+                self.output.push('\n');
+                self.output.push_str(indent);
+            }
+            None if index > 0 => self.output.push_str(", "),
+            None => {}
+        }
+    }
+
+    /// Closes a parenthesized list of elements.
+    fn close(&mut self) {
+        if self.indent.is_some() {
+            self.output.push('\n');
+        }
+        self.output.push(')');
+    }
+
+    /// Joins the clauses of a `WITH`, each on its own line when indenting.
+    fn with(&mut self, clauses: &[String]) {
+        let separator = match &self.indent {
+            Some(indent) => format!("\n{}AND ", indent),
+            None => " AND ".to_string(),
+        };
+        write!(self.output, " WITH {}", clauses.join(&separator)).unwrap();
+    }
+
+    fn create(&mut self, statement: &str, if_not_exists: bool) {
+        self.output.push_str("CREATE ");
+        self.output.push_str(statement);
+        if if_not_exists {
+            self.output.push_str(" IF NOT EXISTS");
+        }
+        self.output.push(' ');
+    }
+
+    fn user_defined_type(&mut self, udt: &ParsedCqlUserDefinedType<&str, CqlIdentifier<&str>>) {
+        self.create("TYPE", udt.if_not_exists());
+        self.qualified_name(Kind::Type, udt.name());
+        self.output.push_str(" (");
+        for (i, (name, cql_type)) in udt.fields().iter().enumerate() {
+            self.element(i);
+            let name = self.name(Kind::Field, name);
+            self.output.push_str(&name);
+            self.output.push(' ');
+            self.cql_type(cql_type);
+        }
+        self.close();
+    }
+
+    fn alter_type(&mut self, alter_type: &CqlAlterType<&str>) {
+        self.output.push_str("ALTER TYPE ");
+        if alter_type.if_exists() {
+            self.output.push_str("IF EXISTS ");
+        }
+        self.qualified_name(Kind::Type, alter_type.name());
+        match alter_type.operation() {
+            CqlAlterTypeOperation::Add(fields) => {
+                self.output.push_str(" ADD ");
+                for (i, (name, cql_type)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        self.output.push_str(", ");
+                    }
+                    let name = self.name(Kind::Field, name);
+                    self.output.push_str(&name);
+                    self.output.push(' ');
+                    self.cql_type(cql_type);
+                }
+            }
+            CqlAlterTypeOperation::Rename(renames) => {
+                self.output.push_str(" RENAME ");
+                for (i, (from, to)) in renames.iter().enumerate() {
+                    if i > 0 {
+                        self.output.push_str(" AND ");
+                    }
+                    let from = self.name(Kind::Field, from);
+                    let to = self.name(Kind::Field, to);
+                    write!(self.output, "{} TO {}", from, to).unwrap();
+                }
+            }
+        }
+    }
+
+    fn table(&mut self, table: &ParsedCqlTable<&str>) {
+        self.create("TABLE", table.if_not_exists());
+        self.qualified_name(Kind::Table, table.name());
+        self.output.push_str(" (");
+        for (i, column) in table.columns().iter().enumerate() {
+            self.element(i);
+            let name = self.name(Kind::Column, column.name());
+            self.output.push_str(&name);
+            self.output.push(' ');
+            self.cql_type(column.cql_type());
+            if column.is_static() {
+                self.output.push_str(" STATIC");
+            }
+            if let Some(mask) = column.mask() {
+                self.mask(mask);
+            }
+            if column.is_primary_key() {
+                self.output.push_str(" PRIMARY KEY");
+            }
+        }
+        // The clause of a single column marked `PRIMARY KEY` repeats the marker, a conflicting
+        // clause is kept so the output fails to resolve as well.
+        let marked = table.columns().iter().any(|column| column.is_primary_key());
+        let repeated = marked && table.conflicting_primary_key().is_empty();
+        if let Some(primary_key) = table.primary_key().as_ref().filter(|_| !repeated) {
+            let mut columns = |columns: &[CqlIdentifier<&str>]| {
+                columns
+                    .iter()
+                    .map(|column| self.name(Kind::Column, column))
+                    .collect::<Vec<_>>()
+            };
+            let primary_key = CqlPrimaryKey::new(
+                columns(primary_key.partition_key()),
+                *primary_key.composite_partition(),
+                columns(primary_key.clustering_columns()),
+            );
+            self.element(table.columns().len());
+            write!(self.output, "PRIMARY KEY {}", primary_key).unwrap();
+        }
+        self.close();
+
+        if let Some(options) = table.options() {
+            self.options(options);
+        }
+    }
+
+    fn create_keyspace(&mut self, create_keyspace: &CqlCreateKeyspace<&str>) {
+        self.create("KEYSPACE", create_keyspace.if_not_exists());
+        let name = self.name(Kind::Keyspace, create_keyspace.name());
+        let mut clauses = vec![format!(
+            "replication = {}",
+            option_map(&create_keyspace.replication().to_entries())
+        )];
+        if let Some(durable_writes) = create_keyspace.durable_writes() {
+            clauses.push(format!("durable_writes = {}", durable_writes));
+        }
+        clauses.extend(create_keyspace.options().iter().map(|(name, value)| {
+            let value = match value {
+                CqlOptionValue::String(string) => self.string(string),
+                value => option_value(value),
+            };
+            format!("{} = {}", name, value)
+        }));
+        self.output.push_str(&name);
+        self.with(&clauses);
+    }
+
+    fn alter_materialized_view(&mut self, view: &CqlAlterMaterializedView<&str>) {
+        self.output.push_str("ALTER MATERIALIZED VIEW ");
+        if view.if_exists() {
+            self.output.push_str("IF EXISTS ");
+        }
+        self.qualified_name(Kind::Table, view.name());
+        self.options(view.options());
+    }
+
+    fn options(&mut self, options: &CqlTableOptions<&str, CqlIdentifier<&str>>) {
+        let mut clauses = Vec::new();
+        if options.compact_storage() {
+            clauses.push("COMPACT STORAGE".to_string());
+        }
+        if !options.clustering_order().is_empty() {
+            let order = options
+                .clustering_order()
+                .iter()
+                .map(|(column, order)| {
+                    let order = match order {
+                        CqlOrder::Asc => "ASC",
+                        CqlOrder::Desc => "DESC",
+                    };
+                    format!("{} {}", self.name(Kind::Column, column), order)
+                })
+                .collect::<Vec<_>>();
+            clauses.push(format!("CLUSTERING ORDER BY ({})", order.join(", ")));
+        }
+        clauses.extend(options.options().iter().map(|option| {
+            let value = match option {
+                // String values are free text, like the `comment` of the table.
+                CqlTableOption::Comment(string)
+                | CqlTableOption::Other(_, CqlOptionValue::String(string)) => self.string(string),
+                CqlTableOption::GcGraceSeconds(value)
+                | CqlTableOption::DefaultTimeToLive(value)
+                | CqlTableOption::MemtableFlushPeriodInMs(value)
+                | CqlTableOption::MinIndexInterval(value)
+                | CqlTableOption::MaxIndexInterval(value)
+                | CqlTableOption::PaxosGraceSeconds(value) => value.to_string(),
+                CqlTableOption::BloomFilterFpChance(value)
+                | CqlTableOption::CrcCheckChance(value) => format!("{:?}", value),
+                CqlTableOption::SpeculativeRetry(value) | CqlTableOption::ReadRepair(value) => {
+                    quote(value)
+                }
+                CqlTableOption::Cdc(value) | CqlTableOption::SynchronousUpdates(value) => {
+                    value.to_string()
+                }
+                CqlTableOption::Id(id) => CqlValue::Uuid(*id).to_string(),
+                CqlTableOption::Compaction(compaction) => option_map(&compaction.to_entries()),
+                CqlTableOption::Compression(compression) => option_map(&compression.to_entries()),
+                CqlTableOption::Caching(caching) => option_map(&caching.to_entries()),
+                CqlTableOption::CdcOptions(entries)
+                | CqlTableOption::TombstoneGc(entries)
+                | CqlTableOption::PerPartitionRateLimit(entries) => option_map(entries),
+                CqlTableOption::Other(_, value) => option_value(value),
+            };
+            format!("{} = {}", option.name(), value)
+        }));
+        if !clauses.is_empty() {
+            self.with(&clauses);
+        }
+    }
+
+    fn select(&mut self, select: &CqlSelect<&str>) {
+        self.output.push_str("SELECT ");
+        if select.json() {
+            self.output.push_str("JSON ");
+        }
+        if select.distinct() {
+            self.output.push_str("DISTINCT ");
+        }
+        self.selection(select.selection());
+        self.output.push_str(" FROM ");
+        self.qualified_name(Kind::Table, select.table());
+        if let Some(where_clause) = select.where_clause() {
+            self.where_clause(where_clause);
+        }
+        for (i, column) in select.group_by().iter().enumerate() {
+            self.output
+                .push_str(if i == 0 { " GROUP BY " } else { ", " });
+            let column = self.name(Kind::Column, column);
+            self.output.push_str(&column);
+        }
+        for (i, ordering) in select.order_by().iter().enumerate() {
+            self.output
+                .push_str(if i == 0 { " ORDER BY " } else { ", " });
+            let column = self.name(Kind::Column, ordering.column());
+            match ordering {
+                CqlOrdering::Column(_, order) => {
+                    let order = match order {
+                        CqlOrder::Asc => "ASC",
+                        CqlOrder::Desc => "DESC",
+                    };
+                    write!(self.output, "{} {}", column, order).unwrap();
+                }
+                CqlOrdering::Ann(_, vector) => {
+                    write!(self.output, "{} ANN OF ", column).unwrap();
+                    self.term(vector);
+                }
+            }
+        }
+        if let Some(limit) = select.per_partition_limit() {
+            self.output.push_str(" PER PARTITION LIMIT ");
+            self.term(limit);
+        }
+        if let Some(limit) = select.limit() {
+            self.output.push_str(" LIMIT ");
+            self.term(limit);
+        }
+        if select.allow_filtering() {
+            self.output.push_str(" ALLOW FILTERING");
+        }
+    }
+
+    fn insert(&mut self, insert: &CqlInsert<&str>) {
+        self.output.push_str("INSERT INTO ");
+        self.qualified_name(Kind::Table, insert.table());
+        match insert.values() {
+            CqlInsertValues::Values(values) => {
+                let columns = values
+                    .iter()
+                    .map(|(column, _)| self.name(Kind::Column, column))
+                    .collect::<Vec<_>>();
+                write!(self.output, " ({}) VALUES (", columns.join(", ")).unwrap();
+                for (i, (_, value)) in values.iter().enumerate() {
+                    if i > 0 {
+                        self.output.push_str(", ");
+                    }
+                    self.term(value);
+                }
+                self.output.push(')');
+            }
+            CqlInsertValues::Json(row, default) => {
+                // The keys of the row are column names, an anonymized string drops them.
+                self.output.push_str(" JSON ");
+                self.term(row);
+                match default {
+                    Some(CqlJsonDefault::Null) => self.output.push_str(" DEFAULT NULL"),
+                    Some(CqlJsonDefault::Unset) => self.output.push_str(" DEFAULT UNSET"),
+                    None => {}
+                }
+            }
+        }
+        if let Some(condition) = insert.condition() {
+            self.condition(condition);
+        }
+        if let Some(using) = insert.using() {
+            self.using(using);
+        }
+    }
+
+    fn update(&mut self, update: &CqlUpdate<&str>) {
+        self.output.push_str("UPDATE ");
+        self.qualified_name(Kind::Table, update.table());
+        if let Some(using) = update.using() {
+            self.using(using);
+        }
+        for (i, assignment) in update.assignments().iter().enumerate() {
+            self.output.push_str(if i == 0 { " SET " } else { ", " });
+            let column = self.name(Kind::Column, assignment.column());
+            self.output.push_str(&column);
+            match assignment {
+                CqlAssignment::Column(_, _) => self.output.push_str(" = "),
+                CqlAssignment::Element(_, element, _) => {
+                    self.output.push('[');
+                    self.term(element);
+                    self.output.push_str("] = ");
+                }
+                CqlAssignment::Field(_, field, _) => {
+                    let field = self.name(Kind::Field, field);
+                    write!(self.output, ".{} = ", field).unwrap();
+                }
+                CqlAssignment::Append(_, _)
+                | CqlAssignment::CounterDelta(_, CqlCounterOperation::Increment, _)
+                | CqlAssignment::BindMarkerDelta(_, CqlCounterOperation::Increment, _) => {
+                    write!(self.output, " = {} + ", column).unwrap()
+                }
+                CqlAssignment::Remove(_, _)
+                | CqlAssignment::CounterDelta(_, CqlCounterOperation::Decrement, _)
+                | CqlAssignment::BindMarkerDelta(_, CqlCounterOperation::Decrement, _) => {
+                    write!(self.output, " = {} - ", column).unwrap()
+                }
+                CqlAssignment::Prepend(_, term) => {
+                    self.output.push_str(" = ");
+                    self.term(term);
+                    write!(self.output, " + {}", column).unwrap();
+                    continue;
+                }
+            }
+            self.term(assignment.term());
+        }
+        self.where_clause(update.where_clause());
+        if let Some(condition) = update.condition() {
+            self.condition(condition);
+        }
+    }
+
+    fn delete(&mut self, delete: &CqlDelete<&str>) {
+        self.output.push_str("DELETE ");
+        for (i, deletion) in delete.deletions().iter().enumerate() {
+            if i > 0 {
+                self.output.push_str(", ");
+            }
+            let column = self.name(Kind::Column, deletion.column());
+            self.output.push_str(&column);
+            match deletion {
+                CqlDeletion::Column(_) => {}
+                CqlDeletion::Element(_, element) => {
+                    self.output.push('[');
+                    self.term(element);
+                    self.output.push(']');
+                }
+                CqlDeletion::Field(_, field) => {
+                    let field = self.name(Kind::Field, field);
+                    write!(self.output, ".{}", field).unwrap();
+                }
+            }
+            self.output.push(' ');
+        }
+        self.output.push_str("FROM ");
+        self.qualified_name(Kind::Table, delete.table());
+        if let Some(using) = delete.using() {
+            self.using(using);
+        }
+        self.where_clause(delete.where_clause());
+        if let Some(condition) = delete.condition() {
+            self.condition(condition);
+        }
+    }
+
+    fn using(&mut self, using: &CqlUsing<&str>) {
+        let parameters = [
+            ("TTL", using.ttl()),
+            ("TIMESTAMP", using.timestamp()),
+            ("TIMEOUT", using.timeout()),
+        ];
+        for (i, (parameter, term)) in parameters
+            .into_iter()
+            .filter_map(|(parameter, term)| Some((parameter, term.as_ref()?)))
+            .enumerate()
+        {
+            self.output
+                .push_str(if i == 0 { " USING " } else { " AND " });
+            write!(self.output, "{} ", parameter).unwrap();
+            self.term(term);
+        }
+    }
+
+    fn condition(&mut self, condition: &CqlCondition<&str>) {
+        match condition {
+            CqlCondition::NotExists => self.output.push_str(" IF NOT EXISTS"),
+            CqlCondition::Exists => self.output.push_str(" IF EXISTS"),
+            CqlCondition::Columns(conditions) => {
+                for (i, condition) in conditions.iter().enumerate() {
+                    self.output.push_str(if i == 0 { " IF " } else { " AND " });
+                    let column = self.name(Kind::Column, condition.column());
+                    self.output.push_str(&column);
+                    if let Some(field) = condition.field() {
+                        let field = self.name(Kind::Field, field);
+                        write!(self.output, ".{}", field).unwrap();
+                    }
+                    write!(self.output, " {} ", condition.operator().symbol()).unwrap();
+                    self.term(condition.term());
+                }
+            }
+        }
+    }
+
+    fn selection(&mut self, selection: &CqlSelection<&str>) {
+        match selection {
+            CqlSelection::All => self.output.push('*'),
+            CqlSelection::Selectors(selectors) => {
+                for (i, selector) in selectors.iter().enumerate() {
+                    if i > 0 {
+                        self.output.push_str(", ");
+                    }
+                    self.selector(selector);
+                }
+            }
+        }
+    }
+
+    fn selector(&mut self, selector: &CqlSelector<&str>) {
+        match selector {
+            CqlSelector::Column(column) => {
+                let column = self.name(Kind::Column, column);
+                self.output.push_str(&column);
+            }
+            CqlSelector::Function(name, arguments) => {
+                self.function_name(name);
+                self.output.push('(');
+                self.selection(arguments);
+                self.output.push(')');
+            }
+            CqlSelector::Cast(selector, cql_type) => {
+                self.output.push_str("CAST(");
+                self.selector(selector);
+                self.output.push_str(" AS ");
+                self.cql_type(cql_type);
+                self.output.push(')');
+            }
+            CqlSelector::Alias(selector, alias) => {
+                self.selector(selector);
+                let alias = self.name(Kind::Column, alias);
+                write!(self.output, " AS {}", alias).unwrap();
+            }
+        }
+    }
+
+    /// Renders the name of a called function, only user defined functions are renamed.
+    fn function_name(&mut self, name: &CqlQualifiedIdentifier<&str>) {
+        // The built-in functions, like `count` or `mask_inner`, are not part of a keyspace.
+        match (name.keyspace(), name.name()) {
+            (Some(_), _) => self.qualified_name(Kind::Function, name),
+            // An unquoted reserved name is the `TOKEN` function, quoting it would name another.
+            (None, CqlIdentifier::Unquoted(function)) if is_reserved_keyword(function) => {
+                self.output.push_str(function)
+            }
+            (None, name) => self.output.push_str(&name.to_string()),
+        }
+    }
+
+    fn where_clause(&mut self, where_clause: &CqlWhereClause<&str>) {
+        self.output.push_str(" WHERE ");
+        for (i, relation) in where_clause.relations().iter().enumerate() {
+            if i > 0 {
+                self.output.push_str(" AND ");
+            }
+            match relation {
+                CqlRelation::Column(column, _, _) => {
+                    let column = self.name(Kind::Column, column);
+                    self.output.push_str(&column);
+                }
+                CqlRelation::Token(columns, _, _) => {
+                    let columns = columns
+                        .iter()
+                        .map(|column| self.name(Kind::Column, column))
+                        .collect::<Vec<_>>();
+                    write!(self.output, "TOKEN({})", columns.join(", ")).unwrap();
+                }
+                CqlRelation::Tuple(columns, _, _) => {
+                    let columns = columns
+                        .iter()
+                        .map(|column| self.name(Kind::Column, column))
+                        .collect::<Vec<_>>();
+                    write!(self.output, "({})", columns.join(", ")).unwrap();
+                }
+            }
+            write!(self.output, " {} ", relation.operator().symbol()).unwrap();
+            self.term(relation.term());
+        }
+    }
+
+    fn mask(&mut self, mask: &CqlColumnMask<&str>) {
+        self.output.push_str(" MASKED WITH ");
+        match mask {
+            CqlColumnMask::Default => self.output.push_str("DEFAULT"),
+            CqlColumnMask::Function(name, arguments) => {
+                self.function_name(name);
+                self.output.push('(');
+                for (i, argument) in arguments.iter().enumerate() {
+                    if i > 0 {
+                        self.output.push_str(", ");
+                    }
+                    self.term(argument);
+                }
+                self.output.push(')');
+            }
+        }
+    }
+
+    /// Renders a literal.
+    fn term(&mut self, term: &CqlTerm<&str>) {
+        let list = |printer: &mut Self, open, terms: &[CqlTerm<&str>], close| {
+            printer.output.push(open);
+            for (i, term) in terms.iter().enumerate() {
+                if i > 0 {
+                    printer.output.push_str(", ");
+                }
+                printer.term(term);
+            }
+            printer.output.push(close);
+        };
+        match term {
+            CqlTerm::Constant(CqlConstant::Null) => self.output.push_str("null"),
+            CqlTerm::Constant(CqlConstant::String(string)) => {
+                let string = self.string(string);
+                self.output.push_str(&string);
+            }
+            CqlTerm::Constant(CqlConstant::Boolean(boolean)) => {
+                write!(self.output, "{}", boolean).unwrap()
+            }
+            CqlTerm::Constant(CqlConstant::Integer(lexeme))
+            | CqlTerm::Constant(CqlConstant::Float(lexeme))
+            | CqlTerm::Constant(CqlConstant::Uuid(lexeme))
+            | CqlTerm::Constant(CqlConstant::Blob(lexeme))
+            | CqlTerm::Constant(CqlConstant::Duration(lexeme)) => self.output.push_str(lexeme),
+            CqlTerm::List(terms) => list(self, '[', terms, ']'),
+            CqlTerm::Set(terms) => list(self, '{', terms, '}'),
+            CqlTerm::TupleLiteral(terms) => list(self, '(', terms, ')'),
+            CqlTerm::Map(entries) => {
+                self.output.push('{');
+                for (i, (key, value)) in entries.iter().enumerate() {
+                    if i > 0 {
+                        self.output.push_str(", ");
+                    }
+                    self.term(key);
+                    self.output.push_str(": ");
+                    self.term(value);
+                }
+                self.output.push('}');
+            }
+            CqlTerm::UdtLiteral(fields) => {
+                self.output.push('{');
+                for (i, (field, value)) in fields.iter().enumerate() {
+                    if i > 0 {
+                        self.output.push_str(", ");
+                    }
+                    let field = self.name(Kind::Field, field);
+                    write!(self.output, "{}: ", field).unwrap();
+                    self.term(value);
+                }
+                self.output.push('}');
+            }
+            CqlTerm::BindMarker(CqlBindMarker::Positional) => self.output.push('?'),
+            CqlTerm::BindMarker(CqlBindMarker::Named(name)) => {
+                write!(self.output, ":{}", name).unwrap()
+            }
+            CqlTerm::FunctionCall(name, arguments) => {
+                self.function_name(name);
+                list(self, '(', arguments, ')');
+            }
+        }
+    }
+
+    fn drop_aggregate(&mut self, drop_aggregate: &CqlDropAggregate<&str>) {
+        self.output.push_str("DROP AGGREGATE ");
+        if drop_aggregate.if_exists() {
+            self.output.push_str("IF EXISTS ");
+        }
+        self.qualified_name(Kind::Function, drop_aggregate.name());
+        if let Some(signature) = drop_aggregate.signature() {
+            self.signature(signature);
+        }
+    }
+
+    fn signature(&mut self, signature: &CqlFunctionSignature<&str>) {
+        self.output.push('(');
+        for (i, cql_type) in signature.iter().enumerate() {
+            if i > 0 {
+                self.output.push_str(", ");
+            }
+            self.cql_type(cql_type);
+        }
+        self.output.push(')');
+    }
+
+    /// Renders the options of a role.
+    fn role_options(&mut self, options: &CqlRoleOptions) {
+        let mut rendered = Vec::new();
+        if let Some(password) = options.password() {
+            rendered.push(format!("PASSWORD = {}", self.string(password)));
+        }
+        if let Some(hashed_password) = options.hashed_password() {
+            rendered.push(format!(
+                "HASHED PASSWORD = {}",
+                self.string(hashed_password)
+            ));
+        }
+        if let Some(login) = options.login() {
+            rendered.push(format!("LOGIN = {}", login));
+        }
+        if let Some(superuser) = options.superuser() {
+            rendered.push(format!("SUPERUSER = {}", superuser));
+        }
+        if let Some(entries) = options.options() {
+            rendered.push(format!("OPTIONS = {}", self.string_map(entries)));
+        }
+        match options.datacenters() {
+            Some(CqlDatacenters::All) => rendered.push("ACCESS TO ALL DATACENTERS".to_string()),
+            Some(CqlDatacenters::Only(datacenters)) => {
+                let datacenters = datacenters
+                    .iter()
+                    .map(|datacenter| self.string(datacenter))
+                    .collect::<Vec<_>>();
+                rendered.push(format!(
+                    "ACCESS TO DATACENTERS {{{}}}",
+                    datacenters.join(", ")
+                ));
+            }
+            None => {}
+        }
+        if !rendered.is_empty() {
+            write!(self.output, " WITH {}", rendered.join(" AND ")).unwrap();
+        }
+    }
+
+    /// Renders a map of strings, like the options of a role.
+    fn string_map(&self, entries: &[(String, String)]) -> String {
+        let entries = entries
+            .iter()
+            .map(|(key, value)| format!("{}: {}", self.string(key), self.string(value)))
+            .collect::<Vec<_>>();
+        format!("{{{}}}", entries.join(", "))
+    }
+
+    /// Renders an index.
+    fn index(&mut self, index: &CqlCreateIndex<&str>) {
+        self.output.push_str("CREATE ");
+        if index.custom() {
+            self.output.push_str("CUSTOM ");
+        }
+        self.output.push_str("INDEX ");
+        if index.if_not_exists() {
+            self.output.push_str("IF NOT EXISTS ");
+        }
+        if let Some(name) = index.name() {
+            let name = self.name(Kind::Index, name);
+            write!(self.output, "{} ", name).unwrap();
+        }
+        self.output.push_str("ON ");
+        self.qualified_name(Kind::Table, index.table());
+        self.output.push_str(" (");
+        if let Some(partition_key) = index.partition_key() {
+            let columns = partition_key
+                .iter()
+                .map(|column| self.name(Kind::Column, column))
+                .collect::<Vec<_>>();
+            write!(self.output, "({}), ", columns.join(", ")).unwrap();
+        }
+        let column = self.name(Kind::Column, index.target().column());
+        match index.target() {
+            CqlIndexTarget::Column(_) => self.output.push_str(&column),
+            CqlIndexTarget::Keys(_) => write!(self.output, "KEYS({})", column).unwrap(),
+            CqlIndexTarget::Values(_) => write!(self.output, "VALUES({})", column).unwrap(),
+            CqlIndexTarget::Entries(_) => write!(self.output, "ENTRIES({})", column).unwrap(),
+            CqlIndexTarget::Full(_) => write!(self.output, "FULL({})", column).unwrap(),
+        }
+        self.output.push(')');
+        if let Some(using) = index.using() {
+            let class = self.string(using.class());
+            write!(self.output, " USING {}", class).unwrap();
+            if let Some(options) = using.options() {
+                let options = self.string_map(options);
+                write!(self.output, " WITH OPTIONS = {}", options).unwrap();
+            }
+        }
+    }
+
+    /// Renders a resource.
+    fn resource(&mut self, resource: &CqlResource<&str>) {
+        match resource {
+            CqlResource::AllKeyspaces => self.output.push_str("ALL KEYSPACES"),
+            CqlResource::Keyspace(keyspace) => {
+                let keyspace = self.name(Kind::Keyspace, keyspace);
+                write!(self.output, "KEYSPACE {}", keyspace).unwrap();
+            }
+            CqlResource::Table(table) => {
+                self.output.push_str("TABLE ");
+                self.qualified_name(Kind::Table, table);
+            }
+            CqlResource::AllRoles => self.output.push_str("ALL ROLES"),
+            CqlResource::Role(role) => {
+                let role = self.name(Kind::Role, role);
+                write!(self.output, "ROLE {}", role).unwrap();
+            }
+            CqlResource::AllFunctions(keyspace) => {
+                self.output.push_str("ALL FUNCTIONS");
+                if let Some(keyspace) = keyspace {
+                    let keyspace = self.name(Kind::Keyspace, keyspace);
+                    write!(self.output, " IN KEYSPACE {}", keyspace).unwrap();
+                }
+            }
+            CqlResource::Function(name, signature) => {
+                self.output.push_str("FUNCTION ");
+                self.qualified_name(Kind::Function, name);
+                self.signature(signature);
+            }
+            CqlResource::AllMBeans => self.output.push_str("ALL MBEANS"),
+            CqlResource::MBean(name) => {
+                let name = self.string(name);
+                write!(self.output, "MBEAN {}", name).unwrap();
+            }
+            CqlResource::MBeans(pattern) => {
+                let pattern = self.string(pattern);
+                write!(self.output, "MBEANS {}", pattern).unwrap();
+            }
+        }
+    }
+
+    fn cql_type(&mut self, cql_type: &CqlType<CqlIdentifier<&str>>) {
+        match cql_type {
+            CqlType::FROZEN(cql_type) => self.parameters("frozen", [&**cql_type]),
+            CqlType::MAP(entry) => self.parameters("map", [&entry.0, &entry.1]),
+            CqlType::SET(element) => self.parameters("set", [&**element]),
+            CqlType::LIST(element) => self.parameters("list", [&**element]),
+            CqlType::TUPLE(types) => self.parameters("tuple", types),
+            CqlType::VECTOR(element, dimension) => {
+                self.output.push_str("vector<");
+                self.cql_type(element);
+                write!(self.output, ", {}>", dimension).unwrap();
+            }
+            CqlType::UserDefined(udt) => {
+                let name = self.name(Kind::Type, udt);
+                self.output.push_str(&name);
+            }
+            cql_type => self.output.push_str(cql_type.kind()),
+        }
+    }
+
+    fn parameters<'a>(
+        &mut self,
+        name: &str,
+        types: impl IntoIterator<Item = &'a CqlType<CqlIdentifier<&'a str>>>,
+    ) {
+        self.output.push_str(name);
+        self.output.push('<');
+        for (i, cql_type) in types.into_iter().enumerate() {
+            if i > 0 {
+                self.output.push_str(", ");
+            }
+            self.cql_type(cql_type);
+        }
+        self.output.push('>');
+    }
+}
+
+/// Scrambles the counter, different counters give different results for the same seed.
+fn mix(seed: u64, counter: u64) -> u64 {
+    // The finalizer of splitmix64, a bijection.
+    let mut z = seed.wrapping_add(counter.wrapping_mul(0x9e37_79b9_7f4a_7c15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+/// Renders the entries of an option map, like `{'class': 'LZ4Compressor'}`.
+fn option_map(entries: &[(String, CqlOptionValue<&str>)]) -> String {
+    let entries = entries
+        .iter()
+        .map(|(key, value)| format!("{}: {}", quote(key), option_value(value)))
+        .collect::<Vec<_>>();
+    format!("{{{}}}", entries.join(", "))
+}
+
+/// Renders the value of an option, the strings in a map, like a compaction class, are kept.
+fn option_value(value: &CqlOptionValue<&str>) -> String {
+    match value {
+        CqlOptionValue::String(string) => quote(string),
+        CqlOptionValue::Map(entries) => option_map(entries),
+        CqlOptionValue::Integer(literal)
+        | CqlOptionValue::Float(literal)
+        | CqlOptionValue::Uuid(literal)
+        | CqlOptionValue::Constant(literal) => literal.to_string(),
+        CqlOptionValue::Boolean(boolean) => boolean.to_string(),
+    }
+}
+
+/// Quotes a string, with its quotes doubled.
+fn quote(string: &str) -> String {
+    format!("'{}'", string.replace('\'', "''"))
+}
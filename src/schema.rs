@@ -0,0 +1,369 @@
+use crate::error::CqlResolveError;
+use crate::model::*;
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+type ParsedStatement<'a> = ParsedCqlStatement<&'a str>;
+type ResolvedTable<'a> = Rc<ResolvedCqlTable<&'a str>>;
+type ResolvedStatement<'a> = ResolvedCqlStatement<&'a str>;
+
+/// A schema which resolves its statements on first access.
+///
+/// [`resolve_references`](crate::resolve_references) resolves every statement of a file, which is
+/// wasteful if only a single table is of interest. The lazy schema only resolves the requested
+/// statement and the user defined types and copied tables it depends on. Resolved statements are
+/// memoized, so user defined types shared between tables are still resolved once.
+///
+/// A statement only sees the definitions preceding it, exactly like in
+/// [`resolve_references`](crate::resolve_references).
+#[derive(Debug)]
+pub struct LazySchema<'a> {
+    statements: Vec<ParsedStatement<'a>>,
+    keyspace: Option<&'a CqlIdentifier<&'a str>>,
+    resolved: RefCell<Vec<Option<ResolvedStatement<'a>>>>,
+    resolutions: Cell<usize>,
+}
+
+impl<'a> LazySchema<'a> {
+    /// Creates a lazy schema of parsed statements.
+    pub fn new(
+        statements: Vec<ParsedStatement<'a>>,
+        keyspace: Option<&'a CqlIdentifier<&'a str>>,
+    ) -> Self {
+        let resolved = RefCell::new(vec![None; statements.len()]);
+        Self {
+            statements,
+            keyspace,
+            resolved,
+            resolutions: Cell::new(0),
+        }
+    }
+
+    /// The parsed statements of the schema.
+    pub fn statements(&self) -> &[ParsedStatement<'a>] {
+        &self.statements
+    }
+
+    /// The number of statements resolved so far.
+    pub fn resolved_count(&self) -> usize {
+        self.resolutions.get()
+    }
+
+    /// Resolves the table with the given name.
+    ///
+    /// Fails with the name of the table if it does not exist, or with the first error of its
+    /// resolution.
+    pub fn table(
+        &self,
+        name: &CqlQualifiedIdentifier<&'a str>,
+    ) -> Result<ResolvedTable<'a>, CqlResolveError<&'a str>> {
+        let index = self
+            .statements
+            .iter()
+            .enumerate()
+            .position(|(index, statement)| {
+                statement
+                    .create_table()
+                    .map(|table| self.matches(index, table, name))
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| name.contextualized_identifier(self.keyspace))?;
+
+        Ok(self.resolve(index)?.create_table().unwrap().clone())
+    }
+
+    /// Resolves the user defined type with the given name.
+    ///
+    /// Fails with the name of the type if it does not exist, or with the first error of its
+    /// resolution.
+    pub fn user_defined_type(
+        &self,
+        name: &CqlQualifiedIdentifier<&'a str>,
+    ) -> Result<Rc<CqlUserDefinedType<&'a str>>, CqlResolveError<&'a str>> {
+        let index = self
+            .statements
+            .iter()
+            .enumerate()
+            .position(|(index, statement)| {
+                statement
+                    .create_user_defined_type()
+                    .map(|udt| self.matches(index, udt, name))
+                    .unwrap_or(false)
+            })
+            .ok_or_else(|| name.contextualized_identifier(self.keyspace))?;
+
+        Ok(self
+            .resolve(index)?
+            .create_user_defined_type()
+            .unwrap()
+            .clone())
+    }
+
+    /// Resolves the statement at the given position.
+    ///
+    /// # Panics
+    /// Panics if the index is out of bounds.
+    pub fn resolve(&self, index: usize) -> Result<ResolvedStatement<'a>, CqlResolveError<&'a str>> {
+        if let Some(resolved) = &self.resolved.borrow()[index] {
+            return Ok(resolved.clone());
+        }
+
+        let statement = &self.statements[index];
+        let active = self.keyspace_at(index);
+        let keyspace = match statement {
+            CqlStatement::CreateTable(table) => table.contextualized_keyspace(active),
+            CqlStatement::CreateTableLike(like) => like.contextualized_keyspace(active),
+            CqlStatement::CreateUserDefinedType(udt) => udt.contextualized_keyspace(active),
+            CqlStatement::AlterMaterializedView(view) => view.contextualized_keyspace(active),
+            CqlStatement::AlterType(alter_type) => alter_type.contextualized_keyspace(active),
+            CqlStatement::DropType(drop_type) => drop_type.contextualized_keyspace(active),
+            CqlStatement::DropAggregate(drop_aggregate) => {
+                drop_aggregate.contextualized_keyspace(active)
+            }
+            CqlStatement::CreateTrigger(create_trigger) => {
+                create_trigger.contextualized_keyspace(active)
+            }
+            CqlStatement::DropTrigger(drop_trigger) => drop_trigger.contextualized_keyspace(active),
+            CqlStatement::CreateIndex(create_index) => {
+                create_index.table().contextualized_keyspace(active)
+            }
+            CqlStatement::Select(select) => select.contextualized_keyspace(active),
+            CqlStatement::Insert(insert) => insert.contextualized_keyspace(active),
+            CqlStatement::Update(update) => update.contextualized_keyspace(active),
+            CqlStatement::Delete(delete) => delete.contextualized_keyspace(active),
+            CqlStatement::Use(keyspace) => Some(keyspace.clone()),
+            CqlStatement::CreateKeyspace(create_keyspace) => Some(create_keyspace.name().clone()),
+            // Roles and permissions are not part of a keyspace.
+            CqlStatement::CreateRole(_)
+            | CqlStatement::AlterRole(_)
+            | CqlStatement::DropRole(_)
+            | CqlStatement::Grant(_)
+            | CqlStatement::Revoke(_) => None,
+        };
+        let references = match statement {
+            CqlStatement::CreateTable(table) => table.collect_udts(),
+            CqlStatement::CreateUserDefinedType(udt) => udt.collect_udts(),
+            CqlStatement::CreateTableLike(_)
+            | CqlStatement::Use(_)
+            | CqlStatement::CreateKeyspace(_)
+            | CqlStatement::AlterMaterializedView(_)
+            | CqlStatement::AlterType(_)
+            | CqlStatement::DropType(_)
+            | CqlStatement::DropAggregate(_)
+            | CqlStatement::CreateTrigger(_)
+            | CqlStatement::DropTrigger(_)
+            | CqlStatement::CreateRole(_)
+            | CqlStatement::AlterRole(_)
+            | CqlStatement::DropRole(_)
+            | CqlStatement::Grant(_)
+            | CqlStatement::Revoke(_)
+            | CqlStatement::CreateIndex(_)
+            | CqlStatement::Select(_)
+            | CqlStatement::Insert(_)
+            | CqlStatement::Update(_)
+            | CqlStatement::Delete(_) => Vec::new(),
+        };
+
+        // Only the dependencies are resolved, an unknown type is reported by `reference_types`.
+        let mut context = Vec::new();
+        for reference in references {
+            let identifier = reference.contextualized_identifier(keyspace.as_ref());
+            let dependency =
+                self.statements[..index]
+                    .iter()
+                    .enumerate()
+                    .position(|(dependency, statement)| {
+                        statement
+                            .create_user_defined_type()
+                            .map(|udt| {
+                                udt.contextualized_identifier(self.keyspace_at(dependency))
+                                    == identifier
+                            })
+                            .unwrap_or(false)
+                    });
+            if let Some(dependency) = dependency {
+                context.push(self.resolve(dependency)?);
+            }
+        }
+        // A copied table depends on the latest definition of its source.
+        if let Some(like) = statement.create_table_like() {
+            let source = like.source().contextualized_identifier(active);
+            let dependency = (0..index).rev().find(|&dependency| {
+                let keyspace = self.keyspace_at(dependency);
+                match &self.statements[dependency] {
+                    CqlStatement::CreateTable(table) => {
+                        table.contextualized_identifier(keyspace) == source
+                    }
+                    CqlStatement::CreateTableLike(like) => {
+                        like.contextualized_identifier(keyspace) == source
+                    }
+                    _ => false,
+                }
+            });
+            if let Some(dependency) = dependency {
+                context.push(self.resolve(dependency)?);
+            }
+        }
+
+        let resolved = statement.clone().reference_types(active, &context)?;
+        self.resolutions.set(self.resolutions.get() + 1);
+        self.resolved.borrow_mut()[index] = Some(resolved.clone());
+
+        Ok(resolved)
+    }
+
+    /// The keyspace of unqualified names in the statement at the given position, the keyspace of
+    /// the last `USE` statement before it.
+    fn keyspace_at(&self, index: usize) -> Option<&CqlIdentifier<&'a str>> {
+        self.statements[..index]
+            .iter()
+            .rev()
+            .find_map(|statement| statement.use_keyspace())
+            .or(self.keyspace)
+    }
+
+    fn matches<T: Identifiable<&'a str>>(
+        &self,
+        index: usize,
+        node: &T,
+        name: &CqlQualifiedIdentifier<&'a str>,
+    ) -> bool {
+        node.contextualized_identifier(self.keyspace_at(index))
+            == name.contextualized_identifier(self.keyspace)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_cql, resolve_references};
+
+    const SCHEMA: &str = r#"
+        CREATE TYPE my_ks.address_type (street_name text);
+        CREATE TYPE my_ks.person_type (home_address frozen<address_type>);
+        CREATE TYPE my_ks.unused_type (some_field int);
+        CREATE TABLE my_ks.broken_table (
+            broken_field frozen<missing_type>,
+            PRIMARY KEY (broken_field)
+        );
+        CREATE TABLE my_ks.people_v1 (
+            person_id uuid,
+            person_data frozen<person_type>,
+            PRIMARY KEY (person_id)
+        );
+        CREATE TABLE my_ks.addresses_v1 (
+            address_id uuid,
+            address_data frozen<address_type>,
+            PRIMARY KEY (address_id)
+        );
+    "#;
+
+    fn name(identifier: &'static str) -> CqlQualifiedIdentifier<&'static str> {
+        CqlQualifiedIdentifier::new(
+            Some(CqlIdentifier::new("my_ks")),
+            CqlIdentifier::new(identifier),
+        )
+    }
+
+    #[test]
+    fn test_resolves_dependency_closure_only() {
+        let (_, statements) = parse_cql(SCHEMA).unwrap();
+        let schema = LazySchema::new(statements, None);
+
+        let table = schema.table(&name("people_v1")).unwrap();
+        assert_eq!(table.columns().len(), 2);
+        // people_v1, person_type and address_type.
+        assert_eq!(schema.resolved_count(), 3);
+
+        // Memoized, nothing is resolved twice.
+        let again = schema.table(&name("people_v1")).unwrap();
+        assert!(Rc::ptr_eq(&table, &again));
+        assert_eq!(schema.resolved_count(), 3);
+
+        let addresses = schema.table(&name("addresses_v1")).unwrap();
+        assert_eq!(schema.resolved_count(), 4);
+        let person = schema.user_defined_type(&name("person_type")).unwrap();
+        let address = match &person.fields()[0].1 {
+            CqlType::FROZEN(inner) => inner.clone().unwrap_user_defined(),
+            _ => unreachable!(),
+        };
+        let address_of_table = match addresses.columns()[1].cql_type() {
+            CqlType::FROZEN(inner) => inner.clone().unwrap_user_defined(),
+            _ => unreachable!(),
+        };
+        assert!(Rc::ptr_eq(&address, &address_of_table));
+    }
+
+    #[test]
+    fn test_errors_surface_on_access() {
+        let (_, statements) = parse_cql(SCHEMA).unwrap();
+        let schema = LazySchema::new(statements, None);
+
+        assert_eq!(
+            schema.table(&name("broken_table")),
+            Err(CqlResolveError::Unresolved(name("missing_type")))
+        );
+        assert_eq!(
+            schema.table(&name("unknown_table")),
+            Err(CqlResolveError::Unresolved(name("unknown_table")))
+        );
+    }
+
+    #[test]
+    fn test_same_result_as_eager_resolution() {
+        let input = include_str!("../tests/fixtures/comments.cql");
+        let (_, statements) = parse_cql(input).unwrap();
+        let eager = resolve_references(statements.clone(), None).unwrap();
+        let schema = LazySchema::new(statements, None);
+        for (index, statement) in eager.iter().enumerate() {
+            assert_eq!(&schema.resolve(index).unwrap(), statement);
+        }
+    }
+
+    #[test]
+    fn test_use() {
+        let input = r#"
+            USE ks_a;
+            CREATE TYPE address_type (street_name text);
+            USE ks_b;
+            CREATE TYPE address_type (street_name text, zip_code int);
+            CREATE TABLE people_b (person_id int PRIMARY KEY, home_1 frozen<address_type>);
+        "#;
+        let (_, statements) = parse_cql(input).unwrap();
+        let eager = resolve_references(statements.clone(), None).unwrap();
+        let schema = LazySchema::new(statements, None);
+
+        let table = schema
+            .table(&CqlQualifiedIdentifier::new(
+                Some(CqlIdentifier::new("ks_b")),
+                CqlIdentifier::new("people_b"),
+            ))
+            .unwrap();
+        assert_eq!(&table, eager[4].create_table().unwrap());
+        // Only the type of ks_b is a dependency.
+        assert_eq!(schema.resolved_count(), 2);
+    }
+
+    #[test]
+    fn test_table_like() {
+        let input = r#"
+            USE ks_a;
+            CREATE TABLE people_a (person_id int PRIMARY KEY);
+            CREATE TABLE people_b (person_id int PRIMARY KEY);
+            CREATE TABLE people_c LIKE people_a;
+            CREATE TABLE ks_b.people_d LIKE people_c;
+        "#;
+        let (_, statements) = parse_cql(input).unwrap();
+        let schema = LazySchema::new(statements, None);
+
+        let like = schema.resolve(4).unwrap();
+        let like = like.create_table_like().unwrap();
+        let source = schema.resolve(1).unwrap();
+        assert!(Rc::ptr_eq(
+            like.source_table().as_ref().unwrap(),
+            source.create_table().unwrap()
+        ));
+        // The copied tables are dependencies, the unrelated table is not.
+        assert_eq!(schema.resolved_count(), 3);
+    }
+}
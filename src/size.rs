@@ -0,0 +1,273 @@
+use crate::model::*;
+use derive_new::new;
+use getset::{CopyGetters, Getters};
+use std::collections::HashMap;
+use std::ops::Deref;
+use std::rc::Rc;
+
+/// The bytes stored with every cell, its write timestamp.
+pub const CELL_OVERHEAD: usize = 8;
+/// The bytes of the length prefixed to each element of a serialized collection, tuple or udt.
+pub const LENGTH_PREFIX: usize = 4;
+
+/// The average sizes assumed for values without a fixed size.
+#[derive(Debug, Clone, PartialEq, Getters, CopyGetters, new)]
+pub struct SizeAssumptions {
+    /// The bytes of a text, ascii or varchar value.
+    #[getset(get_copy = "pub")]
+    text: usize,
+    /// The bytes of a blob value.
+    #[getset(get_copy = "pub")]
+    blob: usize,
+    /// The bytes of a varint or decimal value.
+    #[getset(get_copy = "pub")]
+    number: usize,
+    /// The elements of a list, set or map.
+    #[getset(get_copy = "pub")]
+    collection_elements: usize,
+    /// The bytes of the values of single columns, by column name.
+    #[new(default)]
+    #[getset(get = "pub")]
+    columns: HashMap<CqlIdentifier<String>, usize>,
+}
+
+impl SizeAssumptions {
+    /// Assumes the values of the column have the size, instead of the size of its type.
+    ///
+    /// The name is unquoted, `user_id` is the column `User_Id` as well.
+    pub fn with_column(mut self, column: impl Into<String>, size: usize) -> Self {
+        self.columns.insert(CqlIdentifier::new(column.into()), size);
+        self
+    }
+}
+
+impl Default for SizeAssumptions {
+    fn default() -> Self {
+        Self::new(32, 64, 8, 4)
+    }
+}
+
+/// Estimates the serialized bytes of a value of the type.
+pub fn estimate_value_size<I>(
+    cql_type: &CqlType<Rc<CqlUserDefinedType<I>>>,
+    assumptions: &SizeAssumptions,
+) -> usize {
+    let element = |cql_type| LENGTH_PREFIX + estimate_value_size(cql_type, assumptions);
+    match cql_type {
+        CqlType::BOOLEAN | CqlType::TINYINT => 1,
+        CqlType::SMALLINT => 2,
+        CqlType::INT | CqlType::FLOAT | CqlType::DATE => 4,
+        CqlType::BIGINT
+        | CqlType::COUNTER
+        | CqlType::DOUBLE
+        | CqlType::TIMESTAMP
+        | CqlType::TIME => 8,
+        // An ipv6 address, ipv4 addresses only take 4 bytes.
+        CqlType::UUID | CqlType::TIMEUUID | CqlType::INET | CqlType::DURATION => 16,
+        CqlType::ASCII | CqlType::TEXT | CqlType::VARCHAR => assumptions.text,
+        CqlType::BLOB => assumptions.blob,
+        CqlType::VARINT | CqlType::DECIMAL => assumptions.number,
+        CqlType::FROZEN(cql_type) => estimate_value_size(cql_type, assumptions),
+        CqlType::LIST(cql_type) | CqlType::SET(cql_type) => {
+            LENGTH_PREFIX + assumptions.collection_elements * element(cql_type)
+        }
+        CqlType::VECTOR(cql_type, dimension) => *dimension as usize * element(cql_type),
+        CqlType::MAP(entry) => {
+            LENGTH_PREFIX
+                + assumptions.collection_elements * (element(&entry.0) + element(&entry.1))
+        }
+        CqlType::TUPLE(types) => types.iter().map(element).sum(),
+        CqlType::UserDefined(udt) => udt
+            .fields()
+            .iter()
+            .map(|(_, cql_type)| element(cql_type))
+            .sum(),
+    }
+}
+
+/// Estimates the bytes of one row of the table, without its partition key and static columns.
+///
+/// Every row stores its clustering columns and a cell with a timestamp for each regular column.
+pub fn estimate_row_size<I: Deref<Target = str>>(
+    table: &ResolvedCqlTable<I>,
+    assumptions: &SizeAssumptions,
+) -> usize {
+    let (partition_key, clustering_columns) = table.key_columns();
+    let clustering = clustering_columns
+        .iter()
+        .map(|column| column_size(column, assumptions))
+        .sum::<usize>();
+    let cells = table
+        .columns()
+        .iter()
+        .filter(|column| !column.is_static())
+        .filter(|column| !is_any(&partition_key, column) && !is_any(&clustering_columns, column))
+        .map(|column| CELL_OVERHEAD + column_size(column, assumptions))
+        .sum::<usize>();
+
+    clustering + cells
+}
+
+/// Estimates the bytes of a partition of the table with the number of rows.
+///
+/// A partition stores its partition key and static columns once, and each of its rows.
+pub fn estimate_partition_size<I: Deref<Target = str>>(
+    table: &ResolvedCqlTable<I>,
+    assumptions: &SizeAssumptions,
+    rows_per_partition: usize,
+) -> usize {
+    let (partition_key, _) = table.key_columns();
+    let key = partition_key
+        .iter()
+        .map(|column| column_size(column, assumptions))
+        .sum::<usize>();
+    let statics = table
+        .columns()
+        .iter()
+        .filter(|column| column.is_static())
+        .map(|column| CELL_OVERHEAD + column_size(column, assumptions))
+        .sum::<usize>();
+
+    key + statics + rows_per_partition * estimate_row_size(table, assumptions)
+}
+
+fn column_size<I: Deref<Target = str>>(
+    column: &CqlColumn<I, Rc<CqlUserDefinedType<I>>>,
+    assumptions: &SizeAssumptions,
+) -> usize {
+    let name = match column.name() {
+        CqlIdentifier::Unquoted(name) => CqlIdentifier::new(name.to_string()),
+        CqlIdentifier::Quoted(name) => CqlIdentifier::new_quoted(name.clone()),
+    };
+    assumptions
+        .columns
+        .get(&name)
+        .copied()
+        .unwrap_or_else(|| estimate_value_size(column.cql_type(), assumptions))
+}
+
+fn is_any<I, UdtType>(columns: &[&CqlColumn<I, UdtType>], column: &CqlColumn<I, UdtType>) -> bool {
+    columns.iter().any(|other| std::ptr::eq(*other, column))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{parse_cql, resolve_references};
+
+    const SCHEMA: &str = r#"
+        CREATE TYPE zoo_ks.address_type (street_name text, house_no int, zip_code frozen<list<int>>);
+        CREATE TABLE zoo_ks.keepers_v1 (
+            enclosure_id int,
+            keeper_id uuid,
+            shift_1 timestamp,
+            zoo_name text STATIC,
+            home_1 frozen<address_type>,
+            skill_set set<text>,
+            rating_map map<text, double>,
+            on_duty boolean,
+            PRIMARY KEY ((enclosure_id, keeper_id), shift_1)
+        );
+        CREATE TABLE zoo_ks.animals_v1 (animal_id timeuuid PRIMARY KEY, name_1 varchar);
+    "#;
+
+    type ResolvedTable<'a> = ResolvedCqlTable<&'a str>;
+    type ResolvedType<'a> = CqlType<Rc<CqlUserDefinedType<&'a str>>>;
+
+    /// Calls `f` with each table of the schema and its index.
+    fn tables(f: impl Fn(&ResolvedTable<'_>, usize)) {
+        let (_, statements) = parse_cql(SCHEMA).unwrap();
+        let statements = resolve_references(statements, None).unwrap();
+        for (index, statement) in statements[1..].iter().enumerate() {
+            f(statement.create_table().unwrap(), index);
+        }
+    }
+
+    #[test]
+    fn test_value_size() {
+        let assumptions = SizeAssumptions::default();
+        let (_, statements) = parse_cql(SCHEMA).unwrap();
+        let statements = resolve_references(statements, None).unwrap();
+        let udt = CqlType::UserDefined(statements[0].create_user_defined_type().unwrap().clone());
+
+        // (4 + 32) + (4 + 4) + (4 + (4 + 4 * (4 + 4)))
+        assert_eq!(estimate_value_size(&udt, &assumptions), 84);
+        assert_eq!(
+            estimate_value_size(&ResolvedType::DURATION, &assumptions),
+            16
+        );
+        assert_eq!(
+            estimate_value_size(
+                &ResolvedType::TUPLE(vec![CqlType::INT, CqlType::BLOB]),
+                &assumptions
+            ),
+            4 + 4 + 4 + 64
+        );
+    }
+
+    #[test]
+    fn test_row_size() {
+        let assumptions = SizeAssumptions::default();
+        tables(|table, index| {
+            let expected = match index {
+                // shift_1: 8
+                // home_1: 8 + 84
+                // skill_set: 8 + 4 + 4 * (4 + 32)
+                // rating_map: 8 + 4 + 4 * ((4 + 32) + (4 + 8))
+                // on_duty: 8 + 1
+                0 => 8 + 92 + 156 + 204 + 9,
+                // name_1: 8 + 32
+                _ => 40,
+            };
+            assert_eq!(estimate_row_size(table, &assumptions), expected);
+        });
+    }
+
+    #[test]
+    fn test_partition_size() {
+        let assumptions = SizeAssumptions::default().with_column("zoo_name", 10);
+        tables(|table, index| {
+            let expected = match index {
+                // enclosure_id and keeper_id: 4 + 16
+                // zoo_name: 8 + 10
+                0 => 20 + 18 + 100 * 469,
+                // animal_id: 16
+                _ => 16 + 100 * 40,
+            };
+            assert_eq!(estimate_partition_size(table, &assumptions, 100), expected);
+        });
+    }
+
+    #[test]
+    fn test_column_assumptions() {
+        let assumptions = SizeAssumptions::new(100, 0, 0, 0)
+            .with_column("shift_1", 1)
+            .with_column("rating_map", 2);
+        tables(|table, index| {
+            if index == 0 {
+                // Empty collections, a 100 byte text and the overridden columns.
+                let home = 8 + (4 + 100) + (4 + 4) + (4 + 4);
+                assert_eq!(
+                    estimate_row_size(table, &assumptions),
+                    1 + home + (8 + 4) + (8 + 2) + 9
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn test_column_assumptions_folded() {
+        let (_, statements) =
+            parse_cql("CREATE TABLE t_1 (id_1 int PRIMARY KEY, User_Id text, \"Quoted\" text)")
+                .unwrap();
+        let statements = resolve_references(statements, None).unwrap();
+        let assumptions = SizeAssumptions::new(100, 0, 0, 0)
+            .with_column("user_id", 1)
+            .with_column("quoted", 2);
+        // User_Id: 8 + 1, the quoted column keeps its case and its text: 8 + 100
+        assert_eq!(
+            estimate_row_size(statements[0].create_table().unwrap(), &assumptions),
+            9 + 108
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use crate::utils::{scan_statement_end, ScanState, Whitespace};
+
+/// Collects chunks of CQL text and hands out complete statements.
+///
+/// Statements are split at top-level semicolons, semicolons inside strings, quoted identifiers
+/// and comments do not end a statement. Each returned statement can be parsed with
+/// [`parse_cql`](crate::parse_cql).
+///
+/// ```rust
+/// use cql_nom::stream::StatementFeeder;
+///
+/// let mut feeder = StatementFeeder::new();
+/// feeder.feed("CREATE TYPE my_type (my_field ");
+/// assert_eq!(feeder.next_statement(), None);
+/// feeder.feed("int); CREATE TYPE ");
+/// assert_eq!(
+///     feeder.next_statement().as_deref(),
+///     Some("CREATE TYPE my_type (my_field int);")
+/// );
+/// assert_eq!(feeder.next_statement(), None);
+/// feeder.feed("my_other_type (my_field text)");
+/// assert_eq!(
+///     feeder.finish().as_deref(),
+///     Some(" CREATE TYPE my_other_type (my_field text)")
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct StatementFeeder {
+    buffer: String,
+    /// How far the buffer was searched for the end of the statement.
+    position: usize,
+    /// The construct the search stopped in.
+    state: ScanState,
+}
+
+impl StatementFeeder {
+    /// Creates a new feeder with an empty buffer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a chunk of input to the buffer.
+    pub fn feed(&mut self, chunk: &str) {
+        self.buffer.push_str(chunk);
+    }
+
+    /// Returns the next complete statement including its terminating semicolon,
+    /// or `None` if more data is needed.
+    ///
+    /// The search resumes where the previous call stopped, so every byte is scanned about once.
+    pub fn next_statement(&mut self) -> Option<String> {
+        let end = match scan_statement_end(&self.buffer, self.position, self.state) {
+            Ok(end) => end,
+            Err((position, state)) => {
+                self.position = position;
+                self.state = state;
+                return None;
+            }
+        };
+        let rest = self.buffer.split_off(end + 1);
+        self.position = 0;
+        self.state = ScanState::Code;
+
+        Some(std::mem::replace(&mut self.buffer, rest))
+    }
+
+    /// Signals the end of the input and returns the unterminated last statement, if any.
+    ///
+    /// Trailing whitespace and comments are not a statement and yield `None`.
+    pub fn finish(&mut self) -> Option<String> {
+        let buffer = std::mem::take(&mut self.buffer);
+        self.position = 0;
+        self.state = ScanState::Code;
+        let (rest, _) = Whitespace::split_whitespace(buffer.as_str());
+
+        if rest.is_empty() {
+            None
+        } else {
+            Some(buffer)
+        }
+    }
+
+    /// The buffered input which does not form a complete statement yet.
+    pub fn buffered(&self) -> &str {
+        &self.buffer
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse_cql;
+
+    fn feed_in_chunks(input: &str, chunk_size: usize) -> Vec<String> {
+        let mut feeder = StatementFeeder::new();
+        let mut statements = Vec::new();
+        let mut chars = input.char_indices().peekable();
+        while let Some((start, _)) = chars.peek().copied() {
+            let end = chars
+                .by_ref()
+                .take(chunk_size)
+                .last()
+                .map(|(i, c)| i + c.len_utf8())
+                .unwrap();
+            feeder.feed(&input[start..end]);
+            while let Some(statement) = feeder.next_statement() {
+                statements.push(statement);
+            }
+        }
+        statements.extend(feeder.finish());
+
+        statements
+    }
+
+    #[test]
+    fn test_chunked_fixture() {
+        let input = include_str!("../tests/fixtures/comments.cql");
+        let (_, expected) = parse_cql(input).unwrap();
+
+        let statements = feed_in_chunks(input, 7);
+        assert_eq!(statements.len(), expected.len());
+        let parsed = statements
+            .iter()
+            .flat_map(|statement| {
+                let (remaining, parsed) = parse_cql(statement).unwrap();
+                assert_eq!(remaining, "");
+                parsed
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(parsed, expected);
+    }
+
+    #[test]
+    fn test_semicolons_in_comments_and_quotes() {
+        let input = r#"CREATE TYPE "a;b" ( -- ;
+            my_field int /* ; */
+        ); CREATE TYPE my_type (my_field text)"#;
+        for chunk_size in 1..=4 {
+            let statements = feed_in_chunks(input, chunk_size);
+            assert_eq!(statements.len(), 2);
+            assert!(statements[0].ends_with(");"));
+            assert_eq!(statements[1], " CREATE TYPE my_type (my_field text)");
+        }
+    }
+
+    #[test]
+    fn test_tokens_split_across_chunks() {
+        let mut feeder = StatementFeeder::new();
+        let mut statements = Vec::new();
+        for chunk in [
+            "SELECT $",
+            "$;$",
+            "$ FROM t -",
+            "- ;\n /",
+            "* ; *",
+            "/ WHERE a = '",
+            "'';'",
+            "; SELECT",
+        ] {
+            feeder.feed(chunk);
+            statements.extend(feeder.next_statement());
+        }
+        assert_eq!(
+            statements,
+            vec!["SELECT $$;$$ FROM t -- ;\n /* ; */ WHERE a = ''';';"]
+        );
+        assert_eq!(feeder.buffered(), " SELECT");
+    }
+
+    #[test]
+    fn test_incomplete_statement() {
+        let mut feeder = StatementFeeder::new();
+        feeder.feed("CREATE TYPE \"my;");
+        assert_eq!(feeder.next_statement(), None);
+        assert_eq!(feeder.buffered(), "CREATE TYPE \"my;");
+        feeder.feed("type\" (my_field int);");
+        assert_eq!(
+            feeder.next_statement().as_deref(),
+            Some("CREATE TYPE \"my;type\" (my_field int);")
+        );
+        assert_eq!(feeder.buffered(), "");
+    }
+
+    #[test]
+    fn test_finish_only_comments() {
+        let mut feeder = StatementFeeder::new();
+        feeder.feed("  -- nothing to see here\n");
+        assert_eq!(feeder.next_statement(), None);
+        assert_eq!(feeder.finish(), None);
+    }
+}
@@ -1,18 +1,273 @@
-use nom::error::ParseError;
-use nom::{AsChar, Compare, IResult, InputLength, InputTake, InputTakeAtPosition, Parser};
+use nom::branch::alt;
+use nom::bytes::complete::{is_not, tag, take_until};
+use nom::combinator::{map, recognize};
+use nom::error::{context, ContextError, ErrorKind, ParseError};
+use nom::multi::{many0, separated_list0};
+use nom::sequence::{delimited, pair};
+use nom::{Compare, IResult, InputLength, InputTake, Parser};
+
+/// Input types which can skip leading whitespace and comments.
+///
+/// CQL knows three comment forms: `-- line`, `// line` and `/* block */`.
+pub trait Whitespace: Sized {
+    /// Splits the input into the leading whitespace and comments, and the remaining input.
+    /// Returns `(remaining, skipped)`.
+    fn split_whitespace(self) -> (Self, Self);
+}
+
+impl Whitespace for &str {
+    fn split_whitespace(self) -> (Self, Self) {
+        let bytes = self.as_bytes();
+        let mut position = 0;
+        while position < bytes.len() {
+            match bytes[position] {
+                b' ' | b'\t' | b'\r' | b'\n' => position += 1,
+                b'-' | b'/' if bytes.get(position + 1) == Some(&bytes[position]) => {
+                    // Line comment, runs until the end of the line.
+                    position = bytes[position + 2..]
+                        .iter()
+                        .position(|&b| b == b'\n')
+                        .map(|end| position + 2 + end + 1)
+                        .unwrap_or(bytes.len());
+                }
+                b'/' if bytes.get(position + 1) == Some(&b'*') => {
+                    // Block comment, an unterminated one is left to the caller.
+                    match bytes[position + 2..].windows(2).position(|w| w == b"*/") {
+                        Some(end) => position += 2 + end + 2,
+                        None => break,
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        (&self[position..], &self[..position])
+    }
+}
+
+/// Finds the byte offset of the `;` terminating the first statement of the input.
+///
+/// Semicolons inside string literals, quoted identifiers, `$$` strings and comments are ignored.
+/// Returns `None` if the input does not contain a terminated statement yet.
+pub fn find_statement_end(input: &str) -> Option<usize> {
+    scan_statement_end(input, 0, ScanState::Code).ok()
+}
+
+/// The construct a search for the end of a statement stopped in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScanState {
+    /// The text of the statement, a `;` ends it.
+    #[default]
+    Code,
+    /// A `--` or `//` comment.
+    LineComment,
+    /// A `/* */` comment.
+    BlockComment,
+    /// A `$$` string.
+    DollarString,
+    /// A string literal or a quoted identifier, with its quote.
+    Quoted(u8),
+}
+
+/// Like [`find_statement_end`], but resumes the search at `position` in `state`.
+///
+/// Without a terminated statement, returns the position and the state to resume at once more
+/// input arrived. A trailing byte which may start a two byte token, like the `-` of `--`, is
+/// scanned again then.
+pub fn scan_statement_end(
+    input: &str,
+    mut position: usize,
+    mut state: ScanState,
+) -> Result<usize, (usize, ScanState)> {
+    let bytes = input.as_bytes();
+    loop {
+        let rest = &bytes[position..];
+        match state {
+            ScanState::Code => match rest {
+                [] | [b'-' | b'/' | b'$'] => break,
+                [b';', ..] => return Ok(position),
+                [b'-', b'-', ..] | [b'/', b'/', ..] => {
+                    state = ScanState::LineComment;
+                    position += 2;
+                }
+                [b'/', b'*', ..] => {
+                    state = ScanState::BlockComment;
+                    position += 2;
+                }
+                [b'$', b'$', ..] => {
+                    state = ScanState::DollarString;
+                    position += 2;
+                }
+                [quote @ (b'\'' | b'"'), ..] => {
+                    state = ScanState::Quoted(*quote);
+                    position += 1;
+                }
+                _ => position += 1,
+            },
+            ScanState::LineComment => match memchr::memchr(b'\n', rest) {
+                Some(end) => {
+                    state = ScanState::Code;
+                    position += end + 1;
+                }
+                None => {
+                    position = bytes.len();
+                    break;
+                }
+            },
+            ScanState::BlockComment | ScanState::DollarString => {
+                let close = match state {
+                    ScanState::BlockComment => b"*/",
+                    _ => b"$$",
+                };
+                match rest.windows(2).position(|w| w == close) {
+                    Some(end) => {
+                        state = ScanState::Code;
+                        position += end + 2;
+                    }
+                    None => {
+                        position = bytes.len().saturating_sub(1).max(position);
+                        break;
+                    }
+                }
+            }
+            // A doubled quote is an escaped quote and simply reopens the literal.
+            ScanState::Quoted(quote) => match memchr::memchr(quote, rest) {
+                Some(end) => {
+                    state = ScanState::Code;
+                    position += end + 1;
+                }
+                None => {
+                    position = bytes.len();
+                    break;
+                }
+            },
+        }
+    }
+
+    Err((position, state))
+}
+
+/// Splits the input into its statements, without the terminating semicolons.
+///
+/// Chunks which only consist of whitespace and comments are dropped.
+/// Each chunk is a slice of the input, so its position can be recovered with [`nom::Offset`].
+pub fn split_statements(input: &str) -> Vec<&str> {
+    let mut statements = Vec::new();
+    let mut rest = input;
+    loop {
+        let (chunk, next) = match find_statement_end(rest) {
+            Some(end) => (&rest[..end], Some(&rest[end + 1..])),
+            None => (rest, None),
+        };
+        let (remaining, _) = Whitespace::split_whitespace(chunk);
+        if !remaining.is_empty() {
+            statements.push(chunk);
+        }
+        match next {
+            Some(next) => rest = next,
+            None => return statements,
+        }
+    }
+}
+
+/// Extracts the doc comment from the whitespace and comments skipped in front of a node.
+///
+/// The doc comment consists of the comments directly preceding the node, a blank line breaks
+/// the attachment: comments before a blank line, or followed by one, are dropped. Unless
+/// `line_start`, `leading` follows a token, comments on the line of that token are trailing
+/// comments of the token and dropped too. The comment markers are removed and every line is
+/// trimmed, the comments are joined with newlines.
+pub fn doc_comment(leading: &str, line_start: bool) -> Option<String> {
+    let bytes = leading.as_bytes();
+    let mut comments: Vec<&str> = Vec::new();
+    // Whether a token precedes on the current line, and whether the line has a comment.
+    let mut trailing = !line_start;
+    let mut empty = true;
+    let mut position = 0;
+    while position < bytes.len() {
+        match bytes[position] {
+            b'\n' => {
+                if empty && !trailing {
+                    comments.clear();
+                }
+                trailing = false;
+                empty = true;
+                position += 1;
+            }
+            b'-' | b'/' if bytes.get(position + 1) == Some(&bytes[position]) => {
+                let end = bytes[position + 2..]
+                    .iter()
+                    .position(|&b| b == b'\n')
+                    .map(|end| position + 2 + end)
+                    .unwrap_or(bytes.len());
+                if !trailing {
+                    comments.push(&leading[position + 2..end]);
+                }
+                // The newline ends the comment, the next line starts without a token.
+                trailing = false;
+                empty = true;
+                position = end + 1;
+            }
+            b'/' if bytes.get(position + 1) == Some(&b'*') => {
+                let end = bytes[position + 2..]
+                    .windows(2)
+                    .position(|w| w == b"*/")
+                    .map(|end| position + 2 + end)
+                    .unwrap_or(bytes.len());
+                if !trailing {
+                    comments.push(&leading[position + 2..end]);
+                    empty = false;
+                }
+                position = end + 2;
+            }
+            _ => position += 1,
+        }
+    }
+    if trailing {
+        // The node follows the token on the same line.
+        return None;
+    }
+
+    let lines = comments
+        .iter()
+        .flat_map(|comment| comment.lines())
+        .map(str::trim)
+        .collect::<Vec<_>>();
+    let start = lines.iter().position(|line| !line.is_empty())?;
+    let end = lines.iter().rposition(|line| !line.is_empty())?;
+
+    Some(lines[start..=end].join("\n"))
+}
+
+/// Skips zero or more whitespace characters and comments.
+pub fn ws0<I: Whitespace, E: ParseError<I>>(input: I) -> IResult<I, I, E> {
+    Ok(input.split_whitespace())
+}
+
+/// Skips one or more whitespace characters and comments.
+pub fn ws1<I: Whitespace + InputLength, E: ParseError<I>>(input: I) -> IResult<I, I, E> {
+    let (remaining, skipped) = input.split_whitespace();
+    if skipped.input_len() == 0 {
+        Err(nom::Err::Error(E::from_error_kind(
+            remaining,
+            ErrorKind::MultiSpace,
+        )))
+    } else {
+        Ok((remaining, skipped))
+    }
+}
 
 pub fn space0_around<F: Parser<I, O, E>, I, O, E>(
     mut parser: F,
 ) -> impl FnMut(I) -> IResult<I, O, E>
 where
     E: ParseError<I>,
-    I: InputTakeAtPosition,
-    <I as InputTakeAtPosition>::Item: AsChar + Clone,
+    I: Whitespace + InputLength,
 {
     move |input: I| {
-        let (input, _) = nom::character::complete::multispace0(input)?;
+        let (input, _) = ws0(input)?;
         let (input, o) = parser.parse(input)?;
-        let (input, _) = nom::character::complete::multispace0(input)?;
+        let (input, _) = ws0(input)?;
         Ok((input, o))
     }
 }
@@ -22,11 +277,10 @@ pub fn space1_before<F: Parser<I, O, E>, I, O, E>(
 ) -> impl FnMut(I) -> IResult<I, O, E>
 where
     E: ParseError<I>,
-    I: InputTakeAtPosition,
-    <I as InputTakeAtPosition>::Item: AsChar + Clone,
+    I: Whitespace + InputLength,
 {
     move |input: I| {
-        let (input, _) = nom::character::complete::multispace1(input)?;
+        let (input, _) = ws1(input)?;
         let (input, o) = parser.parse(input)?;
         Ok((input, o))
     }
@@ -36,143 +290,76 @@ pub fn space0_tag<T, Input, Error: ParseError<Input>>(
     tag: T,
 ) -> impl Fn(Input) -> IResult<Input, Input, Error>
 where
-    Input: InputTakeAtPosition + InputTake + Compare<T>,
-    <Input as InputTakeAtPosition>::Item: AsChar + Clone,
-    T: InputLength + Clone,
-{
-    move |input: Input| {
-        let tag = tag.clone();
-        let (input, _) = nom::character::complete::multispace0(input)?;
-        let (input, o) = nom::bytes::complete::tag(tag)(input)?;
-        Ok((input, o))
-    }
-}
-
-pub fn space1_tag<T, Input, Error: ParseError<Input>>(
-    tag: T,
-) -> impl Fn(Input) -> IResult<Input, Input, Error>
-where
-    Input: InputTakeAtPosition + InputTake + Compare<T>,
-    <Input as InputTakeAtPosition>::Item: AsChar + Clone,
+    Input: Whitespace + InputLength + InputTake + Compare<T>,
     T: InputLength + Clone,
 {
     move |input: Input| {
         let tag = tag.clone();
-        let (input, _) = nom::character::complete::multispace1(input)?;
+        let (input, _) = ws0(input)?;
         let (input, o) = nom::bytes::complete::tag(tag)(input)?;
         Ok((input, o))
     }
 }
 
-pub fn space1_tags<T, Input, Error: ParseError<Input>, const TAGS: usize>(
-    tags: [T; TAGS],
-) -> impl Fn(Input) -> IResult<Input, [Input; TAGS], Error>
+/// Matches the tags in order, separated by `separator`, and returns the matched input of every
+/// tag.
+fn separated_tags<T, Input, Error, const TAGS: usize>(
+    tags: &[T; TAGS],
+    mut input: Input,
+    tag: impl Fn(T, Input) -> IResult<Input, Input, Error>,
+    separator: impl Fn(Input) -> IResult<Input, Input, Error>,
+) -> IResult<Input, [Input; TAGS], Error>
 where
-    Input: InputTakeAtPosition + InputTake + Compare<T>,
-    <Input as InputTakeAtPosition>::Item: AsChar + Clone,
-    T: InputLength + Clone,
+    T: Clone,
 {
-    move |mut input: Input| {
-        let tags = tags.clone();
-        let mut output: [std::mem::MaybeUninit<Input>; TAGS] =
-            unsafe { std::mem::MaybeUninit::uninit().assume_init() };
-        for i in 0..TAGS {
-            let (t, o) = nom::bytes::complete::tag(tags[i].clone())(input)?;
-            output[i] = std::mem::MaybeUninit::new(o);
-            input = if i != TAGS - 1 {
-                let (t, _) = nom::character::complete::multispace1(t)?;
-                t
-            } else {
-                t
-            }
+    let mut output = Vec::with_capacity(TAGS);
+    for (i, t) in tags.iter().enumerate() {
+        if i > 0 {
+            let (remaining, _) = separator(input)?;
+            input = remaining;
         }
-        Ok((input, unsafe {
-            (&output as *const _ as *const [Input; TAGS]).read()
-        }))
+        let (remaining, o) = tag(t.clone(), input)?;
+        output.push(o);
+        input = remaining;
     }
-}
 
-pub fn space0_tags<T, Input, Error: ParseError<Input>, const TAGS: usize>(
-    tags: [T; TAGS],
-) -> impl Fn(Input) -> IResult<Input, [Input; TAGS], Error>
-where
-    Input: InputTakeAtPosition + InputTake + Compare<T>,
-    <Input as InputTakeAtPosition>::Item: AsChar + Clone,
-    T: InputLength + Clone,
-{
-    move |mut input: Input| {
-        let tags = tags.clone();
-        let mut output: [std::mem::MaybeUninit<Input>; TAGS] =
-            unsafe { std::mem::MaybeUninit::uninit().assume_init() };
-        for i in 0..TAGS {
-            let (t, o) = nom::bytes::complete::tag(tags[i].clone())(input)?;
-            output[i] = std::mem::MaybeUninit::new(o);
-            input = if i != TAGS - 1 {
-                let (t, _) = nom::character::complete::multispace1(t)?;
-                t
-            } else {
-                t
-            }
-        }
-        Ok((input, unsafe {
-            (&output as *const _ as *const [Input; TAGS]).read()
-        }))
+    match output.try_into() {
+        Ok(output) => Ok((input, output)),
+        Err(_) => unreachable!("every tag has been matched"),
     }
 }
 
-pub fn space1_tags_no_case<T, Input, Error: ParseError<Input>, const TAGS: usize>(
+pub fn space1_tags<T, Input, Error: ParseError<Input>, const TAGS: usize>(
     tags: [T; TAGS],
 ) -> impl Fn(Input) -> IResult<Input, [Input; TAGS], Error>
 where
-    Input: InputTakeAtPosition + InputTake + Compare<T>,
-    <Input as InputTakeAtPosition>::Item: AsChar + Clone,
+    Input: Whitespace + InputLength + InputTake + Compare<T>,
     T: InputLength + Clone,
 {
-    move |mut input: Input| {
-        let tags = tags.clone();
-        let mut output: [std::mem::MaybeUninit<Input>; TAGS] =
-            unsafe { std::mem::MaybeUninit::uninit().assume_init() };
-        for i in 0..TAGS {
-            let (t, o) = nom::bytes::complete::tag_no_case(tags[i].clone())(input)?;
-            output[i] = std::mem::MaybeUninit::new(o);
-            input = if i != TAGS - 1 {
-                let (t, _) = nom::character::complete::multispace1(t)?;
-                t
-            } else {
-                t
-            }
-        }
-        Ok((input, unsafe {
-            (&output as *const _ as *const [Input; TAGS]).read()
-        }))
+    move |input: Input| {
+        separated_tags(
+            &tags,
+            input,
+            |t, input| nom::bytes::complete::tag(t)(input),
+            ws1,
+        )
     }
 }
 
-pub fn space0_tags_no_case<T, Input, Error: ParseError<Input>, const TAGS: usize>(
+pub fn space1_tags_no_case<T, Input, Error: ParseError<Input>, const TAGS: usize>(
     tags: [T; TAGS],
 ) -> impl Fn(Input) -> IResult<Input, [Input; TAGS], Error>
 where
-    Input: InputTakeAtPosition + InputTake + Compare<T>,
-    <Input as InputTakeAtPosition>::Item: AsChar + Clone,
+    Input: Whitespace + InputLength + InputTake + Compare<T>,
     T: InputLength + Clone,
 {
-    move |mut input: Input| {
-        let tags = tags.clone();
-        let mut output: [std::mem::MaybeUninit<Input>; TAGS] =
-            unsafe { std::mem::MaybeUninit::uninit().assume_init() };
-        for i in 0..TAGS {
-            let (t, o) = nom::bytes::complete::tag_no_case(tags[i].clone())(input)?;
-            output[i] = std::mem::MaybeUninit::new(o);
-            input = if i != TAGS - 1 {
-                let (t, _) = nom::character::complete::multispace1(t)?;
-                t
-            } else {
-                t
-            }
-        }
-        Ok((input, unsafe {
-            (&output as *const _ as *const [Input; TAGS]).read()
-        }))
+    move |input: Input| {
+        separated_tags(
+            &tags,
+            input,
+            |t, input| nom::bytes::complete::tag_no_case(t)(input),
+            ws1,
+        )
     }
 }
 
@@ -184,13 +371,12 @@ where
     F0: Parser<I, O0, E>,
     F1: Parser<I, O1, E>,
     E: ParseError<I>,
-    I: InputTakeAtPosition + InputTake + Compare<&'static str>,
-    <I as InputTakeAtPosition>::Item: AsChar + Clone,
+    I: Whitespace + InputLength + InputTake + Compare<&'static str>,
 {
     move |input: I| {
         let (input, o0) = parser_before.parse(input)?;
         let (input, _) = space0_tag("<")(input)?;
-        let (input, _) = nom::character::complete::multispace0(input)?;
+        let (input, _) = ws0(input)?;
         let (input, o1) = parser_inner.parse(input)?;
         let (input, _) = space0_tag(">")(input)?;
         Ok((input, (o0, o1)))
@@ -207,19 +393,279 @@ where
     F1: Parser<I, O1, E>,
     F2: Parser<I, O2, E>,
     E: ParseError<I>,
-    I: InputTakeAtPosition,
-    <I as InputTakeAtPosition>::Item: AsChar + Clone,
+    I: Whitespace + InputLength,
 {
     move |input: I| {
         let (input, o0) = parser_0.parse(input)?;
-        let (input, _) = nom::character::complete::multispace0(input)?;
+        let (input, _) = ws0(input)?;
         let (input, o1) = parser_sep.parse(input)?;
-        let (input, _) = nom::character::complete::multispace0(input)?;
+        let (input, _) = ws0(input)?;
         let (input, o2) = parser_1.parse(input)?;
         Ok((input, (o0, o1, o2)))
     }
 }
 
+/// Matches a keyword case-insensitively, a failure reports the keyword as expected.
+pub fn keyword<I, E>(keyword: &'static str) -> impl FnMut(I) -> IResult<I, I, E>
+where
+    I: InputTake + Compare<&'static str> + Clone,
+    E: ParseError<I> + ContextError<I>,
+{
+    context(keyword, nom::bytes::complete::tag_no_case(keyword))
+}
+
+/// Recognizes a string literal, quoted with `'` or `$$`, and returns it with its quotes.
+pub fn string_literal<'de, E: ParseError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, &'de str, E> {
+    recognize(alt((
+        // A doubled quote is an escaped quote.
+        delimited(tag("'"), many0(alt((tag("''"), is_not("'")))), tag("'")),
+        delimited(tag("$$"), map(take_until("$$"), |_| Vec::new()), tag("$$")),
+    )))(input)
+}
+
+/// Removes the quotes of a string literal and resolves its escaped quotes.
+pub fn unquote(literal: &str) -> String {
+    match literal.strip_prefix("$$") {
+        Some(literal) => literal[..literal.len() - 2].to_string(),
+        None => literal[1..literal.len() - 1].replace("''", "'"),
+    }
+}
+
+/// Parses a string literal, and returns it without its quotes and with its escapes resolved.
+pub fn string_constant<'de, E: ParseError<&'de str> + ContextError<&'de str>>(
+    input: &'de str,
+) -> IResult<&'de str, String, E> {
+    context("string", map(string_literal, unquote))(input)
+}
+
+/// Recognizes a comma separated list in braces, like a set or map literal.
+pub fn braced<'de, O, E: ParseError<&'de str> + ContextError<&'de str>>(
+    element: impl FnMut(&'de str) -> IResult<&'de str, O, E>,
+) -> impl FnMut(&'de str) -> IResult<&'de str, Vec<O>, E> {
+    delimited(
+        tag("{"),
+        separated_list0(tag(","), delimited(ws0, element, ws0)),
+        pair(ws0, context("}", tag("}"))),
+    )
+}
+
+/// Like `separated_list0`, but also returns the error which ended the list.
+///
+/// The error describes what else could have followed the list, if the parser fails after the
+/// list it should be combined with [`or_error`].
+pub fn separated_list0_with_error<I, O, O2, E, F, G>(
+    mut separator: G,
+    mut element: F,
+) -> impl FnMut(I) -> IResult<I, (Vec<O>, Option<E>), E>
+where
+    I: Clone + InputLength,
+    F: Parser<I, O, E>,
+    G: Parser<I, O2, E>,
+    E: ParseError<I>,
+{
+    move |input: I| {
+        let mut elements = Vec::new();
+        let mut input = match element.parse(input.clone()) {
+            Ok((input, o)) => {
+                elements.push(o);
+                input
+            }
+            Err(nom::Err::Error(error)) => return Ok((input, (elements, Some(error)))),
+            Err(e) => return Err(e),
+        };
+        loop {
+            let after_separator = match separator.parse(input.clone()) {
+                Ok((i, _)) => i,
+                Err(nom::Err::Error(error)) => return Ok((input, (elements, Some(error)))),
+                Err(e) => return Err(e),
+            };
+            match element.parse(after_separator) {
+                // Neither the separator nor the element consumed anything, the list would
+                // never end.
+                Ok((i, _)) if i.input_len() == input.input_len() => {
+                    return Err(nom::Err::Error(E::from_error_kind(
+                        input,
+                        ErrorKind::SeparatedList,
+                    )));
+                }
+                Ok((i, o)) => {
+                    elements.push(o);
+                    input = i;
+                }
+                Err(nom::Err::Error(error)) => return Ok((input, (elements, Some(error)))),
+                Err(e) => return Err(e),
+            }
+        }
+    }
+}
+
+/// Returns the first line of the input, cut after `length` characters.
+pub fn snippet(input: &str, length: usize) -> &str {
+    let line = input.lines().next().unwrap_or_default();
+    match line.char_indices().nth(length) {
+        Some((end, _)) => &line[..end],
+        None => line,
+    }
+}
+
+/// The one based line and column, in characters, of a byte offset into the input.
+pub fn line_column(input: &str, offset: usize) -> (usize, usize) {
+    let before = &input[..offset];
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// The number of characters of the input recorded with a trace.
+#[cfg(feature = "tracing")]
+const TRACE_SNIPPET_LENGTH: usize = 32;
+
+/// Emits a debug event if the `tracing` feature is enabled, expands to nothing otherwise.
+macro_rules! debug_event {
+    ($($arg:tt)*) => {
+        #[cfg(feature = "tracing")]
+        tracing::debug!($($arg)*);
+    };
+}
+pub(crate) use debug_event;
+
+/// Errors which know the input they failed at, traced as the offset of a failure.
+pub trait ErrorInput<I> {
+    /// The remaining input at the failure.
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    fn error_input(&self) -> Option<&I>;
+}
+
+impl<I> ErrorInput<I> for nom::error::Error<I> {
+    fn error_input(&self) -> Option<&I> {
+        Some(&self.input)
+    }
+}
+
+impl<I> ErrorInput<I> for nom::error::VerboseError<I> {
+    fn error_input(&self) -> Option<&I> {
+        self.errors.first().map(|(input, _)| input)
+    }
+}
+
+/// The offset into `input` at which the parser failed, if the error knows it.
+#[cfg(feature = "tracing")]
+fn error_offset<'de, E: ErrorInput<&'de str>>(
+    input: &'de str,
+    error: &nom::Err<E>,
+) -> Option<usize> {
+    match error {
+        nom::Err::Error(error) | nom::Err::Failure(error) => error
+            .error_input()
+            .map(|remaining| input.len() - remaining.len()),
+        nom::Err::Incomplete(_) => None,
+    }
+}
+
+/// Runs the parser in a debug span for the production, and reports whether it succeeded.
+///
+/// A failure records the offset it failed at, relative to the start of the span.
+/// Without the `tracing` feature this is the parser itself.
+#[cfg(feature = "tracing")]
+pub fn traced<'de, O, E, F>(
+    production: &'static str,
+    mut parser: F,
+) -> impl FnMut(&'de str) -> IResult<&'de str, O, E>
+where
+    E: ErrorInput<&'de str>,
+    F: Parser<&'de str, O, E>,
+{
+    move |input: &'de str| {
+        let span = tracing::debug_span!(
+            "parse",
+            production,
+            at = snippet(input, TRACE_SNIPPET_LENGTH)
+        );
+        let _entered = span.enter();
+        let result = parser.parse(input);
+        match &result {
+            Ok((remaining, _)) => {
+                tracing::debug!(
+                    consumed = input.len() - remaining.len(),
+                    "parsed {}",
+                    production
+                )
+            }
+            Err(error) => tracing::debug!(
+                offset = error_offset(input, error),
+                "failed to parse {}",
+                production
+            ),
+        }
+
+        result
+    }
+}
+
+/// Runs the parser in a debug span for the production, and reports whether it succeeded.
+///
+/// Without the `tracing` feature this is the parser itself.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub fn traced<I, O, E, F: Parser<I, O, E>>(_production: &'static str, parser: F) -> F {
+    parser
+}
+
+/// Runs each statement of a list in a debug span, which records the index of the statement.
+///
+/// A failure records the offset it failed at, relative to the start of the statement.
+/// Without the `tracing` feature this is the parser itself.
+#[cfg(feature = "tracing")]
+pub fn traced_statements<'de, O, E, F>(
+    mut parser: F,
+) -> impl FnMut(&'de str) -> IResult<&'de str, O, E>
+where
+    E: ErrorInput<&'de str>,
+    F: Parser<&'de str, O, E>,
+{
+    let mut index = 0usize;
+    move |input: &'de str| {
+        let span = tracing::debug_span!(
+            "statement",
+            index,
+            at = snippet(input, TRACE_SNIPPET_LENGTH)
+        );
+        index += 1;
+        let _entered = span.enter();
+        let result = parser.parse(input);
+        if let Err(error) = &result {
+            tracing::debug!(
+                offset = error_offset(input, error),
+                "failed to parse the statement"
+            );
+        }
+
+        result
+    }
+}
+
+/// Runs each statement of a list in a debug span, which records the index of the statement.
+///
+/// Without the `tracing` feature this is the parser itself.
+#[cfg(not(feature = "tracing"))]
+#[inline(always)]
+pub fn traced_statements<I, O, E, F: Parser<I, O, E>>(parser: F) -> F {
+    parser
+}
+
+/// Combines the error of an earlier optional parser with the error of a later one.
+pub fn or_error<I, E: ParseError<I>>(previous: Option<E>) -> impl FnOnce(E) -> E {
+    move |error| match previous {
+        Some(previous) => previous.or(error),
+        None => error,
+    }
+}
+
 pub fn space0_between<I, O, E: ParseError<I>, List: Space0Between<I, O, E>>(
     mut l: List,
 ) -> impl FnMut(I) -> IResult<I, O, E> {
@@ -286,8 +732,7 @@ macro_rules! space0_between_trait_impl(
         $($id_parser: Parser<Input, $id_output, Error>),+,
     > Space0Between<Input, ( $($id_output),+ ), Error> for ( $($id_parser),+ )
     where
-        Input: InputTakeAtPosition,
-        <Input as InputTakeAtPosition>::Item: AsChar + Clone,
+        Input: Whitespace + InputLength,
         Error: ParseError<Input>,
     {
       fn space0_between(&mut self, input: Input) -> IResult<Input, ( $($id_output),+ ), Error> {
@@ -301,7 +746,7 @@ macro_rules! space0_between_trait_inner(
     ($it:tt, $self:expr, $input:ident, $head:ident $($id:ident)+, $id_head:ident $($id_value:ident)+, $($id_value_return:ident)+) => {
         match $self.$it.parse($input) {
             Ok(($input, $id_head)) => {
-                match nom::character::complete::multispace0($input) {
+                match ws0($input) {
                     Ok(($input, _)) => {
                         succ!($it, space0_between_trait_inner!($self, $input, $($id)+, $($id_value)+, $($id_value_return)+))
                     },
@@ -369,8 +814,7 @@ macro_rules! space1_between_trait_impl(
         $($id_parser: Parser<Input, $id_output, Error>),+,
     > Space1Between<Input, ( $($id_output),+ ), Error> for ( $($id_parser),+ )
     where
-        Input: InputTakeAtPosition,
-        <Input as InputTakeAtPosition>::Item: AsChar + Clone,
+        Input: Whitespace + InputLength,
         Error: ParseError<Input>,
     {
       fn space1_between(&mut self, input: Input) -> IResult<Input, ( $($id_output),+ ), Error> {
@@ -384,7 +828,7 @@ macro_rules! space1_between_trait_inner(
     ($it:tt, $self:expr, $input:ident, $head:ident $($id:ident)+, $id_head:ident $($id_value:ident)+, $($id_value_return:ident)+) => {
         match $self.$it.parse($input) {
             Ok(($input, $id_head)) => {
-                match nom::character::complete::multispace1($input) {
+                match ws1($input) {
                     Ok(($input, _)) => {
                         succ!($it, space1_between_trait_inner!($self, $input, $($id)+, $($id_value)+, $($id_value_return)+))
                     },
@@ -409,3 +853,179 @@ space1_between_trait!(
     O0 O1 O2 O3 O4 O5 O6 O7 O8 O9 O10 O11 O12 O13 O14 O15 O16 O17 O18 O19 O20 O21,
     a b c d e f g h i j k l m n o p q r s t u v
 );
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_ws0_whitespace() {
+        let result: IResult<_, _, nom::error::Error<&str>> = ws0(" \t\r\n rest");
+        assert_eq!(result, Ok(("rest", " \t\r\n ")));
+    }
+
+    #[test]
+    fn test_ws0_comments() {
+        let input = "-- line\n  // other line\n/* block\n comment */ rest";
+        let result: IResult<_, _, nom::error::Error<&str>> = ws0(input);
+        assert_eq!(result, Ok(("rest", &input[..input.len() - 4])));
+    }
+
+    #[test]
+    fn test_ws0_comment_at_end_of_input() {
+        let result: IResult<_, _, nom::error::Error<&str>> = ws0(" -- no newline");
+        assert_eq!(result, Ok(("", " -- no newline")));
+    }
+
+    #[test]
+    fn test_ws0_unterminated_block_comment() {
+        let result: IResult<_, _, nom::error::Error<&str>> = ws0(" /* open");
+        assert_eq!(result, Ok(("/* open", " ")));
+    }
+
+    #[test]
+    fn test_ws0_single_slash_and_dash() {
+        let result: IResult<_, _, nom::error::Error<&str>> = ws0(" -1 / 2");
+        assert_eq!(result, Ok(("-1 / 2", " ")));
+    }
+
+    #[test]
+    fn test_separated_list0_with_error_no_progress() {
+        let mut parser = separated_list0_with_error(ws0, tag::<_, _, nom::error::Error<&str>>(""));
+        assert_eq!(
+            parser("abc"),
+            Err(nom::Err::Error(nom::error::Error::new(
+                "abc",
+                ErrorKind::SeparatedList
+            )))
+        );
+        let mut parser =
+            separated_list0_with_error(tag(","), tag::<_, _, nom::error::Error<&str>>("a"));
+        assert_eq!(
+            parser("a,a;"),
+            Ok((
+                ";",
+                (
+                    vec!["a", "a"],
+                    Some(nom::error::Error::new(";", ErrorKind::Tag))
+                )
+            ))
+        );
+    }
+
+    #[test]
+    fn test_split_statements() {
+        let input = "CREATE TYPE a_b (c_d int); -- ;\n ; /* ; */ CREATE TYPE \"e;f\" (g_h 'x;y')";
+        assert_eq!(
+            split_statements(input),
+            vec![
+                "CREATE TYPE a_b (c_d int)",
+                " /* ; */ CREATE TYPE \"e;f\" (g_h 'x;y')"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_doc_comment() {
+        assert_eq!(doc_comment("-- a\n// b\n", true), Some("a\nb".to_string()));
+        assert_eq!(
+            doc_comment("/*\n a\n\n b\n*/ ", true),
+            Some("a\n\nb".to_string())
+        );
+        // A blank line breaks the attachment.
+        assert_eq!(doc_comment("-- a\n\n-- b\n", true), Some("b".to_string()));
+        assert_eq!(doc_comment("-- a\n  \n", true), None);
+        // Comments on the line of the preceding token are trailing comments.
+        assert_eq!(doc_comment(" -- a\n", false), None);
+        assert_eq!(
+            doc_comment(" /* a */\n-- b\n", false),
+            Some("b".to_string())
+        );
+        assert_eq!(doc_comment(" /* a */ ", false), None);
+        assert_eq!(doc_comment("\n  ", false), None);
+    }
+
+    #[test]
+    fn test_ws1_comment_only() {
+        let result: IResult<_, _, nom::error::Error<&str>> = ws1("/**/rest");
+        assert_eq!(result, Ok(("rest", "/**/")));
+    }
+
+    #[test]
+    fn test_ws1_nothing() {
+        let result: IResult<_, _, nom::error::Error<&str>> = ws1("rest");
+        assert_eq!(
+            result,
+            Err(nom::Err::Error(nom::error::Error::new(
+                "rest",
+                ErrorKind::MultiSpace
+            )))
+        );
+    }
+
+    #[test]
+    fn test_space1_tags_no_case_comment_separated() {
+        let result: IResult<_, _, nom::error::Error<&str>> =
+            space1_tags_no_case(["CREATE", "TABLE"])("create/* c */table x");
+        assert_eq!(result, Ok((" x", ["create", "table"])));
+    }
+
+    #[test]
+    fn test_tags_second_tag_fails() {
+        type Result<'a> = IResult<&'a str, [&'a str; 3], nom::error::Error<&'a str>>;
+        let result: Result =
+            space1_tags_no_case(["PER", "PARTITION", "LIMIT"])("per partitio limit");
+        assert_eq!(
+            result,
+            Err(nom::Err::Error(nom::error::Error::new(
+                "partitio limit",
+                ErrorKind::Tag
+            )))
+        );
+        let result: Result = space1_tags(["PER", "PARTITION", "LIMIT"])("PER PARTITION OFFSET");
+        assert_eq!(
+            result,
+            Err(nom::Err::Error(nom::error::Error::new(
+                "OFFSET",
+                ErrorKind::Tag
+            )))
+        );
+        let result: Result = space1_tags(["PER", "PARTITION", "LIMIT"])("PERPARTITION LIMIT");
+        assert_eq!(
+            result,
+            Err(nom::Err::Error(nom::error::Error::new(
+                "PARTITION LIMIT",
+                ErrorKind::MultiSpace
+            )))
+        );
+    }
+
+    #[test]
+    fn test_string_constant() {
+        let parse =
+            |input| -> IResult<_, _, nom::error::VerboseError<&str>> { string_constant(input) };
+        assert_eq!(parse("'' rest"), Ok((" rest", String::new())));
+        assert_eq!(parse("'it''s\nfine'"), Ok(("", "it's\nfine".to_string())));
+        assert_eq!(parse("''''"), Ok(("", "'".to_string())));
+        assert_eq!(parse("$$it's$$"), Ok(("", "it's".to_string())));
+        // Adjacent strings are separate literals.
+        assert_eq!(parse("'a' 'b'"), Ok((" 'b'", "a".to_string())));
+        assert!(matches!(parse("'open"), Err(nom::Err::Error(_))));
+        assert!(matches!(parse("'it''s"), Err(nom::Err::Error(_))));
+    }
+
+    #[test]
+    fn test_dollar_quoted_string() {
+        let input = "$$\n  if (x.equals('a;b')) return \"$1\";\n$$ LANGUAGE java; USE k_1";
+        let result: IResult<_, _, nom::error::VerboseError<&str>> = string_constant(input);
+        assert_eq!(
+            result,
+            Ok((
+                " LANGUAGE java; USE k_1",
+                "\n  if (x.equals('a;b')) return \"$1\";\n".to_string()
+            ))
+        );
+        // The semicolons of the body do not end the statement.
+        assert_eq!(split_statements(input).len(), 2);
+    }
+}
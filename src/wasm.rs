@@ -0,0 +1,846 @@
+use crate::error::Diagnostic;
+use crate::format::{format as format_statements, FormatOptions};
+use crate::model::*;
+use crate::parse_cql_recovering;
+use serde::Serialize;
+use serde_json::{json, Value};
+use wasm_bindgen::prelude::*;
+
+/// The version of the JSON export, increased on incompatible changes of its layout.
+pub const EXPORT_VERSION: u32 = 1;
+
+/// Parses CQL statements and returns the JSON export of the tree.
+///
+/// The result is `{ version, ok: true, statements }` on success and
+/// `{ version, ok: false, diagnostics }` if any statement fails to parse.
+#[wasm_bindgen]
+pub fn parse(input: &str) -> JsValue {
+    to_js(&parse_to_json(input))
+}
+
+/// Parses CQL statements and returns `{ version, diagnostics }`, where each diagnostic has
+/// an `offset`, a one based `line` and `column`, a `message` and the `expected` tokens.
+#[wasm_bindgen]
+pub fn validate(input: &str) -> JsValue {
+    let (_, diagnostics) = parse_statements(input);
+
+    to_js(&json!({
+        "version": EXPORT_VERSION,
+        "diagnostics": diagnostics,
+    }))
+}
+
+/// Parses CQL statements and pretty prints them, see [`format`](fn@crate::format::format).
+///
+/// `options` is an object like `{ indent: 2 }`, or `undefined` for the defaults. The result is
+/// `{ version, ok: true, output }` on success, `{ version, ok: false, diagnostics }` if any
+/// statement fails to parse and `{ version, ok: false, error }` for invalid options.
+#[wasm_bindgen]
+pub fn format(input: &str, options: JsValue) -> JsValue {
+    let options = serde_wasm_bindgen::from_value(options).unwrap_or(Value::Null);
+
+    to_js(&format_to_json(input, &options))
+}
+
+fn to_js(value: &Value) -> JsValue {
+    value
+        .serialize(&serde_wasm_bindgen::Serializer::json_compatible())
+        .unwrap_or(JsValue::NULL)
+}
+
+/// The JSON export of [`parse`].
+pub fn parse_to_json(input: &str) -> Value {
+    let (statements, diagnostics) = parse_statements(input);
+    if diagnostics.is_empty() {
+        json!({
+            "version": EXPORT_VERSION,
+            "ok": true,
+            "statements": statements,
+        })
+    } else {
+        json!({
+            "version": EXPORT_VERSION,
+            "ok": false,
+            "diagnostics": diagnostics,
+        })
+    }
+}
+
+/// The JSON export of [`format`](fn@format), with the options as JSON.
+pub fn format_to_json(input: &str, options: &Value) -> Value {
+    let mut format_options = FormatOptions::default();
+    match options.get("indent") {
+        None | Some(Value::Null) => {}
+        Some(indent) => match indent.as_u64() {
+            Some(indent) => format_options = FormatOptions::new(indent as usize),
+            None => {
+                return json!({
+                    "version": EXPORT_VERSION,
+                    "ok": false,
+                    "error": { "message": "indent must be a non-negative integer" },
+                })
+            }
+        },
+    }
+    let (statements, diagnostics) = parse_cql_recovering(input);
+    if diagnostics.is_empty() {
+        json!({
+            "version": EXPORT_VERSION,
+            "ok": true,
+            "output": format_statements(&statements, &format_options),
+        })
+    } else {
+        json!({
+            "version": EXPORT_VERSION,
+            "ok": false,
+            "diagnostics": diagnostics.iter().map(diagnostic_to_json).collect::<Vec<_>>(),
+        })
+    }
+}
+
+/// Parses every statement on its own, so a broken statement does not hide later errors.
+fn parse_statements(input: &str) -> (Vec<Value>, Vec<Value>) {
+    let (parsed, diagnostics) = parse_cql_recovering(input);
+    let mut statements = Vec::new();
+    for statement in parsed {
+        match statement {
+            CqlStatement::CreateTable(table) => statements.push(table_to_json(&table)),
+            CqlStatement::CreateUserDefinedType(udt) => statements.push(udt_to_json(&udt)),
+            CqlStatement::AlterMaterializedView(view) => {
+                statements.push(materialized_view_to_json(&view))
+            }
+            CqlStatement::AlterType(alter_type) => statements.push(alter_type_to_json(&alter_type)),
+            CqlStatement::DropType(drop_type) => statements.push(json!({
+                "kind": "drop_type",
+                "if_exists": drop_type.if_exists(),
+                "name": qualified_identifier_to_json(drop_type.name()),
+            })),
+            CqlStatement::DropAggregate(drop_aggregate) => statements.push(json!({
+                "kind": "drop_aggregate",
+                "if_exists": drop_aggregate.if_exists(),
+                "name": qualified_identifier_to_json(drop_aggregate.name()),
+                "signature": drop_aggregate
+                    .signature()
+                    .as_ref()
+                    .map(|signature| signature.iter().map(type_to_json).collect::<Vec<_>>()),
+            })),
+            CqlStatement::CreateTrigger(create_trigger) => statements.push(json!({
+                "kind": "create_trigger",
+                "if_not_exists": create_trigger.if_not_exists(),
+                "name": &**create_trigger.name(),
+                "table": qualified_identifier_to_json(create_trigger.table()),
+                "class": create_trigger.class(),
+            })),
+            CqlStatement::DropTrigger(drop_trigger) => statements.push(json!({
+                "kind": "drop_trigger",
+                "if_exists": drop_trigger.if_exists(),
+                "name": &**drop_trigger.name(),
+                "table": qualified_identifier_to_json(drop_trigger.table()),
+            })),
+            CqlStatement::CreateRole(create_role) => statements.push(json!({
+                "kind": "create_role",
+                "if_not_exists": create_role.if_not_exists(),
+                "name": &**create_role.name(),
+                "options": role_options_to_json(create_role.options()),
+            })),
+            CqlStatement::AlterRole(alter_role) => statements.push(json!({
+                "kind": "alter_role",
+                "if_exists": alter_role.if_exists(),
+                "name": &**alter_role.name(),
+                "options": role_options_to_json(alter_role.options()),
+            })),
+            CqlStatement::DropRole(drop_role) => statements.push(json!({
+                "kind": "drop_role",
+                "if_exists": drop_role.if_exists(),
+                "name": &**drop_role.name(),
+            })),
+            CqlStatement::Grant(grant) => statements.push(json!({
+                "kind": "grant",
+                "permission": grant.permission().keyword().to_lowercase(),
+                "resource": resource_to_json(grant.resource()),
+                "role": &**grant.role(),
+            })),
+            CqlStatement::Revoke(revoke) => statements.push(json!({
+                "kind": "revoke",
+                "permission": revoke.permission().keyword().to_lowercase(),
+                "resource": resource_to_json(revoke.resource()),
+                "role": &**revoke.role(),
+            })),
+            CqlStatement::CreateTableLike(like) => statements.push(json!({
+                "kind": "table_like",
+                "if_not_exists": like.if_not_exists(),
+                "name": qualified_identifier_to_json(like.name()),
+                "source": qualified_identifier_to_json(like.source()),
+                "options": like.options().as_ref().map(options_to_json),
+            })),
+            CqlStatement::CreateIndex(create_index) => {
+                statements.push(index_to_json(&create_index))
+            }
+            CqlStatement::Select(select) => statements.push(select_to_json(&select)),
+            CqlStatement::Insert(insert) => statements.push(insert_to_json(&insert)),
+            CqlStatement::Update(update) => statements.push(update_to_json(&update)),
+            CqlStatement::Delete(delete) => statements.push(delete_to_json(&delete)),
+            CqlStatement::Use(keyspace) => {
+                statements.push(json!({ "kind": "use", "keyspace": &*keyspace }))
+            }
+            CqlStatement::CreateKeyspace(create_keyspace) => statements.push(json!({
+                "kind": "create_keyspace",
+                "if_not_exists": create_keyspace.if_not_exists(),
+                "name": &**create_keyspace.name(),
+                "replication": option_map_to_json(&create_keyspace.replication().to_entries()),
+                "durable_writes": create_keyspace.durable_writes(),
+                "options": create_keyspace.options().iter().map(|(name, value)| json!({
+                    "name": name,
+                    "value": option_value_to_json(value),
+                })).collect::<Vec<_>>(),
+            })),
+        }
+    }
+    let diagnostics = diagnostics.iter().map(diagnostic_to_json).collect();
+
+    (statements, diagnostics)
+}
+
+fn diagnostic_to_json(diagnostic: &Diagnostic) -> Value {
+    json!({
+        "offset": diagnostic.offset(),
+        "line": diagnostic.line(),
+        "column": diagnostic.column(),
+        "message": diagnostic.error().message(),
+        "expected": diagnostic.error().expected(),
+    })
+}
+
+fn qualified_identifier_to_json(name: &CqlQualifiedIdentifier<&str>) -> Value {
+    json!({
+        "keyspace": name.keyspace().as_ref().map(|keyspace| &**keyspace),
+        "name": &**name.identifier(),
+    })
+}
+
+fn type_to_json(cql_type: &CqlType<CqlIdentifier<&str>>) -> Value {
+    match cql_type {
+        CqlType::FROZEN(inner) => json!({ "frozen": type_to_json(inner) }),
+        CqlType::MAP(map) => json!({ "map": [type_to_json(&map.0), type_to_json(&map.1)] }),
+        CqlType::SET(inner) => json!({ "set": type_to_json(inner) }),
+        CqlType::LIST(inner) => json!({ "list": type_to_json(inner) }),
+        CqlType::TUPLE(types) => {
+            json!({ "tuple": types.iter().map(type_to_json).collect::<Vec<_>>() })
+        }
+        CqlType::VECTOR(element, dimension) => json!({
+            "vector": { "element": type_to_json(element), "dimension": dimension }
+        }),
+        CqlType::UserDefined(udt) => json!({ "udt": &**udt }),
+        native => json!(format!("{:?}", native).to_lowercase()),
+    }
+}
+
+/// Numbers, uuids, blobs and durations keep their lexeme.
+fn term_to_json(term: &CqlTerm<&str>) -> Value {
+    let terms = |terms: &[CqlTerm<&str>]| terms.iter().map(term_to_json).collect::<Vec<_>>();
+    match term {
+        CqlTerm::Constant(CqlConstant::Null) => Value::Null,
+        CqlTerm::Constant(CqlConstant::String(string)) => json!({ "string": string }),
+        CqlTerm::Constant(CqlConstant::Boolean(boolean)) => json!(boolean),
+        CqlTerm::Constant(CqlConstant::Integer(lexeme))
+        | CqlTerm::Constant(CqlConstant::Float(lexeme))
+        | CqlTerm::Constant(CqlConstant::Uuid(lexeme))
+        | CqlTerm::Constant(CqlConstant::Blob(lexeme))
+        | CqlTerm::Constant(CqlConstant::Duration(lexeme)) => json!({ term.kind(): lexeme }),
+        CqlTerm::List(list) => json!({ "list": terms(list) }),
+        CqlTerm::Set(set) => json!({ "set": terms(set) }),
+        CqlTerm::TupleLiteral(tuple) => json!({ "tuple": terms(tuple) }),
+        CqlTerm::Map(entries) => json!({
+            "map": entries.iter().map(|(key, value)| [term_to_json(key), term_to_json(value)]).collect::<Vec<_>>(),
+        }),
+        CqlTerm::UdtLiteral(fields) => json!({
+            "udt": fields.iter().map(|(field, value)| json!({
+                "field": &**field,
+                "value": term_to_json(value),
+            })).collect::<Vec<_>>(),
+        }),
+        CqlTerm::BindMarker(CqlBindMarker::Positional) => json!({ "bind_marker": Value::Null }),
+        CqlTerm::BindMarker(CqlBindMarker::Named(name)) => json!({ "bind_marker": &**name }),
+        CqlTerm::FunctionCall(name, arguments) => json!({
+            "function": qualified_identifier_to_json(name),
+            "arguments": terms(arguments),
+        }),
+    }
+}
+
+fn table_to_json(table: &ParsedCqlTable<&str>) -> Value {
+    let names = |columns: &[CqlIdentifier<&str>]| {
+        columns
+            .iter()
+            .map(|column| (**column).to_string())
+            .collect::<Vec<_>>()
+    };
+
+    json!({
+        "kind": "table",
+        "if_not_exists": table.if_not_exists(),
+        "name": qualified_identifier_to_json(table.name()),
+        "columns": table.columns().iter().map(|column| json!({
+            "name": &**column.name(),
+            "type": type_to_json(column.cql_type()),
+            "static": column.is_static(),
+            "primary_key": column.is_primary_key(),
+            "mask": column.mask().as_ref().map(|mask| match mask {
+                CqlColumnMask::Default => json!("default"),
+                CqlColumnMask::Function(name, arguments) => json!({
+                    "function": qualified_identifier_to_json(name),
+                    "arguments": arguments.iter().map(term_to_json).collect::<Vec<_>>(),
+                }),
+            }),
+        })).collect::<Vec<_>>(),
+        "primary_key": table.primary_key().as_ref().map(|primary_key| json!({
+            "partition_key": names(primary_key.partition_key()),
+            "composite_partition": primary_key.composite_partition(),
+            "clustering_columns": names(primary_key.clustering_columns()),
+        })),
+        "options": table.options().as_ref().map(options_to_json),
+    })
+}
+
+fn materialized_view_to_json(view: &CqlAlterMaterializedView<&str>) -> Value {
+    json!({
+        "kind": "alter_materialized_view",
+        "if_exists": view.if_exists(),
+        "name": qualified_identifier_to_json(view.name()),
+        "options": options_to_json(view.options()),
+    })
+}
+
+fn alter_type_to_json(alter_type: &CqlAlterType<&str>) -> Value {
+    let operation = match alter_type.operation() {
+        CqlAlterTypeOperation::Add(fields) => json!({
+            "add": fields.iter().map(|(name, cql_type)| json!({
+                "name": &**name,
+                "type": type_to_json(cql_type),
+            })).collect::<Vec<_>>(),
+        }),
+        CqlAlterTypeOperation::Rename(renames) => json!({
+            "rename": renames.iter().map(|(from, to)| json!({
+                "from": &**from,
+                "to": &**to,
+            })).collect::<Vec<_>>(),
+        }),
+    };
+
+    json!({
+        "kind": "alter_type",
+        "if_exists": alter_type.if_exists(),
+        "name": qualified_identifier_to_json(alter_type.name()),
+        "operation": operation,
+    })
+}
+
+fn options_to_json(options: &CqlTableOptions<&str, CqlIdentifier<&str>>) -> Value {
+    json!({
+        "compact_storage": options.compact_storage(),
+        "clustering_order": options.clustering_order().iter().map(|(column, order)| json!({
+            "column": &**column,
+            "order": match order {
+                CqlOrder::Asc => "asc",
+                CqlOrder::Desc => "desc",
+            },
+        })).collect::<Vec<_>>(),
+        "options": options.options().iter().map(|option| json!({
+            "name": option.name(),
+            "value": match option {
+                CqlTableOption::Comment(value)
+                | CqlTableOption::SpeculativeRetry(value)
+                | CqlTableOption::ReadRepair(value) => json!({ "string": value }),
+                CqlTableOption::GcGraceSeconds(value)
+                | CqlTableOption::DefaultTimeToLive(value)
+                | CqlTableOption::MemtableFlushPeriodInMs(value)
+                | CqlTableOption::MinIndexInterval(value)
+                | CqlTableOption::MaxIndexInterval(value)
+                | CqlTableOption::PaxosGraceSeconds(value) => json!({ "integer": value }),
+                CqlTableOption::BloomFilterFpChance(value)
+                | CqlTableOption::CrcCheckChance(value) => json!({ "float": value }),
+                CqlTableOption::Cdc(value) | CqlTableOption::SynchronousUpdates(value) => {
+                    json!({ "boolean": value })
+                }
+                CqlTableOption::Id(id) => json!({ "uuid": CqlValue::Uuid(*id).to_string() }),
+                CqlTableOption::Compaction(compaction) => {
+                    option_map_to_json(&compaction.to_entries())
+                }
+                CqlTableOption::Compression(compression) => {
+                    option_map_to_json(&compression.to_entries())
+                }
+                CqlTableOption::Caching(caching) => option_map_to_json(&caching.to_entries()),
+                CqlTableOption::CdcOptions(entries)
+                | CqlTableOption::TombstoneGc(entries)
+                | CqlTableOption::PerPartitionRateLimit(entries) => option_map_to_json(entries),
+                CqlTableOption::Other(_, value) => option_value_to_json(value),
+            },
+        })).collect::<Vec<_>>(),
+    })
+}
+
+fn option_map_to_json(entries: &[(String, CqlOptionValue<&str>)]) -> Value {
+    json!({
+        "map": entries.iter().map(|(key, value)| json!({
+            "key": key,
+            "value": option_value_to_json(value),
+        })).collect::<Vec<_>>(),
+    })
+}
+
+fn option_value_to_json(value: &CqlOptionValue<&str>) -> Value {
+    match value {
+        CqlOptionValue::String(string) => json!({ "string": string }),
+        CqlOptionValue::Map(entries) => option_map_to_json(entries),
+        CqlOptionValue::Integer(literal) => json!({ "integer": literal }),
+        CqlOptionValue::Float(literal) => json!({ "float": literal }),
+        CqlOptionValue::Boolean(boolean) => json!({ "boolean": boolean }),
+        CqlOptionValue::Uuid(uuid) => json!({ "uuid": uuid }),
+        CqlOptionValue::Constant(constant) => json!({ "constant": constant }),
+    }
+}
+
+fn role_options_to_json(options: &CqlRoleOptions) -> Value {
+    json!({
+        "password": options.password(),
+        "hashed_password": options.hashed_password(),
+        "login": options.login(),
+        "superuser": options.superuser(),
+        "options": options.options().as_ref().map(|options| options.iter().map(|(name, value)| json!({
+            "name": name,
+            "value": value,
+        })).collect::<Vec<_>>()),
+        "datacenters": match options.datacenters() {
+            None => Value::Null,
+            Some(CqlDatacenters::All) => json!("all"),
+            Some(CqlDatacenters::Only(datacenters)) => json!(datacenters),
+        },
+    })
+}
+
+fn select_to_json(select: &CqlSelect<&str>) -> Value {
+    json!({
+        "kind": "select",
+        "json": select.json(),
+        "distinct": select.distinct(),
+        "selection": selection_to_json(select.selection()),
+        "table": qualified_identifier_to_json(select.table()),
+        "where": select.where_clause().as_ref().map(where_clause_to_json),
+        "group_by": select.group_by().iter().map(|column| &**column).collect::<Vec<_>>(),
+        "order_by": select.order_by().iter().map(|ordering| json!({
+            "column": &**ordering.column(),
+            "order": match ordering {
+                CqlOrdering::Column(_, CqlOrder::Asc) => "asc",
+                CqlOrdering::Column(_, CqlOrder::Desc) => "desc",
+                CqlOrdering::Ann(_, _) => "ann",
+            },
+            "vector": match ordering {
+                CqlOrdering::Column(_, _) => None,
+                CqlOrdering::Ann(_, vector) => Some(term_to_json(vector)),
+            },
+        })).collect::<Vec<_>>(),
+        "per_partition_limit": select.per_partition_limit().as_ref().map(term_to_json),
+        "limit": select.limit().as_ref().map(term_to_json),
+        "allow_filtering": select.allow_filtering(),
+    })
+}
+
+fn insert_to_json(insert: &CqlInsert<&str>) -> Value {
+    let values = match insert.values() {
+        CqlInsertValues::Values(values) => json!(values
+            .iter()
+            .map(|(column, value)| json!({ "column": &**column, "value": term_to_json(value) }))
+            .collect::<Vec<_>>()),
+        CqlInsertValues::Json(row, default) => json!({
+            "json": term_to_json(row),
+            "default": default.map(|default| match default {
+                CqlJsonDefault::Null => "null",
+                CqlJsonDefault::Unset => "unset",
+            }),
+        }),
+    };
+    json!({
+        "kind": "insert",
+        "table": qualified_identifier_to_json(insert.table()),
+        "values": values,
+        "if": insert.condition().as_ref().map(condition_to_json),
+        "using": insert.using().as_ref().map(using_to_json),
+    })
+}
+
+fn update_to_json(update: &CqlUpdate<&str>) -> Value {
+    json!({
+        "kind": "update",
+        "table": qualified_identifier_to_json(update.table()),
+        "using": update.using().as_ref().map(using_to_json),
+        "assignments": update.assignments().iter().map(|assignment| {
+            let (operation, element, field) = match assignment {
+                CqlAssignment::Column(_, _) => ("set", None, None),
+                CqlAssignment::Element(_, element, _) => ("set", Some(term_to_json(element)), None),
+                CqlAssignment::Field(_, field, _) => ("set", None, Some(&**field)),
+                CqlAssignment::Append(_, _) => ("append", None, None),
+                CqlAssignment::Prepend(_, _) => ("prepend", None, None),
+                CqlAssignment::Remove(_, _) => ("remove", None, None),
+                CqlAssignment::CounterDelta(_, CqlCounterOperation::Increment, _) => {
+                    ("increment", None, None)
+                }
+                CqlAssignment::CounterDelta(_, CqlCounterOperation::Decrement, _) => {
+                    ("decrement", None, None)
+                }
+                CqlAssignment::BindMarkerDelta(_, CqlCounterOperation::Increment, _) => {
+                    ("add", None, None)
+                }
+                CqlAssignment::BindMarkerDelta(_, CqlCounterOperation::Decrement, _) => {
+                    ("subtract", None, None)
+                }
+            };
+            json!({
+                "column": &**assignment.column(),
+                "operation": operation,
+                "element": element,
+                "field": field,
+                "term": term_to_json(assignment.term()),
+            })
+        }).collect::<Vec<_>>(),
+        "where": where_clause_to_json(update.where_clause()),
+        "if": update.condition().as_ref().map(condition_to_json),
+    })
+}
+
+fn delete_to_json(delete: &CqlDelete<&str>) -> Value {
+    json!({
+        "kind": "delete",
+        "deletions": delete.deletions().iter().map(|deletion| match deletion {
+            CqlDeletion::Column(column) => json!({ "column": &**column }),
+            CqlDeletion::Element(column, element) => json!({
+                "column": &**column,
+                "element": term_to_json(element),
+            }),
+            CqlDeletion::Field(column, field) => json!({
+                "column": &**column,
+                "field": &**field,
+            }),
+        }).collect::<Vec<_>>(),
+        "table": qualified_identifier_to_json(delete.table()),
+        "using": delete.using().as_ref().map(using_to_json),
+        "where": where_clause_to_json(delete.where_clause()),
+        "if": delete.condition().as_ref().map(condition_to_json),
+    })
+}
+
+fn using_to_json(using: &CqlUsing<&str>) -> Value {
+    json!({
+        "ttl": using.ttl().as_ref().map(term_to_json),
+        "timestamp": using.timestamp().as_ref().map(term_to_json),
+        "timeout": using.timeout().as_ref().map(term_to_json),
+    })
+}
+
+fn condition_to_json(condition: &CqlCondition<&str>) -> Value {
+    match condition {
+        CqlCondition::NotExists => json!("not_exists"),
+        CqlCondition::Exists => json!("exists"),
+        CqlCondition::Columns(conditions) => json!(conditions
+            .iter()
+            .map(|condition| json!({
+                "column": &**condition.column(),
+                "field": condition.field().as_ref().map(|field| &**field),
+                "operator": condition.operator().symbol(),
+                "term": term_to_json(condition.term()),
+            }))
+            .collect::<Vec<_>>()),
+    }
+}
+
+fn selection_to_json(selection: &CqlSelection<&str>) -> Value {
+    match selection {
+        CqlSelection::All => json!("*"),
+        CqlSelection::Selectors(selectors) => {
+            json!(selectors.iter().map(selector_to_json).collect::<Vec<_>>())
+        }
+    }
+}
+
+fn selector_to_json(selector: &CqlSelector<&str>) -> Value {
+    match selector {
+        CqlSelector::Column(column) => json!({ "column": &**column }),
+        CqlSelector::Function(name, arguments) => json!({
+            "function": qualified_identifier_to_json(name),
+            "arguments": selection_to_json(arguments),
+        }),
+        CqlSelector::Cast(selector, cql_type) => json!({
+            "cast": selector_to_json(selector),
+            "type": type_to_json(cql_type),
+        }),
+        CqlSelector::Alias(selector, alias) => json!({
+            "alias": &**alias,
+            "selector": selector_to_json(selector),
+        }),
+    }
+}
+
+fn where_clause_to_json(where_clause: &CqlWhereClause<&str>) -> Value {
+    json!(where_clause
+        .relations()
+        .iter()
+        .map(|relation| json!({
+            "columns": relation.columns().iter().map(|column| &**column).collect::<Vec<_>>(),
+            "token": relation.is_token(),
+            "tuple": relation.is_tuple(),
+            "operator": relation.operator().symbol(),
+            "term": term_to_json(relation.term()),
+        }))
+        .collect::<Vec<_>>())
+}
+
+fn index_to_json(index: &CqlCreateIndex<&str>) -> Value {
+    let (target, column) = match index.target() {
+        CqlIndexTarget::Column(column) => ("column", column),
+        CqlIndexTarget::Keys(column) => ("keys", column),
+        CqlIndexTarget::Values(column) => ("values", column),
+        CqlIndexTarget::Entries(column) => ("entries", column),
+        CqlIndexTarget::Full(column) => ("full", column),
+    };
+    json!({
+        "kind": "index",
+        "custom": index.custom(),
+        "if_not_exists": index.if_not_exists(),
+        "name": index.name().as_ref().map(|name| &**name),
+        "table": qualified_identifier_to_json(index.table()),
+        "partition_key": index.partition_key().as_ref().map(|columns| {
+            columns.iter().map(|column| &**column).collect::<Vec<_>>()
+        }),
+        "target": { "kind": target, "column": &**column },
+        "using": index.using().as_ref().map(|using| json!({
+            "class": using.class(),
+            "options": using.options().as_ref().map(|options| options.iter().map(|(name, value)| json!({
+                "name": name,
+                "value": value,
+            })).collect::<Vec<_>>()),
+        })),
+    })
+}
+
+fn resource_to_json(resource: &CqlResource<&str>) -> Value {
+    match resource {
+        CqlResource::AllKeyspaces => json!({ "kind": "all_keyspaces" }),
+        CqlResource::Keyspace(keyspace) => json!({ "kind": "keyspace", "name": &**keyspace }),
+        CqlResource::Table(table) => json!({
+            "kind": "table",
+            "name": qualified_identifier_to_json(table),
+        }),
+        CqlResource::AllRoles => json!({ "kind": "all_roles" }),
+        CqlResource::Role(role) => json!({ "kind": "role", "name": &**role }),
+        CqlResource::AllFunctions(keyspace) => json!({
+            "kind": "all_functions",
+            "keyspace": keyspace.as_ref().map(|keyspace| &**keyspace),
+        }),
+        CqlResource::Function(name, signature) => json!({
+            "kind": "function",
+            "name": qualified_identifier_to_json(name),
+            "signature": signature.iter().map(type_to_json).collect::<Vec<_>>(),
+        }),
+        CqlResource::AllMBeans => json!({ "kind": "all_mbeans" }),
+        CqlResource::MBean(name) => json!({ "kind": "mbean", "name": name }),
+        CqlResource::MBeans(pattern) => json!({ "kind": "mbeans", "pattern": pattern }),
+    }
+}
+
+fn udt_to_json(udt: &ParsedCqlUserDefinedType<&str, CqlIdentifier<&str>>) -> Value {
+    json!({
+        "kind": "type",
+        "if_not_exists": udt.if_not_exists(),
+        "name": qualified_identifier_to_json(udt.name()),
+        "fields": udt.fields().iter().map(|(name, cql_type)| json!({
+            "name": &**name,
+            "type": type_to_json(cql_type),
+        })).collect::<Vec<_>>(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse_to_json() {
+        let value = parse_to_json(
+            "USE my_ks;
+            CREATE TYPE my_ks.my_type (my_field frozen<map<text, my_udt>>);
+            CREATE TABLE my_table (my_field int PRIMARY KEY, my_email text MASKED WITH mask_inner(2, null))
+                WITH CLUSTERING ORDER BY (my_field DESC);
+            SELECT my_email FROM my_table WHERE my_field > 1 ORDER BY my_field LIMIT 5;
+            INSERT INTO my_table (my_field, my_email) VALUES (1, 'a@b.c') IF NOT EXISTS;
+            INSERT INTO my_table JSON '{\"my_field\": 2}' DEFAULT UNSET USING TIMEOUT 1h;
+            UPDATE my_table USING TTL 60 SET my_email = 'd@e.f' WHERE my_field = 1 IF EXISTS;
+            DELETE my_email FROM my_table WHERE my_field = 1 IF my_email = 'd@e.f'",
+        );
+        assert_eq!(
+            value,
+            json!({
+                "version": EXPORT_VERSION,
+                "ok": true,
+                "statements": [
+                    { "kind": "use", "keyspace": "my_ks" },
+                    {
+                        "kind": "type",
+                        "if_not_exists": false,
+                        "name": { "keyspace": "my_ks", "name": "my_type" },
+                        "fields": [{
+                            "name": "my_field",
+                            "type": { "frozen": { "map": ["text", { "udt": "my_udt" }] } },
+                        }],
+                    },
+                    {
+                        "kind": "table",
+                        "if_not_exists": false,
+                        "name": { "keyspace": null, "name": "my_table" },
+                        "columns": [{
+                            "name": "my_field",
+                            "type": "int",
+                            "static": false,
+                            "primary_key": true,
+                            "mask": null,
+                        }, {
+                            "name": "my_email",
+                            "type": "text",
+                            "static": false,
+                            "primary_key": false,
+                            "mask": {
+                                "function": { "keyspace": null, "name": "mask_inner" },
+                                "arguments": [{ "integer": "2" }, null],
+                            },
+                        }],
+                        "primary_key": {
+                            "partition_key": ["my_field"],
+                            "composite_partition": false,
+                            "clustering_columns": [],
+                        },
+                        "options": {
+                            "compact_storage": false,
+                            "clustering_order": [{ "column": "my_field", "order": "desc" }],
+                            "options": [],
+                        },
+                    },
+                    {
+                        "kind": "select",
+                        "json": false,
+                        "distinct": false,
+                        "selection": [{ "column": "my_email" }],
+                        "table": { "keyspace": null, "name": "my_table" },
+                        "where": [{
+                            "columns": ["my_field"],
+                            "token": false,
+                            "tuple": false,
+                            "operator": ">",
+                            "term": { "integer": "1" },
+                        }],
+                        "group_by": [],
+                        "order_by": [{ "column": "my_field", "order": "asc", "vector": null }],
+                        "per_partition_limit": null,
+                        "limit": { "integer": "5" },
+                        "allow_filtering": false,
+                    },
+                    {
+                        "kind": "insert",
+                        "table": { "keyspace": null, "name": "my_table" },
+                        "values": [
+                            { "column": "my_field", "value": { "integer": "1" } },
+                            { "column": "my_email", "value": { "string": "a@b.c" } },
+                        ],
+                        "if": "not_exists",
+                        "using": null,
+                    },
+                    {
+                        "kind": "insert",
+                        "table": { "keyspace": null, "name": "my_table" },
+                        "values": {
+                            "json": { "string": "{\"my_field\": 2}" },
+                            "default": "unset",
+                        },
+                        "if": null,
+                        "using": { "ttl": null, "timestamp": null, "timeout": { "duration": "1h" } },
+                    },
+                    {
+                        "kind": "update",
+                        "table": { "keyspace": null, "name": "my_table" },
+                        "using": { "ttl": { "integer": "60" }, "timestamp": null, "timeout": null },
+                        "assignments": [{
+                            "column": "my_email",
+                            "operation": "set",
+                            "element": null,
+                            "field": null,
+                            "term": { "string": "d@e.f" },
+                        }],
+                        "where": [{
+                            "columns": ["my_field"],
+                            "token": false,
+                            "tuple": false,
+                            "operator": "=",
+                            "term": { "integer": "1" },
+                        }],
+                        "if": "exists",
+                    },
+                    {
+                        "kind": "delete",
+                        "deletions": [{ "column": "my_email" }],
+                        "table": { "keyspace": null, "name": "my_table" },
+                        "using": null,
+                        "where": [{
+                            "columns": ["my_field"],
+                            "token": false,
+                            "tuple": false,
+                            "operator": "=",
+                            "term": { "integer": "1" },
+                        }],
+                        "if": [{
+                            "column": "my_email",
+                            "field": null,
+                            "operator": "=",
+                            "term": { "string": "d@e.f" },
+                        }],
+                    },
+                ],
+            })
+        );
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let input = "CREATE TYPE my_type (my_field int);\nCREATE TABLE my_table (\n  my_field ";
+        let (statements, diagnostics) = parse_statements(input);
+        assert_eq!(statements.len(), 1);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0]["offset"], input.len());
+        assert_eq!(diagnostics[0]["line"], 3);
+        assert_eq!(diagnostics[0]["column"], 12);
+        assert!(diagnostics[0]["expected"]
+            .as_array()
+            .unwrap()
+            .contains(&json!("identifier")));
+        assert_eq!(parse_to_json(input)["ok"], false);
+    }
+
+    #[test]
+    fn test_format_to_json() {
+        let input = "CREATE TABLE t (id int PRIMARY KEY, v text)";
+        assert_eq!(
+            format_to_json(input, &json!({ "indent": 2 })),
+            json!({
+                "version": EXPORT_VERSION,
+                "ok": true,
+                "output": "CREATE TABLE t (\n  id int PRIMARY KEY,\n  v text\n);\n",
+            })
+        );
+        assert_eq!(
+            format_to_json(input, &Value::Null)["output"],
+            "CREATE TABLE t (\n    id int PRIMARY KEY,\n    v text\n);\n"
+        );
+        let invalid = format_to_json(input, &json!({ "indent": -1 }));
+        assert_eq!(invalid["ok"], false);
+        assert!(invalid["error"]["message"].is_string());
+        let broken = format_to_json("CREATE TABLE t (", &Value::Null);
+        assert_eq!(broken["ok"], false);
+        assert_eq!(broken["diagnostics"][0]["line"], 1);
+    }
+}
@@ -0,0 +1,56 @@
+//! Run with `wasm-pack test --node -- --features wasm`.
+#![cfg(all(target_arch = "wasm32", feature = "wasm"))]
+
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const README_EXAMPLE: &str = r#"CREATE TABLE IF NOT EXISTS my_keyspace.my_table (
+    my_field1 int,
+    my_field2 text,
+    PRIMARY KEY (my_field1)
+) WITH CLUSTERING ORDER BY (my_field2 DESC);"#;
+
+fn get(value: &JsValue, key: &str) -> JsValue {
+    js_sys::Reflect::get(value, &JsValue::from_str(key)).unwrap()
+}
+
+#[wasm_bindgen_test]
+fn parse_readme_example() {
+    let result = cql_nom::wasm::parse(README_EXAMPLE);
+    assert_eq!(get(&result, "ok"), JsValue::TRUE);
+    assert_eq!(
+        get(&result, "version"),
+        JsValue::from(cql_nom::wasm::EXPORT_VERSION)
+    );
+    let statements = js_sys::Array::from(&get(&result, "statements"));
+    assert_eq!(statements.length(), 1);
+    let name = get(&get(&statements.get(0), "name"), "name");
+    assert_eq!(name, JsValue::from_str("my_table"));
+}
+
+#[wasm_bindgen_test]
+fn validate_reports_structured_errors() {
+    let result = cql_nom::wasm::validate("CREATE TABLE my_table (\n  my_field ");
+    let diagnostics = js_sys::Array::from(&get(&result, "diagnostics"));
+    assert_eq!(diagnostics.length(), 1);
+    assert_eq!(get(&diagnostics.get(0), "line"), JsValue::from(2));
+}
+
+#[wasm_bindgen_test]
+fn format_readme_example() {
+    let options = js_sys::Object::new();
+    js_sys::Reflect::set(&options, &JsValue::from_str("indent"), &JsValue::from(2)).unwrap();
+    let result = cql_nom::wasm::format(README_EXAMPLE, options.into());
+    assert_eq!(get(&result, "ok"), JsValue::TRUE);
+    let output = get(&result, "output").as_string().unwrap();
+    assert!(
+        output.starts_with("CREATE TABLE IF NOT EXISTS my_keyspace.my_table (\n  my_field1 int,")
+    );
+
+    let result = cql_nom::wasm::format("CREATE TABLE (", JsValue::UNDEFINED);
+    assert_eq!(get(&result, "ok"), JsValue::FALSE);
+    assert_eq!(
+        js_sys::Array::from(&get(&result, "diagnostics")).length(),
+        1
+    );
+}